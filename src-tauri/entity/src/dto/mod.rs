@@ -9,3 +9,6 @@ pub use financial_graph::*;
 
 pub mod price_history;
 pub use price_history::*;
+
+pub mod trade_history_report;
+pub use trade_history_report::*;
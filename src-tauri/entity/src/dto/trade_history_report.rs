@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use sea_orm::prelude::DateTimeUtc;
+use serde::Serialize;
+
+use crate::{dto::*, transaction::dto::TransactionTotals};
+
+/// Platinum traded with one player.
+#[derive(Serialize, Debug, Clone)]
+pub struct TradePartnerSummary {
+    pub user_name: String,
+    pub transactions: usize,
+    pub sales: usize,
+    pub purchases: usize,
+    pub revenue: i64,
+    pub expenses: i64,
+    pub first_trade: DateTimeUtc,
+    pub last_trade: DateTimeUtc,
+}
+
+/// Units and platinum of one item over all its transactions.
+#[derive(Serialize, Debug, Clone)]
+pub struct TradedItemSummary {
+    pub item_name: String,
+    pub item_unique_name: String,
+    pub transactions: usize,
+    pub quantity_sold: i64,
+    pub quantity_bought: i64,
+    pub revenue: i64,
+    pub expenses: i64,
+}
+
+/// Long term view of the ledger: who we trade with, the platinum in and out per month and what moves the most.
+#[derive(Serialize, Debug, Clone)]
+pub struct TradeHistoryReport {
+    pub total_transactions: usize,
+    pub first_trade: Option<DateTimeUtc>,
+    pub last_trade: Option<DateTimeUtc>,
+    pub top_partners: Vec<TradePartnerSummary>,
+    // Labels are "YYYY-MM" in the report timezone, series are "revenue", "expenses" and "profit"
+    pub platinum_flow: FinancialGraphMap<i64>,
+    pub most_traded_items: Vec<TradedItemSummary>,
+}
+
+impl TradeHistoryReport {
+    /// Builds the report from totals computed by the database.
+    /// `partners` and `items` are keyed by user name and item unique name, `names` maps the unique names to
+    /// item names and `months` holds the totals per "YYYY-MM" key.
+    pub fn from_totals(
+        totals: &TransactionTotals,
+        partners: &[TransactionTotals],
+        items: &[TransactionTotals],
+        names: &HashMap<String, String>,
+        months: &HashMap<String, Vec<TransactionTotals>>,
+    ) -> Self {
+        let top_partners = partners
+            .iter()
+            .map(|partner| TradePartnerSummary {
+                user_name: partner.key.clone(),
+                transactions: partner.total_transactions as usize,
+                sales: partner.sale_count as usize,
+                purchases: partner.purchases_count as usize,
+                revenue: partner.revenue,
+                expenses: partner.expenses,
+                first_trade: partner.first_trade.unwrap_or_default(),
+                last_trade: partner.last_trade.unwrap_or_default(),
+            })
+            .collect();
+        let most_traded_items = items
+            .iter()
+            .map(|item| TradedItemSummary {
+                item_name: names.get(&item.key).cloned().unwrap_or_default(),
+                item_unique_name: item.key.clone(),
+                transactions: item.total_transactions as usize,
+                quantity_sold: item.quantity_sold,
+                quantity_bought: item.quantity_bought,
+                revenue: item.revenue,
+                expenses: item.expenses,
+            })
+            .collect();
+        let platinum_flow = FinancialGraphMap::from(months, |month| {
+            let revenue: i64 = month.iter().map(|t| t.revenue).sum();
+            let expenses: i64 = month.iter().map(|t| t.expenses).sum();
+            HashMap::from([
+                ("revenue", revenue),
+                ("expenses", expenses),
+                ("profit", revenue - expenses),
+            ])
+        });
+
+        Self {
+            total_transactions: totals.total_transactions as usize,
+            first_trade: totals.first_trade,
+            last_trade: totals.last_trade,
+            top_partners,
+            platinum_flow,
+            most_traded_items,
+        }
+    }
+}
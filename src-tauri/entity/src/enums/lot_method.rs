@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Which lots a sale consumes first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LotMethod {
+    /// Oldest lots first
+    #[default]
+    Fifo,
+    /// Newest lots first
+    Lifo,
+    /// Lots picked for the sale, the rest oldest first
+    Specific,
+}
@@ -18,3 +18,9 @@ pub use riven_grade::*;
 
 pub mod riven_attribute_grade;
 pub use riven_attribute_grade::*;
+
+pub mod lot_method;
+pub use lot_method::*;
+
+pub mod transaction_source;
+pub use transaction_source::*;
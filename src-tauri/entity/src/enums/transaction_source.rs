@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Where a transaction row came from.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    sea_orm::EnumIter,
+    sea_orm::DeriveActiveEnum,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(Some(15))")]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSource {
+    /// Recorded by the app, from a trade in the log or entered by hand
+    #[default]
+    #[sea_orm(string_value = "app")]
+    App,
+    /// Imported from a Warframe GDPR data export
+    #[sea_orm(string_value = "gdpr")]
+    Gdpr,
+}
+
+impl TransactionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionSource::App => "app",
+            TransactionSource::Gdpr => "gdpr",
+        }
+    }
+}
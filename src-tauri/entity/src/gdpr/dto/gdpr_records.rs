@@ -0,0 +1,20 @@
+use crate::gdpr::*;
+
+/// The rows of one account read from an export, stored together by an import.
+#[derive(Debug, Clone)]
+pub struct GdprAccountRecords {
+    pub account: gdpr_account::Model,
+    pub trades: Vec<gdpr_trade::Model>,
+    pub logins: Vec<gdpr_login::Model>,
+    pub purchases: Vec<gdpr_purchase::Model>,
+    pub transactions: Vec<gdpr_transaction::Model>,
+}
+
+/// How many rows of each kind an import added, rows that were already stored are not counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GdprImportCounts {
+    pub trades: usize,
+    pub logins: usize,
+    pub purchases: usize,
+    pub transactions: usize,
+}
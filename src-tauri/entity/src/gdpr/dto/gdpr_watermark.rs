@@ -0,0 +1,19 @@
+use sea_orm::prelude::DateTimeUtc;
+use serde::{Deserialize, Serialize};
+
+/// Time of the newest stored record of each kind for one account.
+/// A re-import only parses the records from these on, the unique keys of the tables
+/// drop the ones at the same time that are already stored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GdprWatermark {
+    pub trades: Option<DateTimeUtc>,
+    pub logins: Option<DateTimeUtc>,
+    pub purchases: Option<DateTimeUtc>,
+    pub transactions: Option<DateTimeUtc>,
+}
+
+impl GdprWatermark {
+    pub fn is_new(watermark: Option<DateTimeUtc>, date: DateTimeUtc) -> bool {
+        watermark.map_or(true, |last| date >= last)
+    }
+}
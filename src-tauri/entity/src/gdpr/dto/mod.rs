@@ -0,0 +1,4 @@
+pub mod gdpr_records;
+pub use gdpr_records::*;
+pub mod gdpr_watermark;
+pub use gdpr_watermark::*;
@@ -0,0 +1,47 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "gdpr_account")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    #[sea_orm(unique)]
+    pub oid: String,
+    pub display_name: String,
+    pub email: String,
+    // Account fields of the export besides the record lists
+    pub details: serde_json::Value,
+    // Last time an export of this account was loaded
+    pub imported_at: DateTimeUtc,
+    #[sea_orm(updated_at)]
+    pub updated_at: DateTimeUtc,
+    #[sea_orm(created_at)]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        oid: impl Into<String>,
+        display_name: impl Into<String>,
+        email: impl Into<String>,
+        details: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            oid: oid.into(),
+            display_name: display_name.into(),
+            email: email.into(),
+            details,
+            imported_at: Default::default(),
+            updated_at: Default::default(),
+            created_at: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "gdpr_login")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    pub account_oid: String,
+    pub date: DateTimeUtc,
+    pub ip: Option<String>,
+    pub client_type: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        account_oid: impl Into<String>,
+        date: DateTimeUtc,
+        ip: Option<String>,
+        client_type: Option<String>,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            account_oid: account_oid.into(),
+            date,
+            ip,
+            client_type,
+        }
+    }
+}
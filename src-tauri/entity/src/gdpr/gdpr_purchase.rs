@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "gdpr_purchase")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    pub account_oid: String,
+    pub date: DateTimeUtc,
+    pub shop_id: String,
+    pub price: i64,
+    // Items received, as `{ name, quantity }` objects
+    pub items: serde_json::Value,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        account_oid: impl Into<String>,
+        date: DateTimeUtc,
+        shop_id: impl Into<String>,
+        price: i64,
+        items: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            account_oid: account_oid.into(),
+            date,
+            shop_id: shop_id.into(),
+            price,
+            items,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "gdpr_trade")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    pub account_oid: String,
+    pub trade_time: DateTimeUtc,
+    pub player_name: String,
+    pub trade_type: String,
+    pub platinum: i64,
+    pub credits: i64,
+    // The parsed trade with its items
+    pub trade: serde_json::Value,
+    // Set by the user, kept across imports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[sea_orm(updated_at)]
+    pub updated_at: DateTimeUtc,
+    #[sea_orm(created_at)]
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        account_oid: impl Into<String>,
+        trade_time: DateTimeUtc,
+        player_name: impl Into<String>,
+        trade_type: impl Into<String>,
+        platinum: i64,
+        credits: i64,
+        trade: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            account_oid: account_oid.into(),
+            trade_time,
+            player_name: player_name.into(),
+            trade_type: trade_type.into(),
+            platinum,
+            credits,
+            trade,
+            notes: None,
+            updated_at: Default::default(),
+            created_at: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "gdpr_transaction")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    pub account_oid: String,
+    pub date: DateTimeUtc,
+    pub sku: String,
+    pub price: f64,
+    pub currency: String,
+    pub vendor: String,
+    pub account: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn new(
+        account_oid: impl Into<String>,
+        date: DateTimeUtc,
+        sku: impl Into<String>,
+        price: f64,
+        currency: impl Into<String>,
+        vendor: impl Into<String>,
+        account: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            account_oid: account_oid.into(),
+            date,
+            sku: sku.into(),
+            price,
+            currency: currency.into(),
+            vendor: vendor.into(),
+            account: account.into(),
+        }
+    }
+}
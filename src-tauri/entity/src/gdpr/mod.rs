@@ -0,0 +1,8 @@
+pub mod dto;
+pub use dto::*;
+
+pub mod gdpr_account;
+pub mod gdpr_login;
+pub mod gdpr_purchase;
+pub mod gdpr_trade;
+pub mod gdpr_transaction;
@@ -1,7 +1,10 @@
 pub mod dto;
 pub mod enums;
+pub mod gdpr;
+pub mod market_snapshot;
 pub mod setting;
 pub mod stock_item;
+pub mod stock_lot;
 pub mod stock_riven;
 pub mod trade_entry;
 pub mod transaction;
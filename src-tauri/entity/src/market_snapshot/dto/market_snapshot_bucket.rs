@@ -0,0 +1,55 @@
+use sea_orm::prelude::DateTimeUtc;
+use serde::{Deserialize, Serialize};
+
+use crate::market_snapshot::Model;
+
+/// Snapshots of one item merged into a single time bucket.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketSnapshotBucket {
+    #[serde(rename = "timestamp")]
+    pub timestamp: DateTimeUtc,
+    // Best prices seen within the bucket
+    #[serde(rename = "highest_buy")]
+    pub highest_buy: i64,
+    #[serde(rename = "lowest_sell")]
+    pub lowest_sell: i64,
+    // Averages over the bucket
+    #[serde(rename = "avg_highest_buy")]
+    pub avg_highest_buy: f64,
+    #[serde(rename = "avg_lowest_sell")]
+    pub avg_lowest_sell: f64,
+    #[serde(rename = "volume")]
+    pub volume: f64,
+    #[serde(rename = "buy_orders")]
+    pub buy_orders: f64,
+    #[serde(rename = "sell_orders")]
+    pub sell_orders: f64,
+    #[serde(rename = "samples")]
+    pub samples: i64,
+}
+
+impl MarketSnapshotBucket {
+    pub fn new(timestamp: DateTimeUtc) -> Self {
+        Self {
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, snapshot: &Model) {
+        if self.samples == 0 || snapshot.highest_buy > self.highest_buy {
+            self.highest_buy = snapshot.highest_buy;
+        }
+        if self.samples == 0 || snapshot.lowest_sell < self.lowest_sell {
+            self.lowest_sell = snapshot.lowest_sell;
+        }
+        let n = self.samples as f64;
+        let avg = |current: f64, value: f64| (current * n + value) / (n + 1.0);
+        self.avg_highest_buy = avg(self.avg_highest_buy, snapshot.highest_buy as f64);
+        self.avg_lowest_sell = avg(self.avg_lowest_sell, snapshot.lowest_sell as f64);
+        self.volume = avg(self.volume, snapshot.volume);
+        self.buy_orders = avg(self.buy_orders, snapshot.buy_orders as f64);
+        self.sell_orders = avg(self.sell_orders, snapshot.sell_orders as f64);
+        self.samples += 1;
+    }
+}
@@ -0,0 +1,2 @@
+pub mod market_snapshot_bucket;
+pub use market_snapshot_bucket::*;
@@ -0,0 +1,5 @@
+pub mod dto;
+pub use dto::*;
+
+pub mod market_snapshot;
+pub use market_snapshot::*;
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Part of a lot taken by a sale.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConsumedLot {
+    pub lot_id: i64,
+    pub quantity: i64,
+    pub unit_cost: f64,
+}
+
+/// Lots taken by a sale, `uncovered` units had no open lot left.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LotConsumption {
+    pub lots: Vec<ConsumedLot>,
+    pub quantity: i64,
+    pub uncovered: i64,
+    pub cost: f64,
+}
+
+impl LotConsumption {
+    pub fn add(&mut self, lot_id: i64, quantity: i64, unit_cost: f64) {
+        self.lots.push(ConsumedLot {
+            lot_id,
+            quantity,
+            unit_cost,
+        });
+        self.quantity += quantity;
+        self.cost += unit_cost * quantity as f64;
+    }
+
+    /// Realized profit of the covered units, `price` is the total price of the sale.
+    /// Units without a lot are counted at the sale price, so they add no profit.
+    pub fn profit(&self, price: i64) -> Option<i64> {
+        if self.quantity == 0 {
+            return None;
+        }
+        let total = self.quantity + self.uncovered;
+        let covered_price = price as f64 * self.quantity as f64 / total as f64;
+        Some((covered_price - self.cost).round() as i64)
+    }
+}
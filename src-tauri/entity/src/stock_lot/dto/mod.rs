@@ -0,0 +1,2 @@
+pub mod lot_consumption;
+pub use lot_consumption::*;
@@ -0,0 +1,5 @@
+pub mod dto;
+pub use dto::*;
+
+pub mod stock_lot;
+pub use stock_lot::*;
@@ -13,6 +13,9 @@ pub struct MatchRivenStruct {
     pub polarity: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub similarity: Option<f64>,
+    // Lowest score (0-100) of a live auction to price the riven against, the WTS setting when not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_similarity: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required_negative: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -27,6 +30,7 @@ impl MatchRivenStruct {
             re_rolls: None,
             polarity: None,
             similarity: None,
+            min_similarity: None,
             required_negative: None,
             attributes: None,
         }
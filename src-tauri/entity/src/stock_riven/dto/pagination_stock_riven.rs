@@ -1,4 +1,5 @@
 use crate::{dto::*, enums::*, stock_riven::*};
+use sea_orm::prelude::DateTimeUtc;
 use sea_orm::sea_query::Func;
 use sea_orm::*;
 use sea_query::Expr;
@@ -18,6 +19,10 @@ pub struct StockRivenPaginationQueryDto {
     pub sort_direction: FieldChange<SortDirection>,
     #[serde(default)]
     pub status: FieldChange<String>,
+    #[serde(default)]
+    pub from_date: FieldChange<DateTimeUtc>,
+    #[serde(default)]
+    pub to_date: FieldChange<DateTimeUtc>,
     // You can add more fields as needed for filtering
 }
 impl StockRivenPaginationQueryDto {
@@ -28,6 +33,8 @@ impl StockRivenPaginationQueryDto {
             sort_by: FieldChange::Ignore,
             sort_direction: FieldChange::Value(SortDirection::Asc),
             status: FieldChange::Ignore,
+            from_date: FieldChange::Ignore,
+            to_date: FieldChange::Ignore,
         }
     }
     pub fn get_query(&self) -> Select<stock_riven::Entity> {
@@ -57,6 +64,14 @@ impl StockRivenPaginationQueryDto {
             Value(ref q) => stmt = stmt.filter(stock_riven::Column::Status.eq(q)),
             _ => {}
         }
+        match &self.from_date {
+            Value(from_date) => stmt = stmt.filter(stock_riven::Column::CreatedAt.gte(*from_date)),
+            _ => {}
+        }
+        match &self.to_date {
+            Value(to_date) => stmt = stmt.filter(stock_riven::Column::CreatedAt.lte(*to_date)),
+            _ => {}
+        }
         match &self.sort_by {
             Value(sort_by) => {
                 let dir = match &self.sort_direction {
@@ -114,6 +129,8 @@ impl Default for StockRivenPaginationQueryDto {
             sort_by: FieldChange::Ignore,
             sort_direction: FieldChange::Value(SortDirection::Asc),
             status: FieldChange::Ignore,
+            from_date: FieldChange::Ignore,
+            to_date: FieldChange::Ignore,
         }
     }
 }
@@ -3,3 +3,6 @@ pub use pagination_transaction::*;
 
 pub mod update;
 pub use update::*;
+
+pub mod transaction_totals;
+pub use transaction_totals::*;
@@ -35,6 +35,8 @@ pub struct TransactionPaginationQueryDto {
     pub sub_type: FieldChange<SubType>,
     #[serde(default)]
     pub tags: FieldChange<Vec<String>>,
+    #[serde(default)]
+    pub source: FieldChange<TransactionSource>,
 }
 impl TransactionPaginationQueryDto {
     pub fn new(page: i64, limit: i64) -> Self {
@@ -52,6 +54,7 @@ impl TransactionPaginationQueryDto {
             unique_name: FieldChange::Ignore,
             sub_type: FieldChange::Ignore,
             tags: FieldChange::Ignore,
+            source: FieldChange::Ignore,
         }
     }
     pub fn get_query(&self) -> Select<Entity> {
@@ -131,6 +134,10 @@ impl TransactionPaginationQueryDto {
             }
             _ => {}
         }
+        match &self.source {
+            Value(source) => stmt = stmt.filter(transaction::Column::Source.eq(source.clone())),
+            _ => {}
+        }
         match &self.sort_by {
             Value(sort_by) => {
                 let dir = match &self.sort_direction {
@@ -218,6 +225,10 @@ impl TransactionPaginationQueryDto {
         self.tags = FieldChange::Value(tags);
         self
     }
+    pub fn set_source(mut self, source: TransactionSource) -> Self {
+        self.source = FieldChange::Value(source);
+        self
+    }
 }
 
 impl Default for TransactionPaginationQueryDto {
@@ -236,6 +247,7 @@ impl Default for TransactionPaginationQueryDto {
             unique_name: FieldChange::Ignore,
             sub_type: FieldChange::Ignore,
             tags: FieldChange::Ignore,
+            source: FieldChange::Ignore,
         }
     }
 }
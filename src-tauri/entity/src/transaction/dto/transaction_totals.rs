@@ -0,0 +1,119 @@
+use sea_orm::{prelude::DateTimeUtc, DbErr, FromQueryResult, QueryResult};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::dto::FinancialReport;
+
+/// Sums of a set of transactions as computed by the database.
+/// `key` is the group the row belongs to, it is empty for ungrouped totals.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionTotals {
+    pub key: String,
+    pub total_transactions: i64,
+    pub sale_count: i64,
+    pub revenue: i64,
+    pub highest_revenue: i64,
+    pub lowest_revenue: i64,
+    pub purchases_count: i64,
+    pub expenses: i64,
+    pub highest_expense: i64,
+    pub lowest_expense: i64,
+    pub total_credits: i64,
+    pub quantity_sold: i64,
+    pub quantity_bought: i64,
+    pub first_trade: Option<DateTimeUtc>,
+    pub last_trade: Option<DateTimeUtc>,
+}
+
+impl TransactionTotals {
+    pub fn profit(&self) -> i64 {
+        self.revenue - self.expenses
+    }
+
+    /// Adds the totals of `other` to these, the key is kept.
+    pub fn merge(&mut self, other: &TransactionTotals) {
+        if other.sale_count > 0 {
+            if self.sale_count == 0 {
+                self.highest_revenue = other.highest_revenue;
+                self.lowest_revenue = other.lowest_revenue;
+            } else {
+                self.highest_revenue = self.highest_revenue.max(other.highest_revenue);
+                self.lowest_revenue = self.lowest_revenue.min(other.lowest_revenue);
+            }
+        }
+        if other.purchases_count > 0 {
+            if self.purchases_count == 0 {
+                self.highest_expense = other.highest_expense;
+                self.lowest_expense = other.lowest_expense;
+            } else {
+                self.highest_expense = self.highest_expense.max(other.highest_expense);
+                self.lowest_expense = self.lowest_expense.min(other.lowest_expense);
+            }
+        }
+        self.total_transactions += other.total_transactions;
+        self.sale_count += other.sale_count;
+        self.revenue += other.revenue;
+        self.purchases_count += other.purchases_count;
+        self.expenses += other.expenses;
+        self.total_credits += other.total_credits;
+        self.quantity_sold += other.quantity_sold;
+        self.quantity_bought += other.quantity_bought;
+        self.first_trade = match (self.first_trade, other.first_trade) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.last_trade = match (self.last_trade, other.last_trade) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+impl FromQueryResult for TransactionTotals {
+    fn from_query_result(res: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        // Aggregates over no rows are NULL
+        let get = |column: &str| -> Result<i64, DbErr> {
+            Ok(res.try_get::<Option<i64>>(pre, column)?.unwrap_or(0))
+        };
+        Ok(Self {
+            key: res
+                .try_get::<Option<String>>(pre, "key")
+                .ok()
+                .flatten()
+                .unwrap_or_default(),
+            total_transactions: get("total_transactions")?,
+            sale_count: get("sale_count")?,
+            revenue: get("revenue")?,
+            highest_revenue: get("highest_revenue")?,
+            lowest_revenue: get("lowest_revenue")?,
+            purchases_count: get("purchases_count")?,
+            expenses: get("expenses")?,
+            highest_expense: get("highest_expense")?,
+            lowest_expense: get("lowest_expense")?,
+            total_credits: get("total_credits")?,
+            quantity_sold: get("quantity_sold")?,
+            quantity_bought: get("quantity_bought")?,
+            first_trade: res.try_get::<Option<DateTimeUtc>>(pre, "first_trade")?,
+            last_trade: res.try_get::<Option<DateTimeUtc>>(pre, "last_trade")?,
+        })
+    }
+}
+
+impl From<&TransactionTotals> for FinancialReport {
+    fn from(totals: &TransactionTotals) -> Self {
+        FinancialReport::new(
+            totals.total_transactions as usize,
+            totals.sale_count as usize,
+            totals.highest_revenue as f64,
+            totals.lowest_revenue as f64,
+            totals.revenue,
+            totals.purchases_count as usize,
+            totals.highest_expense as f64,
+            totals.lowest_expense as f64,
+            totals.expenses,
+        )
+        .with_properties(json!({
+            "total_credits": totals.total_credits,
+        }))
+    }
+}
@@ -34,6 +34,8 @@ pub struct Model {
     #[sea_orm(created_at)]
     pub created_at: DateTimeUtc,
     pub properties: Option<serde_json::Value>,
+    #[serde(default)]
+    pub source: TransactionSource,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -73,6 +75,7 @@ impl Model {
             properties,
             profit: None,
             credits,
+            source: TransactionSource::App,
             updated_at: Default::default(),
             created_at: Default::default(),
         }
@@ -83,4 +86,7 @@ impl Model {
     pub fn set_credits(&mut self, credits: i64) {
         self.credits = credits;
     }
+    pub fn set_source(&mut self, source: TransactionSource) {
+        self.source = source;
+    }
 }
@@ -1,6 +1,7 @@
 use crate::dto::*;
 use crate::enums::*;
 use crate::wish_list::*;
+use sea_orm::prelude::DateTimeUtc;
 use sea_orm::sea_query::Func;
 use sea_orm::*;
 use sea_query::Expr;
@@ -20,6 +21,10 @@ pub struct WishListPaginationQueryDto {
     pub sort_direction: FieldChange<SortDirection>,
     #[serde(default)]
     pub status: FieldChange<String>,
+    #[serde(default)]
+    pub from_date: FieldChange<DateTimeUtc>,
+    #[serde(default)]
+    pub to_date: FieldChange<DateTimeUtc>,
     // You can add more fields as needed for filtering
 }
 impl WishListPaginationQueryDto {
@@ -30,6 +35,8 @@ impl WishListPaginationQueryDto {
             sort_by: FieldChange::Ignore,
             sort_direction: FieldChange::Value(SortDirection::Asc),
             status: FieldChange::Ignore,
+            from_date: FieldChange::Ignore,
+            to_date: FieldChange::Ignore,
         }
     }
     pub fn get_query(&self) -> Select<Entity> {
@@ -55,6 +62,14 @@ impl WishListPaginationQueryDto {
             Value(ref q) => stmt = stmt.filter(Column::Status.eq(q)),
             _ => {}
         }
+        match &self.from_date {
+            Value(from_date) => stmt = stmt.filter(Column::CreatedAt.gte(*from_date)),
+            _ => {}
+        }
+        match &self.to_date {
+            Value(to_date) => stmt = stmt.filter(Column::CreatedAt.lte(*to_date)),
+            _ => {}
+        }
         match &self.sort_by {
             Value(sort_by) => {
                 let dir = match &self.sort_direction {
@@ -111,6 +126,8 @@ impl Default for WishListPaginationQueryDto {
             sort_by: FieldChange::Ignore,
             sort_direction: FieldChange::Value(SortDirection::Asc),
             status: FieldChange::Ignore,
+            from_date: FieldChange::Ignore,
+            to_date: FieldChange::Ignore,
         }
     }
 }
@@ -15,6 +15,10 @@ mod m20260131_103740_add_tra_credits;
 mod m20260710_124000_add_properties;
 mod m20260710_125000_drop_min_price_columns;
 mod m20260715_160000_make_properties_nullable;
+mod m20261018_120000_create_market_snapshot;
+mod m20261018_130000_create_stock_lot;
+mod m20261018_140000_add_transaction_source;
+mod m20261018_150000_create_gdpr_tables;
 
 pub struct Migrator;
 
@@ -37,6 +41,10 @@ impl MigratorTrait for Migrator {
             Box::new(m20260710_124000_add_properties::Migration),
             Box::new(m20260710_125000_drop_min_price_columns::Migration),
             Box::new(m20260715_160000_make_properties_nullable::Migration),
+            Box::new(m20261018_120000_create_market_snapshot::Migration),
+            Box::new(m20261018_130000_create_stock_lot::Migration),
+            Box::new(m20261018_140000_add_transaction_source::Migration),
+            Box::new(m20261018_150000_create_gdpr_tables::Migration),
         ]
     }
 }
@@ -98,4 +98,5 @@ pub enum Transaction {
     CreatedAt,
     UpdatedAt,
     Properties,
+    Source,
 }
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MarketSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MarketSnapshot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MarketSnapshot::WFMId).uuid().not_null())
+                    .col(ColumnDef::new(MarketSnapshot::WFMUrl).string().not_null())
+                    .col(ColumnDef::new(MarketSnapshot::SubType).json())
+                    .col(
+                        ColumnDef::new(MarketSnapshot::HighestBuy)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::LowestBuy)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::HighestSell)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::LowestSell)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::Volume)
+                            .double()
+                            .not_null()
+                            .default(Value::Double(Some(0.0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::BuyOrders)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::SellOrders)
+                            .integer()
+                            .not_null()
+                            .default(Value::Int(Some(0))),
+                    )
+                    .col(
+                        ColumnDef::new(MarketSnapshot::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Range queries always filter on the item and the time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_market_snapshot_wfm_id_created_at")
+                    .table(MarketSnapshot::Table)
+                    .col(MarketSnapshot::WFMId)
+                    .col(MarketSnapshot::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MarketSnapshot::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum MarketSnapshot {
+    Table,
+    Id,
+    WFMId,
+    WFMUrl,
+    SubType,
+    HighestBuy,
+    LowestBuy,
+    HighestSell,
+    LowestSell,
+    Volume,
+    BuyOrders,
+    SellOrders,
+    CreatedAt,
+}
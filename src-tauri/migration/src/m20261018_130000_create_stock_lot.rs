@@ -0,0 +1,104 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20240406_135257_create_stock_item_table::StockItem;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockLot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(StockLot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(StockLot::WFMId).uuid().not_null())
+                    .col(ColumnDef::new(StockLot::WFMUrl).string().not_null())
+                    .col(ColumnDef::new(StockLot::SubType).json())
+                    .col(ColumnDef::new(StockLot::Quantity).integer().not_null())
+                    .col(ColumnDef::new(StockLot::Remaining).integer().not_null())
+                    .col(
+                        ColumnDef::new(StockLot::UnitCost)
+                            .double()
+                            .not_null()
+                            .default(Value::Double(Some(0.0))),
+                    )
+                    .col(ColumnDef::new(StockLot::AcquiredAt).date_time().not_null())
+                    .col(ColumnDef::new(StockLot::CreatedAt).date_time().not_null())
+                    .col(ColumnDef::new(StockLot::UpdatedAt).date_time().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stock_lot_wfm_url_acquired_at")
+                    .table(StockLot::Table)
+                    .col(StockLot::WFMUrl)
+                    .col(StockLot::AcquiredAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every existing stock item becomes one lot at its average price
+        let existing = Query::select()
+            .columns([StockItem::WFMId, StockItem::WFMUrl, StockItem::SubType])
+            .columns([StockItem::Owned, StockItem::Owned, StockItem::Bought])
+            .columns([
+                StockItem::CreatedAt,
+                StockItem::CreatedAt,
+                StockItem::UpdatedAt,
+            ])
+            .from(StockItem::Table)
+            .and_where(Expr::col(StockItem::Owned).gt(0))
+            .to_owned();
+        let insert = Query::insert()
+            .into_table(StockLot::Table)
+            .columns([
+                StockLot::WFMId,
+                StockLot::WFMUrl,
+                StockLot::SubType,
+                StockLot::Quantity,
+                StockLot::Remaining,
+                StockLot::UnitCost,
+                StockLot::AcquiredAt,
+                StockLot::CreatedAt,
+                StockLot::UpdatedAt,
+            ])
+            .select_from(existing)
+            .map_err(|e| DbErr::Migration(e.to_string()))?
+            .to_owned();
+        manager.exec_stmt(insert).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockLot::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum StockLot {
+    Table,
+    Id,
+    WFMId,
+    WFMUrl,
+    SubType,
+    Quantity,
+    Remaining,
+    UnitCost,
+    AcquiredAt,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,57 @@
+use crate::m20240406_104026_create_transaction_table::Transaction;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every existing row was recorded by the app
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Transaction::Table)
+                    .add_column(
+                        ColumnDef::new(Transaction::Source)
+                            .string_len(15)
+                            .not_null()
+                            .default("app"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Imports look up the rows around each trade time
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_transaction_created_at")
+                    .table(Transaction::Table)
+                    .col(Transaction::CreatedAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_transaction_created_at")
+                    .table(Transaction::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Transaction::Table)
+                    .drop_column(Transaction::Source)
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,303 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GdprAccount::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GdprAccount::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GdprAccount::Oid)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(GdprAccount::DisplayName).string().not_null())
+                    .col(ColumnDef::new(GdprAccount::Email).string().not_null())
+                    .col(ColumnDef::new(GdprAccount::Details).json().not_null())
+                    .col(
+                        ColumnDef::new(GdprAccount::ImportedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GdprAccount::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GdprAccount::UpdatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GdprTrade::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GdprTrade::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GdprTrade::AccountOid).string().not_null())
+                    .col(ColumnDef::new(GdprTrade::TradeTime).date_time().not_null())
+                    .col(ColumnDef::new(GdprTrade::PlayerName).string().not_null())
+                    .col(ColumnDef::new(GdprTrade::TradeType).string().not_null())
+                    .col(
+                        ColumnDef::new(GdprTrade::Platinum)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GdprTrade::Credits)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(GdprTrade::Trade).json().not_null())
+                    .col(ColumnDef::new(GdprTrade::Notes).string())
+                    .col(ColumnDef::new(GdprTrade::CreatedAt).date_time().not_null())
+                    .col(ColumnDef::new(GdprTrade::UpdatedAt).date_time().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GdprLogin::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GdprLogin::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GdprLogin::AccountOid).string().not_null())
+                    .col(ColumnDef::new(GdprLogin::Date).date_time().not_null())
+                    .col(ColumnDef::new(GdprLogin::Ip).string())
+                    .col(ColumnDef::new(GdprLogin::ClientType).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GdprPurchase::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GdprPurchase::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(GdprPurchase::AccountOid).string().not_null())
+                    .col(ColumnDef::new(GdprPurchase::Date).date_time().not_null())
+                    .col(ColumnDef::new(GdprPurchase::ShopId).string().not_null())
+                    .col(
+                        ColumnDef::new(GdprPurchase::Price)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(GdprPurchase::Items).json().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(GdprTransaction::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GdprTransaction::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(GdprTransaction::AccountOid)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GdprTransaction::Date).date_time().not_null())
+                    .col(ColumnDef::new(GdprTransaction::Sku).string().not_null())
+                    .col(
+                        ColumnDef::new(GdprTransaction::Price)
+                            .double()
+                            .not_null()
+                            .default(Value::Double(Some(0.0))),
+                    )
+                    .col(
+                        ColumnDef::new(GdprTransaction::Currency)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(GdprTransaction::Vendor).string().not_null())
+                    .col(ColumnDef::new(GdprTransaction::Account).string().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Each record is stored once, the watermark lookups of the imports use these too
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_gdpr_trade_unique")
+                    .table(GdprTrade::Table)
+                    .col(GdprTrade::AccountOid)
+                    .col(GdprTrade::TradeTime)
+                    .col(GdprTrade::TradeType)
+                    .col(GdprTrade::Platinum)
+                    .col(GdprTrade::Credits)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_gdpr_login_unique")
+                    .table(GdprLogin::Table)
+                    .col(GdprLogin::AccountOid)
+                    .col(GdprLogin::Date)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_gdpr_purchase_unique")
+                    .table(GdprPurchase::Table)
+                    .col(GdprPurchase::AccountOid)
+                    .col(GdprPurchase::Date)
+                    .col(GdprPurchase::ShopId)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_gdpr_transaction_unique")
+                    .table(GdprTransaction::Table)
+                    .col(GdprTransaction::AccountOid)
+                    .col(GdprTransaction::Date)
+                    .col(GdprTransaction::Sku)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GdprTransaction::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(GdprPurchase::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(GdprLogin::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(GdprTrade::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(GdprAccount::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum GdprAccount {
+    Table,
+    Id,
+    Oid,
+    DisplayName,
+    Email,
+    Details,
+    ImportedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum GdprTrade {
+    Table,
+    Id,
+    AccountOid,
+    TradeTime,
+    PlayerName,
+    TradeType,
+    Platinum,
+    Credits,
+    Trade,
+    Notes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+pub enum GdprLogin {
+    Table,
+    Id,
+    AccountOid,
+    Date,
+    Ip,
+    ClientType,
+}
+
+#[derive(DeriveIden)]
+pub enum GdprPurchase {
+    Table,
+    Id,
+    AccountOid,
+    Date,
+    ShopId,
+    Price,
+    Items,
+}
+
+#[derive(DeriveIden)]
+pub enum GdprTransaction {
+    Table,
+    Id,
+    AccountOid,
+    Date,
+    Sku,
+    Price,
+    Currency,
+    Vendor,
+    Account,
+}
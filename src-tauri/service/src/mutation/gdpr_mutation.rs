@@ -0,0 +1,308 @@
+use crate::ErrorFromExt;
+use ::entity::gdpr::*;
+use sea_orm::{sea_query::OnConflict, *};
+use utils::*;
+pub struct GdprMutation;
+
+static COMPONENT: &str = "GdprMutation";
+// Keeps each insert below the SQLite bound parameter limit
+const INSERT_CHUNK_SIZE: usize = 100;
+
+impl GdprMutation {
+    /// Stores the accounts and their records in one transaction, a failed import leaves nothing behind.
+    pub async fn import(
+        db: &DbConn,
+        records: Vec<GdprAccountRecords>,
+    ) -> Result<GdprImportCounts, Error> {
+        let txn = db.begin().await.map_err(|e| {
+            Error::from_db(
+                format!("{}:Import", COMPONENT),
+                "Failed to start the transaction",
+                e,
+                get_location!(),
+            )
+        })?;
+        let mut counts = GdprImportCounts::default();
+        for record in records {
+            Self::upsert_account(&txn, &record.account)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+            counts.trades += Self::insert_trades(&txn, record.trades)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+            counts.logins += Self::insert_logins(&txn, record.logins)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+            counts.purchases += Self::insert_purchases(&txn, record.purchases)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+            counts.transactions += Self::insert_transactions(&txn, record.transactions)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+        }
+        txn.commit().await.map_err(|e| {
+            Error::from_db(
+                format!("{}:Import", COMPONENT),
+                "Failed to commit the transaction",
+                e,
+                get_location!(),
+            )
+        })?;
+        Ok(counts)
+    }
+
+    pub async fn upsert_account<C: ConnectionTrait>(
+        db: &C,
+        form_data: &gdpr_account::Model,
+    ) -> Result<gdpr_account::Model, Error> {
+        let existing = gdpr_account::Entity::find()
+            .filter(gdpr_account::Column::Oid.eq(form_data.oid.to_owned()))
+            .one(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:UpsertAccount", COMPONENT),
+                    "Failed to query existing GDPR Account",
+                    e,
+                    get_location!(),
+                )
+            })?;
+
+        if let Some(existing) = existing {
+            let mut active: gdpr_account::ActiveModel = existing.into();
+            active.display_name = Set(form_data.display_name.to_owned());
+            active.email = Set(form_data.email.to_owned());
+            active.details = Set(form_data.details.to_owned());
+            active.imported_at = Set(chrono::Utc::now());
+            active.updated_at = Set(chrono::Utc::now());
+            return active.update(db).await.map_err(|e| {
+                Error::from_db(
+                    format!("{}:UpsertAccount", COMPONENT),
+                    "Failed to update GDPR Account",
+                    e,
+                    get_location!(),
+                )
+            });
+        }
+
+        gdpr_account::ActiveModel {
+            oid: Set(form_data.oid.to_owned()),
+            display_name: Set(form_data.display_name.to_owned()),
+            email: Set(form_data.email.to_owned()),
+            details: Set(form_data.details.to_owned()),
+            imported_at: Set(chrono::Utc::now()),
+            created_at: Set(chrono::Utc::now()),
+            updated_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|e| {
+            Error::from_db(
+                format!("{}:UpsertAccount", COMPONENT),
+                "Failed to create GDPR Account",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    pub async fn insert_trades<C: ConnectionTrait>(
+        db: &C,
+        trades: Vec<gdpr_trade::Model>,
+    ) -> Result<usize, Error> {
+        let models = trades
+            .into_iter()
+            .map(|trade| gdpr_trade::ActiveModel {
+                account_oid: Set(trade.account_oid),
+                trade_time: Set(trade.trade_time),
+                player_name: Set(trade.player_name),
+                trade_type: Set(trade.trade_type),
+                platinum: Set(trade.platinum),
+                credits: Set(trade.credits),
+                trade: Set(trade.trade),
+                notes: Set(trade.notes),
+                created_at: Set(chrono::Utc::now()),
+                updated_at: Set(chrono::Utc::now()),
+                ..Default::default()
+            })
+            .collect();
+        let key = OnConflict::columns([
+            gdpr_trade::Column::AccountOid,
+            gdpr_trade::Column::TradeTime,
+            gdpr_trade::Column::TradeType,
+            gdpr_trade::Column::Platinum,
+            gdpr_trade::Column::Credits,
+        ]);
+        Self::insert_chunked(db, models, key, "InsertTrades", "GDPR Trades").await
+    }
+
+    pub async fn insert_logins<C: ConnectionTrait>(
+        db: &C,
+        logins: Vec<gdpr_login::Model>,
+    ) -> Result<usize, Error> {
+        let models = logins
+            .into_iter()
+            .map(|login| gdpr_login::ActiveModel {
+                account_oid: Set(login.account_oid),
+                date: Set(login.date),
+                ip: Set(login.ip),
+                client_type: Set(login.client_type),
+                ..Default::default()
+            })
+            .collect();
+        let key = OnConflict::columns([gdpr_login::Column::AccountOid, gdpr_login::Column::Date]);
+        Self::insert_chunked(db, models, key, "InsertLogins", "GDPR Logins").await
+    }
+
+    pub async fn insert_purchases<C: ConnectionTrait>(
+        db: &C,
+        purchases: Vec<gdpr_purchase::Model>,
+    ) -> Result<usize, Error> {
+        let models = purchases
+            .into_iter()
+            .map(|purchase| gdpr_purchase::ActiveModel {
+                account_oid: Set(purchase.account_oid),
+                date: Set(purchase.date),
+                shop_id: Set(purchase.shop_id),
+                price: Set(purchase.price),
+                items: Set(purchase.items),
+                ..Default::default()
+            })
+            .collect();
+        let key = OnConflict::columns([
+            gdpr_purchase::Column::AccountOid,
+            gdpr_purchase::Column::Date,
+            gdpr_purchase::Column::ShopId,
+        ]);
+        Self::insert_chunked(db, models, key, "InsertPurchases", "GDPR Purchases").await
+    }
+
+    pub async fn insert_transactions<C: ConnectionTrait>(
+        db: &C,
+        transactions: Vec<gdpr_transaction::Model>,
+    ) -> Result<usize, Error> {
+        let models = transactions
+            .into_iter()
+            .map(|transaction| gdpr_transaction::ActiveModel {
+                account_oid: Set(transaction.account_oid),
+                date: Set(transaction.date),
+                sku: Set(transaction.sku),
+                price: Set(transaction.price),
+                currency: Set(transaction.currency),
+                vendor: Set(transaction.vendor),
+                account: Set(transaction.account),
+                ..Default::default()
+            })
+            .collect();
+        let key = OnConflict::columns([
+            gdpr_transaction::Column::AccountOid,
+            gdpr_transaction::Column::Date,
+            gdpr_transaction::Column::Sku,
+        ]);
+        Self::insert_chunked(db, models, key, "InsertTransactions", "GDPR Transactions").await
+    }
+
+    /// Inserts the rows in chunks and returns how many were added,
+    /// rows that clash with a stored one on the unique `key` are skipped.
+    async fn insert_chunked<C, A>(
+        db: &C,
+        mut models: Vec<A>,
+        mut key: OnConflict,
+        action: &str,
+        name: &str,
+    ) -> Result<usize, Error>
+    where
+        C: ConnectionTrait,
+        A: ActiveModelTrait + Send,
+        <A::Entity as EntityTrait>::Model: IntoActiveModel<A>,
+    {
+        let key = key.do_nothing().to_owned();
+        let mut inserted = 0;
+        while !models.is_empty() {
+            let chunk: Vec<A> = models
+                .drain(..models.len().min(INSERT_CHUNK_SIZE))
+                .collect();
+            inserted += A::Entity::insert_many(chunk)
+                .on_conflict(key.clone())
+                .exec_without_returning(db)
+                .await
+                .map_err(|e| {
+                    Error::from_db(
+                        format!("{}:{}", COMPONENT, action),
+                        format!("Failed to insert {}", name),
+                        e,
+                        get_location!(),
+                    )
+                })?;
+        }
+        Ok(inserted as usize)
+    }
+
+    pub async fn update_trade_notes(
+        db: &DbConn,
+        id: i64,
+        notes: Option<String>,
+    ) -> Result<gdpr_trade::Model, Error> {
+        let item = gdpr_trade::Entity::find_by_id(id)
+            .one(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:UpdateTradeNotes", COMPONENT),
+                    "Failed to find GDPR Trade by ID",
+                    e,
+                    get_location!(),
+                )
+            })?
+            .ok_or(Error::new(
+                format!("{}:UpdateTradeNotes", COMPONENT),
+                "GDPR Trade not found",
+                get_location!(),
+            ))?;
+
+        let mut active: gdpr_trade::ActiveModel = item.into();
+        active.notes = Set(notes.filter(|n| !n.trim().is_empty()));
+        active.updated_at = Set(chrono::Utc::now());
+        active.update(db).await.map_err(|e| {
+            Error::from_db(
+                format!("{}:UpdateTradeNotes", COMPONENT),
+                "Failed to update GDPR Trade",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    pub async fn delete_all(db: &DbConn) -> Result<(), Error> {
+        let map_err = |e: DbErr| {
+            Error::from_db(
+                format!("{}:DeleteAll", COMPONENT),
+                "Failed to delete all GDPR data",
+                e,
+                get_location!(),
+            )
+        };
+        gdpr_trade::Entity::delete_many()
+            .exec(db)
+            .await
+            .map_err(map_err)?;
+        gdpr_login::Entity::delete_many()
+            .exec(db)
+            .await
+            .map_err(map_err)?;
+        gdpr_purchase::Entity::delete_many()
+            .exec(db)
+            .await
+            .map_err(map_err)?;
+        gdpr_transaction::Entity::delete_many()
+            .exec(db)
+            .await
+            .map_err(map_err)?;
+        gdpr_account::Entity::delete_many()
+            .exec(db)
+            .await
+            .map_err(map_err)?;
+        Ok(())
+    }
+}
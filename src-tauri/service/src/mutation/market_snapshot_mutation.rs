@@ -0,0 +1,72 @@
+use ::entity::market_snapshot::*;
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+use utils::*;
+
+use crate::ErrorFromExt;
+
+pub struct MarketSnapshotMutation;
+
+static COMPONENT: &str = "MarketSnapshotMutation";
+
+impl MarketSnapshotMutation {
+    pub async fn create(
+        db: &DbConn,
+        form_data: &market_snapshot::Model,
+    ) -> Result<market_snapshot::Model, Error> {
+        market_snapshot::ActiveModel {
+            wfm_id: Set(form_data.wfm_id.to_owned()),
+            wfm_url: Set(form_data.wfm_url.to_owned()),
+            sub_type: Set(form_data.sub_type.to_owned()),
+            highest_buy: Set(form_data.highest_buy),
+            lowest_buy: Set(form_data.lowest_buy),
+            highest_sell: Set(form_data.highest_sell),
+            lowest_sell: Set(form_data.lowest_sell),
+            volume: Set(form_data.volume),
+            buy_orders: Set(form_data.buy_orders),
+            sell_orders: Set(form_data.sell_orders),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|e| {
+            Error::from_db(
+                format!("{}:Create", COMPONENT),
+                "Failed to create Market Snapshot",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    /// Deletes every snapshot created before `before`.
+    pub async fn delete_older_than(
+        db: &DbConn,
+        before: DateTime<Utc>,
+    ) -> Result<DeleteResult, Error> {
+        Entity::delete_many()
+            .filter(market_snapshot::Column::CreatedAt.lt(before))
+            .exec(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:DeleteOlderThan", COMPONENT),
+                    "Failed to delete old Market Snapshots",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    pub async fn delete_all(db: &DbConn) -> Result<DeleteResult, Error> {
+        Entity::delete_many().exec(db).await.map_err(|e| {
+            Error::from_db(
+                format!("{}:DeleteAll", COMPONENT),
+                "Failed to delete all Market Snapshots",
+                e,
+                get_location!(),
+            )
+        })
+    }
+}
@@ -1,12 +1,18 @@
+pub mod gdpr_mutation;
+pub mod market_snapshot_mutation;
 pub mod setting_mutation;
 pub mod stock_item_mutation;
+pub mod stock_lot_mutation;
 pub mod stock_riven_mutation;
 pub mod trade_entry_mutation;
 pub mod transaction_mutation;
 pub mod wish_list_mutation;
 
+pub use gdpr_mutation::*;
+pub use market_snapshot_mutation::*;
 pub use setting_mutation::*;
 pub use stock_item_mutation::*;
+pub use stock_lot_mutation::*;
 pub use stock_riven_mutation::*;
 pub use trade_entry_mutation::*;
 pub use transaction_mutation::*;
@@ -12,8 +12,8 @@ pub struct StockItemMutation;
 static COMPONENT: &str = "StockItemMutation";
 
 impl StockItemMutation {
-    pub async fn create(
-        db: &DbConn,
+    pub async fn create<C: ConnectionTrait>(
+        db: &C,
         form_data: stock_item::Model,
     ) -> Result<stock_item::Model, Error> {
         // Remove any properties that are not allowed
@@ -47,8 +47,8 @@ impl StockItemMutation {
         })
     }
 
-    pub async fn sold_by_id(
-        db: &DbConn,
+    pub async fn sold_by_id<C: ConnectionTrait>(
+        db: &C,
         id: i64,
         mut quantity: i64,
     ) -> Result<(String, Option<stock_item::Model>), Error> {
@@ -99,8 +99,8 @@ impl StockItemMutation {
         }
     }
 
-    pub async fn sold_by_url_and_sub_type(
-        db: &DbConn,
+    pub async fn sold_by_url_and_sub_type<C: ConnectionTrait>(
+        db: &C,
         url: &str,
         sub_type: Option<SubType>,
         quantity: i64,
@@ -116,8 +116,8 @@ impl StockItemMutation {
         Ok(("NotFound".to_string(), None))
     }
 
-    pub async fn add_item(
-        db: &DbConn,
+    pub async fn add_item<C: ConnectionTrait>(
+        db: &C,
         mut stock: stock_item::Model,
     ) -> Result<(String, stock_item::Model), Error> {
         // Find the item by id
@@ -170,8 +170,8 @@ impl StockItemMutation {
         }
     }
 
-    pub async fn update_by_id(
-        db: &DbConn,
+    pub async fn update_by_id<C: ConnectionTrait>(
+        db: &C,
         input: UpdateStockItem,
     ) -> Result<stock_item::Model, Error> {
         let item = Entity::find_by_id(input.id)
@@ -203,7 +203,7 @@ impl StockItemMutation {
         })
     }
 
-    pub async fn delete_by_id(db: &DbConn, id: i64) -> Result<DeleteResult, Error> {
+    pub async fn delete_by_id<C: ConnectionTrait>(db: &C, id: i64) -> Result<DeleteResult, Error> {
         let post: stock_item::ActiveModel = Entity::find_by_id(id)
             .one(db)
             .await
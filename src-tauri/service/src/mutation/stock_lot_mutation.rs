@@ -0,0 +1,279 @@
+use ::entity::{
+    enums::{FieldChange, LotMethod},
+    stock_item::{self, UpdateStockItem},
+    stock_lot::*,
+};
+use sea_orm::*;
+use utils::*;
+
+use crate::{ErrorFromExt, StockItemMutation, StockLotQuery};
+
+pub struct StockLotMutation;
+
+static COMPONENT: &str = "StockLotMutation";
+
+impl StockLotMutation {
+    pub async fn create<C: ConnectionTrait>(
+        db: &C,
+        lot: stock_lot::Model,
+    ) -> Result<stock_lot::Model, Error> {
+        stock_lot::ActiveModel {
+            wfm_id: Set(lot.wfm_id),
+            wfm_url: Set(lot.wfm_url),
+            sub_type: Set(lot.sub_type),
+            quantity: Set(lot.quantity),
+            remaining: Set(lot.remaining),
+            unit_cost: Set(lot.unit_cost),
+            acquired_at: Set(lot.acquired_at),
+            created_at: Set(chrono::Utc::now()),
+            updated_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|e| {
+            Error::from_db(
+                format!("{}:Create", COMPONENT),
+                "Failed to create Stock Lot",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    /// Takes `quantity` units from the open lots of an item in the order given by `method`.
+    /// With `LotMethod::Specific` the `lot_ids` are consumed first, in the given order, then the oldest lots.
+    pub async fn consume<C: ConnectionTrait>(
+        db: &C,
+        wfm_url: &str,
+        sub_type: Option<SubType>,
+        quantity: i64,
+        method: &LotMethod,
+        lot_ids: &[i64],
+    ) -> Result<LotConsumption, Error> {
+        let mut lots = StockLotQuery::get_by_url_and_sub_type(db, wfm_url, sub_type, false)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        match method {
+            LotMethod::Fifo => {}
+            LotMethod::Lifo => lots.reverse(),
+            LotMethod::Specific => {
+                let rank = |lot: &stock_lot::Model| {
+                    lot_ids
+                        .iter()
+                        .position(|id| *id == lot.id)
+                        .unwrap_or(lot_ids.len())
+                };
+                // Stable, so the lots that were not picked keep the oldest first order
+                lots.sort_by_key(rank);
+            }
+        }
+
+        let mut consumption = LotConsumption::default();
+        let mut left = quantity.max(1);
+        for lot in lots {
+            if left == 0 {
+                break;
+            }
+            let take = left.min(lot.remaining);
+            left -= take;
+            consumption.add(lot.id, take, lot.unit_cost);
+
+            let mut active: stock_lot::ActiveModel = lot.clone().into();
+            active.remaining = Set(lot.remaining - take);
+            active.updated_at = Set(chrono::Utc::now());
+            active.update(db).await.map_err(|e| {
+                Error::from_db(
+                    format!("{}:Consume", COMPONENT),
+                    "Failed to update Stock Lot",
+                    e,
+                    get_location!(),
+                )
+            })?;
+        }
+        consumption.uncovered = left;
+        Ok(consumption)
+    }
+
+    /// Sells `quantity` units of a stock item and takes them from its lots in one transaction.
+    /// When the item is kept, its bought price becomes the average cost of the lots that are left.
+    pub async fn sell(
+        db: &DbConn,
+        wfm_url: &str,
+        sub_type: Option<SubType>,
+        quantity: i64,
+        method: &LotMethod,
+        lot_ids: &[i64],
+    ) -> Result<(String, Option<stock_item::Model>, LotConsumption), Error> {
+        let txn = begin(db, "Sell").await?;
+        let (operation, item) =
+            StockItemMutation::sold_by_url_and_sub_type(&txn, wfm_url, sub_type.clone(), quantity)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+        let consumption =
+            StockLotMutation::consume(&txn, wfm_url, sub_type.clone(), quantity, method, lot_ids)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+
+        if let (true, Some(item)) = (operation == "Updated", &item) {
+            if !consumption.lots.is_empty() {
+                let average = StockLotQuery::average_cost(&txn, wfm_url, sub_type)
+                    .await
+                    .map_err(|e| e.with_location(get_location!()))?;
+                if let Some(average) = average {
+                    StockItemMutation::update_by_id(
+                        &txn,
+                        UpdateStockItem::new(item.id).with_bought(average),
+                    )
+                    .await
+                    .map_err(|e| e.with_location(get_location!()))?;
+                }
+            }
+        }
+        commit(txn, "Sell").await?;
+        Ok((operation, item, consumption))
+    }
+
+    /// Adds a purchase to the stock, the item and its new lot are written in one transaction.
+    pub async fn buy(
+        db: &DbConn,
+        stock: stock_item::Model,
+        lot: stock_lot::Model,
+    ) -> Result<(String, stock_item::Model, stock_lot::Model), Error> {
+        let txn = begin(db, "Buy").await?;
+        let (operation, item) = StockItemMutation::add_item(&txn, stock)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let lot = StockLotMutation::create(&txn, lot)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        commit(txn, "Buy").await?;
+        Ok((operation, item, lot))
+    }
+
+    /// Updates a stock item, a change of `owned` is applied to its open lots in the same transaction.
+    pub async fn update_item(
+        db: &DbConn,
+        input: UpdateStockItem,
+        method: &LotMethod,
+    ) -> Result<stock_item::Model, Error> {
+        let owned_changed = matches!(input.owned, FieldChange::Value(_));
+        let txn = begin(db, "UpdateItem").await?;
+        let item = StockItemMutation::update_by_id(&txn, input)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        if owned_changed {
+            StockLotMutation::sync_owned(&txn, &item, method)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+        }
+        commit(txn, "UpdateItem").await?;
+        Ok(item)
+    }
+
+    /// Matches the open lots of a stock item with its `owned` quantity after a manual edit.
+    /// Added units get a new lot at the bought price, removed units are taken with `method`.
+    pub async fn sync_owned<C: ConnectionTrait>(
+        db: &C,
+        item: &stock_item::Model,
+        method: &LotMethod,
+    ) -> Result<(), Error> {
+        let lots =
+            StockLotQuery::get_by_url_and_sub_type(db, &item.wfm_url, item.sub_type.clone(), false)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+        let open: i64 = lots.iter().map(|lot| lot.remaining).sum();
+        let owned = item.owned.max(0);
+        if owned > open {
+            let added = owned - open;
+            StockLotMutation::create(
+                db,
+                stock_lot::Model::new(
+                    &item.wfm_id,
+                    &item.wfm_url,
+                    item.sub_type.clone(),
+                    added,
+                    item.bought * added,
+                    chrono::Utc::now(),
+                ),
+            )
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        } else if owned < open {
+            StockLotMutation::consume(
+                db,
+                &item.wfm_url,
+                item.sub_type.clone(),
+                open - owned,
+                method,
+                &[],
+            )
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        }
+        Ok(())
+    }
+
+    /// Closes the open lots of an item, used when the stock item is removed without a sale.
+    pub async fn close_open(
+        db: &DbConn,
+        wfm_url: &str,
+        sub_type: Option<SubType>,
+    ) -> Result<u64, Error> {
+        let lots = StockLotQuery::get_by_url_and_sub_type(db, wfm_url, sub_type, false)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let ids = lots.iter().map(|lot| lot.id).collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let result = Entity::update_many()
+            .col_expr(stock_lot::Column::Remaining, 0.into())
+            .col_expr(stock_lot::Column::UpdatedAt, chrono::Utc::now().into())
+            .filter(stock_lot::Column::Id.is_in(ids))
+            .exec(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:CloseOpen", COMPONENT),
+                    "Failed to close Stock Lots",
+                    e,
+                    get_location!(),
+                )
+            })?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn delete_all(db: &DbConn) -> Result<DeleteResult, Error> {
+        Entity::delete_many().exec(db).await.map_err(|e| {
+            Error::from_db(
+                format!("{}:DeleteAll", COMPONENT),
+                "Failed to delete all Stock Lots",
+                e,
+                get_location!(),
+            )
+        })
+    }
+}
+
+async fn begin(db: &DbConn, operation: &str) -> Result<DatabaseTransaction, Error> {
+    db.begin().await.map_err(|e| {
+        Error::from_db(
+            format!("{}:{}", COMPONENT, operation),
+            "Failed to start the transaction",
+            e,
+            get_location!(),
+        )
+    })
+}
+
+async fn commit(txn: DatabaseTransaction, operation: &str) -> Result<(), Error> {
+    txn.commit().await.map_err(|e| {
+        Error::from_db(
+            format!("{}:{}", COMPONENT, operation),
+            "Failed to commit the transaction",
+            e,
+            get_location!(),
+        )
+    })
+}
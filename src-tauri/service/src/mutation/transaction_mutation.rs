@@ -8,8 +8,8 @@ pub struct TransactionMutation;
 
 static COMPONENT: &str = "TransactionMutation";
 impl TransactionMutation {
-    pub async fn create(
-        db: &DbConn,
+    pub async fn create<C: ConnectionTrait>(
+        db: &C,
         form_data: &transaction::Model,
         use_current_date: bool,
     ) -> Result<transaction::Model, Error> {
@@ -28,6 +28,7 @@ impl TransactionMutation {
             profit: Set(form_data.profit.to_owned()),
             credits: Set(form_data.credits.to_owned()),
             properties: Set(form_data.properties.to_owned()),
+            source: Set(form_data.source.to_owned()),
             created_at: Set(if use_current_date {
                 chrono::Utc::now()
             } else {
@@ -52,6 +53,34 @@ impl TransactionMutation {
         })
     }
 
+    /// Inserts the rows in one database transaction, none are stored when one fails.
+    pub async fn create_all(
+        db: &DbConn,
+        rows: &[transaction::Model],
+    ) -> Result<Vec<transaction::Model>, Error> {
+        let txn = db.begin().await.map_err(|e| {
+            Error::from_db(
+                format!("{}:CreateAll", COMPONENT),
+                "Failed to start the transaction",
+                e,
+                get_location!(),
+            )
+        })?;
+        let mut created = Vec::with_capacity(rows.len());
+        for row in rows {
+            created.push(Self::create(&txn, row, false).await?);
+        }
+        txn.commit().await.map_err(|e| {
+            Error::from_db(
+                format!("{}:CreateAll", COMPONENT),
+                "Failed to commit the transaction",
+                e,
+                get_location!(),
+            )
+        })?;
+        Ok(created)
+    }
+
     pub async fn update_by_id(
         db: &DbConn,
         input: UpdateTransaction,
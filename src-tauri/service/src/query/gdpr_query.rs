@@ -0,0 +1,142 @@
+use crate::ErrorFromExt;
+use ::entity::gdpr::*;
+use sea_orm::*;
+use std::collections::HashMap;
+use utils::*;
+
+pub struct GdprQuery;
+
+static COMPONENT: &str = "GdprQuery";
+impl GdprQuery {
+    pub async fn get_accounts(db: &DbConn) -> Result<Vec<gdpr_account::Model>, Error> {
+        gdpr_account::Entity::find()
+            .order_by_asc(gdpr_account::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetAccounts", COMPONENT),
+                    "Failed to get GDPR Accounts",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    pub async fn get_trades(db: &DbConn) -> Result<Vec<gdpr_trade::Model>, Error> {
+        gdpr_trade::Entity::find()
+            .order_by_asc(gdpr_trade::Column::TradeTime)
+            .order_by_asc(gdpr_trade::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetTrades", COMPONENT),
+                    "Failed to get GDPR Trades",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    pub async fn get_logins(db: &DbConn) -> Result<Vec<gdpr_login::Model>, Error> {
+        gdpr_login::Entity::find()
+            .order_by_asc(gdpr_login::Column::Date)
+            .order_by_asc(gdpr_login::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetLogins", COMPONENT),
+                    "Failed to get GDPR Logins",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    pub async fn get_purchases(db: &DbConn) -> Result<Vec<gdpr_purchase::Model>, Error> {
+        gdpr_purchase::Entity::find()
+            .order_by_asc(gdpr_purchase::Column::Date)
+            .order_by_asc(gdpr_purchase::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetPurchases", COMPONENT),
+                    "Failed to get GDPR Purchases",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    pub async fn get_transactions(db: &DbConn) -> Result<Vec<gdpr_transaction::Model>, Error> {
+        gdpr_transaction::Entity::find()
+            .order_by_asc(gdpr_transaction::Column::Date)
+            .order_by_asc(gdpr_transaction::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetTransactions", COMPONENT),
+                    "Failed to get GDPR Transactions",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    /// Newest stored record of each kind for every imported account, keyed by account OID.
+    pub async fn get_watermarks(db: &DbConn) -> Result<HashMap<String, GdprWatermark>, Error> {
+        let map_err = |e: DbErr| {
+            Error::from_db(
+                format!("{}:GetWatermarks", COMPONENT),
+                "Failed to get GDPR import watermarks",
+                e,
+                get_location!(),
+            )
+        };
+        let mut watermarks = HashMap::new();
+        for account in Self::get_accounts(db)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+        {
+            let oid = account.oid;
+            let trades = gdpr_trade::Entity::find()
+                .filter(gdpr_trade::Column::AccountOid.eq(oid.clone()))
+                .order_by_desc(gdpr_trade::Column::TradeTime)
+                .one(db)
+                .await
+                .map_err(map_err)?;
+            let logins = gdpr_login::Entity::find()
+                .filter(gdpr_login::Column::AccountOid.eq(oid.clone()))
+                .order_by_desc(gdpr_login::Column::Date)
+                .one(db)
+                .await
+                .map_err(map_err)?;
+            let purchases = gdpr_purchase::Entity::find()
+                .filter(gdpr_purchase::Column::AccountOid.eq(oid.clone()))
+                .order_by_desc(gdpr_purchase::Column::Date)
+                .one(db)
+                .await
+                .map_err(map_err)?;
+            let transactions = gdpr_transaction::Entity::find()
+                .filter(gdpr_transaction::Column::AccountOid.eq(oid.clone()))
+                .order_by_desc(gdpr_transaction::Column::Date)
+                .one(db)
+                .await
+                .map_err(map_err)?;
+            watermarks.insert(
+                oid,
+                GdprWatermark {
+                    trades: trades.map(|t| t.trade_time),
+                    logins: logins.map(|l| l.date),
+                    purchases: purchases.map(|p| p.date),
+                    transactions: transactions.map(|t| t.date),
+                },
+            );
+        }
+        Ok(watermarks)
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use ::entity::market_snapshot::*;
+
+use chrono::{DateTime, Utc};
+use sea_orm::*;
+
+use crate::ErrorFromExt;
+use utils::*;
+
+pub struct MarketSnapshotQuery;
+
+static COMPONENT: &str = "MarketSnapshotQuery";
+impl MarketSnapshotQuery {
+    /// Returns the snapshots of an item between `from` and `to`, oldest first.
+    pub async fn get_range(
+        db: &DbConn,
+        wfm_id: &str,
+        sub_type: Option<SubType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<market_snapshot::Model>, Error> {
+        let snapshots = Entity::find()
+            .filter(market_snapshot::Column::WfmId.eq(wfm_id))
+            .filter(market_snapshot::Column::CreatedAt.between(from, to))
+            .order_by_asc(market_snapshot::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetRange", COMPONENT),
+                    "Failed to get Market Snapshots",
+                    e,
+                    get_location!(),
+                )
+            })?;
+        Ok(snapshots
+            .into_iter()
+            .filter(|snapshot| snapshot.sub_type == sub_type)
+            .collect())
+    }
+
+    /// Returns the snapshots of an item between `from` and `to` merged into buckets of `bucket_seconds`.
+    pub async fn get_downsampled(
+        db: &DbConn,
+        wfm_id: &str,
+        sub_type: Option<SubType>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        bucket_seconds: i64,
+    ) -> Result<Vec<MarketSnapshotBucket>, Error> {
+        let snapshots = Self::get_range(db, wfm_id, sub_type, from, to).await?;
+        let bucket_seconds = bucket_seconds.max(1);
+
+        let mut buckets: BTreeMap<i64, MarketSnapshotBucket> = BTreeMap::new();
+        for snapshot in &snapshots {
+            let key = snapshot.created_at.timestamp().div_euclid(bucket_seconds) * bucket_seconds;
+            buckets
+                .entry(key)
+                .or_insert_with(|| {
+                    MarketSnapshotBucket::new(DateTime::from_timestamp(key, 0).unwrap_or_default())
+                })
+                .add(snapshot);
+        }
+        Ok(buckets.into_values().collect())
+    }
+
+    /// Returns the ids of every item with at least one snapshot.
+    pub async fn get_tracked_items(db: &DbConn) -> Result<Vec<String>, Error> {
+        Entity::find()
+            .select_only()
+            .column(market_snapshot::Column::WfmId)
+            .distinct()
+            .into_tuple::<String>()
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetTrackedItems", COMPONENT),
+                    "Failed to get tracked items",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+}
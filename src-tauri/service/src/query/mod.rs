@@ -1,14 +1,20 @@
+pub mod gdpr_query;
 pub mod helper;
+pub mod market_snapshot_query;
 pub mod setting_query;
 pub mod stock_item_query;
+pub mod stock_lot_query;
 pub mod stock_riven_query;
 pub mod trade_entry_query;
 pub mod transaction_query;
 pub mod wish_list_query;
 
+pub use gdpr_query::*;
 pub use helper::*;
+pub use market_snapshot_query::*;
 pub use setting_query::*;
 pub use stock_item_query::*;
+pub use stock_lot_query::*;
 pub use stock_riven_query::*;
 pub use trade_entry_query::*;
 pub use transaction_query::*;
@@ -22,8 +22,8 @@ impl StockItemQuery {
                 .map_err(|e| e.with_location(get_location!()))?;
         Ok(paginated_result)
     }
-    pub async fn find_by_url_name(
-        db: &DbConn,
+    pub async fn find_by_url_name<C: ConnectionTrait>(
+        db: &C,
         url_name: &str,
     ) -> Result<Vec<stock_item::Model>, Error> {
         Entity::find()
@@ -65,8 +65,8 @@ impl StockItemQuery {
             })
     }
 
-    pub async fn find_by_url_name_and_sub_type(
-        db: &DbConn,
+    pub async fn find_by_url_name_and_sub_type<C: ConnectionTrait>(
+        db: &C,
         url_name: &str,
         sub_type: Option<SubType>,
     ) -> Result<Option<stock_item::Model>, Error> {
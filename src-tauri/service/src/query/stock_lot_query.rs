@@ -0,0 +1,61 @@
+use ::entity::stock_lot::*;
+
+use sea_orm::*;
+
+use crate::ErrorFromExt;
+use utils::*;
+
+pub struct StockLotQuery;
+
+static COMPONENT: &str = "StockLotQuery";
+impl StockLotQuery {
+    /// Returns the lots of an item, oldest first. Closed lots are only included when `include_closed` is set.
+    pub async fn get_by_url_and_sub_type<C: ConnectionTrait>(
+        db: &C,
+        wfm_url: &str,
+        sub_type: Option<SubType>,
+        include_closed: bool,
+    ) -> Result<Vec<stock_lot::Model>, Error> {
+        let mut stmt = Entity::find().filter(stock_lot::Column::WfmUrl.eq(wfm_url));
+        if !include_closed {
+            stmt = stmt.filter(stock_lot::Column::Remaining.gt(0));
+        }
+        let lots = stmt
+            .order_by_asc(stock_lot::Column::AcquiredAt)
+            .order_by_asc(stock_lot::Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    format!("{}:GetByUrlAndSubType", COMPONENT),
+                    "Failed to get Stock Lots",
+                    e,
+                    get_location!(),
+                )
+            })?;
+        Ok(lots
+            .into_iter()
+            .filter(|lot| lot.sub_type == sub_type)
+            .collect())
+    }
+
+    /// Weighted average unit cost of the open lots, rounded to whole platinum.
+    pub async fn average_cost<C: ConnectionTrait>(
+        db: &C,
+        wfm_url: &str,
+        sub_type: Option<SubType>,
+    ) -> Result<Option<i64>, Error> {
+        let lots = StockLotQuery::get_by_url_and_sub_type(db, wfm_url, sub_type, false)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let quantity: i64 = lots.iter().map(|lot| lot.remaining).sum();
+        if quantity == 0 {
+            return Ok(None);
+        }
+        let cost: f64 = lots
+            .iter()
+            .map(|lot| lot.unit_cost * lot.remaining as f64)
+            .sum();
+        Ok(Some((cost / quantity as f64).round() as i64))
+    }
+}
@@ -1,10 +1,124 @@
 use crate::{paginate_query, ErrorFromExt};
-use ::entity::transaction::dto::TransactionPaginationQueryDto;
+use ::entity::dto::{FinancialGraph, FinancialReport, TradeHistoryReport};
+use ::entity::enums::{FieldChange, TransactionType};
+use ::entity::transaction::dto::{TransactionPaginationQueryDto, TransactionTotals};
 use ::entity::transaction::{transaction, transaction::Entity as Transaction};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Expr, SimpleExpr};
 use sea_orm::*;
+use std::collections::{BTreeMap, HashMap};
 use utils::*;
 pub struct TransactionQuery;
 
+fn is_type(transaction_type: TransactionType) -> SimpleExpr {
+    Expr::col(transaction::Column::TransactionType).eq(transaction_type.to_string())
+}
+
+/// Price of the transactions of one type, NULL for the others so MIN and MAX skip them.
+fn price_of(transaction_type: TransactionType) -> SimpleExpr {
+    SimpleExpr::Case(Box::new(Expr::case(
+        is_type(transaction_type),
+        Expr::col(transaction::Column::Price),
+    )))
+}
+
+/// Summed price of the transactions of one type, 0 rather than NULL for groups without any
+/// so expressions like `revenue + expenses` stay comparable.
+fn price_sum_of(transaction_type: TransactionType) -> SimpleExpr {
+    Expr::expr(SimpleExpr::Case(Box::new(
+        Expr::case(
+            is_type(transaction_type),
+            Expr::col(transaction::Column::Price),
+        )
+        .finally(0),
+    )))
+    .sum()
+}
+
+/// Quantity of the transactions of one type, 0 for the others.
+fn quantity_of(transaction_type: TransactionType) -> SimpleExpr {
+    Expr::expr(SimpleExpr::Case(Box::new(
+        Expr::case(
+            is_type(transaction_type),
+            Expr::col(transaction::Column::Quantity),
+        )
+        .finally(0),
+    )))
+    .sum()
+}
+
+fn count_of(transaction_type: TransactionType) -> SimpleExpr {
+    Expr::expr(SimpleExpr::Case(Box::new(
+        Expr::case(is_type(transaction_type), 1).finally(0),
+    )))
+    .sum()
+}
+
+/// Selects the columns read by `TransactionTotals` over the rows of the statement.
+fn select_totals(stmt: Select<Transaction>) -> Select<Transaction> {
+    use TransactionType::*;
+    stmt.select_only()
+        .column_as(
+            Expr::col(transaction::Column::Id).count(),
+            "total_transactions",
+        )
+        .column_as(count_of(Sale), "sale_count")
+        .column_as(price_sum_of(Sale), "revenue")
+        .column_as(Expr::expr(price_of(Sale)).max(), "highest_revenue")
+        .column_as(Expr::expr(price_of(Sale)).min(), "lowest_revenue")
+        .column_as(count_of(Purchase), "purchases_count")
+        .column_as(price_sum_of(Purchase), "expenses")
+        .column_as(Expr::expr(price_of(Purchase)).max(), "highest_expense")
+        .column_as(Expr::expr(price_of(Purchase)).min(), "lowest_expense")
+        .column_as(
+            Expr::col(transaction::Column::Credits).sum(),
+            "total_credits",
+        )
+        .column_as(quantity_of(Sale), "quantity_sold")
+        .column_as(quantity_of(Purchase), "quantity_bought")
+        .column_as(
+            Expr::col(transaction::Column::CreatedAt).min(),
+            "first_trade",
+        )
+        .column_as(
+            Expr::col(transaction::Column::CreatedAt).max(),
+            "last_trade",
+        )
+        .column_as(
+            Expr::expr(SimpleExpr::Case(Box::new(
+                Expr::case(is_type(Sale), Expr::col(transaction::Column::Price))
+                    .case(
+                        is_type(Purchase),
+                        Expr::col(transaction::Column::Price).mul(-1),
+                    )
+                    .finally(0),
+            )))
+            .sum(),
+            "profit",
+        )
+}
+
+/// The filters of the query without its sorting, which aggregates have no use for.
+fn filter_query(query: &TransactionPaginationQueryDto) -> Select<Transaction> {
+    let mut query = query.clone();
+    query.sort_by = FieldChange::Ignore;
+    query.get_query()
+}
+
+fn date_key(levels: &[GroupByDate], modifier: String) -> SimpleExpr {
+    Expr::cust_with_values(
+        "strftime(?, \"created_at\", ?)",
+        [sql_date_format(levels), modifier],
+    )
+}
+
+/// Start of the UTC quarter hour of `created_at`, every timezone offset is a multiple of 15 minutes.
+fn quarter_hour_key() -> SimpleExpr {
+    Expr::cust(
+        "datetime((CAST(strftime('%s', \"created_at\") AS INTEGER) / 900) * 900, 'unixepoch')",
+    )
+}
+
 impl TransactionQuery {
     pub async fn get_all(
         db: &DbConn,
@@ -36,4 +150,351 @@ impl TransactionQuery {
             )
         })
     }
+
+    /// Revenue, expenses, profit and counts of the transactions matching the query.
+    pub async fn get_totals(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+    ) -> Result<TransactionTotals, Error> {
+        Self::get_totals_of(db, filter_query(query))
+            .await
+            .map_err(|e| e.with_location(get_location!()))
+    }
+
+    /// Totals of the transactions matching the tags of a summary category or one of its item types.
+    pub async fn get_category_totals(
+        db: &DbConn,
+        tags: &[String],
+        item_types: &[String],
+    ) -> Result<TransactionTotals, Error> {
+        let mut condition =
+            Condition::any().add(transaction::Column::ItemType.is_in(item_types.to_vec()));
+        for tag in tags {
+            // Whole tags only, "mod" must not match "arcane_mod".
+            // instr takes the tag as is, LIKE would read "_" and "%" in it as wildcards
+            condition = condition.add(Expr::cust_with_values(
+                "instr(',' || REPLACE(\"tags\", ' ', '') || ',', ?) > 0",
+                [format!(",{},", tag.replace(' ', ""))],
+            ));
+        }
+        Self::get_totals_of(db, Transaction::find().filter(condition))
+            .await
+            .map_err(|e| e.with_location(get_location!()))
+    }
+
+    async fn get_totals_of(
+        db: &DbConn,
+        stmt: Select<Transaction>,
+    ) -> Result<TransactionTotals, Error> {
+        select_totals(stmt)
+            .into_model::<TransactionTotals>()
+            .one(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    "TransactionQuery:GetTotals".to_string(),
+                    "Failed to get Transaction totals",
+                    e,
+                    get_location!(),
+                )
+            })
+            .map(|totals| totals.unwrap_or_default())
+    }
+
+    /// Totals of the matching transactions grouped by `key`, ordered by expressions of the selected totals.
+    async fn get_grouped_totals(
+        db: &DbConn,
+        stmt: Select<Transaction>,
+        key: SimpleExpr,
+        order_by: &[(&str, Order)],
+        limit: Option<u64>,
+    ) -> Result<Vec<TransactionTotals>, Error> {
+        let mut stmt = select_totals(stmt)
+            .column_as(key.clone(), "key")
+            .group_by(key);
+        for (expr, order) in order_by {
+            stmt = stmt.order_by(Expr::cust(*expr), order.clone());
+        }
+        stmt.limit(limit)
+            .into_model::<TransactionTotals>()
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    "TransactionQuery:GetGroupedTotals".to_string(),
+                    "Failed to get grouped Transaction totals",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    /// Totals per time bucket, keyed like `ReportPeriods::date_key` with the same levels.
+    pub async fn get_totals_by_date(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        levels: &[GroupByDate],
+        periods: &ReportPeriods,
+    ) -> Result<Vec<TransactionTotals>, Error> {
+        let timezone = periods.key_timezone(levels);
+        if let Some(modifier) = timezone.sql_modifier() {
+            return Self::get_grouped_totals(
+                db,
+                filter_query(query),
+                date_key(levels, modifier),
+                &[("key", Order::Asc)],
+                None,
+            )
+            .await
+            .map_err(|e| e.with_location(get_location!()));
+        }
+
+        // SQLite can not follow the daylight saving time of the timezone,
+        // the quarter hours are grouped in UTC and keyed with the offset each one had.
+        let quarters =
+            Self::get_grouped_totals(db, filter_query(query), quarter_hour_key(), &[], None)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+        let mut by_key: BTreeMap<String, TransactionTotals> = BTreeMap::new();
+        for totals in quarters {
+            let start = NaiveDateTime::parse_from_str(&totals.key, "%Y-%m-%d %H:%M:%S")
+                .map_err(|e| {
+                    Error::new(
+                        "TransactionQuery:GetTotalsByDate",
+                        format!("Invalid quarter hour '{}': {}", totals.key, e),
+                        get_location!(),
+                    )
+                })?
+                .and_utc();
+            let key = periods.date_key(start, levels);
+            by_key
+                .entry(key.clone())
+                .or_insert_with(|| TransactionTotals {
+                    key,
+                    ..Default::default()
+                })
+                .merge(&totals);
+        }
+        Ok(by_key.into_values().collect())
+    }
+
+    /// Financial report of the transactions matching the query, with the most traded items.
+    pub async fn get_financial_report(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+    ) -> Result<FinancialReport, Error> {
+        let totals = Self::get_totals(db, query)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let mut report = FinancialReport::from(&totals);
+        for (key, transaction_type) in [
+            ("most_purchased_items", TransactionType::Purchase),
+            ("most_sold_items", TransactionType::Sale),
+        ] {
+            let items = Self::get_top_items(db, query, transaction_type, 5)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?;
+            report.properties.set_property_value(key, items);
+        }
+        Ok(report)
+    }
+
+    /// Profit per time bucket, buckets without transactions between the query dates are zero.
+    pub async fn get_profit_graph(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        levels: &[GroupByDate],
+        periods: &ReportPeriods,
+    ) -> Result<FinancialGraph<i64>, Error> {
+        let mut grouped: HashMap<String, Vec<TransactionTotals>> =
+            Self::get_totals_by_date(db, query, levels, periods)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?
+                .into_iter()
+                .map(|totals| (totals.key.clone(), vec![totals]))
+                .collect();
+        if let (FieldChange::Value(start), FieldChange::Value(end)) =
+            (&query.from_date, &query.to_date)
+        {
+            periods.fill_missing_date_keys(&mut grouped, *start, *end, levels);
+        }
+        Ok(FinancialGraph::<i64>::from(&grouped, |group| {
+            group.iter().map(|totals| totals.profit()).sum()
+        }))
+    }
+
+    /// Item names with the highest traded quantity for one transaction type.
+    pub async fn get_top_items(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        transaction_type: TransactionType,
+        limit: u64,
+    ) -> Result<Vec<(String, i64)>, Error> {
+        filter_query(query)
+            .filter(is_type(transaction_type))
+            .select_only()
+            .column(transaction::Column::ItemName)
+            .column_as(Expr::col(transaction::Column::Quantity).sum(), "quantity")
+            .group_by(transaction::Column::ItemName)
+            .order_by_desc(Expr::cust("quantity"))
+            .limit(limit)
+            .into_tuple::<(String, i64)>()
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    "TransactionQuery:GetTopItems".to_string(),
+                    "Failed to get top traded items",
+                    e,
+                    get_location!(),
+                )
+            })
+    }
+
+    /// Report per item, most profitable first.
+    pub async fn get_item_reports(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        limit: u64,
+    ) -> Result<Vec<FinancialReport>, Error> {
+        let totals = Self::get_grouped_totals(
+            db,
+            filter_query(query),
+            Expr::col(transaction::Column::WfmId).into(),
+            &[("profit", Order::Desc)],
+            Some(limit),
+        )
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+
+        let mut reports = Vec::with_capacity(totals.len());
+        for totals in totals {
+            let item = Transaction::find()
+                .filter(transaction::Column::WfmId.eq(&totals.key))
+                .one(db)
+                .await
+                .map_err(|e| {
+                    Error::from_db(
+                        "TransactionQuery:GetItemReports".to_string(),
+                        "Failed to find Transaction by WFM ID",
+                        e,
+                        get_location!(),
+                    )
+                })?;
+            let mut report = FinancialReport::from(&totals);
+            report.properties.set_property_value("wfm_id", &totals.key);
+            if let Some(item) = item {
+                report
+                    .properties
+                    .set_property_value("item_name", item.item_name);
+                report
+                    .properties
+                    .set_property_value("item_type", item.item_type);
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    /// Report per trading partner, most transactions first. Transactions without a partner are left out.
+    pub async fn get_partner_reports(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        limit: u64,
+    ) -> Result<Vec<FinancialReport>, Error> {
+        let stmt = filter_query(query).filter(transaction::Column::UserName.ne(""));
+        let totals = Self::get_grouped_totals(
+            db,
+            stmt,
+            Expr::col(transaction::Column::UserName).into(),
+            &[("total_transactions", Order::Desc)],
+            Some(limit),
+        )
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+        Ok(totals
+            .iter()
+            .map(|totals| {
+                let mut report = FinancialReport::from(totals);
+                report.properties.set_property_value("user", &totals.key);
+                report
+            })
+            .collect())
+    }
+
+    /// Long term report of the transactions matching the query, `limit` caps the partner and item lists.
+    /// Months are cut in the timezone of `periods`.
+    pub async fn get_history_report(
+        db: &DbConn,
+        query: &TransactionPaginationQueryDto,
+        periods: &ReportPeriods,
+        limit: u64,
+    ) -> Result<TradeHistoryReport, Error> {
+        let totals = Self::get_totals(db, query)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let partners = Self::get_grouped_totals(
+            db,
+            filter_query(query).filter(transaction::Column::UserName.ne("")),
+            Expr::col(transaction::Column::UserName).into(),
+            &[
+                ("revenue + expenses", Order::Desc),
+                ("total_transactions", Order::Desc),
+            ],
+            Some(limit),
+        )
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+        let items = Self::get_grouped_totals(
+            db,
+            filter_query(query),
+            Expr::col(transaction::Column::ItemUniqueName).into(),
+            &[
+                ("quantity_sold + quantity_bought", Order::Desc),
+                ("total_transactions", Order::Desc),
+            ],
+            Some(limit),
+        )
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+        let names = Transaction::find()
+            .select_only()
+            .column(transaction::Column::ItemUniqueName)
+            .column(transaction::Column::ItemName)
+            .filter(
+                transaction::Column::ItemUniqueName
+                    .is_in(items.iter().map(|item| item.key.clone())),
+            )
+            .group_by(transaction::Column::ItemUniqueName)
+            .into_tuple::<(String, String)>()
+            .all(db)
+            .await
+            .map_err(|e| {
+                Error::from_db(
+                    "TransactionQuery:GetHistoryReport".to_string(),
+                    "Failed to get the names of the traded items",
+                    e,
+                    get_location!(),
+                )
+            })?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        let levels = [GroupByDate::Year, GroupByDate::Month];
+        let mut months: HashMap<String, Vec<TransactionTotals>> =
+            Self::get_totals_by_date(db, query, &levels, periods)
+                .await
+                .map_err(|e| e.with_location(get_location!()))?
+                .into_iter()
+                .map(|totals| (totals.key.clone(), vec![totals]))
+                .collect();
+        // Months without trades still get a point so the series stay evenly spaced
+        if let (Some(first), Some(last)) = (totals.first_trade, totals.last_trade) {
+            periods.fill_missing_date_keys(&mut months, first, last, &levels);
+        }
+
+        Ok(TradeHistoryReport::from_totals(
+            &totals, &partners, &items, &names, &months,
+        ))
+    }
 }
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use entity::gdpr::*;
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection};
+use service::{GdprMutation, GdprQuery};
+
+static OID: &str = "5f00000000000000000000a1";
+
+async fn get_connection() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Database connection failed");
+    Migrator::up(&db, None).await.expect("Migration failed");
+    db
+}
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+}
+
+fn trade(minutes: i64, platinum: i64) -> gdpr_trade::Model {
+    gdpr_trade::Model::new(
+        OID,
+        start() + Duration::minutes(minutes),
+        "",
+        "sale",
+        platinum,
+        0,
+        serde_json::json!({}),
+    )
+}
+
+fn records(trades: Vec<gdpr_trade::Model>) -> GdprAccountRecords {
+    GdprAccountRecords {
+        account: gdpr_account::Model::new(OID, "FixtureTrader", "", serde_json::json!({})),
+        trades,
+        logins: vec![gdpr_login::Model::new(OID, start(), None, None)],
+        purchases: vec![],
+        transactions: vec![],
+    }
+}
+
+#[tokio::test]
+async fn import_stores_every_record() {
+    let db = get_connection().await;
+    let counts = GdprMutation::import(&db, vec![records(vec![trade(0, 25), trade(5, 40)])])
+        .await
+        .unwrap();
+    assert_eq!(counts.trades, 2);
+    assert_eq!(counts.logins, 1);
+    assert_eq!(GdprQuery::get_accounts(&db).await.unwrap().len(), 1);
+    assert_eq!(GdprQuery::get_trades(&db).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn reimport_skips_the_stored_records_at_the_watermark() {
+    let db = get_connection().await;
+    GdprMutation::import(&db, vec![records(vec![trade(5, 40)])])
+        .await
+        .unwrap();
+    let watermark = GdprQuery::get_watermarks(&db).await.unwrap()[OID].clone();
+    assert!(GdprWatermark::is_new(
+        watermark.trades,
+        start() + Duration::minutes(5)
+    ));
+
+    // A second trade in the same second as the stored one was not in the first export
+    let counts = GdprMutation::import(&db, vec![records(vec![trade(5, 40), trade(5, 60)])])
+        .await
+        .unwrap();
+    assert_eq!(counts.trades, 1);
+    assert_eq!(counts.logins, 0);
+    assert_eq!(GdprQuery::get_trades(&db).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn a_failed_import_stores_nothing() {
+    let db = get_connection().await;
+    // The transactions are written last, after the account, trades and logins
+    db.execute_unprepared("DROP TABLE gdpr_transaction")
+        .await
+        .unwrap();
+    let mut failing = records(vec![trade(0, 25)]);
+    failing.transactions = vec![gdpr_transaction::Model::new(
+        OID,
+        start(),
+        "sku",
+        4.99,
+        "USD",
+        "vendor",
+        "account",
+    )];
+    assert!(GdprMutation::import(&db, vec![failing]).await.is_err());
+    assert!(GdprQuery::get_accounts(&db).await.unwrap().is_empty());
+    assert!(GdprQuery::get_logins(&db).await.unwrap().is_empty());
+}
@@ -0,0 +1,224 @@
+use chrono::{Duration, TimeZone, Utc};
+use entity::{
+    enums::{FieldChange, LotMethod},
+    stock_item::{self, UpdateStockItem},
+    stock_lot::{self, LotConsumption},
+};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{Database, DatabaseConnection, EntityTrait};
+use service::{StockItemMutation, StockLotMutation, StockLotQuery};
+use utils::Properties;
+
+static URL: &str = "nikana_prime_blade";
+
+async fn get_connection() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Database connection failed");
+    Migrator::up(&db, None).await.expect("Migration failed");
+    db
+}
+
+/// Seeds three lots, oldest first: 2 at 10, 3 at 20 and 5 at 30 platinum.
+async fn seed_lots(db: &DatabaseConnection) -> Vec<i64> {
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let mut ids = vec![];
+    for (days, quantity, unit_cost) in [(0, 2, 10), (1, 3, 20), (2, 5, 30)] {
+        let lot = stock_lot::Model::new(
+            "id",
+            URL,
+            None,
+            quantity,
+            quantity * unit_cost,
+            start + Duration::days(days),
+        );
+        ids.push(StockLotMutation::create(db, lot).await.unwrap().id);
+    }
+    ids
+}
+
+async fn seed_item(db: &DatabaseConnection, owned: i64, bought: i64) -> stock_item::Model {
+    let item = stock_item::Model::new(
+        "id".to_string(),
+        URL.to_string(),
+        "Nikana Prime Blade".to_string(),
+        "/Lotus/Types/Recipes/Weapons/WeaponParts/NikanaPrimeBlade".to_string(),
+        None,
+        bought,
+        owned,
+        false,
+        Properties::default(),
+    );
+    StockItemMutation::create(db, item).await.unwrap()
+}
+
+async fn remaining(db: &DatabaseConnection) -> Vec<i64> {
+    StockLotQuery::get_by_url_and_sub_type(db, URL, None, true)
+        .await
+        .unwrap()
+        .iter()
+        .map(|lot| lot.remaining)
+        .collect()
+}
+
+fn taken(consumption: &LotConsumption) -> Vec<(i64, i64)> {
+    consumption
+        .lots
+        .iter()
+        .map(|lot| (lot.lot_id, lot.quantity))
+        .collect()
+}
+
+#[tokio::test]
+async fn fifo_takes_the_oldest_lots_first() {
+    let db = get_connection().await;
+    let ids = seed_lots(&db).await;
+
+    let consumption = StockLotMutation::consume(&db, URL, None, 4, &LotMethod::Fifo, &[])
+        .await
+        .unwrap();
+
+    assert_eq!(taken(&consumption), vec![(ids[0], 2), (ids[1], 2)]);
+    assert_eq!(consumption.cost, 60.0);
+    assert_eq!(consumption.uncovered, 0);
+    assert_eq!(remaining(&db).await, vec![0, 1, 5]);
+}
+
+#[tokio::test]
+async fn lifo_takes_the_newest_lots_first() {
+    let db = get_connection().await;
+    let ids = seed_lots(&db).await;
+
+    let consumption = StockLotMutation::consume(&db, URL, None, 6, &LotMethod::Lifo, &[])
+        .await
+        .unwrap();
+
+    assert_eq!(taken(&consumption), vec![(ids[2], 5), (ids[1], 1)]);
+    assert_eq!(consumption.cost, 170.0);
+    assert_eq!(remaining(&db).await, vec![2, 2, 0]);
+}
+
+#[tokio::test]
+async fn specific_takes_the_picked_lots_then_the_oldest() {
+    let db = get_connection().await;
+    let ids = seed_lots(&db).await;
+
+    let consumption = StockLotMutation::consume(&db, URL, None, 4, &LotMethod::Specific, &[ids[2]])
+        .await
+        .unwrap();
+    assert_eq!(taken(&consumption), vec![(ids[2], 4)]);
+    assert_eq!(consumption.cost, 120.0);
+
+    let consumption = StockLotMutation::consume(&db, URL, None, 5, &LotMethod::Specific, &[ids[1]])
+        .await
+        .unwrap();
+    assert_eq!(taken(&consumption), vec![(ids[1], 3), (ids[0], 2)]);
+    assert_eq!(consumption.cost, 80.0);
+    assert_eq!(remaining(&db).await, vec![0, 0, 1]);
+}
+
+#[tokio::test]
+async fn units_without_a_lot_are_uncovered() {
+    let db = get_connection().await;
+    seed_lots(&db).await;
+
+    let consumption = StockLotMutation::consume(&db, URL, None, 12, &LotMethod::Fifo, &[])
+        .await
+        .unwrap();
+
+    assert_eq!(consumption.quantity, 10);
+    assert_eq!(consumption.uncovered, 2);
+    assert_eq!(remaining(&db).await, vec![0, 0, 0]);
+}
+
+#[tokio::test]
+async fn sell_updates_the_item_with_the_lots_left() {
+    let db = get_connection().await;
+    seed_lots(&db).await;
+    let item = seed_item(&db, 10, 23).await;
+
+    let (operation, _, consumption) =
+        StockLotMutation::sell(&db, URL, None, 4, &LotMethod::Fifo, &[])
+            .await
+            .unwrap();
+
+    assert_eq!(operation, "Updated");
+    assert_eq!(consumption.lots.len(), 2);
+    let item = stock_item::Entity::find_by_id(item.id)
+        .one(&db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(item.owned, 6);
+    // (1 * 20 + 5 * 30) / 6
+    assert_eq!(item.bought, 28);
+}
+
+#[tokio::test]
+async fn buy_adds_the_item_and_a_lot_for_each_purchase() {
+    let db = get_connection().await;
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let item = |owned, bought| stock_item::Model {
+        owned,
+        bought,
+        ..stock_item::Model::new(
+            "id".to_string(),
+            URL.to_string(),
+            "Nikana Prime Blade".to_string(),
+            "/Lotus/Types/Recipes/Weapons/WeaponParts/NikanaPrimeBlade".to_string(),
+            None,
+            0,
+            0,
+            false,
+            Properties::default(),
+        )
+    };
+
+    let (operation, created, lot) = StockLotMutation::buy(
+        &db,
+        item(2, 40),
+        stock_lot::Model::new("id", URL, None, 2, 40, start),
+    )
+    .await
+    .unwrap();
+    assert_eq!(operation, "Created");
+    assert_eq!((created.owned, created.bought), (2, 20));
+    assert_eq!(lot.unit_cost, 20.0);
+
+    let (operation, updated, _) = StockLotMutation::buy(
+        &db,
+        item(3, 90),
+        stock_lot::Model::new("id", URL, None, 3, 90, start + Duration::days(1)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(operation, "Updated");
+    // (2 * 20 + 90) / 5
+    assert_eq!((updated.owned, updated.bought), (5, 26));
+    assert_eq!(remaining(&db).await, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn changing_owned_adjusts_the_open_lots() {
+    let db = get_connection().await;
+    seed_lots(&db).await;
+    let item = seed_item(&db, 10, 23).await;
+
+    let mut input = UpdateStockItem::new(item.id);
+    input.owned = FieldChange::Value(7);
+    StockLotMutation::update_item(&db, input, &LotMethod::Fifo)
+        .await
+        .unwrap();
+    assert_eq!(remaining(&db).await, vec![0, 2, 5]);
+
+    let mut input = UpdateStockItem::new(item.id);
+    input.owned = FieldChange::Value(9);
+    StockLotMutation::update_item(&db, input, &LotMethod::Fifo)
+        .await
+        .unwrap();
+    assert_eq!(remaining(&db).await, vec![0, 2, 5, 2]);
+    let lots = StockLotQuery::get_by_url_and_sub_type(&db, URL, None, false)
+        .await
+        .unwrap();
+    assert_eq!(lots.last().unwrap().unit_cost, 23.0);
+}
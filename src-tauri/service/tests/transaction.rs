@@ -0,0 +1,336 @@
+use chrono::{TimeZone, Utc};
+use entity::{
+    dto::FinancialReport,
+    enums::{TransactionItemType, TransactionType},
+    transaction::{dto::TransactionPaginationQueryDto, transaction},
+};
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{Database, DatabaseConnection};
+use serde_json::Value;
+use service::{TransactionMutation, TransactionQuery};
+use utils::{group_by, GroupByDate, ReportPeriods};
+
+async fn get_connection() -> DatabaseConnection {
+    let db = Database::connect("sqlite::memory:")
+        .await
+        .expect("Database connection failed");
+    Migrator::up(&db, None).await.expect("Migration failed");
+    db
+}
+
+/// Seeds a ledger over January and March 2026, February has no trades.
+async fn seed_transactions(db: &DatabaseConnection) -> Vec<transaction::Model> {
+    use TransactionType::*;
+    let items = [
+        ("ash_prime_set", "Ash Prime Set", "set"),
+        ("arcane_energize", "Arcane Energize", "arcane_mod"),
+        ("serration", "Serration", "mod"),
+    ];
+    // (month, day), item, type, quantity, user, price
+    let rows = [
+        ((1, 5), 0, Purchase, 1, "buyer", 60),
+        ((1, 6), 0, Sale, 1, "seller", 95),
+        ((1, 20), 1, Purchase, 2, "seller", 80),
+        ((1, 31), 2, Sale, 3, "", 15),
+        ((3, 2), 1, Sale, 1, "seller", 55),
+        ((3, 2), 1, Sale, 1, "other", 50),
+        ((3, 15), 0, Purchase, 1, "buyer", 58),
+    ];
+    let mut transactions = vec![];
+    for ((month, day), item, transaction_type, quantity, user, price) in rows {
+        let (url, name, tag) = items[item];
+        let mut transaction = transaction::Model::new(
+            format!("{}_id", url),
+            url,
+            name,
+            TransactionItemType::Item,
+            format!("/Lotus/{}", name.replace(' ', "")),
+            None,
+            vec![tag.to_string()],
+            transaction_type,
+            quantity,
+            user,
+            price,
+            1000,
+            None,
+        );
+        transaction.created_at = Utc.with_ymd_and_hms(2026, month, day, 12, 0, 0).unwrap();
+        transactions.push(
+            TransactionMutation::create(db, &transaction, false)
+                .await
+                .unwrap(),
+        );
+    }
+    transactions
+}
+
+/// The report as JSON without the item lists only the rows version computes.
+fn report_json(report: &FinancialReport) -> Value {
+    let mut value = serde_json::to_value(report).unwrap();
+    let properties = value["properties"].as_object_mut().unwrap();
+    properties.remove("most_purchased_items");
+    properties.remove("most_sold_items");
+    value
+}
+
+#[tokio::test]
+async fn totals_match_the_report_of_the_rows() {
+    let db = get_connection().await;
+    let transactions = seed_transactions(&db).await;
+
+    let totals = TransactionQuery::get_totals(&db, &TransactionPaginationQueryDto::new(1, -1))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report_json(&FinancialReport::from(&totals)),
+        report_json(&FinancialReport::from(&transactions))
+    );
+    assert_eq!(totals.quantity_sold, 6);
+    assert_eq!(totals.quantity_bought, 4);
+    assert_eq!(totals.first_trade, Some(transactions[0].created_at));
+    assert_eq!(totals.last_trade, Some(transactions[6].created_at));
+}
+
+#[tokio::test]
+async fn totals_by_date_match_the_report_of_each_month() {
+    let db = get_connection().await;
+    let transactions = seed_transactions(&db).await;
+    let periods = ReportPeriods::new("utc", 1, false);
+    let levels = [GroupByDate::Year, GroupByDate::Month];
+
+    let by_date = TransactionQuery::get_totals_by_date(
+        &db,
+        &TransactionPaginationQueryDto::new(1, -1),
+        &levels,
+        &periods,
+    )
+    .await
+    .unwrap();
+
+    let months = group_by(&transactions, |t| periods.date_key(t.created_at, &levels));
+    assert_eq!(
+        by_date.iter().map(|t| t.key.as_str()).collect::<Vec<_>>(),
+        vec!["2026-01", "2026-03"]
+    );
+    for totals in &by_date {
+        assert_eq!(
+            report_json(&FinancialReport::from(totals)),
+            report_json(&FinancialReport::from(&months[&totals.key])),
+            "{}",
+            totals.key
+        );
+    }
+}
+
+#[tokio::test]
+async fn category_tags_are_matched_whole_and_literally() {
+    let db = get_connection().await;
+    seed_transactions(&db).await;
+
+    let arcanes = TransactionQuery::get_category_totals(&db, &["arcane_mod".to_string()], &[])
+        .await
+        .unwrap();
+    assert_eq!(arcanes.total_transactions, 3);
+
+    // "_" and "%" are not wildcards
+    for tag in ["arcane%", "arcane_", "arcane%mod", "mo_"] {
+        let totals = TransactionQuery::get_category_totals(&db, &[tag.to_string()], &[])
+            .await
+            .unwrap();
+        assert_eq!(totals.total_transactions, 0, "{}", tag);
+    }
+}
+
+#[tokio::test]
+async fn history_report_is_built_from_the_totals() {
+    let db = get_connection().await;
+    seed_transactions(&db).await;
+    let periods = ReportPeriods::new("utc", 1, false);
+
+    let report = TransactionQuery::get_history_report(
+        &db,
+        &TransactionPaginationQueryDto::new(1, -1),
+        &periods,
+        2,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.total_transactions, 7);
+    // seller traded 95 + 80 + 55, buyer 60 + 58, other 50 and the empty user is left out
+    let partners = report
+        .top_partners
+        .iter()
+        .map(|p| (p.user_name.as_str(), p.transactions, p.revenue, p.expenses))
+        .collect::<Vec<_>>();
+    assert_eq!(partners, vec![("seller", 3, 150, 80), ("buyer", 2, 0, 118)]);
+    assert_eq!(
+        report.top_partners[0].first_trade,
+        Utc.with_ymd_and_hms(2026, 1, 6, 12, 0, 0).unwrap()
+    );
+
+    let items = report
+        .most_traded_items
+        .iter()
+        .map(|i| (i.item_name.as_str(), i.quantity_sold, i.quantity_bought))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        vec![("Arcane Energize", 2, 2), ("Ash Prime Set", 1, 2)]
+    );
+
+    let flow = serde_json::to_value(&report.platinum_flow).unwrap();
+    assert_eq!(
+        flow["labels"],
+        serde_json::json!(["2026-01", "2026-02", "2026-03"])
+    );
+}
+
+#[tokio::test]
+async fn one_sided_partners_are_ranked_by_their_volume() {
+    let db = get_connection().await;
+    seed_transactions(&db).await;
+    // Only ever sold to, once, but for more than anyone else traded
+    let mut whale = transaction::Model::new(
+        "serration_id",
+        "serration",
+        "Serration",
+        TransactionItemType::Item,
+        "/Lotus/Serration",
+        None,
+        vec!["mod".to_string()],
+        TransactionType::Sale,
+        1,
+        "whale",
+        1000,
+        1000,
+        None,
+    );
+    whale.created_at = Utc.with_ymd_and_hms(2026, 3, 20, 12, 0, 0).unwrap();
+    TransactionMutation::create(&db, &whale, false)
+        .await
+        .unwrap();
+
+    let report = TransactionQuery::get_history_report(
+        &db,
+        &TransactionPaginationQueryDto::new(1, -1),
+        &ReportPeriods::new("utc", 1, false),
+        3,
+    )
+    .await
+    .unwrap();
+
+    let partners = report
+        .top_partners
+        .iter()
+        .map(|p| (p.user_name.as_str(), p.revenue, p.expenses))
+        .collect::<Vec<_>>();
+    // buyer only sold to us and is ranked on the expenses alone
+    assert_eq!(
+        partners,
+        vec![("whale", 1000, 0), ("seller", 150, 80), ("buyer", 0, 118)]
+    );
+}
+
+#[tokio::test]
+async fn named_timezones_bucket_each_row_with_its_own_offset() {
+    let db = get_connection().await;
+    // 23:30 on Jan 31 in Berlin (+01:00) and 00:30 on Jul 1 (+02:00), no single offset gets both right
+    let mut transactions = vec![];
+    for (created_at, price) in [
+        (Utc.with_ymd_and_hms(2026, 1, 31, 22, 30, 0).unwrap(), 10),
+        (Utc.with_ymd_and_hms(2026, 6, 30, 22, 30, 0).unwrap(), 20),
+        (Utc.with_ymd_and_hms(2026, 6, 30, 21, 45, 0).unwrap(), 30),
+    ] {
+        let mut transaction = transaction::Model::new(
+            "serration_id",
+            "serration",
+            "Serration",
+            TransactionItemType::Item,
+            "/Lotus/Serration",
+            None,
+            vec!["mod".to_string()],
+            TransactionType::Sale,
+            1,
+            "buyer",
+            price,
+            1000,
+            None,
+        );
+        transaction.created_at = created_at;
+        transactions.push(
+            TransactionMutation::create(&db, &transaction, false)
+                .await
+                .unwrap(),
+        );
+    }
+    let periods = ReportPeriods::new("Europe/Berlin", 1, false);
+    let levels = [GroupByDate::Year, GroupByDate::Month, GroupByDate::Day];
+
+    let by_date = TransactionQuery::get_totals_by_date(
+        &db,
+        &TransactionPaginationQueryDto::new(1, -1),
+        &levels,
+        &periods,
+    )
+    .await
+    .unwrap();
+
+    let days = group_by(&transactions, |t| periods.date_key(t.created_at, &levels));
+    assert_eq!(
+        by_date.iter().map(|t| t.key.as_str()).collect::<Vec<_>>(),
+        vec!["2026-01-31", "2026-06-30", "2026-07-01"]
+    );
+    assert_eq!(by_date.len(), days.len());
+    for totals in &by_date {
+        assert_eq!(
+            report_json(&FinancialReport::from(totals)),
+            report_json(&FinancialReport::from(&days[&totals.key])),
+            "{}",
+            totals.key
+        );
+    }
+}
+
+#[tokio::test]
+async fn create_all_stores_every_row() {
+    let db = get_connection().await;
+    let rows = (1..=3)
+        .map(|day| {
+            let mut row = transaction::Model::new(
+                "ash_prime_set_id",
+                "ash_prime_set",
+                "Ash Prime Set",
+                TransactionItemType::Item,
+                "/Lotus/AshPrimeSet",
+                None,
+                vec!["set".to_string()],
+                TransactionType::Sale,
+                1,
+                "",
+                100 + day as i64,
+                0,
+                None,
+            );
+            row.created_at = Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap();
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let created = TransactionMutation::create_all(&db, &rows).await.unwrap();
+    assert_eq!(created.len(), 3);
+    let stored = TransactionQuery::get_all(&db, TransactionPaginationQueryDto::new(1, -1))
+        .await
+        .unwrap()
+        .results;
+    let mut prices = stored.iter().map(|t| t.price).collect::<Vec<_>>();
+    prices.sort();
+    assert_eq!(prices, vec![101, 102, 103]);
+    // The import dates are kept
+    assert!(stored.iter().all(|t| t
+        .created_at
+        .date_naive()
+        .to_string()
+        .starts_with("2026-01-0")));
+}
@@ -13,7 +13,7 @@ use wf_market::types::websocket::{WsClient, WsMessage};
 use wf_market::types::{Chat, ChatMessage};
 use wf_market::Client as WFClient;
 
-use crate::app::AppState;
+use crate::app::{AppState, WebhookEvent};
 use crate::app::types::app_state::get_active_chat_id;
 use crate::utils::modules::states;
 use crate::utils::ErrorFromExt;
@@ -115,6 +115,7 @@ fn handle_new_message(wfm_client: &WFClient<WFAuthenticated>, msg: &WsMessage) {
         }
         emit_update_user!(json!({ "unread_messages": un_read }));
         state.settings.notifications.on_wfm_chat_message.send(
+            WebhookEvent::WfmChatMessage,
             &HashMap::from([
                 (
                     "<WFM_MESSAGE>".to_string(),
@@ -19,6 +19,12 @@ impl Default for DebuggingSettings {
 pub struct DebuggingLiveScraperSettings {
     pub entries: Vec<ItemEntry>,
     pub fake_orders: bool,
+    // Route every order/auction change to the decision journal instead of Warframe Market
+    #[serde(default)]
+    pub simulation: bool,
+    // Store the price info and orders of every processed item for the backtester
+    #[serde(default)]
+    pub record_snapshots: bool,
 }
 
 impl Default for DebuggingLiveScraperSettings {
@@ -26,6 +32,8 @@ impl Default for DebuggingLiveScraperSettings {
         DebuggingLiveScraperSettings {
             entries: Vec::new(),
             fake_orders: false,
+            simulation: false,
+            record_snapshots: false,
         }
     }
 }
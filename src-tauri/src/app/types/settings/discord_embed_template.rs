@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{render_template, validate_template, NotifyLists};
+
+// Discord rejects embeds over these sizes
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct DiscordEmbedFieldTemplate {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+/// Embed sent instead of the default one, every text is a notification template.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct DiscordEmbedTemplate {
+    pub title: String,
+    pub description: String,
+    // Hex colour like "#58B9FF"
+    pub color: String,
+    // Image url, e.g. "<ITEM_ICON>"
+    #[serde(default)]
+    pub thumbnail: String,
+    #[serde(default)]
+    pub fields: Vec<DiscordEmbedFieldTemplate>,
+}
+
+impl DiscordEmbedTemplate {
+    pub fn color_value(&self) -> Option<u32> {
+        u32::from_str_radix(self.color.trim().trim_start_matches('#'), 16)
+            .ok()
+            .filter(|c| *c <= 0xFFFFFF)
+    }
+
+    /// Returns the embed object, fields that render empty are left out since Discord refuses them.
+    pub fn render(&self, variables: &HashMap<String, String>, lists: &NotifyLists) -> Value {
+        let render = |text: &str| render_template(text, variables, lists).trim().to_string();
+        let fields = self
+            .fields
+            .iter()
+            .map(|f| (render(&f.name), render(&f.value), f.inline))
+            .filter(|(name, value, _)| !name.is_empty() && !value.is_empty())
+            .map(|(name, value, inline)| {
+                json!({
+                    "name": truncate(&name, MAX_FIELD_NAME_LENGTH),
+                    "value": truncate(&value, MAX_FIELD_VALUE_LENGTH),
+                    "inline": inline
+                })
+            })
+            .take(MAX_FIELDS)
+            .collect::<Vec<_>>();
+
+        let mut embed = json!({
+            "title": truncate(&render(&self.title), MAX_TITLE_LENGTH),
+            "description": truncate(&render(&self.description), MAX_DESCRIPTION_LENGTH),
+            "color": self.color_value().unwrap_or(5814783),
+            "fields": fields,
+        });
+        let thumbnail = render(&self.thumbnail);
+        if thumbnail.starts_with("http") {
+            embed["thumbnail"] = json!({ "url": thumbnail });
+        }
+        embed
+    }
+
+    /// Returns every problem of the template, empty when it can be sent.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        let mut check = |label: &str, text: &str| {
+            if let Err(e) = validate_template(text) {
+                errors.push(format!("{}: {}", label, e));
+            }
+        };
+        check("title", &self.title);
+        check("description", &self.description);
+        check("thumbnail", &self.thumbnail);
+        for (i, field) in self.fields.iter().enumerate() {
+            check(&format!("fields[{}].name", i), &field.name);
+            check(&format!("fields[{}].value", i), &field.value);
+        }
+        if !self.color.is_empty() && self.color_value().is_none() {
+            errors.push(format!("color: '{}' is not a hex colour", self.color));
+        }
+        if self.fields.len() > MAX_FIELDS {
+            errors.push(format!("fields: at most {} are allowed", MAX_FIELDS));
+        }
+        if self.title.chars().count() > MAX_TITLE_LENGTH {
+            errors.push(format!(
+                "title: longer than {} characters",
+                MAX_TITLE_LENGTH
+            ));
+        }
+        errors
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max - 1).collect();
+    out.push('…');
+    out
+}
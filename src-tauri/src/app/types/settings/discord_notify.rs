@@ -2,9 +2,10 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use utils::{get_location, info, Error, LoggerOptions};
+use utils::get_location;
 
-use crate::APP;
+use super::{render_template, validate_template, DiscordEmbedTemplate, NotifyLists};
+use crate::{notify_queue::NotifyChannel, utils::modules::states, APP};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiscordNotify {
@@ -12,6 +13,9 @@ pub struct DiscordNotify {
     pub content: String,
     pub webhook: String,
     pub user_ids: Vec<String>,
+    // Replaces the default embed when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embed: Option<DiscordEmbedTemplate>,
 }
 impl DiscordNotify {
     pub fn new(
@@ -24,9 +28,21 @@ impl DiscordNotify {
             content: content.into(),
             webhook: webhook.into(),
             user_ids,
+            embed: None,
         }
     }
-    pub fn send(&self, variables: &HashMap<String, String>) {
+    /// Returns the problems of the content and embed templates, empty when they can be sent.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if let Err(e) = validate_template(&self.content) {
+            errors.push(format!("content: {}", e));
+        }
+        if let Some(embed) = &self.embed {
+            errors.extend(embed.validate().into_iter().map(|e| format!("embed.{}", e)));
+        }
+        errors
+    }
+    pub fn send(&self, variables: &HashMap<String, String>, lists: &NotifyLists) {
         let variables = &mut variables.clone();
         if self.webhook.is_empty() {
             return;
@@ -42,51 +58,35 @@ impl DiscordNotify {
         } else {
             variables.insert("<MENTION>".to_string(), "".to_string());
         }
-        let mut content = self.content.clone();
-        for (k, v) in variables.iter() {
-            content = content.replace(&format!("{}", k), v);
-        }
-        let webhook = self.webhook.clone();
+        let content = render_template(&self.content, variables, lists);
+        let embed = self.embed.as_ref().map(|e| e.render(variables, lists));
         let tauri_app = APP.get().expect("App handle not found");
         let app_info = tauri_app.package_info().clone();
-        tauri::async_runtime::spawn(async move {
-            let client = reqwest::Client::new();
-            let timestamp = chrono::Local::now()
-                .to_utc()
-                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                .to_string();
-            let body = json!({
-                "content": content,
-                "embeds": [
-                    {
-                        "description": "",
-                        "color": 5814783,
-                        "footer": {
-                            "text": format!("{} v{} ({})",app_info.name, app_info.version, app_info.authors),
-                            "icon_url": "https://raw.githubusercontent.com/Kenya-DK/quantframe-react/refs/heads/main/app-icon.png"
-                        },
-                        "timestamp": timestamp
-                    }
-                ]
-            });
-            let res = client.post(webhook).json(&body).send().await;
-            match res {
-                Ok(_) => {
-                    info(
-                        "Helper",
-                        "Message sent to discord",
-                        &LoggerOptions::default(),
-                    );
-                }
-                Err(e) => {
-                    let err = Error::new(
-                        "DiscordNotificationError",
-                        &format!("{:?}", e),
-                        get_location!(),
-                    );
-                    err.log("discord_notification.log");
-                }
-            }
+        let timestamp = chrono::Local::now()
+            .to_utc()
+            .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+            .to_string();
+        let mut embed = embed.unwrap_or(json!({
+            "description": "",
+            "color": 5814783,
+        }));
+        embed["footer"] = json!({
+            "text": format!("{} v{} ({})",app_info.name, app_info.version, app_info.authors),
+            "icon_url": "https://raw.githubusercontent.com/Kenya-DK/quantframe-react/refs/heads/main/app-icon.png"
         });
+        embed["timestamp"] = json!(timestamp);
+        let body = json!({
+            "content": content,
+            "embeds": [embed]
+        });
+        match states::notify_queue() {
+            Ok(queue) => {
+                queue.enqueue(NotifyChannel::Discord, &self.webhook, HashMap::new(), body);
+            }
+            Err(e) => {
+                e.with_location(get_location!())
+                    .log("discord_notification.log");
+            }
+        }
     }
 }
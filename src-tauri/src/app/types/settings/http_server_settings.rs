@@ -5,9 +5,13 @@ pub struct HttpServerSettings {
     pub enable: bool,
     pub host: String,
     pub port: u16,
+    // Bearer token required by every API route, the API is closed while it is empty
+    #[serde(default)]
+    pub token: String,
 }
 impl HttpServerSettings {
     pub fn uuid(&self) -> String {
+        // The token is read on every request, changing it needs no restart
         format!("E:{}:H{}:P{}", self.enable, self.host, self.port)
     }
 }
@@ -17,6 +21,7 @@ impl Default for HttpServerSettings {
             enable: false,
             host: "127.0.0.1".into(),
             port: 4808,
+            token: "".into(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use entity::enums::LotMethod;
 use serde::{Deserialize, Serialize};
 use utils::SubType;
 
@@ -9,6 +10,9 @@ use super::*;
 pub struct ItemGeneralSettings {
     pub blacklist: Vec<BlackListItemSetting>,
     pub buy_list: Vec<BuyListItemSetting>,
+    // Which purchase lots a sale consumes when the realized profit is calculated
+    #[serde(default)]
+    pub lot_method: LotMethod,
 }
 impl ItemGeneralSettings {
     pub fn is_item_blacklisted(
@@ -38,6 +42,7 @@ impl Default for ItemGeneralSettings {
         Self {
             blacklist: Vec::new(),
             buy_list: Vec::new(),
+            lot_method: LotMethod::default(),
         }
     }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::PricingSettings;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemWtbSettings {
     pub volume_threshold: i64,
@@ -14,6 +16,8 @@ pub struct ItemWtbSettings {
     pub max_stock_quantity: i64,
     pub max_price_drop: i64,
     pub min_listings_below: i64,
+    #[serde(default)]
+    pub pricing: PricingSettings,
 }
 
 impl Default for ItemWtbSettings {
@@ -31,6 +35,7 @@ impl Default for ItemWtbSettings {
             max_stock_quantity: -1,
             max_price_drop: -1,
             min_listings_below: -1,
+            pricing: PricingSettings::default(),
         }
     }
 }
@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 
+use super::PricingSettings;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ItemWtsSettings {
     pub min_sma: i64,
     pub min_profit: i64,
     pub max_price_drop: i64,
     pub min_listings_below: i64,
+    #[serde(default)]
+    pub pricing: PricingSettings,
 }
 
 impl Default for ItemWtsSettings {
@@ -15,6 +19,7 @@ impl Default for ItemWtsSettings {
             min_profit: 10,
             max_price_drop: -1,
             min_listings_below: -1,
+            pricing: PricingSettings::default(),
         }
     }
 }
@@ -9,6 +9,13 @@ pub struct LiveScraperGeneralSettings {
     pub stock_mode: StockMode,
     pub trade_modes: Vec<TradeMode>,
     pub delete_conflicting_orders: bool,
+    // Store the top of the order book of every processed item, off by default as it writes a row per item per cycle
+    pub market_snapshots: bool,
+    // Days to keep the market snapshots, -1 keeps them forever
+    pub market_snapshot_retention_days: i64,
+    // Token bucket budget shared by every Warframe Market request of the live scraper
+    pub requests_per_second: f64,
+    pub request_burst: f64,
 }
 
 impl Default for LiveScraperGeneralSettings {
@@ -21,6 +28,10 @@ impl Default for LiveScraperGeneralSettings {
             stock_mode: StockMode::All,
             trade_modes: vec![TradeMode::Buy, TradeMode::Sell, TradeMode::WishList],
             delete_conflicting_orders: false,
+            market_snapshots: false,
+            market_snapshot_retention_days: 30,
+            requests_per_second: 2.0,
+            request_burst: 3.0,
         }
     }
 }
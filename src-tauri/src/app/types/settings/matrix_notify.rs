@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utils::{get_location, Error};
+
+use super::{render_template, validate_template, NotificationChannel, NotifyLists, WebhookEvent};
+use crate::{
+    notify_queue::{HttpMethod, NotifyChannel, QueuedNotification},
+    utils::modules::states,
+};
+
+// Stands for the access token in the queued request
+const ACCESS_TOKEN: &str = "{access_token}";
+
+/// Posts the notification as a text message to a Matrix room with the access token of a bot account.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatrixNotify {
+    pub enabled: bool,
+    // e.g. "https://matrix.org"
+    pub homeserver: String,
+    // e.g. "!abcdef:matrix.org"
+    pub room_id: String,
+    pub access_token: String,
+    pub content: String,
+}
+
+impl MatrixNotify {
+    pub fn new(
+        homeserver: impl Into<String>,
+        room_id: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            enabled: true,
+            homeserver: homeserver.into(),
+            room_id: room_id.into(),
+            access_token: String::new(),
+            content: content.into(),
+        }
+    }
+
+    /// Url of a new message event, the transaction id makes retries of the same message idempotent.
+    fn get_send_url(&self, txn_id: &str) -> Result<Url, Error> {
+        let mut url = Url::parse(&self.homeserver).map_err(|e| {
+            Error::new(
+                "MatrixNotify",
+                format!("Invalid homeserver url {}: {}", self.homeserver, e),
+                get_location!(),
+            )
+        })?;
+        url.path_segments_mut()
+            .map_err(|_| {
+                Error::new(
+                    "MatrixNotify",
+                    format!("Invalid homeserver url {}", self.homeserver),
+                    get_location!(),
+                )
+            })?
+            .pop_if_empty()
+            .extend([
+                "_matrix",
+                "client",
+                "v3",
+                "rooms",
+                &self.room_id,
+                "send",
+                "m.room.message",
+                txn_id,
+            ]);
+        Ok(url)
+    }
+}
+
+impl NotificationChannel for MatrixNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        _event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        _value: Option<&Value>,
+    ) {
+        if self.room_id.is_empty() || self.access_token.is_empty() {
+            return;
+        }
+        let url = match self.get_send_url(&uuid::Uuid::new_v4().to_string()) {
+            Ok(url) => url,
+            Err(e) => {
+                e.log(NotifyChannel::Matrix.log_file());
+                return;
+            }
+        };
+        let body = json!({
+            "msgtype": "m.text",
+            "body": render_template(&self.content, variables, lists),
+        });
+        let headers = HashMap::from([(
+            "Authorization".to_string(),
+            format!("Bearer {}", ACCESS_TOKEN),
+        )]);
+        match states::notify_queue() {
+            Ok(queue) => {
+                queue.enqueue_message(
+                    QueuedNotification::new(
+                        NotifyChannel::Matrix,
+                        HttpMethod::Put,
+                        url.as_str(),
+                        headers,
+                        body,
+                    )
+                    .with_secret(ACCESS_TOKEN, &self.access_token),
+                );
+            }
+            Err(e) => {
+                e.with_location(get_location!())
+                    .log(NotifyChannel::Matrix.log_file());
+            }
+        }
+    }
+    fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if let Err(e) = validate_template(&self.content) {
+            errors.push(format!("content: {}", e));
+        }
+        if !self.enabled {
+            return errors;
+        }
+        if self.room_id.is_empty() || self.access_token.is_empty() {
+            errors.push("room_id and access_token are required".to_string());
+        }
+        if let Err(e) = self.get_send_url("validate") {
+            errors.push(format!("homeserver: {}", e.message));
+        }
+        errors
+    }
+}
@@ -28,12 +28,21 @@ pub use riven_general_settings::*;
 pub mod riven_wts_settings;
 pub use riven_wts_settings::*;
 
+pub mod riven_wtb_settings;
+pub use riven_wtb_settings::*;
+
+pub mod riven_watch_setting;
+pub use riven_watch_setting::*;
+
 pub mod black_list_item_setting;
 pub use black_list_item_setting::*;
 
 pub mod buy_list_item_setting;
 pub use buy_list_item_setting::*;
 
+pub mod pricing_settings;
+pub use pricing_settings::*;
+
 pub mod summary_settings;
 pub use summary_settings::*;
 
@@ -55,12 +64,39 @@ pub use system_notify::*;
 pub mod discord_notify;
 pub use discord_notify::*;
 
+pub mod discord_embed_template;
+pub use discord_embed_template::*;
+
+pub mod notify_template;
+pub use notify_template::*;
+
 pub mod webhook_notify;
 pub use webhook_notify::*;
 
+pub mod webhook_event;
+pub use webhook_event::*;
+
+pub mod notification_channel;
+pub use notification_channel::*;
+
+pub mod telegram_notify;
+pub use telegram_notify::*;
+
+pub mod ntfy_notify;
+pub use ntfy_notify::*;
+
+pub mod matrix_notify;
+pub use matrix_notify::*;
+
 pub mod notifications_setting;
 pub use notifications_setting::*;
 
+pub mod price_alert_settings;
+pub use price_alert_settings::*;
+
+pub mod price_alert_rule;
+pub use price_alert_rule::*;
+
 pub mod log_settings;
 pub use log_settings::*;
 
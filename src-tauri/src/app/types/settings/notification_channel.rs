@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::*;
+
+/// Something a notification can be sent through.
+/// Every channel gets the same `<VAR>` variables and lists, the webhook also gets the event payload.
+pub trait NotificationChannel {
+    fn is_enabled(&self) -> bool;
+    fn notify(
+        &self,
+        event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        value: Option<&Value>,
+    );
+    /// Returns the problems of the channel settings, empty when it can be sent.
+    fn validate(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Channels that can be added to a notification besides the built-in system, Discord and webhook ones.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannelSetting {
+    Telegram(TelegramNotify),
+    Ntfy(NtfyNotify),
+    Matrix(MatrixNotify),
+}
+
+impl NotificationChannelSetting {
+    pub fn as_channel(&self) -> &dyn NotificationChannel {
+        match self {
+            NotificationChannelSetting::Telegram(channel) => channel,
+            NotificationChannelSetting::Ntfy(channel) => channel,
+            NotificationChannelSetting::Matrix(channel) => channel,
+        }
+    }
+
+    /// Credential the channel sends, kept out of the notification queue.
+    pub fn secret(&self) -> Option<&str> {
+        let secret = match self {
+            NotificationChannelSetting::Telegram(channel) => &channel.bot_token,
+            NotificationChannelSetting::Ntfy(channel) => &channel.token,
+            NotificationChannelSetting::Matrix(channel) => &channel.access_token,
+        };
+        Some(secret.as_str()).filter(|s| !s.is_empty())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannelSetting::Telegram(_) => "telegram",
+            NotificationChannelSetting::Ntfy(_) => "ntfy",
+            NotificationChannelSetting::Matrix(_) => "matrix",
+        }
+    }
+}
+
+impl NotificationChannel for SystemNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        _event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        _lists: &NotifyLists,
+        _value: Option<&Value>,
+    ) {
+        self.send(variables);
+    }
+}
+
+impl NotificationChannel for DiscordNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        _event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        _value: Option<&Value>,
+    ) {
+        self.send(variables, lists);
+    }
+    fn validate(&self) -> Vec<String> {
+        DiscordNotify::validate(self)
+    }
+}
+
+impl NotificationChannel for WebHookNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        event: WebhookEvent,
+        _variables: &HashMap<String, String>,
+        _lists: &NotifyLists,
+        value: Option<&Value>,
+    ) {
+        if let Some(value) = value {
+            self.send(event, value.clone());
+        }
+    }
+}
@@ -9,6 +9,8 @@ pub struct NotificationSetting {
     system_notify: SystemNotify,
     discord_notify: DiscordNotify,
     webhook_notify: WebHookNotify,
+    #[serde(default)]
+    channels: Vec<NotificationChannelSetting>,
 }
 impl NotificationSetting {
     pub fn new(
@@ -20,20 +22,63 @@ impl NotificationSetting {
             discord_notify,
             system_notify,
             webhook_notify,
+            channels: vec![],
         }
     }
 }
 
 impl NotificationSetting {
-    pub fn send(&self, variables: &HashMap<String, String>, value: Option<Value>) {
-        if self.system_notify.enabled {
-            self.system_notify.send(variables);
-        }
-        if self.discord_notify.enabled {
-            self.discord_notify.send(variables);
+    pub fn send(
+        &self,
+        event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        value: Option<Value>,
+    ) {
+        self.send_with_lists(event, variables, &NotifyLists::new(), value);
+    }
+    /// Same as `send`, the lists feed the `{{#each}}` sections of the templates.
+    pub fn send_with_lists(
+        &self,
+        event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        value: Option<Value>,
+    ) {
+        let built_in: [&dyn NotificationChannel; 3] = [
+            &self.system_notify,
+            &self.discord_notify,
+            &self.webhook_notify,
+        ];
+        for channel in built_in
+            .into_iter()
+            .chain(self.channels.iter().map(|c| c.as_channel()))
+            .filter(|c| c.is_enabled())
+        {
+            channel.notify(event, variables, lists, value.as_ref());
         }
-        if self.webhook_notify.enabled && value.is_some() {
-            self.webhook_notify.send(value.unwrap());
+    }
+}
+impl NotificationSetting {
+    pub fn secrets(&self) -> impl Iterator<Item = &str> {
+        self.channels.iter().filter_map(|c| c.secret())
+    }
+
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors: Vec<String> = self
+            .discord_notify
+            .validate()
+            .into_iter()
+            .map(|e| format!("discord_notify.{}", e))
+            .collect();
+        for (i, channel) in self.channels.iter().enumerate() {
+            errors.extend(
+                channel
+                    .as_channel()
+                    .validate()
+                    .into_iter()
+                    .map(|e| format!("channels[{}].{}.{}", i, channel.as_str(), e)),
+            );
         }
+        errors
     }
 }
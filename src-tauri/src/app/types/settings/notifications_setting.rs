@@ -8,6 +8,10 @@ pub struct NotificationsSetting {
     pub on_new_conversation: NotificationSetting,
     pub on_wfm_chat_message: NotificationSetting,
     pub on_new_trade: NotificationSetting,
+    pub on_riven_deal: NotificationSetting,
+    pub on_price_alert: NotificationSetting,
+    #[serde(default)]
+    pub price_alerts: PriceAlertSettings,
 }
 
 impl Default for NotificationsSetting {
@@ -29,6 +33,53 @@ impl Default for NotificationsSetting {
                 SystemNotify::new("Item <TR_TYPE>", "From: <PLAYER_NAME>\nOffered: <OF_COUNT> Received: <RE_COUNT> Plat: <TOTAL_PLAT>","cat_meow.mp3", 1.0),
                 WebHookNotify::new("<WEBHOOK_URL>"),
             ),
+            on_riven_deal: NotificationSetting::new(
+                DiscordNotify::new("<MENTION>\n```ansi\n\x1B[1;36m🎯 Riven Deal\x1B[0m\n\n\x1B[1;33m🗡️ Riven:    \x1B[0m \x1B[1;37m<WEAPON_NAME> <MOD_NAME>\x1B[0m\n\x1B[1;33m👤 Seller:   \x1B[0m \x1B[1;37m<SELLER>\x1B[0m\n\x1B[1;33m💎 Price:    \x1B[0m \x1B[1;37m<PRICE>\x1B[0m \x1B[0;32m(target <TARGET_PRICE>)\x1B[0m\n\x1B[1;33m📊 Score:    \x1B[0m \x1B[0;35m<SCORE> | <GRADE>\x1B[0m\n\n\x1B[1;34m✨ Attributes\x1B[0m\n<ATTRIBUTES>\n\n```\n<AUCTION_URL>", "", vec![]),
+                SystemNotify::new("Riven Deal: <WEAPON_NAME> <MOD_NAME>", "<PRICE>p from <SELLER> | Score: <SCORE> | <GRADE>","cat_meow.mp3", 1.0),
+                WebHookNotify::new("<WEBHOOK_URL>"),
+            ),
+            on_price_alert: NotificationSetting::new(
+                DiscordNotify::new("<MENTION>\n```ansi\n\x1B[1;36m🔔 Price Alert\x1B[0m\n\n\x1B[1;33m📦 Item:      \x1B[0m \x1B[1;37m<ITEM_NAME>\x1B[0m\n\x1B[1;33m📏 Rule:      \x1B[0m \x1B[0;35m<RULE>\x1B[0m \x1B[0;32m(<THRESHOLD>)\x1B[0m\n\x1B[1;33m💎 Value:     \x1B[0m \x1B[1;37m<VALUE>\x1B[0m\n\n\x1B[1;34m📊 Market\x1B[0m\nLowest Sell: <LOWEST_SELL> | Highest Buy: <HIGHEST_BUY> | Week Shift: <WEEK_SHIFT>%\n```", "", vec![]),
+                SystemNotify::new("Price Alert: <ITEM_NAME>", "<RULE> <THRESHOLD> | Now: <VALUE>","cat_meow.mp3", 1.0),
+                WebHookNotify::new("<WEBHOOK_URL>"),
+            ),
+            price_alerts: PriceAlertSettings::default(),
         }
     }
 }
+
+impl NotificationsSetting {
+    /// Tokens of every notification channel, to send the queued messages that only store their fingerprint.
+    pub fn secrets(&self) -> Vec<String> {
+        [
+            &self.on_new_conversation,
+            &self.on_wfm_chat_message,
+            &self.on_new_trade,
+            &self.on_riven_deal,
+            &self.on_price_alert,
+        ]
+        .into_iter()
+        .flat_map(|setting| setting.secrets())
+        .map(|secret| secret.to_string())
+        .collect()
+    }
+
+    /// Returns the template problems of every event, prefixed with the event key.
+    pub fn validate(&self) -> Vec<String> {
+        [
+            ("on_new_conversation", &self.on_new_conversation),
+            ("on_wfm_chat_message", &self.on_wfm_chat_message),
+            ("on_new_trade", &self.on_new_trade),
+            ("on_riven_deal", &self.on_riven_deal),
+            ("on_price_alert", &self.on_price_alert),
+        ]
+        .into_iter()
+        .flat_map(|(key, setting)| {
+            setting
+                .validate()
+                .into_iter()
+                .map(move |e| format!("{}.{}", key, e))
+        })
+        .collect()
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+/// Repeated sections of a notification, e.g. the items of a trade.
+/// Each entry holds the `<VAR>` variables of one loop pass.
+pub type NotifyLists = HashMap<String, Vec<HashMap<String, String>>>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        name: String,
+        body: Vec<Node>,
+    },
+}
+
+/// Renders a notification template.
+/// Besides `<VAR>` substitution it supports `{{#if VAR}}..{{else}}..{{/if}}`, true when the variable is set
+/// and not empty, `0` or `false` or when the list has entries, and `{{#each LIST}}..{{/each}}`.
+/// A template that does not parse is sent with plain substitution only.
+pub fn render_template(
+    template: &str,
+    variables: &HashMap<String, String>,
+    lists: &NotifyLists,
+) -> String {
+    match parse(template) {
+        Ok(nodes) => render_nodes(&nodes, variables, lists),
+        Err(_) => substitute(template, variables),
+    }
+}
+
+/// Checks that every section of the template is opened and closed in order.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    parse(template).map(|_| ())
+}
+
+fn substitute(text: &str, variables: &HashMap<String, String>) -> String {
+    let mut content = text.to_string();
+    for (k, v) in variables.iter() {
+        content = content.replace(k.as_str(), v);
+    }
+    content
+}
+
+fn is_truthy(name: &str, variables: &HashMap<String, String>, lists: &NotifyLists) -> bool {
+    if let Some(list) = lists.get(name) {
+        return !list.is_empty();
+    }
+    match variables.get(&format!("<{}>", name)) {
+        Some(value) => !matches!(value.trim(), "" | "0" | "false"),
+        None => false,
+    }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    variables: &HashMap<String, String>,
+    lists: &NotifyLists,
+) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&substitute(text, variables)),
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let branch = if is_truthy(name, variables, lists) {
+                    then
+                } else {
+                    otherwise
+                };
+                out.push_str(&render_nodes(branch, variables, lists));
+            }
+            Node::Each { name, body } => {
+                for entry in lists.get(name).map(|l| l.as_slice()).unwrap_or(&[]) {
+                    let mut scoped = variables.clone();
+                    scoped.extend(entry.iter().map(|(k, v)| (k.clone(), v.clone())));
+                    out.push_str(&render_nodes(body, &scoped, lists));
+                }
+            }
+        }
+    }
+    out
+}
+
+enum Frame {
+    If {
+        name: String,
+        then: Vec<Node>,
+        in_else: bool,
+    },
+    Each {
+        name: String,
+    },
+}
+
+fn parse(template: &str) -> Result<Vec<Node>, String> {
+    // Each open section keeps the nodes that came before it
+    let mut stack: Vec<(Frame, Vec<Node>)> = vec![];
+    let mut current: Vec<Node> = vec![];
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            current.push(Node::Text(rest[..start].to_string()));
+        }
+        let Some(end) = rest[start..].find("}}") else {
+            return Err(format!("Unclosed tag at '{}'", &rest[start..]));
+        };
+        let tag = rest[start + 2..start + end].trim();
+        rest = &rest[start + end + 2..];
+
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let frame = Frame::If {
+                name: name.trim().to_string(),
+                then: vec![],
+                in_else: false,
+            };
+            stack.push((frame, std::mem::take(&mut current)));
+        } else if let Some(name) = tag.strip_prefix("#each ") {
+            let frame = Frame::Each {
+                name: name.trim().to_string(),
+            };
+            stack.push((frame, std::mem::take(&mut current)));
+        } else if tag == "else" {
+            match stack.last_mut() {
+                Some((Frame::If { then, in_else, .. }, _)) if !*in_else => {
+                    *then = std::mem::take(&mut current);
+                    *in_else = true;
+                }
+                _ => return Err("'{{else}}' outside of an '{{#if}}' section".to_string()),
+            }
+        } else if tag == "/if" {
+            match stack.pop() {
+                Some((
+                    Frame::If {
+                        name,
+                        then,
+                        in_else,
+                    },
+                    parent,
+                )) => {
+                    let body = std::mem::replace(&mut current, parent);
+                    let (then, otherwise) = if in_else {
+                        (then, body)
+                    } else {
+                        (body, vec![])
+                    };
+                    current.push(Node::If {
+                        name,
+                        then,
+                        otherwise,
+                    });
+                }
+                _ => return Err("'{{/if}}' without a matching '{{#if}}'".to_string()),
+            }
+        } else if tag == "/each" {
+            match stack.pop() {
+                Some((Frame::Each { name }, parent)) => {
+                    let body = std::mem::replace(&mut current, parent);
+                    current.push(Node::Each { name, body });
+                }
+                _ => return Err("'{{/each}}' without a matching '{{#each}}'".to_string()),
+            }
+        } else {
+            return Err(format!("Unknown tag '{{{{{}}}}}'", tag));
+        }
+    }
+    if !rest.is_empty() {
+        current.push(Node::Text(rest.to_string()));
+    }
+    match stack.last() {
+        Some((Frame::If { name, .. }, _)) => Err(format!("'{{{{#if {}}}}}' is never closed", name)),
+        Some((Frame::Each { name }, _)) => Err(format!("'{{{{#each {}}}}}' is never closed", name)),
+        None => Ok(current),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn render(template: &str, variables: &[(&str, &str)]) -> String {
+        render_template(template, &vars(variables), &NotifyLists::new())
+    }
+
+    fn items() -> NotifyLists {
+        NotifyLists::from([(
+            "ITEMS".to_string(),
+            vec![
+                vars(&[("<NAME>", "Ash Prime"), ("<QUANTITY>", "2")]),
+                vars(&[("<NAME>", "Nikana Prime"), ("<QUANTITY>", "1")]),
+            ],
+        )])
+    }
+
+    #[test]
+    fn nested_if_inside_each() {
+        let template = "{{#each ITEMS}}<NAME>{{#if QUANTITY}} x<QUANTITY>{{/if}}{{#if PLAT}} for <PLAT>p{{/if}};{{/each}}";
+        let variables = vars(&[("<PLAT>", "30")]);
+        assert_eq!(
+            render_template(template, &variables, &items()),
+            "Ash Prime x2 for 30p;Nikana Prime x1 for 30p;"
+        );
+    }
+
+    #[test]
+    fn nested_each_inside_if() {
+        let template = "{{#if ITEMS}}Items:{{#each ITEMS}} <NAME>{{/each}}{{else}}No items{{/if}}";
+        assert_eq!(
+            render_template(template, &HashMap::new(), &items()),
+            "Items: Ash Prime Nikana Prime"
+        );
+        assert_eq!(render(template, &[]), "No items");
+    }
+
+    #[test]
+    fn nested_if_inside_if() {
+        let template = "{{#if A}}a{{#if B}}b{{else}}!b{{/if}}{{else}}!a{{/if}}";
+        assert_eq!(render(template, &[("<A>", "1"), ("<B>", "1")]), "ab");
+        assert_eq!(render(template, &[("<A>", "1")]), "a!b");
+        assert_eq!(render(template, &[("<B>", "1")]), "!a");
+    }
+
+    #[test]
+    fn falsy_values_take_the_else_branch() {
+        let template = "{{#if VALUE}}yes{{else}}no{{/if}}";
+        for value in ["0", "false", "", "  "] {
+            assert_eq!(render(template, &[("<VALUE>", value)]), "no", "{:?}", value);
+        }
+        assert_eq!(render(template, &[]), "no");
+        let empty = NotifyLists::from([("VALUE".to_string(), vec![])]);
+        assert_eq!(render_template(template, &HashMap::new(), &empty), "no");
+        for value in ["1", "true", "00", "text"] {
+            assert_eq!(
+                render(template, &[("<VALUE>", value)]),
+                "yes",
+                "{:?}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn stray_closing_tags_are_errors() {
+        assert!(parse("text{{/if}}").is_err());
+        assert!(parse("{{#each ITEMS}}{{/if}}").is_err());
+        assert!(parse("{{#if A}}{{/each}}").is_err());
+        assert!(parse("{{else}}").is_err());
+        assert!(parse("{{#if A}}{{else}}{{else}}{{/if}}").is_err());
+        assert!(parse("{{unknown}}").is_err());
+    }
+
+    #[test]
+    fn unclosed_sections_are_errors() {
+        assert_eq!(
+            parse("{{#if A}}text"),
+            Err("'{{#if A}}' is never closed".to_string())
+        );
+        assert_eq!(
+            parse("{{#each ITEMS}}{{#if A}}{{/if}}"),
+            Err("'{{#each ITEMS}}' is never closed".to_string())
+        );
+        assert!(parse("text {{#if A").is_err());
+        assert!(validate_template("{{#if A}}{{#each B}}{{/each}}{{/if}}").is_ok());
+    }
+
+    #[test]
+    fn parse_keeps_the_text_around_sections() {
+        assert_eq!(
+            parse("a{{#if B}}c{{else}}d{{/if}}e"),
+            Ok(vec![
+                Node::Text("a".to_string()),
+                Node::If {
+                    name: "B".to_string(),
+                    then: vec![Node::Text("c".to_string())],
+                    otherwise: vec![Node::Text("d".to_string())],
+                },
+                Node::Text("e".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn broken_templates_fall_back_to_plain_substitution() {
+        let variables = [("<NAME>", "Ash Prime")];
+        assert_eq!(
+            render("{{#if NAME}}<NAME>", &variables),
+            "{{#if NAME}}Ash Prime"
+        );
+        assert_eq!(render("<NAME> {{/if}}", &variables), "Ash Prime {{/if}}");
+        assert_eq!(render("Sold <NAME>", &variables), "Sold Ash Prime");
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utils::get_location;
+
+use super::{render_template, validate_template, NotificationChannel, NotifyLists, WebhookEvent};
+use crate::{
+    notify_queue::{HttpMethod, NotifyChannel, QueuedNotification},
+    utils::modules::states,
+};
+
+// Stands for the access token in the queued request
+const TOKEN: &str = "{token}";
+
+/// Publishes the notification to an ntfy topic, on ntfy.sh or a self-hosted server.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NtfyNotify {
+    pub enabled: bool,
+    pub server: String,
+    pub topic: String,
+    // Access token for protected topics, sent as a bearer token
+    #[serde(default)]
+    pub token: String,
+    pub title: String,
+    pub content: String,
+    // 1 (min) to 5 (max), 3 is the ntfy default
+    pub priority: u8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl NtfyNotify {
+    pub fn new(
+        topic: impl Into<String>,
+        title: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            enabled: true,
+            server: "https://ntfy.sh".to_string(),
+            topic: topic.into(),
+            token: String::new(),
+            title: title.into(),
+            content: content.into(),
+            priority: 3,
+            tags: vec![],
+        }
+    }
+}
+
+impl NotificationChannel for NtfyNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        _event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        _value: Option<&Value>,
+    ) {
+        if self.server.is_empty() || self.topic.is_empty() {
+            return;
+        }
+        // Publishing as JSON goes to the server root with the topic in the body
+        let body = json!({
+            "topic": self.topic,
+            "title": render_template(&self.title, variables, lists),
+            "message": render_template(&self.content, variables, lists),
+            "priority": self.priority.clamp(1, 5),
+            "tags": self.tags,
+        });
+        let mut message = QueuedNotification::new(
+            NotifyChannel::Ntfy,
+            HttpMethod::Post,
+            self.server.trim_end_matches('/'),
+            HashMap::new(),
+            body,
+        );
+        if !self.token.is_empty() {
+            message
+                .headers
+                .insert("Authorization".to_string(), format!("Bearer {}", TOKEN));
+            message = message.with_secret(TOKEN, &self.token);
+        }
+        match states::notify_queue() {
+            Ok(queue) => {
+                queue.enqueue_message(message);
+            }
+            Err(e) => {
+                e.with_location(get_location!())
+                    .log(NotifyChannel::Ntfy.log_file());
+            }
+        }
+    }
+    fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if let Err(e) = validate_template(&self.title) {
+            errors.push(format!("title: {}", e));
+        }
+        if let Err(e) = validate_template(&self.content) {
+            errors.push(format!("content: {}", e));
+        }
+        if self.enabled && (self.server.is_empty() || self.topic.is_empty()) {
+            errors.push("server and topic are required".to_string());
+        }
+        errors
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use utils::SubType;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PriceAlertCondition {
+    /// Cheapest live sell order is under the price
+    LowestSellBelow { price: i64 },
+    /// Best live buy order is over the price
+    HighestBuyAbove { price: i64 },
+    /// Lowest sell minus highest buy is over the spread
+    SpreadAbove { spread: i64 },
+    /// The moving average moved at least this many percent over the week, negative for a drop
+    MovingAvgShift { percent: f64 },
+}
+
+impl PriceAlertCondition {
+    /// Whether the live order book of the item is needed, the others only use the cached price info.
+    pub fn needs_live_orders(&self) -> bool {
+        !matches!(self, PriceAlertCondition::MovingAvgShift { .. })
+    }
+
+    pub fn threshold(&self) -> f64 {
+        match self {
+            PriceAlertCondition::LowestSellBelow { price } => *price as f64,
+            PriceAlertCondition::HighestBuyAbove { price } => *price as f64,
+            PriceAlertCondition::SpreadAbove { spread } => *spread as f64,
+            PriceAlertCondition::MovingAvgShift { percent } => *percent,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PriceAlertCondition::LowestSellBelow { .. } => "lowest_sell_below",
+            PriceAlertCondition::HighestBuyAbove { .. } => "highest_buy_above",
+            PriceAlertCondition::SpreadAbove { .. } => "spread_above",
+            PriceAlertCondition::MovingAvgShift { .. } => "moving_avg_shift",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriceAlertRule {
+    pub id: String,
+    pub enabled: bool,
+    pub wfm_url: String,
+    #[serde(rename = "subType", alias = "sub_type")]
+    pub sub_type: Option<SubType>,
+    pub condition: PriceAlertCondition,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::PriceAlertRule;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriceAlertSettings {
+    pub enabled: bool,
+    pub update_interval: i64, // in seconds
+    pub rules: Vec<PriceAlertRule>,
+}
+
+impl Default for PriceAlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            update_interval: 300,
+            rules: vec![],
+        }
+    }
+}
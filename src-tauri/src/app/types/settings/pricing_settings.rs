@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PricingStrategySetting {
+    /// Match the best order on our side
+    #[default]
+    MatchTop,
+    /// Beat the best order by the amount of platinum
+    Undercut { amount: i64 },
+    /// A percentage of the closed moving average
+    PercentOfMovingAvg { percent: f64 },
+    /// The median of the K best orders
+    MedianOfTopK { k: usize },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PricingItemSetting {
+    #[serde(rename = "wfmId", alias = "wfm_id")]
+    pub wfm_id: String,
+    pub strategy: PricingStrategySetting,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PricingCategorySetting {
+    // Tag of the tradable item, e.g. "mod", "arcane_enhancement" or "prime"
+    pub tag: String,
+    pub strategy: PricingStrategySetting,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PricingSettings {
+    pub default: PricingStrategySetting,
+    pub items: Vec<PricingItemSetting>,
+    pub categories: Vec<PricingCategorySetting>,
+}
+
+impl PricingSettings {
+    /// Returns the strategy for an item: the item override first, then the first matching category.
+    pub fn resolve(&self, wfm_id: &str, tags: &[String]) -> &PricingStrategySetting {
+        if let Some(item) = self.items.iter().find(|item| item.wfm_id == wfm_id) {
+            return &item.strategy;
+        }
+        if let Some(category) = self
+            .categories
+            .iter()
+            .find(|category| tags.contains(&category.tag))
+        {
+            return &category.strategy;
+        }
+        &self.default
+    }
+}
+
+impl Default for PricingSettings {
+    fn default() -> Self {
+        Self {
+            default: PricingStrategySetting::default(),
+            items: Vec::new(),
+            categories: Vec::new(),
+        }
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 pub struct RivenSettings {
     pub general: RivenGeneralSettings,
     pub wts: RivenWtsSettings,
+    #[serde(default)]
+    pub wtb: RivenWtbSettings,
 }
 
 impl Default for RivenSettings {
@@ -12,6 +14,7 @@ impl Default for RivenSettings {
         Self {
             general: RivenGeneralSettings::default(),
             wts: RivenWtsSettings::default(),
+            wtb: RivenWtbSettings::default(),
         }
     }
 }
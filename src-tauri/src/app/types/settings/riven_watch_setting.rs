@@ -0,0 +1,14 @@
+use entity::stock_riven::MatchRivenStruct;
+use serde::{Deserialize, Serialize};
+
+/// A riven we want to buy, auctions of the weapon are searched with the filter and reported under the target price.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RivenWatchSetting {
+    pub id: String,
+    pub enabled: bool,
+    pub wfm_weapon_url: String,
+    pub filter: MatchRivenStruct,
+    pub target_price: i64,
+    // Lowest match score (0-100) an auction needs to be reported
+    pub min_score: f64,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::RivenWatchSetting;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RivenWtbSettings {
+    pub enabled: bool,
+    pub update_interval: i64, // in seconds
+    pub watches: Vec<RivenWatchSetting>,
+}
+
+impl Default for RivenWtbSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            update_interval: 60,
+            watches: vec![],
+        }
+    }
+}
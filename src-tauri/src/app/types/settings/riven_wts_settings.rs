@@ -5,6 +5,8 @@ pub struct RivenWtsSettings {
     pub min_profit: i64,
     pub threshold_percentage: f64,
     pub max_results: i64,
+    /// Lowest similarity score (0-100) a live auction needs to count as a comparable
+    pub min_similarity: f64,
 }
 
 impl Default for RivenWtsSettings {
@@ -13,6 +15,7 @@ impl Default for RivenWtsSettings {
             min_profit: 25,
             threshold_percentage: 15.0,
             max_results: 5,
+            min_similarity: 50.0,
         }
     }
 }
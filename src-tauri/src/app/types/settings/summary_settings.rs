@@ -1,11 +1,39 @@
 use super::*;
 use serde::{Deserialize, Serialize};
+use utils::{ReportPeriods, ReportTimezone};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SummarySettings {
     pub recent_days: i64,
     pub recent_transactions: i64,
     pub categories: Vec<SummaryCategorySetting>,
+    /// `local`, `utc`, a UTC offset like `-05:00` or an IANA name like `Europe/Berlin`.
+    #[serde(default)]
+    pub timezone: String,
+    /// First day of the week, counted from Sunday.
+    #[serde(default = "default_week_start")]
+    pub week_start: u32,
+    /// Days start at the Warframe daily reset (00:00 UTC) instead of midnight.
+    #[serde(default)]
+    pub daily_reset: bool,
+}
+
+fn default_week_start() -> u32 {
+    1
+}
+
+impl SummarySettings {
+    /// Problems that keep the settings from being saved, empty when there are none.
+    pub fn validate(&self) -> Vec<String> {
+        ReportTimezone::parse(&self.timezone)
+            .err()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn report_periods(&self) -> ReportPeriods {
+        ReportPeriods::new(&self.timezone, self.week_start, self.daily_reset)
+    }
 }
 
 impl Default for SummarySettings {
@@ -13,6 +41,9 @@ impl Default for SummarySettings {
         SummarySettings {
             recent_days: 7,
             recent_transactions: 10,
+            timezone: "local".to_string(),
+            week_start: default_week_start(),
+            daily_reset: false,
             categories: vec![
                 SummaryCategorySetting::new(
                     "/imgs/categories/mods.png",
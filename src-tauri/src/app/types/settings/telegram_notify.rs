@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utils::get_location;
+
+use super::{render_template, validate_template, NotificationChannel, NotifyLists, WebhookEvent};
+use crate::{
+    notify_queue::{HttpMethod, NotifyChannel, QueuedNotification},
+    utils::modules::states,
+};
+
+// Telegram refuses longer messages
+const MAX_MESSAGE_LENGTH: usize = 4096;
+// Stands for the bot token in the queued request
+const BOT_TOKEN: &str = "{bot_token}";
+
+/// Sends the notification as a message from a Telegram bot to a chat.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TelegramNotify {
+    pub enabled: bool,
+    pub bot_token: String,
+    // User, group or channel id, or "@channel_name"
+    pub chat_id: String,
+    pub content: String,
+}
+
+impl TelegramNotify {
+    pub fn new(
+        bot_token: impl Into<String>,
+        chat_id: impl Into<String>,
+        content: impl Into<String>,
+    ) -> Self {
+        Self {
+            enabled: true,
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            content: content.into(),
+        }
+    }
+}
+
+impl NotificationChannel for TelegramNotify {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    fn notify(
+        &self,
+        _event: WebhookEvent,
+        variables: &HashMap<String, String>,
+        lists: &NotifyLists,
+        _value: Option<&Value>,
+    ) {
+        if self.bot_token.is_empty() || self.chat_id.is_empty() {
+            return;
+        }
+        let text: String = render_template(&self.content, variables, lists)
+            .chars()
+            .take(MAX_MESSAGE_LENGTH)
+            .collect();
+        let body = json!({
+            "chat_id": self.chat_id,
+            "text": text,
+            "disable_web_page_preview": true,
+        });
+        match states::notify_queue() {
+            Ok(queue) => {
+                queue.enqueue_message(
+                    QueuedNotification::new(
+                        NotifyChannel::Telegram,
+                        HttpMethod::Post,
+                        format!("https://api.telegram.org/bot{}/sendMessage", BOT_TOKEN),
+                        HashMap::new(),
+                        body,
+                    )
+                    .with_secret(BOT_TOKEN, &self.bot_token),
+                );
+            }
+            Err(e) => {
+                e.with_location(get_location!())
+                    .log(NotifyChannel::Telegram.log_file());
+            }
+        }
+    }
+    fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if let Err(e) = validate_template(&self.content) {
+            errors.push(format!("content: {}", e));
+        }
+        if self.enabled && (self.bot_token.is_empty() || self.chat_id.is_empty()) {
+            errors.push("bot_token and chat_id are required".to_string());
+        }
+        errors
+    }
+}
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the webhook envelope and payloads, bumped on breaking changes.
+pub const WEBHOOK_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    NewConversation,
+    WfmChatMessage,
+    NewTrade,
+    RivenDeal,
+    PriceAlert,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 5] = [
+        WebhookEvent::NewConversation,
+        WebhookEvent::WfmChatMessage,
+        WebhookEvent::NewTrade,
+        WebhookEvent::RivenDeal,
+        WebhookEvent::PriceAlert,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::NewConversation => "new_conversation",
+            WebhookEvent::WfmChatMessage => "wfm_chat_message",
+            WebhookEvent::NewTrade => "new_trade",
+            WebhookEvent::RivenDeal => "riven_deal",
+            WebhookEvent::PriceAlert => "price_alert",
+        }
+    }
+
+    /// JSON schema of the envelope sent for the event, the same files ship in `resources/webhooks`.
+    pub fn schema(&self) -> &'static str {
+        match self {
+            WebhookEvent::NewConversation => {
+                include_str!("../../../../resources/webhooks/new_conversation.schema.json")
+            }
+            WebhookEvent::WfmChatMessage => {
+                include_str!("../../../../resources/webhooks/wfm_chat_message.schema.json")
+            }
+            WebhookEvent::NewTrade => {
+                include_str!("../../../../resources/webhooks/new_trade.schema.json")
+            }
+            WebhookEvent::RivenDeal => {
+                include_str!("../../../../resources/webhooks/riven_deal.schema.json")
+            }
+            WebhookEvent::PriceAlert => {
+                include_str!("../../../../resources/webhooks/price_alert.schema.json")
+            }
+        }
+    }
+}
+
+/// Body of every webhook request, `payload` is described by the schema of the event.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookEnvelope {
+    pub id: String,
+    pub event: WebhookEvent,
+    pub timestamp: DateTime<Utc>,
+    pub schema_version: u32,
+    pub payload: Value,
+}
+
+impl WebhookEnvelope {
+    pub fn new(event: WebhookEvent, payload: Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event,
+            timestamp: Utc::now(),
+            schema_version: WEBHOOK_SCHEMA_VERSION,
+            payload,
+        }
+    }
+}
@@ -1,60 +1,117 @@
+use std::collections::HashMap;
+
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use utils::{get_location, info, Error, LoggerOptions};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use utils::get_location;
+
+use super::{WebhookEnvelope, WebhookEvent};
+use crate::{notify_queue::NotifyChannel, utils::modules::states, APP};
 
-use crate::APP;
+pub static SIGNATURE_HEADER: &str = "X-Quantframe-Signature";
+pub static EVENT_HEADER: &str = "X-Quantframe-Event";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WebHookNotify {
     pub enabled: bool,
     pub url: String,
+    // Signs the requests when set, see `sign_payload`
+    #[serde(default)]
+    pub secret: String,
 }
 impl WebHookNotify {
     pub fn new(url: impl Into<String>) -> Self {
         Self {
             enabled: false,
             url: url.into(),
+            secret: String::new(),
         }
     }
-    pub fn send(&self, value: Value) {
+    /// Queues the payload wrapped in a `WebhookEnvelope` of the event.
+    pub fn send(&self, event: WebhookEvent, payload: Value) {
         if self.url.is_empty() {
             return;
         }
-        let url = self.url.clone();
         let tauri_app = APP.get().expect("App handle not found");
         let app_info = tauri_app.package_info().clone();
-        tauri::async_runtime::spawn(async move {
-            let client = reqwest::Client::new();
-            let res = client
-                .post(&url)
-                .header("Content-Type", "application/json")
-                .header(
-                    "User-Agent",
-                    format!(
-                        "{} v{} ({})",
-                        app_info.name, app_info.version, app_info.authors
-                    ),
-                )
-                .json(&value)
-                .send()
-                .await;
-            match res {
-                Ok(_) => {
-                    info(
-                        "Helper",
-                        &format!("Message sent to webhook: {}", url),
-                        &LoggerOptions::default(),
-                    );
-                }
-                Err(e) => {
-                    let err = Error::new(
-                        "WebhookNotificationError",
-                        &format!("{:?}", e),
-                        get_location!(),
-                    );
-                    err.log("webhook_notification.log");
-                }
+        let envelope = WebhookEnvelope::new(event, payload);
+        // The queue posts the value as is, so the signed text is the exact request body
+        let body = json!(envelope);
+        // The queue sets the JSON content type itself
+        let mut headers = HashMap::from([
+            (
+                "User-Agent".to_string(),
+                format!(
+                    "{} v{} ({})",
+                    app_info.name, app_info.version, app_info.authors
+                ),
+            ),
+            (EVENT_HEADER.to_string(), event.as_str().to_string()),
+        ]);
+        if !self.secret.is_empty() {
+            headers.insert(
+                SIGNATURE_HEADER.to_string(),
+                format!("sha256={}", sign_payload(&self.secret, &body.to_string())),
+            );
+        }
+        match states::notify_queue() {
+            Ok(queue) => {
+                queue.enqueue(NotifyChannel::Webhook, &self.url, headers, body);
+            }
+            Err(e) => {
+                e.with_location(get_location!())
+                    .log("webhook_notification.log");
             }
+        }
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of the raw request body, receivers compute the same with their copy of the secret.
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body.as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_with_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn the_signed_text_is_the_posted_body() {
+        let payload = json!({
+            "item": "Ash Prime Set",
+            "player": "Продавец_77",
+            "price": 120.5,
+            "note": "line\nbreak \"quoted\"",
+            "tags": ["prime", "set"],
         });
+        let body = json!(WebhookEnvelope::new(WebhookEvent::NewTrade, payload));
+        let request = reqwest::Client::new()
+            .post("http://localhost/hook")
+            .json(&body)
+            .build()
+            .unwrap();
+        let posted = request.body().and_then(|b| b.as_bytes()).unwrap();
+        assert_eq!(posted, body.to_string().as_bytes());
+        assert_eq!(
+            sign_payload("secret", std::str::from_utf8(posted).unwrap()),
+            sign_payload("secret", &body.to_string())
+        );
     }
 }
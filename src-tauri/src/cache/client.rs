@@ -110,18 +110,10 @@ impl CacheState {
             .clone()
     }
 
-    pub async fn new(
-        qf_client: &QFClient,
-        user: &User,
-        lang: impl Into<String>,
-    ) -> Result<Self, Error> {
-        let lang = lang.into();
-        let version =
-            CacheVersion::load().expect("Failed to load cache version from cache_version.json");
-
-        let mut client = CacheState {
+    fn empty(base_path: PathBuf, version: CacheVersion) -> Self {
+        CacheState {
             self_arc: OnceLock::new(),
-            base_path: helper::get_app_storage_path().join("cache"),
+            base_path,
             version,
             weapons: Arc::new(Mutex::new(MultiKeyMap::new())),
             all_items_module: OnceLock::new(),
@@ -155,7 +147,27 @@ impl CacheState {
             quest_module: OnceLock::new(),
             gear_module: OnceLock::new(),
             syndicate_module: OnceLock::new(),
-        };
+        }
+    }
+
+    /// Builds the cache from an already extracted cache folder, without checking for updates or prices.
+    /// Used by the log replay tests with the fixture cache.
+    pub fn from_folder(base_path: impl Into<PathBuf>, lang: &str) -> Result<Self, Error> {
+        let client = Self::empty(base_path.into(), CacheVersion::default());
+        client.load_modules(lang)?;
+        Ok(client)
+    }
+
+    pub async fn new(
+        qf_client: &QFClient,
+        user: &User,
+        lang: impl Into<String>,
+    ) -> Result<Self, Error> {
+        let lang = lang.into();
+        let version =
+            CacheVersion::load().expect("Failed to load cache version from cache_version.json");
+
+        let mut client = Self::empty(helper::get_app_storage_path().join("cache"), version);
         if !user.verification || user.qf_banned || user.wfm_banned {
             warning(
                 "Cache:Client",
@@ -258,12 +270,19 @@ impl CacheState {
         }
 
         // Update Item Prices if user is verified
-        self.language().load(&lang)?;
-        let language_module = self.language();
-        let language = language_module.as_ref();
         self.item_price()
             .load(qf_client, price_require_update)
             .await?;
+        self.theme().load()?;
+        self.chat_icon().load()?;
+        self.load_modules(&lang)?;
+        Ok((cache_version_id, price_version_id))
+    }
+
+    fn load_modules(&self, lang: &str) -> Result<(), Error> {
+        self.language().load(lang)?;
+        let language_module = self.language();
+        let language = language_module.as_ref();
         self.tradable_item().load(language)?;
         self.arcane().load(language)?;
         self.archgun().load(language)?;
@@ -284,16 +303,14 @@ impl CacheState {
         self.skin().load(language)?;
         self.warframe().load(language)?;
         self.quest().load(language)?;
-        self.theme().load()?;
-        self.chat_icon().load()?;
         self.riven_good_roll().load(language)?;
         self.recipe().load(language)?;
         self.bundle().load(language)?;
         self.gear().load(language)?;
         self.syndicate().load(language)?;
-        self.weapon().load(&self)?;
-        self.all_items().load(&self)?;
-        Ok((cache_version_id, price_version_id))
+        self.weapon().load(self)?;
+        self.all_items().load(self)?;
+        Ok(())
     }
 
     async fn extract(&self, qf_client: &QFClient) -> Result<(), Error> {
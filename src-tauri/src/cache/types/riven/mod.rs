@@ -6,3 +6,6 @@ pub use riven_helper::*;
 
 pub mod modifier;
 pub use modifier::*;
+
+pub mod riven_similarity;
+pub use riven_similarity::*;
@@ -4,7 +4,10 @@ use entity::{
 };
 use utils::{get_location, Error};
 
-use crate::cache::{types::*, CacheState};
+use crate::{
+    cache::{types::*, CacheState},
+    wf_inventory::UpgradeFingerprint,
+};
 
 const BASE_RIVEN: f64 = 90.0;
 const RIVEN_DENOM: f64 = 53687091.0;
@@ -84,90 +87,96 @@ pub fn build_riven_mod_name(attrs: &Vec<RivenAttribute>, buffs: usize) -> String
 // ATTRIBUTE BUILDERS
 // --------------------------------------------------
 
-// pub fn build_riven_attributes_from_fingerprint(
-//     cache: &RivenModule,
-//     weapon: &CacheWeaponBase,
-//     fingerprint: &UpgradeFingerprint,
-//     multipliers: &Modifier,
-// ) -> Result<Vec<RivenAttribute>, Error> {
-//     let mut out = Vec::with_capacity(fingerprint.buffs.len() + fingerprint.curses.len());
-
-//     for (stats, is_buff) in [(&fingerprint.buffs, true), (&fingerprint.curses, false)] {
-//         for raw in stats {
-//             let upgrade = cache
-//                 .get_stat_tag_by(&weapon.upgrade_type, &raw.tag)
-//                 .map_err(|e| e.with_location(get_location!()))?;
-
-//             let value = derive_riven_roll_value(
-//                 raw.value as f64,
-//                 &raw.tag,
-//                 upgrade.value,
-//                 &upgrade.localization_string,
-//                 weapon,
-//                 multipliers,
-//                 is_buff,
-//             );
-
-//             let mut attr =
-//                 RivenAttribute::new(is_buff, value, upgrade.wfm_url, upgrade.localization_string);
-//             attr.value = format_riven_stat_value(apply_rank_multiplier(
-//                 attr.value,
-//                 1.0,
-//                 (fingerprint.mod_rank) as f64,
-//             ));
-//             attr.properties
-//                 .set_property_value("suffix", upgrade.suffix.unwrap_or_default());
-//             attr.properties
-//                 .set_property_value("prefix", upgrade.prefix.unwrap_or_default());
-//             attr.properties.set_property_value("raw_value", raw.value);
-//             attr.properties.set_property_value("tag", raw.tag.clone());
-//             out.push(attr);
-//         }
-//     }
-
-//     Ok(out)
-// }
+pub fn build_riven_attributes_from_fingerprint(
+    cache: &CacheState,
+    weapon: &CacheWeaponBase,
+    fingerprint: &UpgradeFingerprint,
+    multipliers: &Modifier,
+) -> Result<Vec<RivenAttribute>, Error> {
+    let mut out = Vec::with_capacity(fingerprint.buffs.len() + fingerprint.curses.len());
+
+    for (stats, is_buff) in [(&fingerprint.buffs, true), (&fingerprint.curses, false)] {
+        for raw in stats {
+            let upgrade = cache
+                .mods()
+                .get_stat_tag_by(&weapon.upgrade_type, &raw.tag)
+                .map_err(|e| e.with_location(get_location!()))?;
+
+            let value = derive_riven_roll_value(
+                raw.value as f64,
+                &raw.tag,
+                upgrade.value,
+                &upgrade.formatted_value,
+                weapon,
+                multipliers,
+                is_buff,
+            );
+
+            let mut attr = RivenAttribute::new(
+                is_buff,
+                value,
+                upgrade.wfm_url.clone(),
+                upgrade.formatted_value.clone(),
+            );
+            attr.value = format_riven_stat_value(apply_rank_multiplier(
+                attr.value,
+                1.0,
+                fingerprint.mod_rank as f64,
+            ));
+            attr.properties
+                .set_property_value("suffix", upgrade.suffix.clone());
+            attr.properties
+                .set_property_value("prefix", upgrade.prefix.clone());
+            attr.properties.set_property_value("raw_value", raw.value);
+            attr.properties
+                .set_property_value("tag", upgrade.unique_name.clone());
+            out.push(attr);
+        }
+    }
+
+    Ok(out)
+}
 
 // --------------------------------------------------
 // ATTRIBUTE MATH (core engine)
 // --------------------------------------------------
 
-// pub fn derive_riven_roll_value(
-//     raw_value: f64,
-//     tag: &str,
-//     upgrade_base: f64,
-//     localization: &str,
-//     weapon: &CacheWeaponBase,
-//     multipliers: &Modifier,
-//     is_buff: bool,
-// ) -> f64 {
-//     let normalized = (0.9 + raw_value / RIVEN_DENOM / 100.0).clamp(0.9, 1.1);
-
-//     let mut value = BASE_RIVEN
-//         * upgrade_base
-//         * weapon.disposition
-//         * if is_buff {
-//             multipliers.good
-//         } else {
-//             multipliers.bad
-//         };
-
-//     if !is_buff && tag == "WeaponMeleeComboPointsOnHitMod" && value > 0.0 {
-//         value = -value;
-//     }
-
-//     if localization.contains('%') {
-//         value *= 100.0;
-//     }
-
-//     value *= normalized;
-
-//     if TWO_DIGIT_TAGS.contains(&tag) {
-//         value += TWO_DIGIT_BONUS;
-//     }
-
-//     value
-// }
+pub fn derive_riven_roll_value(
+    raw_value: f64,
+    tag: &str,
+    upgrade_base: f64,
+    localization: &str,
+    weapon: &CacheWeaponBase,
+    multipliers: &Modifier,
+    is_buff: bool,
+) -> f64 {
+    let normalized = (0.9 + raw_value / RIVEN_DENOM / 100.0).clamp(0.9, 1.1);
+
+    let mut value = BASE_RIVEN
+        * upgrade_base
+        * weapon.disposition
+        * if is_buff {
+            multipliers.good
+        } else {
+            multipliers.bad
+        };
+
+    if !is_buff && tag == "WeaponMeleeComboPointsOnHitMod" && value > 0.0 {
+        value = -value;
+    }
+
+    if localization.contains('%') {
+        value *= 100.0;
+    }
+
+    value *= normalized;
+
+    if TWO_DIGIT_TAGS.contains(&tag) {
+        value += TWO_DIGIT_BONUS;
+    }
+
+    value
+}
 
 // --------------------------------------------------
 // DISPLAY
@@ -346,3 +355,105 @@ pub fn get_latter_grade(random_per_one: f64) -> &'static str {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::wf_inventory::UpgradeStat;
+
+    const UPGRADE_TYPE: &str = "/Lotus/Upgrades/Mods/Randomized/LotusRifleRandomModRare";
+
+    /// The mod cache in `tests/fixtures/riven_attributes` holds a critical chance and a reload speed stat.
+    fn cache() -> CacheState {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/riven_attributes/cache");
+        CacheState::from_folder(path, "en").unwrap()
+    }
+
+    fn weapon() -> CacheWeaponBase {
+        CacheWeaponBase {
+            upgrade_type: UPGRADE_TYPE.to_string(),
+            disposition: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn stat(tag: &str, value: i64) -> UpgradeStat {
+        UpgradeStat {
+            tag: tag.to_string(),
+            value,
+        }
+    }
+
+    /// A rank 8 riven, the buff rolled at 100% and the curse at 90% of its base value.
+    fn fingerprint(buff_tag: &str) -> UpgradeFingerprint {
+        UpgradeFingerprint {
+            mod_rank: 8,
+            buffs: vec![stat(buff_tag, 536_870_910)],
+            curses: vec![stat("WeaponReloadSpeedMod", 0)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builds_buffs_then_curses_from_the_fingerprint() {
+        let fingerprint = fingerprint("WeaponCritChanceMod");
+        let multipliers = lookup_riven_multipliers(2, 1).unwrap();
+        let attributes =
+            build_riven_attributes_from_fingerprint(&cache(), &weapon(), &fingerprint, multipliers)
+                .unwrap();
+
+        assert_eq!(attributes.len(), 2);
+        let (buff, curse) = (&attributes[0], &attributes[1]);
+        assert!(buff.positive);
+        assert_eq!(buff.wfm_url, "critical_chance");
+        assert!((buff.value - 111.4).abs() < 1e-9);
+        assert_eq!(
+            buff.properties.get_property_value("prefix", String::new()),
+            "crita"
+        );
+        assert_eq!(
+            buff.properties.get_property_value("suffix", String::new()),
+            "cron"
+        );
+        assert_eq!(
+            buff.properties.get_property_value("raw_value", 0),
+            536_870_910
+        );
+
+        assert!(!curse.positive);
+        assert_eq!(curse.wfm_url, "reload_speed");
+        assert!((curse.value + 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tags_the_attributes_with_the_upgrade_unique_name() {
+        // The fingerprint may name the stat by its market url, the tag is always the cache unique name
+        let fingerprint = fingerprint("critical_chance");
+        let multipliers = lookup_riven_multipliers(2, 1).unwrap();
+        let attributes =
+            build_riven_attributes_from_fingerprint(&cache(), &weapon(), &fingerprint, multipliers)
+                .unwrap();
+
+        let tags = attributes
+            .iter()
+            .map(|a| a.properties.get_property_value("tag", String::new()))
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec!["WeaponCritChanceMod", "WeaponReloadSpeedMod"]);
+    }
+
+    #[test]
+    fn unknown_stats_are_an_error() {
+        let fingerprint = fingerprint("WeaponUnknownMod");
+        let multipliers = lookup_riven_multipliers(2, 1).unwrap();
+        assert!(build_riven_attributes_from_fingerprint(
+            &cache(),
+            &weapon(),
+            &fingerprint,
+            multipliers
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use entity::stock_riven;
+use serde::Serialize;
+use utils::Error;
+use wf_market::types::{Auction, AuctionWithOwner};
+
+use crate::cache::{derive_riven_summary_attributes, CacheState, CacheWeaponBase};
+
+// Weights of the parts of a similarity score, they add up to 1
+const POSITIVE_WEIGHT: f64 = 0.5;
+const NEGATIVE_WEIGHT: f64 = 0.2;
+const RE_ROLLS_WEIGHT: f64 = 0.1;
+const MASTERY_RANK_WEIGHT: f64 = 0.1;
+const POLARITY_WEIGHT: f64 = 0.1;
+
+// Differences past these count as nothing alike
+const MAX_RE_ROLLS_DIFFERENCE: f64 = 10.0;
+const MAX_MASTERY_RANK_DIFFERENCE: f64 = 8.0;
+
+/// What the price of a riven depends on, attribute values are scaled to max rank.
+#[derive(Debug, Clone, Serialize)]
+pub struct RivenRollProfile {
+    /// (wfm_url, value at max rank, positive)
+    pub attributes: Vec<(String, f64, bool)>,
+    pub re_rolls: i64,
+    pub mastery_rank: i64,
+    pub polarity: String,
+}
+
+impl RivenRollProfile {
+    pub fn new(
+        attributes: impl IntoIterator<Item = (String, f64, bool)>,
+        rank: i64,
+        re_rolls: i64,
+        mastery_rank: i64,
+        polarity: impl Into<String>,
+    ) -> Self {
+        let rank = rank.clamp(0, 8) as f64;
+        Self {
+            attributes: attributes
+                .into_iter()
+                .map(|(url, value, positive)| (url, value / (rank + 1.0) * 9.0, positive))
+                .collect(),
+            re_rolls,
+            mastery_rank,
+            polarity: polarity.into().to_lowercase(),
+        }
+    }
+
+    pub fn from_stock_riven(riven: &stock_riven::Model) -> Self {
+        Self::new(
+            riven
+                .attributes
+                .0
+                .iter()
+                .map(|a| (a.wfm_url.clone(), a.value, a.positive)),
+            riven.sub_type.as_ref().and_then(|s| s.rank).unwrap_or(0),
+            riven.re_rolls,
+            riven.mastery_rank,
+            &riven.polarity,
+        )
+    }
+
+    pub fn from_auction(auction: &Auction) -> Self {
+        Self::new(
+            auction
+                .item
+                .attributes
+                .as_deref()
+                .unwrap_or(&[])
+                .iter()
+                .map(|a| (a.url_name.clone(), a.value, a.positive)),
+            auction.item.mod_rank.unwrap_or(0) as i64,
+            auction.item.re_rolls.unwrap_or(0) as i64,
+            auction.item.mastery_level.unwrap_or(0) as i64,
+            auction.item.polarity.clone().unwrap_or_default(),
+        )
+    }
+
+    fn stats(&self, positive: bool) -> Vec<&(String, f64, bool)> {
+        self.attributes
+            .iter()
+            .filter(|(_, _, p)| *p == positive)
+            .collect()
+    }
+}
+
+/// How alike two rivens of the same weapon are, every part goes from 0 to 1 and `score` from 0 to 100.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct RivenSimilarity {
+    pub score: f64,
+    pub positive: f64,
+    pub negative: f64,
+    pub re_rolls: f64,
+    pub mastery_rank: f64,
+    pub polarity: f64,
+}
+
+/// A live auction picked to price a riven against.
+#[derive(Debug, Clone, Serialize)]
+pub struct RivenComparable {
+    pub id: String,
+    pub price: i64,
+    pub similarity: RivenSimilarity,
+}
+
+/// Roll range per attribute at the weapon disposition, used to tell how far apart two values are.
+/// Attributes the cache does not know are left out, `score_riven_similarity` falls back to the value itself then.
+pub fn riven_attribute_ranges(
+    cache: &CacheState,
+    weapon: &CacheWeaponBase,
+    profile: &RivenRollProfile,
+) -> Result<HashMap<String, f64>, Error> {
+    let attributes = derive_riven_summary_attributes(cache, weapon, &profile.attributes, 8)?;
+    Ok(attributes
+        .iter()
+        .map(|a| {
+            let min = a.properties.get_property_value("min", 0.0f64);
+            let max = a.properties.get_property_value("max", 0.0f64);
+            (a.wfm_url.clone(), (max - min).abs())
+        })
+        .filter(|(_, range)| *range > 0.0)
+        .collect())
+}
+
+pub fn score_riven_similarity(
+    target: &RivenRollProfile,
+    other: &RivenRollProfile,
+    ranges: &HashMap<String, f64>,
+) -> RivenSimilarity {
+    let positive = score_stats(&target.stats(true), &other.stats(true), ranges);
+    let negative = score_stats(&target.stats(false), &other.stats(false), ranges);
+    let re_rolls = score_difference(target.re_rolls, other.re_rolls, MAX_RE_ROLLS_DIFFERENCE);
+    let mastery_rank = score_difference(
+        target.mastery_rank,
+        other.mastery_rank,
+        MAX_MASTERY_RANK_DIFFERENCE,
+    );
+    let polarity = if target.polarity == other.polarity {
+        1.0
+    } else {
+        0.0
+    };
+
+    let score = (positive * POSITIVE_WEIGHT
+        + negative * NEGATIVE_WEIGHT
+        + re_rolls * RE_ROLLS_WEIGHT
+        + mastery_rank * MASTERY_RANK_WEIGHT
+        + polarity * POLARITY_WEIGHT)
+        * 100.0;
+    RivenSimilarity {
+        score: (score * 10.0).round() / 10.0,
+        positive,
+        negative,
+        re_rolls,
+        mastery_rank,
+        polarity,
+    }
+}
+
+/// The `limit` auctions most similar to `target` with a score of at least `min_score`, cheapest first.
+/// A `limit` of 0 or less keeps every auction above the score.
+pub fn find_comparable_auctions(
+    target: &RivenRollProfile,
+    auctions: &[AuctionWithOwner],
+    ranges: &HashMap<String, f64>,
+    min_score: f64,
+    limit: i64,
+) -> Vec<RivenComparable> {
+    pick_comparables(
+        target,
+        auctions.iter().map(|a| {
+            (
+                a.auction.id.clone(),
+                a.auction.starting_price as i64,
+                RivenRollProfile::from_auction(&a.auction),
+            )
+        }),
+        ranges,
+        min_score,
+        limit,
+    )
+}
+
+/// Same as `find_comparable_auctions` for `(id, price, profile)` candidates.
+pub fn pick_comparables(
+    target: &RivenRollProfile,
+    candidates: impl IntoIterator<Item = (String, i64, RivenRollProfile)>,
+    ranges: &HashMap<String, f64>,
+    min_score: f64,
+    limit: i64,
+) -> Vec<RivenComparable> {
+    let mut comparables = candidates
+        .into_iter()
+        .map(|(id, price, profile)| RivenComparable {
+            id,
+            price,
+            similarity: score_riven_similarity(target, &profile, ranges),
+        })
+        .filter(|c| c.similarity.score >= min_score)
+        .collect::<Vec<_>>();
+    comparables.sort_by(|a, b| {
+        b.similarity
+            .score
+            .total_cmp(&a.similarity.score)
+            .then(a.price.cmp(&b.price))
+    });
+    if limit > 0 {
+        comparables.truncate(limit as usize);
+    }
+    comparables.sort_by_key(|c| c.price);
+    comparables
+}
+
+/// Overlap of two stat lists, a shared stat counts half for being there and half for how close its value is.
+fn score_stats(
+    target: &[&(String, f64, bool)],
+    other: &[&(String, f64, bool)],
+    ranges: &HashMap<String, f64>,
+) -> f64 {
+    let total = target.len().max(other.len());
+    if total == 0 {
+        return 1.0;
+    }
+    let mut matched = 0.0;
+    for (url, value, _) in target {
+        let Some((_, other_value, _)) = other.iter().find(|(u, _, _)| u == url) else {
+            continue;
+        };
+        let range = ranges
+            .get(url)
+            .copied()
+            .unwrap_or_else(|| value.abs().max(1.0));
+        let closeness = 1.0 - ((value - other_value).abs() / range).min(1.0);
+        matched += 0.5 + 0.5 * closeness;
+    }
+    matched / total as f64
+}
+
+fn score_difference(a: i64, b: i64, max_difference: f64) -> f64 {
+    1.0 - ((a - b).abs() as f64 / max_difference).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A rank 8 riven, values of multiples of 9 stay exact through the rank scaling.
+    fn profile(
+        attributes: &[(&str, f64, bool)],
+        re_rolls: i64,
+        mastery_rank: i64,
+        polarity: &str,
+    ) -> RivenRollProfile {
+        RivenRollProfile::new(
+            attributes
+                .iter()
+                .map(|(url, value, positive)| (url.to_string(), *value, *positive)),
+            8,
+            re_rolls,
+            mastery_rank,
+            polarity,
+        )
+    }
+
+    fn ranges() -> HashMap<String, f64> {
+        HashMap::from([
+            ("critical_chance".to_string(), 72.0),
+            ("multishot".to_string(), 72.0),
+            ("zoom".to_string(), 36.0),
+        ])
+    }
+
+    fn target() -> RivenRollProfile {
+        profile(
+            &[
+                ("critical_chance", 144.0, true),
+                ("multishot", 90.0, true),
+                ("zoom", -45.0, false),
+            ],
+            10,
+            12,
+            "Madurai",
+        )
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn same_roll_scores_100() {
+        let similarity = score_riven_similarity(&target(), &target(), &ranges());
+        assert_eq!(similarity.score, 100.0);
+    }
+
+    #[test]
+    fn values_are_compared_at_max_rank() {
+        let unranked = RivenRollProfile::new(
+            [
+                ("critical_chance".to_string(), 16.0, true),
+                ("multishot".to_string(), 10.0, true),
+                ("zoom".to_string(), -5.0, false),
+            ],
+            0,
+            10,
+            12,
+            "madurai",
+        );
+        let similarity = score_riven_similarity(&target(), &unranked, &ranges());
+        assert_eq!(similarity.score, 100.0);
+    }
+
+    #[test]
+    fn parts_of_a_fixed_profile() {
+        let other = profile(
+            &[
+                ("critical_chance", 126.0, true),
+                ("toxin_damage", 54.0, true),
+                ("zoom", -45.0, false),
+            ],
+            14,
+            16,
+            "naramon",
+        );
+        let similarity = score_riven_similarity(&target(), &other, &ranges());
+        // Critical chance is a quarter of its range away and multishot is missing: (0.5 + 0.375) / 2
+        assert_close(similarity.positive, 0.4375);
+        assert_close(similarity.negative, 1.0);
+        assert_close(similarity.re_rolls, 0.6);
+        assert_close(similarity.mastery_rank, 0.5);
+        assert_close(similarity.polarity, 0.0);
+        // (0.4375 * 0.5 + 0.2 + 0.6 * 0.1 + 0.5 * 0.1) * 100 = 52.875
+        assert_eq!(similarity.score, 52.9);
+    }
+
+    #[test]
+    fn unknown_ranges_fall_back_to_the_value() {
+        let other = profile(
+            &[
+                ("critical_chance", 144.0, true),
+                ("multishot", 90.0, true),
+                ("zoom", -27.0, false),
+            ],
+            10,
+            12,
+            "madurai",
+        );
+        let similarity = score_riven_similarity(&target(), &other, &HashMap::new());
+        // Zoom is 18 away from a value of 45: 0.5 + 0.5 * 0.6
+        assert_close(similarity.negative, 0.8);
+        assert_close(similarity.positive, 1.0);
+    }
+
+    #[test]
+    fn no_stats_on_either_side_count_as_alike() {
+        let bare = profile(&[], 10, 12, "madurai");
+        let similarity = score_riven_similarity(&bare, &bare, &ranges());
+        assert_eq!(similarity.score, 100.0);
+    }
+
+    #[test]
+    fn comparables_keep_the_most_similar_cheapest_first() {
+        let close = profile(
+            &[
+                ("critical_chance", 126.0, true),
+                ("multishot", 90.0, true),
+                ("zoom", -45.0, false),
+            ],
+            10,
+            12,
+            "madurai",
+        );
+        let far = profile(&[("toxin_damage", 54.0, true)], 0, 4, "naramon");
+        let candidates = vec![
+            ("same".to_string(), 300, target()),
+            ("close".to_string(), 200, close),
+            ("far".to_string(), 50, far),
+            ("same_cheaper".to_string(), 250, target()),
+        ];
+        let ids = |comparables: Vec<RivenComparable>| {
+            comparables.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        };
+
+        let comparables = pick_comparables(&target(), candidates.clone(), &ranges(), 50.0, 2);
+        assert_eq!(ids(comparables), vec!["same_cheaper", "same"]);
+
+        let comparables = pick_comparables(&target(), candidates.clone(), &ranges(), 50.0, 0);
+        assert_eq!(ids(comparables), vec!["close", "same_cheaper", "same"]);
+
+        let comparables = pick_comparables(&target(), candidates, &ranges(), 0.0, 0);
+        assert_eq!(
+            ids(comparables),
+            vec!["far", "close", "same_cheaper", "same"]
+        );
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     APP, HAS_STARTED,
 };
 use serde_json::{json, Value};
-use utils::Error;
+use utils::{get_location, Error};
 
 #[tauri::command]
 pub async fn initialized() -> Result<bool, Error> {
@@ -48,6 +48,22 @@ pub async fn app_update_settings(
     app: tauri::State<'_, Mutex<AppState>>,
     log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
 ) -> Result<Settings, Error> {
+    let errors = settings.notifications.validate();
+    if !errors.is_empty() {
+        return Err(Error::new(
+            "Command::AppUpdateSettings",
+            format!("Invalid notification templates: {}", errors.join("; ")),
+            get_location!(),
+        ));
+    }
+    let errors = settings.summary_settings.validate();
+    if !errors.is_empty() {
+        return Err(Error::new(
+            "Command::AppUpdateSettings",
+            format!("Invalid summary settings: {}", errors.join("; ")),
+            get_location!(),
+        ));
+    }
     let mut app = app.lock()?;
     settings.notifications.custom_sounds = app.settings.notifications.custom_sounds.clone();
     let log_parser = log_parser.lock()?;
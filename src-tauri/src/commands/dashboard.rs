@@ -6,32 +6,32 @@ use crate::{
     DATABASE,
 };
 use chrono::Utc;
-use entity::{
-    dto::*,
-    transaction::{self, dto::*},
-};
+use entity::{dto::*, transaction::dto::*};
 use serde_json::{json, Value};
 use service::*;
 use utils::*;
 
-pub async fn get_total_summary(transactions: &Vec<transaction::Model>) -> Result<Value, Error> {
-    let (this_year_total, this_year_graph) = generate_transaction_summary(
-        transactions,
-        Utc::now(),
-        GroupByDate::Year,
-        &[GroupByDate::Year, GroupByDate::Month],
-        false,
-    );
+pub async fn get_total_summary(periods: &ReportPeriods) -> Result<Value, Error> {
+    let conn = DATABASE.get().unwrap();
+    let this_year = periods.start_end_of(Utc::now(), GroupByDate::Year);
+    let (this_year_total, this_year_graph) =
+        generate_transaction_summary(this_year, &[GroupByDate::Year, GroupByDate::Month], periods)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
 
     let (last_year_total, last_year_graph) = generate_transaction_summary(
-        transactions,
-        Utc::now() - chrono::Duration::days(365),
-        GroupByDate::Year,
+        periods.start_end_of(this_year.0 - chrono::Duration::days(1), GroupByDate::Year),
         &[GroupByDate::Year, GroupByDate::Month],
-        false,
-    );
+        periods,
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))?;
 
-    let mut payload = json!(FinancialReport::from(transactions));
+    let total =
+        TransactionQuery::get_financial_report(conn, &TransactionPaginationQueryDto::new(1, -1))
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+    let mut payload = json!(total);
     payload["present_year"] = json!({
         "summary": this_year_total,
         "chart": this_year_graph
@@ -44,40 +44,50 @@ pub async fn get_total_summary(transactions: &Vec<transaction::Model>) -> Result
     Ok(payload)
 }
 
-pub async fn get_today_summary(transactions: &Vec<transaction::Model>) -> Result<Value, Error> {
+pub async fn get_today_summary(periods: &ReportPeriods) -> Result<Value, Error> {
+    // A day starting at the daily reset can span two dates in the reporting timezone
+    let group_by: &[GroupByDate] = if periods.daily_reset {
+        &[
+            GroupByDate::Year,
+            GroupByDate::Month,
+            GroupByDate::Day,
+            GroupByDate::Hour,
+        ]
+    } else {
+        &[GroupByDate::Hour]
+    };
     let (report, graph) = generate_transaction_summary(
-        transactions,
-        Utc::now(),
-        GroupByDate::Day,
-        &[GroupByDate::Hour],
-        false,
-    );
+        periods.start_end_of(Utc::now(), GroupByDate::Day),
+        group_by,
+        periods,
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))?;
     Ok(json!({
         "summary": report,
         "chart": graph
     }))
 }
-pub async fn get_best_selling_items(
-    transactions: &Vec<transaction::Model>,
-) -> Result<FinancialReport, Error> {
-    let mut reports = Vec::new();
-
-    let items = group_by(transactions, |item| item.wfm_id.clone());
-
-    for (_, group) in items {
-        if let Some(first_item) = group.first() {
-            let data = json!({
-                "wfm_id": first_item.wfm_id,
-                "item_name": first_item.item_name,
-                "item_type": first_item.item_type,
-            });
 
-            let report = FinancialReport::from(&group);
-            reports.push(report.with_properties(data));
-        }
-    }
-
-    reports.sort_by(|a, b| b.total_profit.cmp(&a.total_profit));
+pub async fn get_week_summary(periods: &ReportPeriods) -> Result<Value, Error> {
+    let (report, graph) = generate_transaction_summary(
+        periods.week_of(Utc::now()),
+        &[GroupByDate::Year, GroupByDate::Month, GroupByDate::Day],
+        periods,
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))?;
+    Ok(json!({
+        "summary": report,
+        "chart": graph
+    }))
+}
+pub async fn get_best_selling_items() -> Result<FinancialReport, Error> {
+    let conn = DATABASE.get().unwrap();
+    let reports =
+        TransactionQuery::get_item_reports(conn, &TransactionPaginationQueryDto::new(1, -1), 1)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
 
     let best_seller = reports.into_iter().next().unwrap_or_else(|| {
         FinancialReport::default().with_properties(json!({
@@ -89,91 +99,64 @@ pub async fn get_best_selling_items(
 
     Ok(best_seller)
 }
-pub async fn get_recent_days_summary(
-    transactions: &Vec<transaction::Model>,
-    days: i64,
-) -> Result<Value, Error> {
-    let (mut start, end) = get_start_end_of(Utc::now(), GroupByDate::Day);
-    start = start - chrono::Duration::days(days); // Include the end date
-
-    let transactions = filters_by(transactions, |t| {
-        t.created_at >= start && t.created_at <= end
-    });
-
-    let mut grouped = group_by_date(
-        &transactions,
-        |t| t.created_at,
-        &[GroupByDate::Year, GroupByDate::Month, GroupByDate::Day],
-    );
-
-    fill_missing_date_keys(
-        &mut grouped,
-        start,
-        end,
+pub async fn get_recent_days_summary(days: i64, periods: &ReportPeriods) -> Result<Value, Error> {
+    let now = Utc::now();
+    // Include the end date
+    let (start, _) = periods.start_end_of(now - chrono::Duration::days(days), GroupByDate::Day);
+    let (_, end) = periods.start_end_of(now, GroupByDate::Day);
+
+    let (summary, graph) = generate_transaction_summary(
+        (start, end),
         &[GroupByDate::Year, GroupByDate::Month, GroupByDate::Day],
-    );
-
-    let graph = FinancialGraph::<i64>::from(&grouped, |group| {
-        FinancialReport::from(&group.to_vec()).total_profit
-    });
+        periods,
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))?;
 
     Ok(json!({
-        "summary": FinancialReport::from(&transactions),
+        "summary": summary,
         "chart": graph
     }))
 }
 pub async fn get_category_summary(
-    transactions: &Vec<transaction::Model>,
     categories: &Vec<SummaryCategorySetting>,
 ) -> Result<Vec<FinancialReport>, Error> {
+    let conn = DATABASE.get().unwrap();
     let mut items = vec![];
     for category in categories {
-        let tags = &category.tags;
-        let types = &category.types;
-        let filtered_transactions = filters_by(transactions, |t| {
-            let tag_matches = t
-                .tags
-                .split(',')
-                .any(|tag| tags.contains(&tag.trim().to_string()));
-
-            let type_matches = types.contains(&t.item_type.to_string());
-
-            tag_matches || type_matches
-        });
-        items.push(
-            FinancialReport::from(&filtered_transactions).with_properties(json!({
-                "icon": category.icon,
-                "name": category.name,
-            })),
-        );
+        let totals = TransactionQuery::get_category_totals(conn, &category.tags, &category.types)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        items.push(FinancialReport::from(&totals).with_properties(json!({
+            "icon": category.icon,
+            "name": category.name,
+        })));
     }
     Ok(items)
 }
 #[tauri::command]
 pub async fn dashboard_summary(app: tauri::State<'_, Mutex<AppState>>) -> Result<Value, Error> {
     let conn = DATABASE.get().unwrap();
-    let transactions = TransactionQuery::get_all(
-        &conn,
-        TransactionPaginationQueryDto::new(1, -1)
+    let app = app.lock()?.clone();
+    let recent_transactions = TransactionQuery::get_all(
+        conn,
+        TransactionPaginationQueryDto::new(1, app.settings.summary_settings.recent_transactions)
             .set_sort_by("created_at".to_string())
             .set_sort_direction(SortDirection::Desc),
     )
     .await
-    .unwrap();
+    .map_err(|e| e.with_location(get_location!()))?;
 
-    let app = app.lock()?.clone();
-    let category_summary = get_category_summary(
-        &transactions.results,
-        &app.settings.summary_settings.categories,
-    )
-    .await?;
+    let category_summary = get_category_summary(&app.settings.summary_settings.categories).await?;
+    let periods = app.settings.summary_settings.report_periods();
 
     Ok(json!({
-        "total": get_total_summary(&transactions.results).await.unwrap(),
-        "today": get_today_summary(&transactions.results).await.unwrap(),
-        "recent_days": get_recent_days_summary(&transactions.results, app.settings.summary_settings.recent_days).await.unwrap(),
-        "best_seller": get_best_selling_items(&transactions.results).await?,
+        "total": get_total_summary(&periods).await?,
+        "today": get_today_summary(&periods).await?,
+        "this_week": get_week_summary(&periods).await?,
+        "recent_days": get_recent_days_summary(app.settings.summary_settings.recent_days, &periods).await?,
+        "best_seller": get_best_selling_items().await?,
         "categories": category_summary,
-        "resent_transactions": transactions.take_top(app.settings.summary_settings.recent_transactions as usize)
+        "resent_transactions": recent_transactions.results
     }))
 }
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use entity::{
     dto::{PaginatedResult, PaginationQueryDto},
@@ -12,7 +15,9 @@ use wf_market::enums::OrderType;
 
 use crate::{
     add_metric, app::AppState, enums::TradeMode, helper::paginate,
-    log_parser::LogParserState, utils::SubTypeExt, APP,
+    log_parser::{replay_file, LogParserState, ReplayedTrade},
+    utils::SubTypeExt,
+    APP,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -140,6 +145,13 @@ pub async fn debug_export_ee_logs(
     Ok("".to_string())
 }
 #[tauri::command]
+pub async fn debug_replay_ee_log(path: String) -> Result<Vec<ReplayedTrade>, Error> {
+    let trades =
+        replay_file(&PathBuf::from(&path)).map_err(|e| e.with_location(get_location!()))?;
+    add_metric!("replay_ee_log", "success");
+    Ok(trades)
+}
+#[tauri::command]
 pub async fn debug_test(app: tauri::State<'_, Mutex<AppState>>) -> Result<Properties, Error> {
     let mut properties = Properties::default();
     let app = app.lock()?.clone();
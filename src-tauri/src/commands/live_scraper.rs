@@ -1,4 +1,7 @@
-use std::sync::{atomic::Ordering, Arc, Mutex};
+use std::{
+    path::PathBuf,
+    sync::{atomic::Ordering, Arc, Mutex},
+};
 
 use serde_json::{json, Value};
 use utils::Error;
@@ -7,7 +10,7 @@ use crate::{
     add_metric,
     app::{ItemSettings, ItemWtbSettings},
     cache::{CacheState, ItemPriceInfo},
-    live_scraper::{self, LiveScraperState},
+    live_scraper::{self, BacktestReport, LiveScraperState, MarketSnapshot, SchedulerStats},
     send_event,
     types::*,
 };
@@ -38,6 +41,12 @@ pub async fn live_scraper_get_state(
     }))
 }
 #[tauri::command]
+pub async fn live_scraper_get_scheduler_stats(
+    live_scraper: tauri::State<'_, Arc<LiveScraperState>>,
+) -> Result<SchedulerStats, Error> {
+    Ok(live_scraper.scheduler().stats())
+}
+#[tauri::command]
 pub async fn live_scraper_get_interesting_wtb_items(
     settings: ItemSettings,
     cache: tauri::State<'_, Mutex<CacheState>>,
@@ -51,3 +60,14 @@ pub async fn live_scraper_get_interesting_wtb_items(
     }
     Ok(items)
 }
+#[tauri::command]
+pub async fn live_scraper_backtest(
+    settings: ItemSettings,
+    path: Option<String>,
+) -> Result<BacktestReport, Error> {
+    let folder = path
+        .map(PathBuf::from)
+        .unwrap_or_else(MarketSnapshot::folder);
+    let snapshots = MarketSnapshot::load_all(&folder)?;
+    Ok(live_scraper::run_backtest(&settings, snapshots))
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use entity::market_snapshot::*;
+use service::MarketSnapshotQuery;
+use utils::{get_location, Error, SubType};
+
+use crate::DATABASE;
+
+#[tauri::command]
+pub async fn market_snapshot_get_range(
+    wfm_id: String,
+    sub_type: Option<SubType>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Model>, Error> {
+    let conn = DATABASE.get().unwrap();
+    match MarketSnapshotQuery::get_range(conn, &wfm_id, sub_type, from, to).await {
+        Ok(data) => Ok(data),
+        Err(e) => Err(e.with_location(get_location!())),
+    }
+}
+
+#[tauri::command]
+pub async fn market_snapshot_get_downsampled(
+    wfm_id: String,
+    sub_type: Option<SubType>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    bucket_seconds: i64,
+) -> Result<Vec<MarketSnapshotBucket>, Error> {
+    let conn = DATABASE.get().unwrap();
+    match MarketSnapshotQuery::get_downsampled(conn, &wfm_id, sub_type, from, to, bucket_seconds)
+        .await
+    {
+        Ok(data) => Ok(data),
+        Err(e) => Err(e.with_location(get_location!())),
+    }
+}
+
+#[tauri::command]
+pub async fn market_snapshot_get_tracked_items() -> Result<Vec<String>, Error> {
+    let conn = DATABASE.get().unwrap();
+    match MarketSnapshotQuery::get_tracked_items(conn).await {
+        Ok(data) => Ok(data),
+        Err(e) => Err(e.with_location(get_location!())),
+    }
+}
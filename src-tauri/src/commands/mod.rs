@@ -12,7 +12,7 @@ pub mod user;
 pub mod live_scraper;
 // pub mod log;
 // pub mod log_parser;
-// pub mod notification;
+pub mod notification;
 pub mod order;
 // pub mod pages;
 // pub mod qf_client;
@@ -21,6 +21,7 @@ pub mod debug;
 pub mod handlers;
 pub mod item;
 pub mod market;
+pub mod market_snapshot;
 pub mod riven;
 pub mod sound;
 pub mod stock_item;
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+use utils::{get_location, Error};
+
+use crate::{app::WebhookEvent, notify_queue::*};
+
+#[tauri::command]
+pub async fn notification_get_deliveries(
+    queue: tauri::State<'_, Arc<NotifyQueueState>>,
+) -> Result<Vec<QueuedNotification>, Error> {
+    Ok(queue.get_all())
+}
+
+#[tauri::command]
+pub async fn notification_resend(
+    id: Option<String>,
+    queue: tauri::State<'_, Arc<NotifyQueueState>>,
+) -> Result<usize, Error> {
+    queue.resend(id.as_deref())
+}
+
+/// The published JSON schema of every webhook event, keyed by event name.
+#[tauri::command]
+pub async fn notification_get_webhook_schemas() -> Result<Value, Error> {
+    let mut schemas = Map::new();
+    for event in WebhookEvent::ALL {
+        let schema = serde_json::from_str(event.schema()).map_err(|e| {
+            Error::new(
+                "Command::NotificationGetWebhookSchemas",
+                format!("Invalid schema for {}: {}", event.as_str(), e),
+                get_location!(),
+            )
+        })?;
+        schemas.insert(event.as_str().to_string(), schema);
+    }
+    Ok(Value::Object(schemas))
+}
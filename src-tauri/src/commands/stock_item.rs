@@ -1,9 +1,8 @@
 use std::{collections::HashMap, sync::Mutex};
 
-use entity::{dto::*, stock_item::*};
-use service::{StockItemMutation, StockItemQuery};
-use tauri_plugin_dialog::DialogExt;
-use utils::{get_location, group_by, info, Error, LoggerOptions, OperationSet, SubType};
+use entity::{dto::*, stock_item::*, stock_lot};
+use service::{StockItemMutation, StockItemQuery, StockLotMutation, StockLotQuery};
+use utils::{get_location, group_by, Error, ExportOptions, OperationSet, SubType};
 use wf_market::enums::OrderType;
 
 use crate::{
@@ -13,7 +12,8 @@ use crate::{
     handlers::{handle_item_by_entity, handle_wfm_item, stock_item::handle_item},
     helper::{self},
     types::PermissionsFlags,
-    APP, DATABASE,
+    utils::modules::states,
+    DATABASE,
 };
 
 #[tauri::command]
@@ -64,7 +64,14 @@ pub async fn stock_item_sell(
     sub_type: Option<SubType>,
     quantity: i64,
     price: i64,
+    lot_ids: Option<Vec<i64>>,
 ) -> Result<stock_item::Model, Error> {
+    // Lots picked by the user, used when the lot method is `specific`
+    let mut flags = OperationSet::new();
+    if let Some(lot_ids) = lot_ids.filter(|ids| !ids.is_empty()) {
+        let ids = lot_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        flags.add(format!("Lots:{}", ids.join(",")));
+    }
     match handle_item(
         wfm_url,
         sub_type,
@@ -72,7 +79,7 @@ pub async fn stock_item_sell(
         price,
         "",
         OrderType::Sell,
-        &OperationSet::new(),
+        &flags,
     )
     .await
     {
@@ -116,6 +123,10 @@ pub async fn stock_item_delete(id: i64) -> Result<stock_item::Model, Error> {
         Ok(_) => {}
         Err(e) => return Err(e.with_location(get_location!())),
     }
+    // Removed without a sale, the open lots are closed without a profit
+    StockLotMutation::close_open(conn, &item.wfm_url, item.sub_type.clone())
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
 
     Ok(item)
 }
@@ -125,19 +136,47 @@ pub async fn stock_item_delete_multiple(ids: Vec<i64>) -> Result<i64, Error> {
     let conn = DATABASE.get().unwrap();
     let mut deleted_count = 0;
 
-    for id in ids {
-        match StockItemMutation::delete_by_id(conn, id).await {
+    let items = StockItemQuery::find_by_ids(conn, ids)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    for item in items {
+        match StockItemMutation::delete_by_id(conn, item.id).await {
             Ok(_) => deleted_count += 1,
             Err(e) => return Err(e.with_location(get_location!())),
         }
+        StockLotMutation::close_open(conn, &item.wfm_url, item.sub_type.clone())
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
     }
     Ok(deleted_count)
 }
 
+#[tauri::command]
+pub async fn get_stock_item_lots(
+    wfm_url: String,
+    sub_type: Option<SubType>,
+    include_closed: Option<bool>,
+) -> Result<Vec<stock_lot::Model>, Error> {
+    let conn = DATABASE.get().unwrap();
+    StockLotQuery::get_by_url_and_sub_type(
+        conn,
+        &wfm_url,
+        sub_type,
+        include_closed.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))
+}
+
 #[tauri::command]
 pub async fn stock_item_update(input: UpdateStockItem) -> Result<stock_item::Model, Error> {
     let conn = DATABASE.get().unwrap();
-    match StockItemMutation::update_by_id(conn, input).await {
+    let method = states::get_settings()?
+        .live_scraper
+        .items
+        .general
+        .lot_method;
+    match StockLotMutation::update_item(conn, input, &method).await {
         Ok(stock_item) => Ok(stock_item),
         Err(e) => return Err(e.with_location(get_location!())),
     }
@@ -149,12 +188,17 @@ pub async fn stock_item_update_multiple(
     input: UpdateStockItem,
 ) -> Result<Vec<stock_item::Model>, Error> {
     let conn = DATABASE.get().unwrap();
+    let method = states::get_settings()?
+        .live_scraper
+        .items
+        .general
+        .lot_method;
     let mut updated_items = Vec::new();
 
     for id in ids {
         let mut update_input = input.clone();
         update_input.id = id;
-        match StockItemMutation::update_by_id(conn, update_input).await {
+        match StockLotMutation::update_item(conn, update_input, &method).await {
             Ok(stock_item) => updated_items.push(stock_item),
             Err(e) => return Err(e.with_location(get_location!())),
         }
@@ -209,54 +253,48 @@ pub async fn stock_item_get_by_id(
 
     Ok(item)
 }
-#[tauri::command]
-pub async fn export_stock_item_json(
+/// File name of the exports, the extension is added by the format.
+pub const EXPORT_NAME: &str = "Quantframe_Stock_Item";
+
+/// Every item matching the query, when the user may export data.
+pub async fn get_stock_item_export(
     app_state: tauri::State<'_, Mutex<AppState>>,
     mut query: StockItemPaginationQueryDto,
-) -> Result<String, Error> {
+) -> Result<Vec<stock_item::Model>, Error> {
     let app_state = app_state.lock()?.clone();
-    let app = APP.get().unwrap();
     if let Err(e) = app_state.user.has_permission(PermissionsFlags::ExportData) {
-        e.log("export_stock_item_json.log");
+        e.log("export_stock_item.log");
         return Err(e);
     }
-
     let conn = DATABASE.get().unwrap();
     query.pagination.limit = -1; // fetch all
-    match StockItemQuery::get_all(conn, query).await {
-        Ok(stock_items) => {
-            let file_path = app
-                .dialog()
-                .file()
-                .add_filter("Quantframe_Stock_Item", &["json"])
-                .blocking_save_file();
-            if let Some(file_path) = file_path {
-                let json = serde_json::to_string_pretty(&stock_items.results).map_err(|e| {
-                    Error::new(
-                        "Command::ExportStockItemJson",
-                        format!("Failed to serialize stock item to JSON: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                std::fs::write(file_path.as_path().unwrap(), json).map_err(|e| {
-                    Error::new(
-                        "Command::ExportStockItemJson",
-                        format!("Failed to write stock item to file: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                info(
-                    "Command::ExportStockItemJson",
-                    format!("Exported stock item to JSON file: {}", file_path),
-                    &LoggerOptions::default(),
-                );
-                add_metric!("export_stock_item_json", "success");
-                return Ok(file_path.to_string());
-            }
-            // do something with the optional file path here
-            // the file path is `None` if the user closed the dialog
-            return Ok("".to_string());
-        }
-        Err(e) => return Err(e.with_location(get_location!())),
+    let stock_items = StockItemQuery::get_all(conn, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(stock_items.results)
+}
+
+/// Exports the items matching the query, to `path` when given or to a file picked in a save dialog.
+#[tauri::command]
+pub async fn export_stock_item(
+    app_state: tauri::State<'_, Mutex<AppState>>,
+    query: StockItemPaginationQueryDto,
+    options: ExportOptions,
+    path: Option<String>,
+) -> Result<String, Error> {
+    let stock_items = get_stock_item_export(app_state, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let file_path = helper::write_export(
+        "Command::ExportStockItem",
+        EXPORT_NAME,
+        &stock_items,
+        &options,
+        path,
+    )
+    .map_err(|e| e.with_location(get_location!()))?;
+    if !file_path.is_empty() {
+        add_metric!("export_stock_item", options.format.extension());
     }
+    Ok(file_path)
 }
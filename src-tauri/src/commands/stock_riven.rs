@@ -2,8 +2,7 @@ use std::{collections::HashMap, sync::Mutex};
 
 use entity::{dto::*, stock_riven::*};
 use service::{StockRivenMutation, StockRivenQuery};
-use tauri_plugin_dialog::DialogExt;
-use utils::{get_location, group_by, info, Error, LoggerOptions, OperationSet};
+use utils::{get_location, group_by, info, Error, ExportOptions, OperationSet};
 use wf_market::enums::OrderType;
 
 use crate::{
@@ -14,7 +13,7 @@ use crate::{
     helper,
     types::PermissionsFlags,
     utils::ErrorFromExt,
-    APP, DATABASE,
+    DATABASE,
 };
 
 #[tauri::command]
@@ -248,52 +247,45 @@ pub async fn stock_riven_get_by_id(
     Ok(item)
 }
 
-#[tauri::command]
-pub async fn export_stock_riven_json(
+/// File name of the exports, the extension is added by the format.
+pub const EXPORT_NAME: &str = "Quantframe_Stock_Riven";
+
+/// Every item matching the query, when the user may export data.
+pub async fn get_stock_riven_export(
     app_state: tauri::State<'_, Mutex<AppState>>,
     mut query: StockRivenPaginationQueryDto,
-) -> Result<String, Error> {
+) -> Result<Vec<stock_riven::Model>, Error> {
     let app_state = app_state.lock()?.clone();
-    let app = APP.get().unwrap();
     if let Err(e) = app_state.user.has_permission(PermissionsFlags::ExportData) {
-        e.log("export_stock_riven_json.log");
+        e.log("export_stock_riven.log");
         return Err(e);
     }
     let conn = DATABASE.get().unwrap();
     query.pagination.limit = -1; // fetch all
-    match StockRivenQuery::get_all(conn, query).await {
-        Ok(stock_riven) => {
-            let file_path = app
-                .dialog()
-                .file()
-                .add_filter("Quantframe_Stock_Riven", &["json"])
-                .blocking_save_file();
-            if let Some(file_path) = file_path {
-                let json = serde_json::to_string_pretty(&stock_riven.results).map_err(|e| {
-                    Error::new(
-                        "Command::ExportStockRivenJson",
-                        format!("Failed to serialize stock riven to JSON: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                std::fs::write(file_path.as_path().unwrap(), json).map_err(|e| {
-                    Error::new(
-                        "Command::ExportStockRivenJson",
-                        format!("Failed to write stock riven to file: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                info(
-                    "Command::ExportStockRivenJson",
-                    format!("Exported stock riven to JSON file: {}", file_path),
-                    &LoggerOptions::default(),
-                );
-                return Ok(file_path.to_string());
-            }
-            // do something with the optional file path here
-            // the file path is `None` if the user closed the dialog
-            return Ok("".to_string());
-        }
-        Err(e) => return Err(e.with_location(get_location!())),
-    }
+    let stock_rivens = StockRivenQuery::get_all(conn, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(stock_rivens.results)
+}
+
+/// Exports the items matching the query, to `path` when given or to a file picked in a save dialog.
+#[tauri::command]
+pub async fn export_stock_riven(
+    app_state: tauri::State<'_, Mutex<AppState>>,
+    query: StockRivenPaginationQueryDto,
+    options: ExportOptions,
+    path: Option<String>,
+) -> Result<String, Error> {
+    let stock_rivens = get_stock_riven_export(app_state, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let file_path = helper::write_export(
+        "Command::ExportStockRiven",
+        EXPORT_NAME,
+        &stock_rivens,
+        &options,
+        path,
+    )
+    .map_err(|e| e.with_location(get_location!()))?;
+    Ok(file_path)
 }
@@ -7,10 +7,9 @@ use entity::{
 };
 use serde_json::json;
 use service::{TransactionMutation, TransactionQuery};
-use tauri_plugin_dialog::DialogExt;
-use utils::{get_location, group_by, info, warning, Error, LoggerOptions};
+use utils::{get_location, info, warning, Error, ExportOptions, LoggerOptions};
 
-use crate::{add_metric, app::AppState, types::PermissionsFlags, APP, DATABASE};
+use crate::{add_metric, app::AppState, helper, types::PermissionsFlags, DATABASE};
 
 #[tauri::command]
 pub async fn get_transaction_pagination(
@@ -23,39 +22,42 @@ pub async fn get_transaction_pagination(
     };
 }
 
+// Trading partners listed in the financial report
+const TRADING_PARTNERS_LIMIT: u64 = 10;
+
 #[tauri::command]
 pub async fn get_transaction_financial_report(
     query: TransactionPaginationQueryDto,
 ) -> Result<FinancialReport, Error> {
-    let items = get_transaction_pagination(query.clone()).await?.results;
-
-    let mut trading_partners = group_by(&items, |item| {
-        if item.user_name == "" {
-            "Unknown".to_string()
-        } else {
-            item.user_name.clone()
-        }
-    });
-    // Remove Unknown trading partners
-    trading_partners.remove("Unknown");
-    let mut trading_partners = trading_partners
-        .iter()
-        .map(|(name, items)| {
-            FinancialReport::from(items).with_properties(json!({
-                "user": name,
-            }))
-        })
-        .collect::<Vec<FinancialReport>>();
-    trading_partners.sort_by(|a, b| b.total_transactions.cmp(&a.total_transactions));
-
-    let mut report = FinancialReport::from(&items);
-    report.properties.set_property_value(
-        "trading_partners",
-        trading_partners.into_iter().take(10).collect::<Vec<_>>(),
-    );
+    let conn = DATABASE.get().unwrap();
+    let mut report = TransactionQuery::get_financial_report(conn, &query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let trading_partners =
+        TransactionQuery::get_partner_reports(conn, &query, TRADING_PARTNERS_LIMIT)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+    report
+        .properties
+        .set_property_value("trading_partners", trading_partners);
     Ok(report)
 }
 
+// Partners and items listed in the history report
+const HISTORY_REPORT_LIMIT: u64 = 25;
+
+#[tauri::command]
+pub async fn get_transaction_history_report(
+    app: tauri::State<'_, Mutex<AppState>>,
+    query: TransactionPaginationQueryDto,
+) -> Result<TradeHistoryReport, Error> {
+    let conn = DATABASE.get().unwrap();
+    let periods = app.lock()?.settings.summary_settings.report_periods();
+    TransactionQuery::get_history_report(conn, &query, &periods, HISTORY_REPORT_LIMIT)
+        .await
+        .map_err(|e| e.with_location(get_location!()))
+}
+
 #[tauri::command]
 pub async fn transaction_delete(id: i64) -> Result<transaction::Model, Error> {
     let conn = DATABASE.get().unwrap();
@@ -108,55 +110,50 @@ pub async fn transaction_update(input: UpdateTransaction) -> Result<transaction:
         Err(e) => return Err(e.with_location(get_location!())),
     }
 }
-#[tauri::command]
-pub async fn export_transaction_json(
+/// File name of the exports, the extension is added by the format.
+pub const EXPORT_NAME: &str = "Quantframe_Transactions";
+
+/// Every item matching the query, when the user may export data.
+pub async fn get_transaction_export(
     app_state: tauri::State<'_, Mutex<AppState>>,
     mut query: TransactionPaginationQueryDto,
-) -> Result<String, Error> {
+) -> Result<Vec<transaction::Model>, Error> {
     let app_state = app_state.lock()?.clone();
-    let app = APP.get().unwrap();
     if let Err(e) = app_state.user.has_permission(PermissionsFlags::ExportData) {
-        e.log("export_transaction_json.log");
+        e.log("export_transaction.log");
         return Err(e);
     }
     let conn = DATABASE.get().unwrap();
     query.pagination.limit = -1; // fetch all
-    match TransactionQuery::get_all(conn, query).await {
-        Ok(transaction) => {
-            let file_path = app
-                .dialog()
-                .file()
-                .add_filter("Quantframe_Transactions", &["json"])
-                .blocking_save_file();
-            if let Some(file_path) = file_path {
-                let json = serde_json::to_string_pretty(&transaction.results).map_err(|e| {
-                    Error::new(
-                        "Command::ExportTransactionJson",
-                        format!("Failed to serialize transactions to JSON: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                std::fs::write(file_path.as_path().unwrap(), json).map_err(|e| {
-                    Error::new(
-                        "Command::ExportTransactionJson",
-                        format!("Failed to write transactions to file: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                info(
-                    "Command::ExportTransactionJson",
-                    format!("Exported transactions to JSON file: {}", file_path),
-                    &LoggerOptions::default(),
-                );
-                add_metric!("export_transaction_json", "success");
-                return Ok(file_path.to_string());
-            }
-            // do something with the optional file path here
-            // the file path is `None` if the user closed the dialog
-            return Ok("".to_string());
-        }
-        Err(e) => return Err(e.with_location(get_location!())),
+    let transactions = TransactionQuery::get_all(conn, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(transactions.results)
+}
+
+/// Exports the items matching the query, to `path` when given or to a file picked in a save dialog.
+#[tauri::command]
+pub async fn export_transaction(
+    app_state: tauri::State<'_, Mutex<AppState>>,
+    query: TransactionPaginationQueryDto,
+    options: ExportOptions,
+    path: Option<String>,
+) -> Result<String, Error> {
+    let transactions = get_transaction_export(app_state, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let file_path = helper::write_export(
+        "Command::ExportTransaction",
+        EXPORT_NAME,
+        &transactions,
+        &options,
+        path,
+    )
+    .map_err(|e| e.with_location(get_location!()))?;
+    if !file_path.is_empty() {
+        add_metric!("export_transaction", options.format.extension());
     }
+    Ok(file_path)
 }
 
 #[tauri::command]
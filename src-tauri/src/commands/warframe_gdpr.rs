@@ -1,18 +1,29 @@
 use std::sync::{Arc, Mutex};
 
 use serde_json::{json, Value};
-use utils::Error;
+use utils::{get_location, Error};
 
-use crate::log_parser::LogParserState;
+use crate::log_parser::{
+    import_gdpr_trades, GdprImportSummary, GdprParseStats, LogParserState, WarframeGDPRModule,
+};
+
+fn get_module(
+    log_parser: &tauri::State<'_, Mutex<Arc<LogParserState>>>,
+) -> Result<Arc<WarframeGDPRModule>, Error> {
+    let log_parser = log_parser.lock()?;
+    Ok(log_parser.warframe_gdpr())
+}
 
 #[tauri::command]
 pub async fn wfgdpr_get_state(
     log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
 ) -> Result<Value, Error> {
-    // Read the file content
-    let log_parser = log_parser.lock()?;
+    let gdpr = get_module(&log_parser)?;
+    gdpr.ensure_restored()
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
     Ok(json!({
-        "was_initialized": log_parser.warframe_gdpr().was_initialized(),
+        "was_initialized": gdpr.was_initialized(),
     }))
 }
 
@@ -20,17 +31,62 @@ pub async fn wfgdpr_get_state(
 pub async fn wfgdpr_load(
     file_path: String,
     log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
+) -> Result<GdprParseStats, Error> {
+    let gdpr = get_module(&log_parser)?;
+    gdpr.load(&file_path)
+        .await
+        .map_err(|e| e.with_location(get_location!()))
+}
+
+#[tauri::command]
+pub async fn wfgdpr_update_trade_notes(
+    id: i64,
+    notes: Option<String>,
+    log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
 ) -> Result<(), Error> {
-    // Read the file content
-    let log_parser = log_parser.lock()?;
-    log_parser.warframe_gdpr().load(&file_path)?;
-    Ok(())
+    let gdpr = get_module(&log_parser)?;
+    gdpr.update_trade_notes(id, notes)
+        .await
+        .map_err(|e| e.with_location(get_location!()))
+}
+
+#[tauri::command]
+pub async fn wfgdpr_clear(
+    log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
+) -> Result<(), Error> {
+    let gdpr = get_module(&log_parser)?;
+    gdpr.clear()
+        .await
+        .map_err(|e| e.with_location(get_location!()))
 }
 
 #[tauri::command]
 pub async fn wfgdpr_get_accounts(
     log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
 ) -> Result<Value, Error> {
-    let log_parser = log_parser.lock()?;
-    Ok(json!(log_parser.warframe_gdpr().accounts()))
+    let gdpr = get_module(&log_parser)?;
+    gdpr.ensure_restored()
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(json!(gdpr.accounts()))
+}
+
+#[tauri::command]
+pub async fn wfgdpr_import_transactions(
+    log_parser: tauri::State<'_, Mutex<Arc<LogParserState>>>,
+) -> Result<GdprImportSummary, Error> {
+    let gdpr = get_module(&log_parser)?;
+    gdpr.ensure_restored()
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    if !gdpr.was_initialized() {
+        return Err(Error::new(
+            "Command::WfgdprImportTransactions",
+            "Load a Warframe GDPR export first",
+            get_location!(),
+        ));
+    }
+    import_gdpr_trades(&gdpr.accounts())
+        .await
+        .map_err(|e| e.with_location(get_location!()))
 }
@@ -1,12 +1,26 @@
-use entity::stock_riven::StockRivenPaginationQueryDto;
+use entity::{
+    stock_item::{CreateStockItem, StockItemPaginationQueryDto, UpdateStockItem},
+    stock_riven::{self, StockRivenPaginationQueryDto, UpdateStockRiven},
+};
 use serde_json::{json, Value};
-use service::StockRivenQuery;
+use service::{StockItemMutation, StockItemQuery, StockRivenMutation, StockRivenQuery};
 use std::sync::{Arc, Mutex};
-use utils::Error;
+use utils::{get_location, info, Error, LoggerOptions};
+use wf_market::enums::OrderType;
 
-use crate::wf_inventory::WFInventoryState;
+use crate::app::AppState;
+use crate::commands::{stock_item::stock_item_delete, stock_riven::stock_riven_delete};
+use crate::handlers::handle_riven_by_entity;
+use crate::helper::suggest_riven_price;
+use crate::types::UIEvent;
+use crate::utils::modules::states;
+use crate::utils::CreateStockItemExt;
 use crate::wf_inventory::WFItemPaginationDto;
-use crate::DATABASE;
+use crate::wf_inventory::{
+    ItemReconciliation, ReconciliationKind, ReconciliationReport, RivenReconciliation,
+    WFInventoryState,
+};
+use crate::{add_metric, send_event, DATABASE};
 
 #[tauri::command]
 pub async fn wf_inventory_get_rivens(
@@ -26,3 +40,219 @@ pub async fn wf_inventory_get_rivens(
 
     Ok(json!(veiled))
 }
+
+#[tauri::command]
+pub async fn wf_inventory_reconcile(
+    wf_inventory: tauri::State<'_, Mutex<Arc<WFInventoryState>>>,
+) -> Result<ReconciliationReport, Error> {
+    let wf_inventory = wf_inventory.lock()?.clone();
+    reconcile(&wf_inventory)
+        .await
+        .map_err(|e| e.with_location(get_location!()))
+}
+
+/// Applies the picked entries of a reconciliation report.
+/// Item quantities are set directly, without opening or consuming lots or recording a sale,
+/// as the cost of the difference is unknown. Removed stock also loses its market order or auction.
+#[tauri::command]
+pub async fn wf_inventory_apply_reconciliation(
+    items: Vec<ItemReconciliation>,
+    rivens: Vec<RivenReconciliation>,
+    wf_inventory: tauri::State<'_, Mutex<Arc<WFInventoryState>>>,
+    app: tauri::State<'_, Mutex<AppState>>,
+) -> Result<ReconciliationReport, Error> {
+    let wf_inventory = wf_inventory.lock()?.clone();
+    let conn = DATABASE.get().unwrap();
+    let file = "wf_inventory_apply_reconciliation.log";
+
+    for item in items.iter() {
+        match (&item.kind, item.stock_id) {
+            (ReconciliationKind::OverCounted, Some(id)) if item.inventory_quantity <= 0 => {
+                stock_item_delete(id).await?;
+            }
+            (_, Some(id)) if item.difference() != 0 => {
+                StockItemMutation::update_by_id(
+                    conn,
+                    UpdateStockItem::new(id).with_owned(item.inventory_quantity),
+                )
+                .await
+                .map_err(|e| e.with_location(get_location!()).log(file))?;
+            }
+            (ReconciliationKind::MissingInStock, None) => {
+                let mut create = CreateStockItem::new(
+                    &item.wfm_url,
+                    item.sub_type.clone(),
+                    item.inventory_quantity,
+                );
+                create
+                    .validate()
+                    .map_err(|e| e.with_location(get_location!()).log(file))?;
+                StockItemMutation::create(conn, create.to_model())
+                    .await
+                    .map_err(|e| e.with_location(get_location!()).log(file))?;
+            }
+            _ => {}
+        }
+    }
+
+    for riven in rivens.iter() {
+        match (&riven.kind, riven.stock_id, &riven.riven) {
+            (ReconciliationKind::UnveiledNotInStock, _, Some(base)) => {
+                handle_riven_by_entity(base.to_create(), "", OrderType::Buy, &[])
+                    .await
+                    .map_err(|e| e.with_location(get_location!()).log(file))?;
+            }
+            (ReconciliationKind::OverCounted, Some(id), _) => {
+                stock_riven_delete(id, app.clone()).await?;
+            }
+            _ => {}
+        }
+    }
+
+    info(
+        "Command:WFInventoryApplyReconciliation",
+        format!(
+            "Applied {} item and {} riven reconciliations",
+            items.len(),
+            rivens.len()
+        ),
+        &LoggerOptions::default(),
+    );
+    add_metric!("wf_inventory_apply_reconciliation", "manual");
+    if !items.is_empty() {
+        send_event!(UIEvent::RefreshStockItems, json!({"source": "WFInventory"}));
+    }
+    if !rivens.is_empty() {
+        send_event!(
+            UIEvent::RefreshStockRivens,
+            json!({"source": "WFInventory"})
+        );
+    }
+    reconcile(&wf_inventory)
+        .await
+        .map_err(|e| e.with_location(get_location!()))
+}
+
+/// Creates stock rivens from the picked inventory rivens, graded and priced from similar live auctions.
+/// Rivens that are already in stock are left out.
+#[tauri::command]
+pub async fn wf_inventory_import_rivens(
+    uuids: Vec<String>,
+    bought: Option<i64>,
+    suggest_price: Option<bool>,
+    wf_inventory: tauri::State<'_, Mutex<Arc<WFInventoryState>>>,
+    app: tauri::State<'_, Mutex<AppState>>,
+) -> Result<Vec<stock_riven::Model>, Error> {
+    let wf_inventory = wf_inventory.lock()?.clone();
+    let app = app.lock()?.clone();
+    let conn = DATABASE.get().unwrap();
+    let file = "wf_inventory_import_rivens.log";
+    wf_inventory.reload().await?;
+
+    let in_stock = StockRivenQuery::get_all(conn, StockRivenPaginationQueryDto::new(1, -1))
+        .await
+        .map_err(|e| e.with_location(get_location!()))?
+        .results
+        .into_iter()
+        .map(|r| r.uuid)
+        .collect::<Vec<_>>();
+    let rivens = wf_inventory
+        .riven()
+        .get_all_rivens()?
+        .into_iter()
+        .filter(|r| uuids.contains(&r.base.uuid) && !in_stock.contains(&r.base.uuid))
+        .collect::<Vec<_>>();
+
+    let mut imported = vec![];
+    for riven in rivens {
+        let mut input = riven.base.to_create();
+        input.bought = bought.filter(|b| *b > 0);
+        let grade = riven
+            .base
+            .properties
+            .get_property_value("grade", Value::Null);
+        input.properties.set_property_value("grade", grade.clone());
+
+        // A failed lookup leaves the riven without a list price instead of stopping the import
+        let suggested_price = if suggest_price.unwrap_or(true) {
+            let scraper = states::live_scraper();
+            scraper.wait_for_request().await;
+            match suggest_riven_price(
+                &riven.base.wfm_url,
+                &riven.base.attributes,
+                &app.wfm_client,
+                &app.user.wfm_username,
+            )
+            .await
+            {
+                Ok(price) => {
+                    scraper.scheduler().on_success();
+                    price
+                }
+                Err(e) => {
+                    e.with_location(get_location!()).log(file);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let (_, mut created) = handle_riven_by_entity(input, "", OrderType::Buy, &[])
+            .await
+            .map_err(|e| e.with_location(get_location!()).log(file))?;
+        if suggested_price.is_some() {
+            created = StockRivenMutation::update_by_id(
+                conn,
+                UpdateStockRiven::new(created.id).with_list_price(suggested_price),
+            )
+            .await
+            .map_err(|e| e.with_location(get_location!()).log(file))?;
+        }
+        info(
+            "Command:WFInventoryImportRivens",
+            format!(
+                "Imported riven {} {} | Grade: {} | Suggested price: {:?}",
+                created.weapon_name, created.mod_name, grade, suggested_price
+            ),
+            &LoggerOptions::default(),
+        );
+        imported.push(created);
+    }
+
+    add_metric!("wf_inventory_import_rivens", "manual");
+    if !imported.is_empty() {
+        send_event!(
+            UIEvent::RefreshStockRivens,
+            json!({"source": "WFInventory"})
+        );
+        if bought.unwrap_or(0) > 0 {
+            send_event!(
+                UIEvent::RefreshTransactions,
+                json!({"source": "WFInventory"})
+            );
+        }
+    }
+    Ok(imported)
+}
+
+async fn reconcile(wf_inventory: &WFInventoryState) -> Result<ReconciliationReport, Error> {
+    let conn = DATABASE.get().unwrap();
+    wf_inventory.reload().await?;
+
+    let inventory_items = wf_inventory.item().get_tradable_items()?;
+    let inventory_rivens = wf_inventory.riven().get_all_rivens()?;
+    let stock_items = StockItemQuery::get_all(conn, StockItemPaginationQueryDto::new(1, -1))
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let stock_rivens = StockRivenQuery::get_all(conn, StockRivenPaginationQueryDto::new(1, -1))
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+
+    Ok(ReconciliationReport::new(
+        &inventory_items,
+        &inventory_rivens,
+        &stock_items.results,
+        &stock_rivens.results,
+    ))
+}
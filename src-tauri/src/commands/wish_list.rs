@@ -1,9 +1,8 @@
 use entity::{dto::*, wish_list::*};
 use service::{WishListMutation, WishListQuery};
 use std::{collections::HashMap, sync::Mutex};
-use tauri_plugin_dialog::DialogExt;
 use utils::SubType;
-use utils::{get_location, group_by, info, Error, LoggerOptions, OperationSet};
+use utils::{get_location, group_by, Error, ExportOptions, OperationSet};
 use wf_market::enums::OrderType;
 
 use crate::{
@@ -13,7 +12,7 @@ use crate::{
     handlers::{handle_wfm_item, handle_wish_list, handle_wish_list_by_entity},
     helper,
     types::PermissionsFlags,
-    APP, DATABASE,
+    DATABASE,
 };
 
 #[tauri::command]
@@ -202,53 +201,48 @@ pub async fn wish_list_get_by_id(
 
     Ok(item)
 }
-#[tauri::command]
-pub async fn export_wish_list_json(
+/// File name of the exports, the extension is added by the format.
+pub const EXPORT_NAME: &str = "Quantframe_Wish_List";
+
+/// Every item matching the query, when the user may export data.
+pub async fn get_wish_list_export(
     app_state: tauri::State<'_, Mutex<AppState>>,
     mut query: WishListPaginationQueryDto,
-) -> Result<String, Error> {
+) -> Result<Vec<wish_list::Model>, Error> {
     let app_state = app_state.lock()?.clone();
-    let app = APP.get().unwrap();
     if let Err(e) = app_state.user.has_permission(PermissionsFlags::ExportData) {
-        e.log("export_wish_list_json.log");
+        e.log("export_wish_list.log");
         return Err(e);
     }
     let conn = DATABASE.get().unwrap();
     query.pagination.limit = -1; // fetch all
-    match WishListQuery::get_all(conn, query).await {
-        Ok(wish_list) => {
-            let file_path = app
-                .dialog()
-                .file()
-                .add_filter("Quantframe_Wish_List", &["json"])
-                .blocking_save_file();
-            if let Some(file_path) = file_path {
-                let json = serde_json::to_string_pretty(&wish_list.results).map_err(|e| {
-                    Error::new(
-                        "Command::ExportWishListJson",
-                        format!("Failed to serialize wish list to JSON: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                std::fs::write(file_path.as_path().unwrap(), json).map_err(|e| {
-                    Error::new(
-                        "Command::ExportWishListJson",
-                        format!("Failed to write wish list to file: {}", e),
-                        get_location!(),
-                    )
-                })?;
-                info(
-                    "Command::ExportWishListJson",
-                    format!("Exported wish list to JSON file: {}", file_path),
-                    &LoggerOptions::default(),
-                );
-                add_metric!("export_wish_list_json", "success");
-                return Ok(file_path.to_string());
-            }
-            // do something with the optional file path here
-            // the file path is `None` if the user closed the dialog
-            return Ok("".to_string());
-        }
-        Err(e) => return Err(e.with_location(get_location!())),
+    let wish_list = WishListQuery::get_all(conn, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(wish_list.results)
+}
+
+/// Exports the items matching the query, to `path` when given or to a file picked in a save dialog.
+#[tauri::command]
+pub async fn export_wish_list(
+    app_state: tauri::State<'_, Mutex<AppState>>,
+    query: WishListPaginationQueryDto,
+    options: ExportOptions,
+    path: Option<String>,
+) -> Result<String, Error> {
+    let wish_list = get_wish_list_export(app_state, query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let file_path = helper::write_export(
+        "Command::ExportWishList",
+        EXPORT_NAME,
+        &wish_list,
+        &options,
+        path,
+    )
+    .map_err(|e| e.with_location(get_location!()))?;
+    if !file_path.is_empty() {
+        add_metric!("export_wish_list", options.format.extension());
     }
+    Ok(file_path)
 }
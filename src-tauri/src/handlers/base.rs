@@ -155,8 +155,8 @@ pub async fn handle_transaction(
     let component = "HandleTransaction";
     let mut use_current_date = true; // This can be made dynamic based on flags if needed
 
-    // Find the existing transaction in the database
-    if transaction.transaction_type == TransactionType::Sale {
+    // Without lot history the profit is estimated from the last purchase
+    if transaction.transaction_type == TransactionType::Sale && transaction.profit.is_none() {
         let existing_transaction = TransactionQuery::get_all(
             conn,
             TransactionPaginationQueryDto::new(1, 1)
@@ -178,6 +178,8 @@ pub async fn handle_transaction(
 
             transaction.set_profit(total_profit);
         }
+    }
+    if transaction.transaction_type == TransactionType::Sale {
         // Overall credits calculation
         transaction.set_credits(transaction.price * crate::enums::TradeItemType::Platinum.to_tax());
     }
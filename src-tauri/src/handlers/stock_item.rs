@@ -1,11 +1,18 @@
-use entity::{dto::*, enums::*, stock_item::*, wish_list::CreateWishListItem};
+use entity::{
+    dto::*, enums::*, stock_item::*, stock_lot::LotConsumption, wish_list::CreateWishListItem,
+};
 use serde::{Deserialize, Serialize};
-use service::StockItemMutation;
+use serde_json::json;
+use service::StockLotMutation;
 use utils::SubType;
 use utils::{get_location, info, warning, Error, OperationSet};
 use wf_market::enums::OrderType;
 
-use crate::{handlers::*, utils::CreateStockItemExt, DATABASE};
+use crate::{
+    handlers::*,
+    utils::{modules::states, CreateStockItemExt},
+    DATABASE,
+};
 
 // --------------------------------------------------
 // Helper functions.
@@ -89,6 +96,24 @@ fn log(
         }
     }
 }
+/// Lot ids picked for a sale, `Lots:12,15` in the flags.
+fn picked_lots(flags: &OperationSet) -> Vec<i64> {
+    flags
+        .get_value_after("Lots")
+        .map(|ids| {
+            ids.split(',')
+                .filter_map(|id| id.trim().parse::<i64>().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+fn acquired_at(flags: &OperationSet) -> chrono::DateTime<chrono::Utc> {
+    flags
+        .get_value_after("SetDate")
+        .and_then(|date| chrono::DateTime::parse_from_rfc3339(&date).ok())
+        .map(|date| date.with_timezone(&chrono::Utc))
+        .unwrap_or_else(chrono::Utc::now)
+}
 fn should_run_wfm(flags: &OperationSet, operations: &OperationSet) -> bool {
     if let Some(value) = flags.get_value_after("SkipWFMCheck") {
         !operations.has(value)
@@ -119,22 +144,32 @@ pub async fn handle_item_by_entity(
     })?;
 
     let mut model = item.to_model();
+    let mut lot_consumption: Option<LotConsumption> = None;
 
     // --------------------------------------------------
     // Stock mutation (buy / sell)
     // --------------------------------------------------
     match order_type {
         OrderType::Sell => {
-            let (s_operation, updated_item) = StockItemMutation::sold_by_url_and_sub_type(
+            let method = states::get_settings()?
+                .live_scraper
+                .items
+                .general
+                .lot_method;
+            // The sale and the lots it consumes are written in one transaction
+            let (s_operation, updated_item, consumption) = StockLotMutation::sell(
                 con,
                 &item.wfm_url,
                 item.sub_type.clone(),
                 item.quantity,
+                &method,
+                &picked_lots(flags),
             )
             .await
             .map_err(|e| e.with_location(get_location!()).log(file))?;
 
             operations.add(format!("ItemSell_{s_operation}"));
+            operations.add(format!("LotsConsumed_{}", consumption.lots.len()));
             log(
                 component,
                 &item,
@@ -147,15 +182,29 @@ pub async fn handle_item_by_entity(
             if let Some(updated) = updated_item {
                 model = updated;
             }
+            lot_consumption = Some(consumption);
         }
 
         OrderType::Buy => {
-            let (s_operation, created_item) = StockItemMutation::add_item(con, model)
-                .await
-                .map_err(|e| e.with_location(get_location!()).log(file))?;
+            // Every purchase is its own lot, sales take their cost from the lots
+            let (s_operation, created_item, _) = StockLotMutation::buy(
+                con,
+                model,
+                entity::stock_lot::Model::new(
+                    &item.wfm_id,
+                    &item.wfm_url,
+                    item.sub_type.clone(),
+                    item.quantity,
+                    item.bought.unwrap_or(0),
+                    acquired_at(flags),
+                ),
+            )
+            .await
+            .map_err(|e| e.with_location(get_location!()).log(file))?;
 
             model = created_item;
             operations.add(format!("ItemBuy_{s_operation}"));
+            operations.add("LotCreated");
             log(component, &item, &None, &s_operation, &flags, &operations);
         }
     }
@@ -199,6 +248,14 @@ pub async fn handle_item_by_entity(
 
     if order_type == OrderType::Sell {
         tx.transaction_type = TransactionType::Sale;
+        if let Some(consumption) = &lot_consumption {
+            if let Some(profit) = consumption.profit(tx.price) {
+                tx.set_profit(profit);
+                let mut properties = tx.properties.take().unwrap_or_else(|| json!({}));
+                properties["lots"] = json!(consumption);
+                tx.properties = Some(properties);
+            }
+        }
     }
 
     handle_transaction(tx, &flags)
@@ -5,6 +5,7 @@ use entity::{
     stock_riven::RivenAttribute,
     transaction::TransactionPaginationQueryDto,
 };
+use serde::Serialize;
 use serde_json::{json, Value};
 use service::TransactionQuery;
 use std::{
@@ -12,6 +13,7 @@ use std::{
     path::PathBuf,
 };
 use tauri::{Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+use tauri_plugin_dialog::DialogExt;
 use utils::SubType;
 use utils::*;
 use wf_market::{enums::OrderType, types::AuctionLike, Authenticated};
@@ -71,26 +73,73 @@ pub fn get_desktop_path() -> PathBuf {
     };
     desktop_path
 }
-pub fn generate_transaction_summary(
-    transactions: &Vec<entity::transaction::Model>,
-    date: DateTime<Utc>,
-    group_by1: GroupByDate,
-    group_by2: &[GroupByDate],
-    _previous: bool,
-) -> (FinancialReport, FinancialGraph<i64>) {
-    let (start, end) = get_start_end_of(date, group_by1);
-    let transactions = filters_by(transactions, |t| {
-        t.created_at >= start && t.created_at <= end
-    });
-
-    let mut grouped = group_by_date(&transactions, |t| t.created_at, group_by2);
-
-    fill_missing_date_keys(&mut grouped, start, end, group_by2);
-
-    let graph = FinancialGraph::<i64>::from(&grouped, |group| {
-        FinancialReport::from(&group.to_vec()).total_profit
-    });
-    (FinancialReport::from(&transactions), graph)
+/// Report of the period from `start` to `end` with its profit graph grouped by `group_by`.
+pub async fn generate_transaction_summary(
+    (start, end): (DateTime<Utc>, DateTime<Utc>),
+    group_by: &[GroupByDate],
+    periods: &ReportPeriods,
+) -> Result<(FinancialReport, FinancialGraph<i64>), Error> {
+    let conn = DATABASE.get().unwrap();
+    let query = TransactionPaginationQueryDto::new(1, -1)
+        .set_from_date(start)
+        .set_to_date(end);
+
+    let report = TransactionQuery::get_financial_report(conn, &query)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    let graph = TransactionQuery::get_profit_graph(conn, &query, group_by, periods)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok((report, graph))
+}
+
+/// Writes `items` in the export format to `path`, or to a file picked in a save dialog without one.
+/// Returns the written path, empty when the dialog was closed.
+pub fn write_export<T: Serialize>(
+    component: &str,
+    name: &str,
+    items: &[T],
+    options: &ExportOptions,
+    path: Option<String>,
+) -> Result<String, Error> {
+    let file_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let app = APP.get().unwrap();
+            let file_path = app
+                .dialog()
+                .file()
+                .add_filter(name, &[options.format.extension()])
+                .blocking_save_file();
+            match file_path {
+                Some(file_path) => file_path.as_path().unwrap().to_path_buf(),
+                // The dialog was closed
+                None => return Ok("".to_string()),
+            }
+        }
+    };
+    let content =
+        export_items(items, options, name).map_err(|e| e.with_location(get_location!()))?;
+    fs::write(&file_path, content).map_err(|e| {
+        Error::from_io(
+            component,
+            &file_path,
+            "writing the export",
+            e,
+            get_location!(),
+        )
+    })?;
+    info(
+        component,
+        format!(
+            "Exported {} items as {} to {}",
+            items.len(),
+            options.format.extension(),
+            file_path.display()
+        ),
+        &LoggerOptions::default(),
+    );
+    Ok(file_path.display().to_string())
 }
 
 /// Paginate a vector of items
@@ -550,3 +599,47 @@ pub async fn populate_riven_market_properties(
     properties.set_property_value("ui_operations", operations.operations.clone());
     Ok(vec![])
 }
+
+/// Suggests a list price for a riven from the live auctions of similar rolls on the same weapon.
+/// The cheapest comparable buyouts are averaged like the riven scraper does, `None` when nobody sells one.
+pub async fn suggest_riven_price(
+    weapon_url: &str,
+    attributes: &[RivenAttribute],
+    wfm: &wf_market::client::Client<Authenticated>,
+    wfm_username: &str,
+) -> Result<Option<i64>, Error> {
+    let settings = crate::utils::modules::states::get_settings()?;
+    let wts = settings.live_scraper.rivens.wts;
+
+    let mut filter =
+        wf_market::types::AuctionFilter::new(wf_market::enums::AuctionType::Riven, weapon_url)
+            .with_buyout_policy("direct");
+    filter.similarity_attributes = Some(
+        attributes
+            .iter()
+            .map(|att| {
+                wf_market::types::ItemAttribute::new(att.wfm_url.clone(), att.positive, att.value)
+            })
+            .collect(),
+    );
+    filter.similarity = Some(34);
+
+    let mut auctions = wfm.auction().search_auctions(filter).await.map_err(|e| {
+        Error::from_wfm(
+            "Helper::SuggestRivenPrice",
+            &format!("Failed to search auctions for {}", weapon_url),
+            e,
+            get_location!(),
+        )
+    })?;
+    auctions.filter_username(wfm_username, true);
+    if auctions.total_auctions() == 0 {
+        return Ok(None);
+    }
+    auctions.sort_by_platinum();
+    Ok(Some(average_filtered_lowest_prices(
+        auctions.prices(),
+        wts.max_results,
+        wts.threshold_percentage,
+    )))
+}
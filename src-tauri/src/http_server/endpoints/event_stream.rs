@@ -0,0 +1,202 @@
+use serde_json::{json, Value};
+use std::{
+    io::Write,
+    net::TcpStream,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+use utils::*;
+
+use crate::http_server::{helper::*, request::*};
+
+// Idle connections get a comment line this often so proxies and clients keep them open
+static KEEP_ALIVE: Duration = Duration::from_secs(15);
+static MAX_SUBSCRIBERS: usize = 32;
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+static SUBSCRIBERS: OnceLock<Mutex<Vec<Subscriber>>> = OnceLock::new();
+
+fn subscribers() -> &'static Mutex<Vec<Subscriber>> {
+    SUBSCRIBERS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+struct Subscriber {
+    id: u64,
+    filter: EventFilter,
+    sender: Sender<String>,
+}
+
+/// Event types a client asked for, `?events=LiveScraper:*,App:OnNotify`.
+/// A trailing `*` matches by prefix, an empty filter matches every event.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    patterns: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn parse(value: Option<&String>) -> Self {
+        let patterns = value
+            .map(|v| {
+                v.split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { patterns }
+    }
+
+    pub fn matches(&self, event: &str) -> bool {
+        self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => event.starts_with(prefix),
+                    None => pattern == event,
+                })
+    }
+}
+
+/// Pushes an event emitted to the frontend to the subscribed HTTP clients.
+/// `message` and `message_update` events are typed by their inner `event` field (`LiveScraper:OnMessage`).
+pub fn publish_event(name: &str, payload: &Value) {
+    let Some(subscribers) = SUBSCRIBERS.get() else {
+        return;
+    };
+    let Ok(mut subscribers) = subscribers.lock() else {
+        return;
+    };
+    if subscribers.is_empty() {
+        return;
+    }
+    let event_type = match name {
+        "message" | "message_update" => payload
+            .get("event")
+            .and_then(|v| v.as_str())
+            .unwrap_or(name),
+        _ => name,
+    };
+    let frame = format!(
+        "event: {}\ndata: {}\n\n",
+        event_type,
+        json!({ "event": event_type, "source": name, "payload": payload })
+    );
+    // Drop the subscribers whose connection thread has ended
+    subscribers.retain(|s| !s.filter.matches(event_type) || s.sender.send(frame.clone()).is_ok());
+}
+
+#[derive(Debug)]
+pub struct EventStreamRoute {}
+impl EventStreamRoute {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {})
+    }
+
+    pub async fn handle_request(&self, request: &HttpRequest, stream: &mut TcpStream) -> bool {
+        match request.route("/events") {
+            Some(RoutePath::Collection) => {}
+            Some(_) => {
+                respond_not_found(stream, &format!("No route for {}", request.path));
+                return true;
+            }
+            None => return false,
+        }
+        if request.method != "GET" {
+            respond_method_not_allowed(stream, &request.method, &request.path);
+            return true;
+        }
+        self.handle_subscribe(request, stream);
+        true
+    }
+
+    /// Keeps the connection open as a Server-Sent Events stream, one thread per client.
+    fn handle_subscribe(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        let filter = EventFilter::parse(request.query.get("events"));
+        let (sender, receiver) = mpsc::channel::<String>();
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut subscribers = subscribers().lock().unwrap();
+            if subscribers.len() >= MAX_SUBSCRIBERS {
+                respond_error(
+                    stream,
+                    503,
+                    "too_many_subscribers",
+                    &format!("Only {} event streams can be open at once", MAX_SUBSCRIBERS),
+                    None,
+                );
+                return;
+            }
+            subscribers.push(Subscriber {
+                id,
+                filter: filter.clone(),
+                sender,
+            });
+        }
+
+        let mut stream = match stream.try_clone() {
+            Ok(stream) => stream,
+            Err(e) => {
+                remove_subscriber(id);
+                respond_error(stream, 500, "stream_failed", &e.to_string(), None);
+                return;
+            }
+        };
+        let headers = "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             Cache-Control: no-cache\r\n\
+             Connection: keep-alive\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             \r\n";
+        let hello = format!(
+            "event: connected\ndata: {}\n\n",
+            json!({ "id": id, "events": filter.patterns })
+        );
+        info(
+            "HttpServer:EventStream",
+            format!("Client {} subscribed to {:?}", id, filter.patterns),
+            &LoggerOptions::default(),
+        );
+
+        thread::spawn(move || {
+            if stream.write_all(headers.as_bytes()).is_ok()
+                && stream.write_all(hello.as_bytes()).is_ok()
+            {
+                loop {
+                    let frame = match receiver.recv_timeout(KEEP_ALIVE) {
+                        Ok(frame) => frame,
+                        Err(RecvTimeoutError::Timeout) => ": keep-alive\n\n".to_string(),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    };
+                    if stream.write_all(frame.as_bytes()).is_err() || stream.flush().is_err() {
+                        break;
+                    }
+                }
+            }
+            remove_subscriber(id);
+            info(
+                "HttpServer:EventStream",
+                format!("Client {} disconnected", id),
+                &LoggerOptions::default(),
+            );
+        });
+    }
+}
+
+fn remove_subscriber(id: u64) {
+    if let Ok(mut subscribers) = subscribers().lock() {
+        subscribers.retain(|s| s.id != id);
+    }
+}
+
+/// Closes every open event stream, the server is stopping.
+pub fn close_event_streams() {
+    if let Ok(mut subscribers) = subscribers().lock() {
+        subscribers.clear();
+    }
+}
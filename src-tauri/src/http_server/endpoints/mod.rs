@@ -6,3 +6,12 @@ pub use stock_riven::*;
 
 pub mod wish_list;
 pub use wish_list::*;
+
+pub mod transaction;
+pub use transaction::*;
+
+pub mod trade_entry;
+pub use trade_entry::*;
+
+pub mod event_stream;
+pub use event_stream::*;
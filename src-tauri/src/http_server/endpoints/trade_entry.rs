@@ -0,0 +1,102 @@
+use entity::trade_entry::*;
+use service::TradeEntryQuery;
+use std::{net::TcpStream, sync::Arc};
+use utils::*;
+
+use crate::{
+    commands::trade_entry::{
+        get_trade_entry_pagination, trade_entry_create, trade_entry_delete, trade_entry_update,
+    },
+    http_server::{helper::*, request::*},
+    DATABASE,
+};
+
+#[derive(Debug)]
+pub struct TradeEntryRoute {}
+impl TradeEntryRoute {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {})
+    }
+
+    pub async fn handle_request(&self, request: &HttpRequest, stream: &mut TcpStream) -> bool {
+        let Some(route) = request.route("/trade_entry") else {
+            return false;
+        };
+        match (request.method.as_str(), route) {
+            (_, RoutePath::InvalidId(id)) => respond_invalid_id(stream, &id),
+            ("GET", RoutePath::Collection) => self.handle_list(request, stream).await,
+            ("POST", RoutePath::Collection) => self.handle_post(&request.body, stream).await,
+            ("GET", RoutePath::Item(id)) => self.handle_get(id, stream).await,
+            ("PUT" | "PATCH", RoutePath::Item(id)) => self.handle_update(id, request, stream).await,
+            ("DELETE", RoutePath::Item(id)) => self.handle_delete(id, stream).await,
+            _ => respond_method_not_allowed(stream, &request.method, &request.path),
+        }
+        true
+    }
+
+    async fn find(&self, id: i64, stream: &mut TcpStream) -> Option<trade_entry::Model> {
+        let conn = DATABASE.get().unwrap();
+        match TradeEntryQuery::get_by_id(conn, id).await {
+            Ok(Some(item)) => Some(item),
+            Ok(None) => {
+                respond_not_found(stream, &format!("Trade entry with ID {} not found", id));
+                None
+            }
+            Err(e) => {
+                respond_app_error(
+                    stream,
+                    500,
+                    "query_failed",
+                    e.with_location(get_location!()),
+                );
+                None
+            }
+        }
+    }
+
+    async fn handle_list(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        match request.query_as::<TradeEntryPaginationQueryDto>() {
+            Ok(query) => {
+                let result = get_trade_entry_pagination(query).await;
+                respond_result(stream, 200, result, 500, "query_failed");
+            }
+            Err(e) => respond_invalid_json(stream, e),
+        }
+    }
+
+    async fn handle_get(&self, id: i64, stream: &mut TcpStream) {
+        if let Some(item) = self.find(id, stream).await {
+            respond_result(stream, 200, Ok(item), 500, "query_failed");
+        }
+    }
+
+    async fn handle_post(&self, body: &str, stream: &mut TcpStream) {
+        match serde_json::from_str::<CreateTradeEntry>(body) {
+            Ok(input) => {
+                let result = trade_entry_create(input).await;
+                respond_result(stream, 200, result, 400, "create_failed");
+            }
+            Err(e) => respond_invalid_json(stream, e),
+        }
+    }
+
+    async fn handle_update(&self, id: i64, request: &HttpRequest, stream: &mut TcpStream) {
+        let input = match request.body_with_id::<UpdateTradeEntry>(id) {
+            Ok(input) => input,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = trade_entry_update(input).await;
+        respond_result(stream, 200, result, 400, "update_failed");
+    }
+
+    async fn handle_delete(&self, id: i64, stream: &mut TcpStream) {
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = trade_entry_delete(id).await;
+        respond_result(stream, 200, result, 500, "delete_failed");
+    }
+}
@@ -0,0 +1,169 @@
+use entity::transaction::*;
+use serde_json::{json, Value};
+use service::{TransactionMutation, TransactionQuery};
+use std::{
+    net::TcpStream,
+    sync::{Arc, Mutex},
+};
+use tauri::Manager;
+use utils::*;
+
+use crate::{
+    add_metric,
+    app::AppState,
+    commands::transaction::{
+        get_transaction_export, get_transaction_pagination, transaction_delete, transaction_update,
+        EXPORT_NAME,
+    },
+    http_server::{helper::*, request::*},
+    send_event,
+    types::UIEvent,
+    APP, DATABASE,
+};
+
+#[derive(Debug)]
+pub struct TransactionRoute {}
+impl TransactionRoute {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {})
+    }
+
+    pub async fn handle_request(&self, request: &HttpRequest, stream: &mut TcpStream) -> bool {
+        let Some(route) = request.route("/transaction") else {
+            return false;
+        };
+        match (request.method.as_str(), route) {
+            ("POST", RoutePath::InvalidId(id)) if id == "export" => {
+                self.handle_export(request, stream).await
+            }
+            (_, RoutePath::InvalidId(id)) => respond_invalid_id(stream, &id),
+            ("GET", RoutePath::Collection) => self.handle_list(request, stream).await,
+            ("POST", RoutePath::Collection) => self.handle_post(&request.body, stream).await,
+            ("GET", RoutePath::Item(id)) => self.handle_get(id, stream).await,
+            ("PUT" | "PATCH", RoutePath::Item(id)) => self.handle_update(id, request, stream).await,
+            ("DELETE", RoutePath::Item(id)) => self.handle_delete(id, stream).await,
+            _ => respond_method_not_allowed(stream, &request.method, &request.path),
+        }
+        true
+    }
+
+    async fn find(&self, id: i64, stream: &mut TcpStream) -> Option<transaction::Model> {
+        let conn = DATABASE.get().unwrap();
+        match TransactionQuery::find_by_id(conn, id).await {
+            Ok(Some(item)) => Some(item),
+            Ok(None) => {
+                respond_not_found(stream, &format!("Transaction with ID {} not found", id));
+                None
+            }
+            Err(e) => {
+                respond_app_error(
+                    stream,
+                    500,
+                    "query_failed",
+                    e.with_location(get_location!()),
+                );
+                None
+            }
+        }
+    }
+
+    async fn handle_list(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        match request.query_as::<TransactionPaginationQueryDto>() {
+            Ok(query) => {
+                let result = get_transaction_pagination(query).await;
+                respond_result(stream, 200, result, 500, "query_failed");
+            }
+            Err(e) => respond_invalid_json(stream, e),
+        }
+    }
+
+    async fn handle_get(&self, id: i64, stream: &mut TcpStream) {
+        if let Some(item) = self.find(id, stream).await {
+            respond_result(stream, 200, Ok(item), 500, "query_failed");
+        }
+    }
+
+    /// Creates a transaction as given, without touching the stock.
+    /// `created_at` defaults to now so scripts can log a trade without a date.
+    async fn handle_post(&self, body: &str, stream: &mut TcpStream) {
+        let mut value = match serde_json::from_str::<Value>(body) {
+            Ok(value) => value,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        let use_current_date = value.get("created_at").is_none();
+        if let Value::Object(map) = &mut value {
+            let now = json!(chrono::Utc::now());
+            map.entry("created_at").or_insert(now.clone());
+            map.entry("updated_at").or_insert(now);
+        }
+        let model = match serde_json::from_value::<transaction::Model>(value) {
+            Ok(model) => model,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        let conn = DATABASE.get().unwrap();
+        match TransactionMutation::create(conn, &model, use_current_date).await {
+            Ok(transaction) => {
+                add_metric!("http_server_transaction_create", "success");
+                send_event!(
+                    UIEvent::RefreshTransactions,
+                    json!({"id": transaction.id, "source": "HttpServer"})
+                );
+                respond_result(stream, 200, Ok(transaction), 400, "create_failed");
+            }
+            Err(e) => respond_app_error(
+                stream,
+                400,
+                "create_failed",
+                e.with_location(get_location!()),
+            ),
+        }
+    }
+
+    async fn handle_update(&self, id: i64, request: &HttpRequest, stream: &mut TcpStream) {
+        let input = match request.body_with_id::<UpdateTransaction>(id) {
+            Ok(input) => input,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = transaction_update(input).await;
+        if result.is_ok() {
+            send_event!(
+                UIEvent::RefreshTransactions,
+                json!({"id": id, "source": "HttpServer"})
+            );
+        }
+        respond_result(stream, 200, result, 400, "update_failed");
+    }
+
+    async fn handle_delete(&self, id: i64, stream: &mut TcpStream) {
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = transaction_delete(id).await;
+        if result.is_ok() {
+            send_event!(
+                UIEvent::RefreshTransactions,
+                json!({"id": id, "source": "HttpServer"})
+            );
+        }
+        respond_result(stream, 200, result, 500, "delete_failed");
+    }
+
+    /// Sends the transactions matching the filters as a file in the export format, see `ExportRequest`.
+    async fn handle_export(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        let export = match request.body_as::<ExportRequest<TransactionPaginationQueryDto>>() {
+            Ok(export) => export,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        let app = APP.get().unwrap();
+        let result = get_transaction_export(app.state::<Mutex<AppState>>(), export.query)
+            .await
+            .and_then(|items| export_items(&items, &export.options, EXPORT_NAME));
+        if result.is_ok() {
+            add_metric!("export_transaction", export.options.format.extension());
+        }
+        respond_export(stream, EXPORT_NAME, &export.options, result);
+    }
+}
@@ -1,15 +1,26 @@
-use entity::wish_list::CreateWishListItem;
+use entity::wish_list::*;
 use serde_json::json;
-use std::{net::TcpStream, sync::Arc};
+use service::WishListQuery;
+use std::{
+    net::TcpStream,
+    sync::{Arc, Mutex},
+};
+use tauri::Manager;
 use utils::*;
 use wf_market::enums::OrderType;
 
 use crate::{
     add_metric,
+    app::AppState,
+    commands::wish_list::{
+        get_wish_list_export, get_wish_list_pagination, wish_list_delete, wish_list_update,
+        EXPORT_NAME,
+    },
     handlers::handle_wish_list_by_entity,
-    http_server::{respond_json, respond_text},
+    http_server::{helper::*, request::*},
     send_event,
     types::UIEvent,
+    APP, DATABASE,
 };
 
 #[derive(Debug)]
@@ -19,16 +30,58 @@ impl WishListRoute {
         Arc::new(Self {})
     }
 
-    pub async fn handle_request(
-        &self,
-        method: &str,
-        path: &str,
-        body: &str,
-        stream: &mut TcpStream,
-    ) {
-        let base_path = "/wish_list";
-        if method == "POST" && path == base_path {
-            self.handle_post(body, stream).await;
+    pub async fn handle_request(&self, request: &HttpRequest, stream: &mut TcpStream) -> bool {
+        let Some(route) = request.route("/wish_list") else {
+            return false;
+        };
+        match (request.method.as_str(), route) {
+            ("POST", RoutePath::InvalidId(id)) if id == "export" => {
+                self.handle_export(request, stream).await
+            }
+            (_, RoutePath::InvalidId(id)) => respond_invalid_id(stream, &id),
+            ("GET", RoutePath::Collection) => self.handle_list(request, stream).await,
+            ("POST", RoutePath::Collection) => self.handle_post(&request.body, stream).await,
+            ("GET", RoutePath::Item(id)) => self.handle_get(id, stream).await,
+            ("PUT" | "PATCH", RoutePath::Item(id)) => self.handle_update(id, request, stream).await,
+            ("DELETE", RoutePath::Item(id)) => self.handle_delete(id, stream).await,
+            _ => respond_method_not_allowed(stream, &request.method, &request.path),
+        }
+        true
+    }
+
+    async fn find(&self, id: i64, stream: &mut TcpStream) -> Option<wish_list::Model> {
+        let conn = DATABASE.get().unwrap();
+        match WishListQuery::find_by_id(conn, id).await {
+            Ok(Some(item)) => Some(item),
+            Ok(None) => {
+                respond_not_found(stream, &format!("Wish list item with ID {} not found", id));
+                None
+            }
+            Err(e) => {
+                respond_app_error(
+                    stream,
+                    500,
+                    "query_failed",
+                    e.with_location(get_location!()),
+                );
+                None
+            }
+        }
+    }
+
+    async fn handle_list(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        match request.query_as::<WishListPaginationQueryDto>() {
+            Ok(query) => {
+                let result = get_wish_list_pagination(query).await;
+                respond_result(stream, 200, result, 500, "query_failed");
+            }
+            Err(e) => respond_invalid_json(stream, e),
+        }
+    }
+
+    async fn handle_get(&self, id: i64, stream: &mut TcpStream) {
+        if let Some(item) = self.find(id, stream).await {
+            respond_result(stream, 200, Ok(item), 500, "query_failed");
         }
     }
 
@@ -49,14 +102,67 @@ impl WishListRoute {
                             UIEvent::RefreshWishListItems,
                             json!({"id": updated_item.id, "source": "HttpServer"})
                         );
-                        respond_json(stream, 200, &serde_json::to_string(&updated_item).unwrap());
+                        respond_result(stream, 200, Ok(updated_item), 400, "create_failed");
                     }
                     Err(e) => {
-                        respond_json(stream, 400, json!(e).to_string().as_str());
+                        respond_app_error(
+                            stream,
+                            400,
+                            "create_failed",
+                            e.with_location(get_location!()),
+                        );
                     }
                 }
             }
-            Err(e) => respond_text(stream, 400, &format!("Invalid JSON: {}", e)),
+            Err(e) => respond_invalid_json(stream, e),
+        }
+    }
+
+    async fn handle_update(&self, id: i64, request: &HttpRequest, stream: &mut TcpStream) {
+        let input = match request.body_with_id::<UpdateWishList>(id) {
+            Ok(input) => input,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = wish_list_update(input).await;
+        if result.is_ok() {
+            send_event!(
+                UIEvent::RefreshWishListItems,
+                json!({"id": id, "source": "HttpServer"})
+            );
+        }
+        respond_result(stream, 200, result, 400, "update_failed");
+    }
+
+    async fn handle_delete(&self, id: i64, stream: &mut TcpStream) {
+        if self.find(id, stream).await.is_none() {
+            return;
+        }
+        let result = wish_list_delete(id).await;
+        if result.is_ok() {
+            send_event!(
+                UIEvent::RefreshWishListItems,
+                json!({"id": id, "source": "HttpServer"})
+            );
+        }
+        respond_result(stream, 200, result, 500, "delete_failed");
+    }
+
+    /// Sends the wish list items matching the filters as a file in the export format, see `ExportRequest`.
+    async fn handle_export(&self, request: &HttpRequest, stream: &mut TcpStream) {
+        let export = match request.body_as::<ExportRequest<WishListPaginationQueryDto>>() {
+            Ok(export) => export,
+            Err(e) => return respond_invalid_json(stream, e),
+        };
+        let app = APP.get().unwrap();
+        let result = get_wish_list_export(app.state::<Mutex<AppState>>(), export.query)
+            .await
+            .and_then(|items| export_items(&items, &export.options, EXPORT_NAME));
+        if result.is_ok() {
+            add_metric!("export_wish_list", export.options.format.extension());
         }
+        respond_export(stream, EXPORT_NAME, &export.options, result);
     }
 }
@@ -1,8 +1,29 @@
 use std::{io::Write, net::TcpStream};
 
+use serde::Serialize;
+use serde_json::{json, Value};
+use utils::{Error, ExportOptions};
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "OK",
+    }
+}
+
 pub fn respond_json(stream: &mut TcpStream, status: u16, body: &str) {
     let response = format!(
-        "HTTP/1.1 {} OK\r\n\
+        "HTTP/1.1 {} {}\r\n\
          Content-Type: application/json\r\n\
          Content-Length: {}\r\n\
          Access-Control-Allow-Origin: *\r\n\
@@ -10,6 +31,7 @@ pub fn respond_json(stream: &mut TcpStream, status: u16, body: &str) {
          Access-Control-Allow-Headers: Content-Type, Authorization\r\n\
          \r\n{}",
         status,
+        status_text(status),
         body.len(),
         body
     );
@@ -18,7 +40,7 @@ pub fn respond_json(stream: &mut TcpStream, status: u16, body: &str) {
 
 pub fn respond_text(stream: &mut TcpStream, status: u16, msg: &str) {
     let response = format!(
-        "HTTP/1.1 {} OK\r\n\
+        "HTTP/1.1 {} {}\r\n\
          Content-Type: text/plain\r\n\
          Content-Length: {}\r\n\
          Access-Control-Allow-Origin: *\r\n\
@@ -26,12 +48,52 @@ pub fn respond_text(stream: &mut TcpStream, status: u16, msg: &str) {
          Access-Control-Allow-Headers: Content-Type, Authorization\r\n\
          \r\n{}",
         status,
+        status_text(status),
         msg.len(),
         msg
     );
     let _ = stream.write_all(response.as_bytes());
 }
 
+/// Sends `body` as a download named `file_name`.
+pub fn respond_file(stream: &mut TcpStream, content_type: &str, file_name: &str, body: &[u8]) {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: {}\r\n\
+         Content-Disposition: attachment; filename=\"{}\"\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, PUT, DELETE, PATCH, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type, Authorization\r\n\
+         Access-Control-Expose-Headers: Content-Disposition\r\n\
+         \r\n",
+        content_type,
+        file_name,
+        body.len()
+    );
+    if stream.write_all(head.as_bytes()).is_ok() {
+        let _ = stream.write_all(body);
+    }
+}
+
+/// Sends the exported file named `name` with the extension of the format, or the error with the `export_failed` code.
+pub fn respond_export(
+    stream: &mut TcpStream,
+    name: &str,
+    options: &ExportOptions,
+    result: Result<Vec<u8>, Error>,
+) {
+    match result {
+        Ok(content) => respond_file(
+            stream,
+            options.format.content_type(),
+            &format!("{}.{}", name, options.format.extension()),
+            &content,
+        ),
+        Err(e) => respond_app_error(stream, 500, "export_failed", e),
+    }
+}
+
 pub fn respond_cors_preflight(stream: &mut TcpStream) {
     let response = "HTTP/1.1 200 OK\r\n\
          Access-Control-Allow-Origin: *\r\n\
@@ -42,3 +104,77 @@ pub fn respond_cors_preflight(stream: &mut TcpStream) {
          \r\n";
     let _ = stream.write_all(response.as_bytes());
 }
+
+/// Every API error uses the same body: `{"status", "error", "message", "details"}`.
+/// `error` is a stable code for scripts, `details` holds the serialized `utils::Error` when there is one.
+pub fn respond_error(
+    stream: &mut TcpStream,
+    status: u16,
+    code: &str,
+    message: &str,
+    details: Option<Value>,
+) {
+    let body = json!({
+        "status": status,
+        "error": code,
+        "message": message,
+        "details": details.unwrap_or(Value::Null),
+    });
+    respond_json(stream, status, &body.to_string());
+}
+
+pub fn respond_app_error(stream: &mut TcpStream, status: u16, code: &str, error: Error) {
+    let message = error.message.clone();
+    respond_error(stream, status, code, &message, Some(json!(error)));
+}
+
+pub fn respond_invalid_json(stream: &mut TcpStream, error: serde_json::Error) {
+    respond_error(
+        stream,
+        400,
+        "invalid_json",
+        &format!("Invalid JSON: {}", error),
+        None,
+    );
+}
+
+pub fn respond_not_found(stream: &mut TcpStream, message: &str) {
+    respond_error(stream, 404, "not_found", message, None);
+}
+
+pub fn respond_invalid_id(stream: &mut TcpStream, id: &str) {
+    respond_error(
+        stream,
+        400,
+        "invalid_id",
+        &format!("'{}' is not a valid ID", id),
+        None,
+    );
+}
+
+pub fn respond_method_not_allowed(stream: &mut TcpStream, method: &str, path: &str) {
+    respond_error(
+        stream,
+        405,
+        "method_not_allowed",
+        &format!("{} is not supported on {}", method, path),
+        None,
+    );
+}
+
+/// Writes the value as JSON, or the error with `error_status` and the `error` code.
+pub fn respond_result<T: Serialize>(
+    stream: &mut TcpStream,
+    status: u16,
+    result: Result<T, Error>,
+    error_status: u16,
+    code: &str,
+) {
+    match result {
+        Ok(value) => match serde_json::to_string(&value) {
+            Ok(body) => respond_json(stream, status, &body),
+            Err(e) => respond_error(stream, 500, "serialization_failed", &e.to_string(), None),
+        },
+        Err(e) => respond_app_error(stream, error_status, code, e),
+    }
+}
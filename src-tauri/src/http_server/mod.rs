@@ -8,3 +8,6 @@ pub use endpoints::*;
 
 pub mod helper;
 pub use helper::*;
+
+pub mod request;
+pub use request::*;
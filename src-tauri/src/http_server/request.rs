@@ -0,0 +1,196 @@
+use std::{collections::HashMap, io::Read, net::TcpStream};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+use utils::ExportOptions;
+
+// Requests larger than this are rejected before the body is read
+static MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    // Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+/// Which part of a resource a request targets, `/stock_item` or `/stock_item/{id}`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoutePath {
+    Collection,
+    Item(i64),
+    InvalidId(String),
+}
+
+/// Body of `POST /{resource}/export`: the pagination filters and the export options.
+/// The file is sent back in the response.
+#[derive(Debug, Deserialize)]
+pub struct ExportRequest<Q> {
+    #[serde(flatten)]
+    pub query: Q,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+impl HttpRequest {
+    /// Reads the request line, the headers and the body (up to `Content-Length`) from the stream.
+    pub fn read(stream: &mut TcpStream) -> Option<Self> {
+        let mut data = Vec::new();
+        let mut buffer = [0; 4096];
+        let header_end = loop {
+            let size = stream.read(&mut buffer).ok()?;
+            if size == 0 {
+                return None;
+            }
+            data.extend_from_slice(&buffer[..size]);
+            if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos;
+            }
+            if data.len() > MAX_BODY_SIZE {
+                return None;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+        let mut lines = head.lines();
+        let parts: Vec<&str> = lines.next().unwrap_or("").split_whitespace().collect();
+        if parts.len() < 2 {
+            return None;
+        }
+        let headers: HashMap<String, String> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+
+        let content_length = headers
+            .get("content-length")
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(0)
+            .min(MAX_BODY_SIZE);
+        let mut body = data[header_end + 4..].to_vec();
+        while body.len() < content_length {
+            let size = stream.read(&mut buffer).ok()?;
+            if size == 0 {
+                break;
+            }
+            body.extend_from_slice(&buffer[..size]);
+        }
+        body.truncate(content_length);
+
+        let (path, query) = match parts[1].split_once('?') {
+            Some((path, query)) => (path, parse_query(query)),
+            None => (parts[1], HashMap::new()),
+        };
+        Some(Self {
+            method: parts[0].to_uppercase(),
+            path: path.trim_end_matches('/').to_string(),
+            query,
+            headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(|v| v.as_str())
+    }
+
+    /// Token from the `Authorization: Bearer <token>` header.
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.header("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        if scheme.eq_ignore_ascii_case("bearer") {
+            Some(token.trim())
+        } else {
+            None
+        }
+    }
+
+    /// Matches the path against a resource, `None` when the request is for another resource.
+    pub fn route(&self, base_path: &str) -> Option<RoutePath> {
+        if self.path == base_path {
+            return Some(RoutePath::Collection);
+        }
+        let id = self.path.strip_prefix(base_path)?.strip_prefix('/')?;
+        if id.contains('/') {
+            return None;
+        }
+        match id.parse::<i64>() {
+            Ok(id) => Some(RoutePath::Item(id)),
+            Err(_) => Some(RoutePath::InvalidId(id.to_string())),
+        }
+    }
+
+    pub fn body_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+
+    /// Parses the body as an update DTO, the id comes from the path.
+    pub fn body_with_id<T: DeserializeOwned>(&self, id: i64) -> Result<T, serde_json::Error> {
+        let mut value: Value = if self.body.trim().is_empty() {
+            Value::Object(Map::new())
+        } else {
+            serde_json::from_str(&self.body)?
+        };
+        if let Value::Object(map) = &mut value {
+            map.insert("id".to_string(), Value::from(id));
+        }
+        serde_json::from_value(value)
+    }
+
+    /// Parses a pagination DTO from a JSON body, or from the query string when there is no body.
+    /// Query values are read as JSON when they parse (`page=2`, `tags=["a"]`) and as strings otherwise,
+    /// quote numeric strings (`query="123"`).
+    pub fn query_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        if !self.body.trim().is_empty() {
+            return self.body_as();
+        }
+        let map = self
+            .query
+            .iter()
+            .map(|(key, value)| {
+                let value = serde_json::from_str::<Value>(value)
+                    .unwrap_or_else(|_| Value::String(value.clone()));
+                (key.clone(), value)
+            })
+            .collect::<Map<String, Value>>();
+        serde_json::from_value(Value::Object(map))
+    }
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (decode_component(key), decode_component(value)),
+            None => (decode_component(pair), String::new()),
+        })
+        .collect()
+}
+
+fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -1,5 +1,4 @@
 use std::{
-    io::Read,
     net::{TcpListener, TcpStream},
     sync::{Arc, Mutex, OnceLock},
     thread::{self, JoinHandle},
@@ -7,7 +6,7 @@ use std::{
 };
 use utils::*;
 
-use crate::http_server::{respond_cors_preflight, StockItemRoute, StockRivenRoute, WishListRoute};
+use crate::{http_server::*, utils::modules::states};
 
 #[derive(Debug)]
 pub struct HttpServer {
@@ -15,6 +14,9 @@ pub struct HttpServer {
     stock_item_route: OnceLock<Arc<StockItemRoute>>,
     stock_riven_route: OnceLock<Arc<StockRivenRoute>>,
     wish_list_route: OnceLock<Arc<WishListRoute>>,
+    transaction_route: OnceLock<Arc<TransactionRoute>>,
+    trade_entry_route: OnceLock<Arc<TradeEntryRoute>>,
+    event_stream_route: OnceLock<Arc<EventStreamRoute>>,
     server_thread: Mutex<Option<JoinHandle<()>>>,
     running: Arc<Mutex<bool>>,
     host: Mutex<String>,
@@ -27,6 +29,9 @@ impl HttpServer {
             stock_item_route: OnceLock::new(),
             stock_riven_route: OnceLock::new(),
             wish_list_route: OnceLock::new(),
+            transaction_route: OnceLock::new(),
+            trade_entry_route: OnceLock::new(),
+            event_stream_route: OnceLock::new(),
             server_thread: Mutex::new(None),
             running: Arc::new(Mutex::new(false)),
             host: Mutex::new(format!("{}:{}", host, port)),
@@ -50,6 +55,21 @@ impl HttpServer {
             .get_or_init(|| WishListRoute::new())
             .clone()
     }
+    pub fn transaction(&self) -> Arc<TransactionRoute> {
+        self.transaction_route
+            .get_or_init(|| TransactionRoute::new())
+            .clone()
+    }
+    pub fn trade_entry(&self) -> Arc<TradeEntryRoute> {
+        self.trade_entry_route
+            .get_or_init(|| TradeEntryRoute::new())
+            .clone()
+    }
+    pub fn event_stream(&self) -> Arc<EventStreamRoute> {
+        self.event_stream_route
+            .get_or_init(|| EventStreamRoute::new())
+            .clone()
+    }
     pub fn set_host(&self, new_host: impl Into<String>, port: u16) -> String {
         let new_host = format!("{}:{}", new_host.into(), port);
         let mut host = self.host.lock().unwrap();
@@ -159,6 +179,7 @@ impl HttpServer {
 
         *running = false;
         drop(running); // Release lock before waiting for thread
+        close_event_streams();
 
         info(
             "HTTPServer",
@@ -215,42 +236,114 @@ impl HttpServer {
 
 // ---------- HTTP SERVER ----------
 async fn handle_client(mut stream: TcpStream, client: Arc<HttpServer>) {
-    let mut buffer = [0; 4096];
-    if let Ok(size) = stream.read(&mut buffer) {
-        if size == 0 {
-            return;
-        }
+    // Accepted sockets can inherit the listener's non-blocking mode
+    let _ = stream.set_nonblocking(false);
+    let Some(request) = HttpRequest::read(&mut stream) else {
+        return;
+    };
 
-        let request = String::from_utf8_lossy(&buffer[..size]);
-        let request_line = request.lines().next().unwrap_or("");
-        let parts: Vec<&str> = request_line.split_whitespace().collect();
-        if parts.len() < 2 {
-            return;
-        }
-
-        let method = parts[0];
-        let path = parts[1];
-        let body = request.split("\r\n\r\n").nth(1).unwrap_or("");
+    // Extract Origin header for CORS handling
+    if request.method == "OPTIONS" {
+        respond_cors_preflight(&mut stream);
+        return;
+    }
 
-        // Extract Origin header for CORS handling
-        if method == "OPTIONS" {
-            respond_cors_preflight(&mut stream);
-            return;
-        }
+    if !authorize(&request, &mut stream) {
+        return;
+    }
 
-        let stock_item_route = client.stock_item();
-        stock_item_route
-            .handle_request(method, path, body, &mut stream)
+    let handled = client
+        .stock_item()
+        .handle_request(&request, &mut stream)
+        .await
+        || client
+            .stock_riven()
+            .handle_request(&request, &mut stream)
+            .await
+        || client
+            .wish_list()
+            .handle_request(&request, &mut stream)
+            .await
+        || client
+            .transaction()
+            .handle_request(&request, &mut stream)
+            .await
+        || client
+            .trade_entry()
+            .handle_request(&request, &mut stream)
+            .await
+        || client
+            .event_stream()
+            .handle_request(&request, &mut stream)
             .await;
+    if !handled {
+        respond_not_found(&mut stream, &format!("No route for {}", request.path));
+    }
+}
 
-        let stock_riven_route = client.stock_riven();
-        stock_riven_route
-            .handle_request(method, path, body, &mut stream)
-            .await;
+/// Every route needs `Authorization: Bearer <token>` with the token from the HTTP server settings.
+/// The API stays closed until a token is set.
+/// `?token=` is only accepted on `/events`, browsers cannot set headers on an `EventSource`.
+fn authorize(request: &HttpRequest, stream: &mut TcpStream) -> bool {
+    let token = match states::get_settings() {
+        Ok(settings) => settings.advanced_settings.http_server.token,
+        Err(e) => {
+            respond_app_error(stream, 503, "unavailable", e.with_location(get_location!()));
+            return false;
+        }
+    };
+    if token.is_empty() {
+        respond_error(
+            stream,
+            401,
+            "token_not_configured",
+            "Set an API token in the HTTP server settings to use the API",
+            None,
+        );
+        return false;
+    }
+    let given = request
+        .bearer_token()
+        .or_else(|| match request.path.as_str() {
+            "/events" => request.query.get("token").map(|t| t.as_str()),
+            _ => None,
+        });
+    match given {
+        Some(given) if constant_time_eq(given.as_bytes(), token.as_bytes()) => true,
+        Some(_) => {
+            warning(
+                "HTTPServer:Authorize",
+                format!(
+                    "Rejected {} {}: invalid token",
+                    request.method, request.path
+                ),
+                &LoggerOptions::default(),
+            );
+            respond_error(
+                stream,
+                401,
+                "invalid_token",
+                "The API token is not valid",
+                None,
+            );
+            false
+        }
+        None => {
+            respond_error(
+                stream,
+                401,
+                "missing_token",
+                "Send the API token as 'Authorization: Bearer <token>'",
+                None,
+            );
+            false
+        }
+    }
+}
 
-        let wish_list_route = client.wish_list();
-        wish_list_route
-            .handle_request(method, path, body, &mut stream)
-            .await;
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
@@ -24,6 +24,7 @@ use crate::cache::client::CacheState;
 use crate::live_scraper::LiveScraperState;
 use crate::log_parser::init_detections;
 use crate::log_parser::LogParserState;
+use crate::notify_queue::NotifyQueueState;
 use crate::wf_inventory::WFInventoryState;
 
 mod app;
@@ -39,6 +40,7 @@ mod enums;
 mod helper;
 mod live_scraper;
 mod log_parser;
+mod notify_queue;
 mod types;
 
 pub static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
@@ -127,6 +129,9 @@ async fn setup_manages(app: tauri::AppHandle, use_temp_db: bool) -> Result<(), E
     // Clear the logs older then 7 days
     clear_logs(7)?;
 
+    // Messages left from the last run go out as soon as the queue starts
+    app.manage(NotifyQueueState::new());
+
     // Clone the fields needed for CacheState before moving app_state
     let app_state = AppState::new(app.clone(), use_temp_db, false).await?;
     let qf_client = app_state.qf_client.clone();
@@ -241,6 +246,10 @@ pub fn run() {
             commands::analytics::analytics_set_last_user_activity,
             // Alert commands
             commands::alert::alert_get_alerts,
+            // Notification commands
+            commands::notification::notification_get_deliveries,
+            commands::notification::notification_resend,
+            commands::notification::notification_get_webhook_schemas,
             // Cache commands
             commands::cache::cache_get_tradable_items,
             commands::cache::cache_get_riven_attributes,
@@ -257,7 +266,9 @@ pub fn run() {
             // Live Scraper commands
             commands::live_scraper::live_scraper_get_state,
             commands::live_scraper::live_scraper_toggle,
+            commands::live_scraper::live_scraper_get_scheduler_stats,
             commands::live_scraper::live_scraper_get_interesting_wtb_items,
+            commands::live_scraper::live_scraper_backtest,
             // Stock Item commands
             commands::stock_item::get_stock_item_pagination,
             commands::stock_item::get_stock_item_financial_report,
@@ -269,7 +280,8 @@ pub fn run() {
             commands::stock_item::stock_item_get_by_id,
             commands::stock_item::stock_item_update_multiple,
             commands::stock_item::stock_item_delete_multiple,
-            commands::stock_item::export_stock_item_json,
+            commands::stock_item::export_stock_item,
+            commands::stock_item::get_stock_item_lots,
             // Stock Riven commands
             commands::stock_riven::get_stock_riven_pagination,
             commands::stock_riven::get_stock_riven_financial_report,
@@ -279,13 +291,14 @@ pub fn run() {
             commands::stock_riven::stock_riven_sell,
             commands::stock_riven::stock_riven_update,
             commands::stock_riven::stock_riven_get_by_id,
-            commands::stock_riven::export_stock_riven_json,
+            commands::stock_riven::export_stock_riven,
             commands::stock_riven::stock_riven_update_multiple,
             commands::stock_riven::stock_riven_delete_multiple,
             // Debug commands
             commands::debug::debug_get_wfm_state,
             commands::debug::debug_get_ee_logs,
             commands::debug::debug_export_ee_logs,
+            commands::debug::debug_replay_ee_log,
             commands::debug::debug_test,
             // Order commands
             commands::order::get_wfm_orders_pagination,
@@ -311,7 +324,7 @@ pub fn run() {
             commands::wish_list::wish_list_delete,
             commands::wish_list::wish_list_update,
             commands::wish_list::wish_list_get_by_id,
-            commands::wish_list::export_wish_list_json,
+            commands::wish_list::export_wish_list,
             commands::wish_list::wish_list_update_multiple,
             commands::wish_list::wish_list_delete_multiple,
             // Chat commands
@@ -324,10 +337,11 @@ pub fn run() {
             // Transaction commands
             commands::transaction::get_transaction_pagination,
             commands::transaction::get_transaction_financial_report,
+            commands::transaction::get_transaction_history_report,
             commands::transaction::transaction_update,
             commands::transaction::transaction_delete,
             commands::transaction::transaction_delete_bulk,
-            commands::transaction::export_transaction_json,
+            commands::transaction::export_transaction,
             commands::transaction::transaction_calculate_tax,
             // Item commands
             commands::item::item_prices_lookup,
@@ -341,6 +355,10 @@ pub fn run() {
             commands::riven::export_riven_price_data,
             // Market commands
             commands::market::get_user_activity,
+            // Market Snapshot commands
+            commands::market_snapshot::market_snapshot_get_range,
+            commands::market_snapshot::market_snapshot_get_downsampled,
+            commands::market_snapshot::market_snapshot_get_tracked_items,
             // Trade Entry commands
             commands::trade_entry::get_trade_entry_pagination,
             commands::trade_entry::trade_entry_get_by_id,
@@ -355,6 +373,9 @@ pub fn run() {
             commands::warframe_gdpr::wfgdpr_get_state,
             commands::warframe_gdpr::wfgdpr_load,
             commands::warframe_gdpr::wfgdpr_get_accounts,
+            commands::warframe_gdpr::wfgdpr_import_transactions,
+            commands::warframe_gdpr::wfgdpr_update_trade_notes,
+            commands::warframe_gdpr::wfgdpr_clear,
             // Sound commands
             commands::sound::sound_get_custom_sounds,
             commands::sound::sound_add_custom_sound,
@@ -364,6 +385,9 @@ pub fn run() {
             commands::handlers::handles_handle_items,
             // WFInventory commands
             commands::wf_inventory::wf_inventory_get_rivens,
+            commands::wf_inventory::wf_inventory_reconcile,
+            commands::wf_inventory::wf_inventory_apply_reconciliation,
+            commands::wf_inventory::wf_inventory_import_rivens,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
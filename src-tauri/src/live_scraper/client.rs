@@ -1,7 +1,7 @@
 use crate::{
     emit_error,
     enums::*,
-    live_scraper::modules::*,
+    live_scraper::{modules::*, DecisionJournal, RequestScheduler},
     notify_gui, play_sound, send_event,
     types::UIEvent,
     utils::{modules::states, OrderListExt},
@@ -10,11 +10,11 @@ use serde_json::json;
 use std::{
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, OnceLock,
+        Arc, Mutex, OnceLock,
     },
     time::{Duration, Instant},
 };
-use utils::{get_location, warning, LogLevel, LoggerOptions};
+use utils::{get_location, info, warning, LogLevel, LoggerOptions};
 
 #[derive(Debug)]
 pub struct LiveScraperState {
@@ -22,6 +22,10 @@ pub struct LiveScraperState {
     pub just_started: Arc<AtomicBool>,
     item_module: OnceLock<Arc<ItemModule>>,
     riven_module: OnceLock<Arc<RivenModule>>,
+    riven_wtb_module: OnceLock<Arc<RivenWtbModule>>,
+    price_alert_module: OnceLock<Arc<PriceAlertModule>>,
+    journal: Mutex<DecisionJournal>,
+    scheduler: Mutex<RequestScheduler>,
 }
 
 impl LiveScraperState {
@@ -31,6 +35,10 @@ impl LiveScraperState {
             just_started: Arc::new(AtomicBool::new(true)),
             item_module: OnceLock::new(),
             riven_module: OnceLock::new(),
+            riven_wtb_module: OnceLock::new(),
+            price_alert_module: OnceLock::new(),
+            journal: Mutex::new(DecisionJournal::default()),
+            scheduler: Mutex::new(RequestScheduler::default()),
         })
     }
 
@@ -39,6 +47,10 @@ impl LiveScraperState {
             .get_or_init(|| ItemModule::new(self.clone()));
         self.riven_module
             .get_or_init(|| RivenModule::new(self.clone()));
+        self.riven_wtb_module
+            .get_or_init(|| RivenWtbModule::new(self.clone()));
+        self.price_alert_module
+            .get_or_init(|| PriceAlertModule::new(self.clone()));
     }
 
     pub fn start(self: &Arc<Self>) {
@@ -54,6 +66,10 @@ impl LiveScraperState {
             return;
         }
         self.just_started.store(true, Ordering::SeqCst);
+        self.scheduler().configure(
+            settings.live_scraper.general.request_burst,
+            settings.live_scraper.general.requests_per_second,
+        );
         if settings.live_scraper.general.stock_mode == StockMode::All
             || settings.live_scraper.general.stock_mode == StockMode::Item
         {
@@ -75,9 +91,23 @@ impl LiveScraperState {
                 let mut last_riven_update = Instant::now()
                     .checked_sub(Duration::from_secs(riven_interval * 2))
                     .unwrap_or(Instant::now());
+                let riven_wtb_interval =
+                    settings.live_scraper.rivens.wtb.update_interval.max(1) as u64;
+                let mut last_riven_wtb_update = Instant::now()
+                    .checked_sub(Duration::from_secs(riven_wtb_interval * 2))
+                    .unwrap_or(Instant::now());
+                let price_alert_interval =
+                    settings.notifications.price_alerts.update_interval.max(1) as u64;
+                let mut last_price_alert_update = Instant::now()
+                    .checked_sub(Duration::from_secs(price_alert_interval * 2))
+                    .unwrap_or(Instant::now());
 
                 while is_running.load(Ordering::SeqCst) {
                     let app = states::app_state().expect("App state not initialized");
+                    let simulation = app.settings.debugging.live_scraper.simulation;
+                    if simulation {
+                        this.journal().start_cycle();
+                    }
                     // let wfm_client = app.wfm_client;
                     // println!("{}", wfm_client.order().cache_orders());
                     if matches!(
@@ -113,6 +143,29 @@ impl LiveScraperState {
                         }
                     }
 
+                    // Riven wish list, searches auctions of the watched weapons for underpriced rolls in every stock mode
+                    if app.settings.live_scraper.rivens.wtb.enabled
+                        && last_riven_wtb_update.elapsed() > Duration::from_secs(riven_wtb_interval)
+                    {
+                        last_riven_wtb_update = Instant::now();
+                        if let Err(e) = this.riven_wtb().check().await {
+                            e.with_location(get_location!())
+                                .log("live_scraper_riven_wtb.log");
+                        }
+                    }
+
+                    // Price alerts run in every stock mode
+                    if app.settings.notifications.price_alerts.enabled
+                        && last_price_alert_update.elapsed()
+                            > Duration::from_secs(price_alert_interval)
+                    {
+                        last_price_alert_update = Instant::now();
+                        if let Err(e) = this.price_alert().check().await {
+                            e.with_location(get_location!())
+                                .log("live_scraper_price_alert.log");
+                        }
+                    }
+
                     if matches!(
                         app.settings.live_scraper.general.stock_mode,
                         StockMode::Item | StockMode::All
@@ -151,7 +204,23 @@ impl LiveScraperState {
                             }
                         }
                     }
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    if simulation {
+                        let flushed = this.journal().flush();
+                        match flushed {
+                            Ok(Some(path)) => info(
+                                "LiveScraper:Simulation",
+                                &format!("Decision journal written to {}", path.display()),
+                                &LoggerOptions::default(),
+                            ),
+                            Ok(None) => {}
+                            Err(e) => {
+                                e.with_location(get_location!()).log("live_scraper.log");
+                            }
+                        }
+                    }
+                    this.send_scheduler_stats();
+                    let delay = this.scheduler().cycle_delay(Duration::from_secs(1));
+                    tokio::time::sleep(delay).await;
                     just_started.store(false, Ordering::SeqCst);
                 }
             }
@@ -170,6 +239,49 @@ impl LiveScraperState {
         self.just_started.load(Ordering::SeqCst)
     }
 
+    pub fn journal(&self) -> std::sync::MutexGuard<'_, DecisionJournal> {
+        self.journal
+            .lock()
+            .expect("Failed to lock decision journal")
+    }
+
+    pub fn scheduler(&self) -> std::sync::MutexGuard<'_, RequestScheduler> {
+        self.scheduler
+            .lock()
+            .expect("Failed to lock request scheduler")
+    }
+
+    /// Waits until the request budget allows another Warframe Market request.
+    /// Returns false when the live scraper was stopped while waiting.
+    pub async fn acquire_request(&self) -> bool {
+        loop {
+            if !self.is_running() {
+                return false;
+            }
+            let wait = self.scheduler().try_acquire();
+            match wait {
+                Some(wait) => tokio::time::sleep(wait.min(Duration::from_secs(1))).await,
+                None => return true,
+            }
+        }
+    }
+
+    /// Same budget as `acquire_request` for requests made outside the scraper loop, waits even when the scraper is stopped.
+    pub async fn wait_for_request(&self) {
+        loop {
+            let wait = self.scheduler().try_acquire();
+            match wait {
+                Some(wait) => tokio::time::sleep(wait.min(Duration::from_secs(1))).await,
+                None => return,
+            }
+        }
+    }
+
+    pub fn send_scheduler_stats(&self) {
+        let stats = self.scheduler().stats();
+        send_event!(UIEvent::UpdateLiveScraperSchedulerStats, json!(stats));
+    }
+
     pub fn item(&self) -> Arc<ItemModule> {
         self.item_module
             .get()
@@ -183,4 +295,18 @@ impl LiveScraperState {
             .expect("Riven module not initialized")
             .clone()
     }
+
+    pub fn riven_wtb(&self) -> Arc<RivenWtbModule> {
+        self.riven_wtb_module
+            .get()
+            .expect("Riven WTB module not initialized")
+            .clone()
+    }
+
+    pub fn price_alert(&self) -> Arc<PriceAlertModule> {
+        self.price_alert_module
+            .get()
+            .expect("Price alert module not initialized")
+            .clone()
+    }
 }
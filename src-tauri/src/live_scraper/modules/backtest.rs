@@ -0,0 +1,403 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use utils::{get_location, info, Error, LoggerOptions, OperationSet, SubType};
+use wf_market::{
+    enums::{OrderType, StatusType},
+    types::{OrderList, OrderWithUser},
+};
+
+use crate::{
+    app::ItemSettings,
+    cache::types::ItemPriceInfo,
+    enums::TradeMode,
+    live_scraper::{
+        build_pricing_strategy, decide_buy, decide_sell, is_disabled, is_interesting_item,
+        ItemEntry, OrderSummary, StockOverrides,
+    },
+    utils::SubTypeExt,
+};
+
+static COMPONENT: &str = "LiveScraper:Backtest:";
+
+//
+// Market Snapshot
+//
+
+/// Market state of one item at one point in time, recorded by the item live scraper.
+///
+/// Unlike the `market_snapshot` table, which only keeps the top of the order book,
+/// this holds every order and the price info the WTB/WTS decisions are made from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MarketSnapshot {
+    pub timestamp: String,
+    pub wfm_url: String,
+    pub wfm_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<SubType>,
+    // Tradable item tags, used to resolve category pricing strategies
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub price: ItemPriceInfo,
+    pub orders: OrderList<OrderWithUser>,
+}
+
+impl MarketSnapshot {
+    pub fn new(
+        entry: &ItemEntry,
+        tags: &[String],
+        price: &ItemPriceInfo,
+        orders: &OrderList<OrderWithUser>,
+    ) -> Self {
+        Self {
+            timestamp: chrono::Local::now().naive_local().to_string(),
+            wfm_url: entry.wfm_url.clone(),
+            wfm_id: entry.wfm_id.clone(),
+            sub_type: entry.sub_type.clone(),
+            tags: tags.to_vec(),
+            price: price.clone(),
+            orders: orders.clone(),
+        }
+    }
+
+    pub fn folder() -> PathBuf {
+        PathBuf::from(utils::get_base_path()).join("snapshots")
+    }
+
+    /// Writes the snapshot to `snapshots/<uuid>/<timestamp>.json`.
+    pub fn save(&self, uuid: &str) -> Result<(), Error> {
+        let path = Self::folder().join(uuid).join(format!(
+            "{}.json",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        utils::write_json_file(&path, self).map_err(|e| {
+            Error::from_io(
+                &format!("{}SaveSnapshot", COMPONENT),
+                &path,
+                "writing the market snapshot",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    /// Loads every snapshot below `folder`, oldest first.
+    pub fn load_all(folder: &Path) -> Result<Vec<MarketSnapshot>, Error> {
+        let mut snapshots = vec![];
+        let mut folders = vec![folder.to_path_buf()];
+        while let Some(current) = folders.pop() {
+            let entries = std::fs::read_dir(&current).map_err(|e| {
+                Error::from_io(
+                    &format!("{}LoadSnapshots", COMPONENT),
+                    &current,
+                    "reading the snapshot folder",
+                    e,
+                    get_location!(),
+                )
+            })?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    folders.push(path);
+                } else if path.extension().map_or(false, |ext| ext == "json") {
+                    snapshots.push(utils::read_json_file::<MarketSnapshot>(&path)?);
+                }
+            }
+        }
+        snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(snapshots)
+    }
+}
+
+//
+// Report
+//
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BacktestItemReport {
+    pub wfm_id: String,
+    pub wfm_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<SubType>,
+    pub snapshots: usize,
+    pub buy_fills: i64,
+    pub sell_fills: i64,
+    pub units_held: i64,
+    pub average_cost: f64,
+    pub capital_tied_up: i64,
+    pub max_capital_tied_up: i64,
+    pub realized_profit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_buy_price: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_sell_price: Option<i64>,
+    #[serde(skip)]
+    open_buy_profit: f64,
+}
+
+impl BacktestItemReport {
+    fn buy(&mut self, price: i64) {
+        let cost = self.average_cost * self.units_held as f64 + price as f64;
+        self.units_held += 1;
+        self.average_cost = cost / self.units_held as f64;
+        self.buy_fills += 1;
+        self.update_capital();
+    }
+
+    fn sell(&mut self, price: i64) {
+        self.realized_profit += price - self.average_cost.round() as i64;
+        self.units_held -= 1;
+        self.sell_fills += 1;
+        if self.units_held == 0 {
+            self.average_cost = 0.0;
+            self.open_sell_price = None;
+        }
+        self.update_capital();
+    }
+
+    fn update_capital(&mut self) {
+        self.capital_tied_up = (self.average_cost * self.units_held as f64).round() as i64;
+        self.max_capital_tied_up = self.max_capital_tied_up.max(self.capital_tied_up);
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub snapshots: usize,
+    pub first_snapshot: String,
+    pub last_snapshot: String,
+    pub realized_profit: i64,
+    pub capital_tied_up: i64,
+    pub max_capital_tied_up: i64,
+    pub items: Vec<BacktestItemReport>,
+}
+
+//
+// Backtest
+//
+
+/// Replays the snapshots through the same WTB/WTS decisions as the item live scraper.
+///
+/// Fill model: an open WTB order fills one unit on the next snapshot where the closed trade
+/// minimum price is at or below our price, an open WTS order fills one unit when the closed
+/// trade maximum price is at or above our price.
+pub fn run_backtest(settings: &ItemSettings, snapshots: Vec<MarketSnapshot>) -> BacktestReport {
+    let mut items: HashMap<String, BacktestItemReport> = HashMap::new();
+    let mut report = BacktestReport {
+        snapshots: snapshots.len(),
+        first_snapshot: snapshots
+            .first()
+            .map(|s| s.timestamp.clone())
+            .unwrap_or_default(),
+        last_snapshot: snapshots
+            .last()
+            .map(|s| s.timestamp.clone())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    for snapshot in snapshots {
+        let mut entry = ItemEntry::from(&snapshot.price);
+        entry.wfm_url = snapshot.wfm_url.clone();
+        entry.wfm_id = snapshot.wfm_id.clone();
+        entry.sub_type = snapshot.sub_type.clone();
+        let uuid = entry.uuid();
+        let price = &snapshot.price;
+
+        // Same order filters as the live scraper, minus our own username
+        let mut orders = snapshot.orders.clone();
+        orders.filter_by_sub_type(
+            wf_market::types::SubType::from_entity(entry.sub_type.clone()),
+            false,
+        );
+        orders.filter_user_status(StatusType::InGame, false);
+        orders.sort_by_platinum();
+        entry.apply_market_info(&orders);
+
+        // Open buy orders of every item, used by the knapsack budget
+        let open_orders: Vec<OrderSummary> = items
+            .iter()
+            .filter_map(|(id, item)| {
+                item.open_buy_price
+                    .map(|p| (p, item.open_buy_profit, item.wfm_id.clone(), id.clone()))
+            })
+            .collect();
+
+        let item = items
+            .entry(uuid.clone())
+            .or_insert_with(|| BacktestItemReport {
+                wfm_id: entry.wfm_id.clone(),
+                wfm_url: entry.wfm_url.clone(),
+                sub_type: entry.sub_type.clone(),
+                ..Default::default()
+            });
+        item.snapshots += 1;
+
+        // Resolve the orders placed at the previous snapshot
+        if price.volume > 0.0 {
+            if let Some(bid) = item.open_buy_price {
+                if price.min_price as i64 <= bid {
+                    item.buy(bid);
+                }
+            }
+            if let Some(ask) = item.open_sell_price {
+                if item.units_held > 0 && price.max_price as i64 >= ask {
+                    item.sell(ask);
+                }
+            }
+        }
+
+        // --- Buy ---
+        let max_stock = settings.wtb.max_stock_quantity;
+        let can_buy = is_interesting_item(settings, price)
+            && !settings.general.is_item_blacklisted(
+                &entry.wfm_id,
+                &entry.sub_type,
+                &TradeMode::Buy,
+            )
+            && (is_disabled(max_stock) || item.units_held < max_stock);
+        if can_buy {
+            let operations = match item.open_buy_price {
+                Some(_) => OperationSet::from(vec!["Update"]),
+                None => OperationSet::from(vec!["Create"]),
+            };
+            let strategy =
+                build_pricing_strategy(settings.wtb.pricing.resolve(&entry.wfm_id, &snapshot.tags));
+            let decision = decide_buy(
+                settings,
+                strategy.as_ref(),
+                &entry,
+                price,
+                item.open_buy_price.unwrap_or(0),
+                orders.get_price_list(OrderType::Buy, None),
+                open_orders,
+                operations,
+            );
+            if decision.operations.has("Delete") {
+                item.open_buy_price = None;
+            } else if !decision.operations.has("Skip") {
+                item.open_buy_price = Some(decision.post_price);
+                item.open_buy_profit = decision.potential_profit as f64;
+            }
+            // The global knapsack pass removes the orders that were not selected
+            for (_, _, _, id) in decision.deferred {
+                if let Some(other) = items.get_mut(&id) {
+                    other.open_buy_price = None;
+                }
+            }
+        } else {
+            item.open_buy_price = None;
+        }
+
+        // --- Sell ---
+        let item = items.get_mut(&uuid).expect("Item report should exist");
+        let can_sell = item.units_held > 0
+            && !settings.general.is_item_blacklisted(
+                &entry.wfm_id,
+                &entry.sub_type,
+                &TradeMode::Sell,
+            );
+        if can_sell {
+            let operations = match item.open_sell_price {
+                Some(_) => OperationSet::from(vec!["Update"]),
+                None => OperationSet::from(vec!["Create"]),
+            };
+            let strategy =
+                build_pricing_strategy(settings.wts.pricing.resolve(&entry.wfm_id, &snapshot.tags));
+            let decision = decide_sell(
+                settings,
+                strategy.as_ref(),
+                &entry,
+                price,
+                item.average_cost.round() as i64,
+                item.open_sell_price.unwrap_or(0),
+                orders.get_price_list(OrderType::Sell, None),
+                &StockOverrides::default(),
+                operations,
+            );
+            item.open_sell_price = if decision.operations.has("Delete") {
+                None
+            } else {
+                Some(decision.post_price)
+            };
+        }
+
+        let total_capital: i64 = items.values().map(|i| i.capital_tied_up).sum();
+        report.max_capital_tied_up = report.max_capital_tied_up.max(total_capital);
+    }
+
+    report.items = items.into_values().collect();
+    report
+        .items
+        .sort_by(|a, b| b.realized_profit.cmp(&a.realized_profit));
+    report.realized_profit = report.items.iter().map(|i| i.realized_profit).sum();
+    report.capital_tied_up = report.items.iter().map(|i| i.capital_tied_up).sum();
+    info(
+        format!("{}Run", COMPONENT),
+        &format!(
+            "Replayed {} snapshots for {} items | Realized Profit: {} | Capital: {} | Max Capital: {}",
+            report.snapshots,
+            report.items.len(),
+            report.realized_profit,
+            report.capital_tied_up,
+            report.max_capital_tied_up
+        ),
+        &LoggerOptions::default(),
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buys_average_the_cost_and_track_the_capital() {
+        let mut item = BacktestItemReport::default();
+        item.buy(10);
+        item.buy(20);
+        assert_eq!(item.units_held, 2);
+        assert_eq!(item.buy_fills, 2);
+        assert_eq!(item.average_cost, 15.0);
+        assert_eq!(item.capital_tied_up, 30);
+        assert_eq!(item.max_capital_tied_up, 30);
+    }
+
+    #[test]
+    fn sells_realize_profit_against_the_average_cost() {
+        let mut item = BacktestItemReport::default();
+        item.buy(10);
+        item.buy(20);
+        item.open_sell_price = Some(25);
+
+        item.sell(25);
+        assert_eq!(item.realized_profit, 10);
+        assert_eq!(item.units_held, 1);
+        assert_eq!(item.capital_tied_up, 15);
+        assert_eq!(item.open_sell_price, Some(25));
+
+        item.sell(12);
+        assert_eq!(item.realized_profit, 7);
+        assert_eq!(item.units_held, 0);
+        assert_eq!(item.sell_fills, 2);
+        assert_eq!(item.average_cost, 0.0);
+        assert_eq!(item.capital_tied_up, 0);
+        assert_eq!(item.open_sell_price, None);
+        // The peak stays recorded after the stock is sold off
+        assert_eq!(item.max_capital_tied_up, 30);
+    }
+
+    #[test]
+    fn an_empty_replay_reports_nothing() {
+        let report = run_backtest(&ItemSettings::default(), vec![]);
+        assert_eq!(report.snapshots, 0);
+        assert!(report.first_snapshot.is_empty());
+        assert!(report.last_snapshot.is_empty());
+        assert_eq!(report.realized_profit, 0);
+        assert_eq!(report.max_capital_tied_up, 0);
+        assert!(report.items.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use entity::enums::stock_status::StockStatus;
+use utils::{OperationSet, Properties};
+use wf_market::enums::OrderType;
+
+use crate::{
+    app::ItemSettings,
+    cache::types::ItemPriceInfo,
+    live_scraper::{
+        is_disabled, knapsack, should_apply_max_price_drop, ItemEntry, PricingContext,
+        PricingStrategy,
+    },
+};
+
+// The decisions in this module are free of API and database calls,
+// so the live scraper and the backtester share the exact same pricing rules.
+
+/// Summary of one open buy order: (price, potential profit, wfm_id, order id)
+pub type OrderSummary = (i64, f64, String, String);
+
+//
+// Buying
+//
+
+#[derive(Clone, Debug, Default)]
+pub struct BuyDecision {
+    pub post_price: i64,
+    // How far above (positive) or below (negative) the post price is from the closed average.
+    pub closed_avg_metric: i64,
+    pub potential_profit: i64,
+    pub operations: OperationSet,
+    // Orders the knapsack did not select, deleted after all items are processed.
+    pub deferred: Vec<OrderSummary>,
+}
+
+/// Decides the WTB price for an item, starting from the price picked by `strategy`.
+/// A decision with the `Skip` operation means the order should be left as is.
+pub fn decide_buy(
+    settings: &ItemSettings,
+    strategy: &dyn PricingStrategy,
+    entry: &ItemEntry,
+    price: &ItemPriceInfo,
+    current_order_price: i64,
+    buy_prices: Vec<i64>,
+    open_orders: Vec<OrderSummary>,
+    mut operations: OperationSet,
+) -> BuyDecision {
+    let market_info = &entry.buy_market_info;
+    let closed_avg = price.moving_avg.unwrap_or(0.0);
+    let mut post_price = strategy.price(&PricingContext {
+        order_type: OrderType::Buy,
+        market_info,
+        price,
+        prices: &buy_prices,
+    });
+
+    // Nothing to compete with
+    if entry.buy_market_info.volume == 0 || entry.sell_market_info.volume == 0 {
+        operations.add("NoVolume");
+        operations.add("Skip");
+        return BuyDecision {
+            post_price,
+            operations,
+            ..Default::default()
+        };
+    }
+
+    // Handle Max Price Drop & Min Listings Below
+    if let Some(reason) = should_apply_max_price_drop(
+        settings.wtb.max_price_drop,
+        settings.wtb.min_listings_below,
+        current_order_price,
+        post_price,
+        buy_prices,
+        OrderType::Buy,
+    ) {
+        post_price = current_order_price;
+        operations.add(reason);
+    }
+
+    let closed_avg_metric = closed_avg as i64 - post_price;
+
+    // Rough expected profit: the margin between our buy price and the average sell price, minus 1 plat buffer.
+    let potential_profit = closed_avg_metric - 1;
+
+    // Per-item max price cap
+    let item_max_price = settings.general.get_item_max_price(&entry.wfm_id);
+    if item_max_price > 0 && post_price > item_max_price {
+        operations.add("AboveMaxBuyPrice");
+        post_price = item_max_price;
+    }
+
+    // Global average price cap
+    if !is_disabled(settings.wtb.avg_price_cap) && post_price > settings.wtb.avg_price_cap {
+        operations.add("AboveAvgPrice");
+        operations.add("Delete");
+    }
+
+    // Knapsack solver: enforces a total platinum budget across all buy orders.
+    let mut deferred = vec![];
+    if !is_disabled(settings.wtb.max_total_price_cap) {
+        let mut all_orders = open_orders;
+        if !all_orders.iter().any(|i| i.2 == entry.wfm_id) {
+            all_orders.push((
+                post_price,
+                potential_profit as f64,
+                entry.wfm_id.clone(),
+                String::new(),
+            ));
+        }
+        let (selected, unselected) = knapsack(all_orders, settings.wtb.max_total_price_cap);
+        let selected_ids: HashSet<_> = selected.iter().map(|o| &o.2).collect();
+
+        if !selected_ids.contains(&entry.wfm_id) {
+            operations.add("Skip");
+            operations.add("Delete");
+            return BuyDecision {
+                post_price,
+                closed_avg_metric,
+                potential_profit,
+                operations,
+                deferred: unselected,
+            };
+        }
+        deferred = unselected;
+    }
+
+    // Paying more than the market average
+    if closed_avg_metric < 0 {
+        operations.add("Delete");
+        operations.add("Overpriced");
+    }
+
+    // Spread between lowest and highest buy orders is too narrow
+    if market_info.price_range < settings.wtb.profit_threshold {
+        operations.add("Delete");
+        operations.add("Underpriced");
+    }
+
+    BuyDecision {
+        // Warframe Market prices cannot be below 1 platinum.
+        post_price: post_price.max(1),
+        closed_avg_metric,
+        potential_profit,
+        operations,
+        deferred,
+    }
+}
+
+//
+// Selling
+//
+
+/// Per-item overrides stored in the stock item properties.
+#[derive(Clone, Debug, Default)]
+pub struct StockOverrides {
+    pub min_price: Option<i64>,
+    pub min_profit: Option<i64>,
+    pub min_sma: Option<i64>,
+}
+
+impl StockOverrides {
+    pub fn from_properties(properties: &Properties) -> Self {
+        Self {
+            min_price: properties.get_property_value("min_price", None::<i64>),
+            min_profit: properties.get_property_value("min_profit", None::<i64>),
+            min_sma: properties.get_property_value("min_sma", None::<i64>),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SellDecision {
+    pub post_price: i64,
+    pub lowest_price: i64,
+    pub profit: i64,
+    pub minimum_sma: i64,
+    pub minimum_profit: i64,
+    pub operations: OperationSet,
+    // Status and list price the stock item gets locked to, the first rule that triggers wins.
+    pub stock_status: Option<(StockStatus, Option<i64>)>,
+}
+
+/// Decides the WTS price for a stock item bought for `bought_price`, starting from the price picked by `strategy`.
+pub fn decide_sell(
+    settings: &ItemSettings,
+    strategy: &dyn PricingStrategy,
+    entry: &ItemEntry,
+    price: &ItemPriceInfo,
+    bought_price: i64,
+    current_order_price: i64,
+    sell_prices: Vec<i64>,
+    overrides: &StockOverrides,
+    mut operations: OperationSet,
+) -> SellDecision {
+    let market_info = &entry.sell_market_info;
+    let closed_avg = price.moving_avg.unwrap_or(0.0) as i64;
+    let mut stock_status = None;
+
+    // Lowest competitor price, fall back to 0 if no sellers exist.
+    let lowest_price = if market_info.volume >= 2 {
+        market_info.lowest_price
+    } else {
+        if overrides.min_price.is_none() {
+            operations.add("Delete");
+            operations.add("NoSellers");
+            stock_status = Some((StockStatus::NoSellers, None));
+        }
+        0
+    };
+
+    let mut post_price = if market_info.volume >= 2 {
+        strategy.price(&PricingContext {
+            order_type: OrderType::Sell,
+            market_info,
+            price,
+            prices: &sell_prices,
+        })
+    } else {
+        lowest_price
+    };
+
+    // Clamp to per-item minimum price if set
+    if let Some(min_price) = overrides.min_price {
+        if post_price < min_price {
+            post_price = min_price;
+            operations.add("MinimumPrice");
+        }
+    }
+
+    // Prevent prices from dropping too fast relative to existing order,
+    // the live sell loop has always read this guard from the WTB settings
+    if let Some(reason) = should_apply_max_price_drop(
+        settings.wtb.max_price_drop,
+        settings.wtb.min_listings_below,
+        current_order_price,
+        post_price,
+        sell_prices,
+        OrderType::Sell,
+    ) {
+        post_price = current_order_price;
+        operations.add(reason);
+    }
+
+    // Enforce SMA floor: don't sell below (closed average - min_sma) if there's competition
+    let minimum_sma = overrides.min_sma.unwrap_or(settings.wts.min_sma);
+    if !is_disabled(minimum_sma)
+        && post_price < (closed_avg - minimum_sma)
+        && lowest_price > bought_price
+    {
+        post_price = closed_avg;
+        operations.add("SMALimit");
+        stock_status.get_or_insert((StockStatus::SMALimit, Some(post_price)));
+    }
+
+    // Ensure profit meets the minimum threshold by raising the price if needed
+    let mut profit = post_price - bought_price;
+    let minimum_profit = overrides.min_profit.unwrap_or(settings.wts.min_profit);
+    if !is_disabled(minimum_profit) && profit < minimum_profit {
+        post_price += minimum_profit - profit;
+        operations.add("LowProfit");
+        stock_status.get_or_insert((StockStatus::ToLowProfit, Some(post_price)));
+        profit = post_price - bought_price;
+    }
+
+    SellDecision {
+        // Warframe Market prices cannot be below 1 platinum.
+        post_price: post_price.max(1),
+        lowest_price,
+        profit,
+        minimum_sma,
+        minimum_profit,
+        operations,
+        stock_status,
+    }
+}
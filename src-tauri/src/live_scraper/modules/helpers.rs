@@ -91,7 +91,7 @@ pub async fn get_syndicate_interesting_items(
         syndicates.contains(&item.syndicate_unique_name)
     };
     let min_price = |item: &SyndicateItemPrice| {
-        is_disabled(settings.wts.min_price) || item.min_price <= settings.wts.min_price as f64
+        is_disabled(settings.wts.min_price) || item.min_price >= settings.wts.min_price as f64
     };
 
     let combined_filter = |item: &SyndicateItemPrice| {
@@ -103,13 +103,25 @@ pub async fn get_syndicate_interesting_items(
     };
 
     // Filter items based on settings
-    let filtered_items: Vec<SyndicateItemPrice> = items
+    let mut filtered_items: Vec<SyndicateItemPrice> = items
         .into_iter()
         .filter(|item| combined_filter(item))
         .collect();
 
+    // Rank by standing efficiency, cheapest standing per platinum first
+    filtered_items.sort_by(|a, b| standing_per_platinum(a).total_cmp(&standing_per_platinum(b)));
+
     Ok(filtered_items)
 }
+
+/// Returns how much standing is spent for each platinum the item sells for.
+/// Lower is better, items without a price are ranked last.
+pub fn standing_per_platinum(item: &SyndicateItemPrice) -> f64 {
+    if item.min_price <= 0.0 {
+        return f64::MAX;
+    }
+    item.standing_cost as f64 / item.min_price
+}
 pub fn get_interesting_items(settings: &ItemSettings) -> Vec<ItemPriceInfo> {
     if let Some(items) = INTERESTING_ITEMS.get() {
         if let Some(interesting_items) = items.get(&settings.get_query_id()) {
@@ -118,6 +130,25 @@ pub fn get_interesting_items(settings: &ItemSettings) -> Vec<ItemPriceInfo> {
     }
     let cache = states::cache_client().expect("Failed to get cache client");
 
+    let items = cache
+        .item_price()
+        .get_by_filter(|item| is_interesting_item(settings, item));
+    if items.is_empty() {
+        info(
+            "LiveScraper:Helpers:GetInterestingItems",
+            &format!(
+                "No interesting items found for settings: {}",
+                settings.get_query_id()
+            ),
+            &LoggerOptions::default(),
+        );
+        return vec![];
+    }
+    items
+}
+
+/// Returns true when the item passes the WTB filters of the given settings.
+pub fn is_interesting_item(settings: &ItemSettings, item: &ItemPriceInfo) -> bool {
     let volume_threshold = settings.wtb.volume_threshold;
     let avg_price_cap = settings.wtb.avg_price_cap;
     let trading_tax_cap = settings.wtb.trading_tax_cap;
@@ -148,28 +179,12 @@ pub fn get_interesting_items(settings: &ItemSettings) -> Vec<ItemPriceInfo> {
         |item: &ItemPriceInfo| is_disabled(trading_tax_cap) || item.trading_tax < trading_tax_cap;
 
     // Combine multiple filters dynamically
-    let combined_filter = |item: &ItemPriceInfo| {
-        volume_filter(item)
-            && profit_filter(item)
-            && avg_price_filter(item)
-            && week_price_shift_filter(item)
-            && trading_tax_cap_filter(item)
-            && profit_margin_filter(item)
-    };
-
-    let items = cache.item_price().get_by_filter(combined_filter);
-    if items.is_empty() {
-        info(
-            "LiveScraper:Helpers:GetInterestingItems",
-            &format!(
-                "No interesting items found for settings: {}",
-                settings.get_query_id()
-            ),
-            &LoggerOptions::default(),
-        );
-        return vec![];
-    }
-    items
+    volume_filter(item)
+        && profit_filter(item)
+        && avg_price_filter(item)
+        && week_price_shift_filter(item)
+        && trading_tax_cap_filter(item)
+        && profit_margin_filter(item)
 }
 
 pub fn knapsack(
@@ -310,16 +325,14 @@ pub async fn collect_interesting_items(
         }
     }
 
-    // --- Syndicate Mode --- Disabled for now, WIP
-    if settings.live_scraper.has_trade_mode(TradeMode::Syndicate) {
-        return Ok(interesting_items.into_values().collect());
-        let Ok(_) = app
+    // --- Syndicate Mode ---
+    if settings.live_scraper.has_trade_mode(TradeMode::Syndicate)
+        && app
             .user
             .has_permission(PermissionsFlags::from_str("syndicate_prices_search"))
-        else {
-            return Ok(interesting_items.into_values().collect());
-        };
-
+            .is_ok()
+    {
+        let cache = states::cache_client()?;
         let items = get_syndicate_interesting_items(&app, &settings.live_scraper.syndicate)
             .await
             .map_err(|e| e.with_location(get_location!()))?;
@@ -331,10 +344,28 @@ pub async fn collect_interesting_items(
                 &TradeMode::Syndicate,
             )
         }) {
+            // The syndicate prices only know the WFM id, resolve the url from the cache
+            let item_info = match cache.tradable_item().get_by(&item.wfm_id) {
+                Ok(item_info) => item_info,
+                Err(e) => {
+                    e.set_component(format!("{}Syndicate", component))
+                        .log("live_scraper_item.log");
+                    continue;
+                }
+            };
+            let mut item_entry = ItemEntry::from(&item);
+            item_entry.wfm_url = item_info.wfm_url.clone();
+            let syndicate_properties = item_entry.properties.properties.clone();
+
             interesting_items
-                .entry(item.uuid.clone())
-                .and_modify(|entry| entry.operations.add("Syndicate".to_string()))
-                .or_insert_with(|| ItemEntry::from(&item));
+                .entry(item_entry.uuid())
+                .and_modify(|entry| {
+                    entry.operations.add("Syndicate".to_string());
+                    entry
+                        .properties
+                        .merge_properties(syndicate_properties, true, true);
+                })
+                .or_insert(item_entry);
         }
     }
     Ok(interesting_items.into_values().collect())
@@ -465,9 +496,12 @@ pub fn orders_to_delete(
         settings.live_scraper.has_trade_mode(TradeMode::Buy),
         settings.live_scraper.has_trade_mode(TradeMode::Sell),
         settings.live_scraper.has_trade_mode(TradeMode::WishList),
+        settings.live_scraper.has_trade_mode(TradeMode::Syndicate),
     ) {
-        (true, false, true) => my_orders.order_ids(OrderType::Sell),
-        (false, true, false) => my_orders.order_ids(OrderType::Buy),
+        (true, false, true, false) => my_orders.order_ids(OrderType::Sell),
+        (false, true, false, _) | (false, false, false, true) => {
+            my_orders.order_ids(OrderType::Buy)
+        }
         _ => vec![],
     }
 }
@@ -485,6 +519,7 @@ pub async fn load_orders(
         }
     }
 
+    states::live_scraper().acquire_request().await;
     fetch_and_cache_orders(component, client, item_url, fake_path).await
 }
 async fn handler_wfm_error(
@@ -497,6 +532,12 @@ async fn handler_wfm_error(
 ) -> utils::Error {
     let log_level = match e {
         wf_market::errors::ApiError::AuctionLimitExceeded(_) => LogLevel::Warning,
+        wf_market::errors::ApiError::TooManyRequests(ref err) => {
+            states::live_scraper()
+                .scheduler()
+                .on_rate_limited(json!(err.retry_after).as_f64());
+            LogLevel::Warning
+        }
         wf_market::errors::ApiError::OrderLimitExceededSamePrice(_)
         | wf_market::errors::ApiError::NotFound(_)
         | wf_market::errors::ApiError::OrderLimitExceeded(_) => {
@@ -525,6 +566,68 @@ async fn handler_wfm_error(
     err
 }
 
+/// Returns true when the live scraper should record its decisions instead of calling Warframe Market.
+pub fn is_simulation() -> bool {
+    states::get_settings()
+        .map(|settings| settings.debugging.live_scraper.simulation)
+        .unwrap_or(false)
+}
+
+/// Adds a record to the decision journal of the current cycle.
+pub fn record_decision(record: DecisionRecord) {
+    states::live_scraper().journal().record(record);
+}
+
+/// Resolves which Warframe Market call `progress_order` would make for the given operations.
+pub fn order_action(trade_operations: &OperationSet, can_create_order: bool) -> &'static str {
+    let delete = trade_operations.has("Delete");
+    if trade_operations.has("Create") && !delete && can_create_order {
+        "Create"
+    } else if trade_operations.has("Update") && !delete {
+        "Update"
+    } else if trade_operations.has("Update") && delete {
+        "Delete"
+    } else {
+        "Skip"
+    }
+}
+
+/// Stores the current order book of an item in the market snapshot table.
+pub async fn store_market_snapshot(entry: &ItemEntry, price: &ItemPriceInfo) -> Result<(), Error> {
+    let conn = DATABASE.get().unwrap();
+    let snapshot = entity::market_snapshot::Model {
+        id: Default::default(),
+        wfm_id: entry.wfm_id.clone(),
+        wfm_url: entry.wfm_url.clone(),
+        sub_type: entry.sub_type.clone(),
+        highest_buy: entry.buy_market_info.highest_price,
+        lowest_buy: entry.buy_market_info.lowest_price,
+        highest_sell: entry.sell_market_info.highest_price,
+        lowest_sell: entry.sell_market_info.lowest_price,
+        volume: price.volume,
+        buy_orders: entry.buy_market_info.volume as i64,
+        sell_orders: entry.sell_market_info.volume as i64,
+        created_at: Default::default(),
+    };
+    MarketSnapshotMutation::create(conn, &snapshot)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(())
+}
+
+/// Deletes the market snapshots older than the configured retention.
+pub async fn prune_market_snapshots(retention_days: i64) -> Result<(), Error> {
+    if is_disabled(retention_days) {
+        return Ok(());
+    }
+    let conn = DATABASE.get().unwrap();
+    let before = chrono::Utc::now() - chrono::Duration::days(retention_days);
+    MarketSnapshotMutation::delete_older_than(conn, before)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?;
+    Ok(())
+}
+
 pub async fn progress_order(
     component: &str,
     entry: &ItemEntry,
@@ -544,8 +647,36 @@ pub async fn progress_order(
     let update_string = properties.get_property_value("update_string", String::new());
     let original_update_string =
         properties.get_property_value("original_update_string", String::new());
+    let action = order_action(trade_operations, can_create_order);
+
+    // Simulation mode: record the decision and leave the market untouched
+    if is_simulation() {
+        record_decision(
+            DecisionRecord::new(
+                component,
+                "order",
+                action,
+                &entry.wfm_id,
+                match order_type {
+                    OrderType::Buy => "buy",
+                    OrderType::Sell => "sell",
+                },
+                post_price as i64,
+                trade_operations,
+                &Properties::from(json!(properties.properties)),
+            )
+            .set_sub_type(entry.sub_type.clone())
+            .set_quantity(quantity, per_trade),
+        );
+        return Ok(OperationSet::default());
+    }
 
-    if trade_operations.has("Create") && !trade_operations.has("Delete") && can_create_order {
+    // Order changes share the request budget with the market lookups
+    if matches!(action, "Create" | "Update" | "Delete") {
+        states::live_scraper().acquire_request().await;
+    }
+
+    if action == "Create" {
         match wfm_client
             .order()
             .create(
@@ -584,7 +715,7 @@ pub async fn progress_order(
                 return Err(err);
             }
         }
-    } else if trade_operations.has("Update") && !trade_operations.has("Delete") {
+    } else if action == "Update" {
         match wfm_client
             .order()
             .update(
@@ -621,7 +752,7 @@ pub async fn progress_order(
                 return Err(err);
             }
         }
-    } else if trade_operations.has("Update") && trade_operations.has("Delete") {
+    } else if action == "Delete" {
         match wfm_client.order().delete(&order_id).await {
             Ok(_) => {
                 info(
@@ -666,16 +797,22 @@ pub async fn delete_order(
     order_type: OrderType,
     wfm_client: &wf_market::Client<wf_market::Authenticated>,
 ) -> Result<OperationSet, Error> {
+    let (order_id, current_price, mut properties, mut trade_operations) =
+        get_order_info(entry, order_type, wfm_client);
+    if order_id.is_empty() {
+        return Ok(OperationSet::default()); // No order to delete
+    }
+    trade_operations.add("Delete");
     progress_order(
         component,
         entry,
         wfm_client,
         order_type,
-        1,
-        Some(1),
+        current_price.max(1) as u32,
+        None,
         &LoggerOptions::default(),
-        &mut wf_market::types::Properties::default(),
-        &OperationSet::default(),
+        &mut properties,
+        &trade_operations,
     )
     .await
 }
@@ -689,18 +826,34 @@ pub async fn fetch_and_cache_orders(
     item_url: &str,
     cache_path: Option<&Path>,
 ) -> Result<OrderList<OrderWithUser>, Error> {
-    let orders = wfm_client
-        .order()
-        .get_orders_by_item(item_url)
-        .await
-        .map_err(|e| {
-            Error::from_wfm(
+    let scraper = states::live_scraper();
+    let orders = match wfm_client.order().get_orders_by_item(item_url).await {
+        Ok(orders) => {
+            scraper.scheduler().on_success();
+            orders
+        }
+        Err(e) => {
+            let retry_after = match &e {
+                wf_market::errors::ApiError::TooManyRequests(err) => {
+                    Some(json!(err.retry_after).as_f64())
+                }
+                _ => None,
+            };
+            let mut err = Error::from_wfm(
                 format!("{}:FetchAndCacheOrders", component),
                 &format!("Failed to get live orders for item {}", item_url),
                 e,
                 get_location!(),
-            )
-        })?;
+            );
+            // Rate limits pause the scheduler instead of stopping the live scraper
+            if let Some(retry_after) = retry_after {
+                scraper.scheduler().on_rate_limited(retry_after);
+                err.log_level = LogLevel::Warning;
+                err.properties.set_property_value("rate_limited", true);
+            }
+            return Err(err);
+        }
+    };
 
     if let Some(path) = cache_path {
         utils::write_json_file(path, &orders)?;
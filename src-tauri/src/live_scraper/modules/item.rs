@@ -77,6 +77,20 @@ impl ItemModule {
                 );
                 break;
             }
+            if is_simulation() {
+                record_decision(DecisionRecord::new(
+                    comp("Delete"),
+                    "order",
+                    "Delete",
+                    "",
+                    "",
+                    0,
+                    &OperationSet::from(vec!["Delete", "Unwanted"]),
+                    &Properties::from(json!({ "id": id })),
+                ));
+                current_index -= 1;
+                continue;
+            }
             match app.wfm_client.order().delete(id).await {
                 Ok(_) => {
                     info(
@@ -139,8 +153,19 @@ impl ItemModule {
         let cache = states::cache_client()?;
         let client = self.client.upgrade().expect("Client should not be dropped");
         let use_fake = app.settings.debugging.live_scraper.fake_orders;
+        let record_snapshots = app.settings.debugging.live_scraper.record_snapshots;
+        let general_settings = &app.settings.live_scraper.general;
+        let store_snapshots = general_settings.market_snapshots;
         let mut current_index = 1;
 
+        if store_snapshots {
+            if let Err(e) =
+                prune_market_snapshots(general_settings.market_snapshot_retention_days).await
+            {
+                e.log(LOG_FILE);
+            }
+        }
+
         // Snapshot existing buy order IDs before any processing this cycle
         let existing_buy_order_ids: HashSet<String> = app
             .wfm_client
@@ -151,8 +176,17 @@ impl ItemModule {
             .map(|o| o.id.clone())
             .collect();
 
-        // Sort by priority (highest first)
-        interesting_items.sort_by(|a, b| b.priority.cmp(&a.priority));
+        // Sort by priority (highest first), syndicate items by standing efficiency
+        interesting_items.sort_by(|a, b| {
+            b.priority.cmp(&a.priority).then_with(|| {
+                match (a.standing_per_platinum(), b.standing_per_platinum()) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    _ => std::cmp::Ordering::Equal,
+                }
+            })
+        });
+        // Items that waited the longest move up, so the tail of large lists still gets checked
+        let mut interesting_items = client.scheduler().schedule(interesting_items);
         let total = interesting_items.len();
 
         for item_entry in interesting_items.iter_mut() {
@@ -197,13 +231,29 @@ impl ItemModule {
                 .join("fake_orders")
                 .join(format!("order_{}.json", item_info.wfm_url));
 
-            let mut orders = load_orders(
+            let mut orders = match load_orders(
                 &comp("ProcessItem:LoadOrders:"),
                 &app.wfm_client,
                 &item_entry.wfm_url,
                 use_fake.then_some(&order_path),
             )
-            .await?;
+            .await
+            {
+                Ok(orders) => orders,
+                Err(e) if e.properties.get_property_value("rate_limited", false) => {
+                    warning(
+                        comp("ProcessItem"),
+                        &format!(
+                            "Rate limited when getting live orders for item {}. Skipping this item for now.",
+                            item_info.name
+                        ),
+                        &LoggerOptions::default(),
+                    );
+                    client.send_scheduler_stats();
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
 
             // Apply filters to orders
             orders.filter_by_sub_type(
@@ -217,6 +267,20 @@ impl ItemModule {
             // Add Market Info to ItemEntry
             item_entry.apply_market_info(&orders);
 
+            if store_snapshots {
+                if let Err(e) = store_market_snapshot(item_entry, &item_price).await {
+                    e.log(LOG_FILE);
+                }
+            }
+            if record_snapshots {
+                if let Err(e) =
+                    MarketSnapshot::new(&item_entry, &item_info.tags, &item_price, &orders)
+                        .save(&item_entry.uuid())
+                {
+                    e.log(LOG_FILE);
+                }
+            }
+
             info(
                 &comp("ProcessItem"),
                 &format!(
@@ -287,24 +351,25 @@ impl ItemModule {
                 );
             }
 
-            // Process syndicate logic (future expansion) DISABLED FOR NOW WIP
-            if item_entry.operations.has("Syndicate") {
-                // if let Err(e) = self
-                //     .progress_syndicate(&item_info, item_entry, &item_price, &orders)
-                //     .await
-                // {
-                //     return Err(e.with_location(get_location!()));
-                // }
-
-                // info(
-                //     &comp("ProgressSyndicate"),
-                //     &format!(
-                //         "Successfully processed syndicate for item: {}",
-                //         item_entry.wfm_url
-                //     ),
-                //     &LoggerOptions::default(),
-                // );
+            // Process syndicate logic, stock items already have their own sell order
+            if item_entry.operations.has("Syndicate") && !item_entry.operations.has("Sell") {
+                if let Err(e) = self
+                    .progress_syndicate(&item_info, item_entry, &item_price, &orders)
+                    .await
+                {
+                    return Err(e.with_location(get_location!()));
+                }
+
+                info(
+                    &comp("ProgressSyndicate"),
+                    &format!(
+                        "Successfully processed syndicate for item: {}",
+                        item_entry.wfm_url
+                    ),
+                    &LoggerOptions::default(),
+                );
             }
+            client.scheduler().mark_checked(&item_entry.uuid());
             current_index += 1;
         }
 
@@ -334,6 +399,21 @@ impl ItemModule {
                         // Skip orders created this cycle — let them survive until next check
                         continue;
                     }
+                    if is_simulation() {
+                        record_decision(DecisionRecord::new(
+                            &component,
+                            "order",
+                            "Delete",
+                            &order.2,
+                            "buy",
+                            order.0,
+                            &OperationSet::from(vec!["Delete", "Knapsack"]),
+                            &Properties::from(
+                                json!({ "id": order.3, "potential_profit": order.1 }),
+                            ),
+                        ));
+                        continue;
+                    }
                     if let Err(err) = app.wfm_client.order().delete(&order.3).await {
                         error(
                             &component,
@@ -383,18 +463,13 @@ impl ItemModule {
 
         // WTB (Want to Buy) configuration used for price calculations
         let max_stock_quantity = settings.wtb.max_stock_quantity;
-        let avg_price_cap = settings.wtb.avg_price_cap;
-        let max_total_price_cap = settings.wtb.max_total_price_cap;
         let profit_threshold = settings.wtb.profit_threshold;
 
         // Current market snapshot for this item's buy orders
         let market_info = &entry.buy_market_info;
 
-        // Determine the initial post price based on market conditions
-        let mut post_price = market_info.highest_price;
-
         // Existing Warframe Market order state and metadata
-        let (order_id, current_order_price, mut properties, mut trade_operations) =
+        let (order_id, current_order_price, mut properties, trade_operations) =
             get_order_info(entry, OrderType::Buy, &wfm_client);
 
         // Conditions
@@ -434,110 +509,52 @@ impl ItemModule {
             }
         }
 
-        // Handle Max Price Drop & Min Listings Below
-        if let Some(reason) = should_apply_max_price_drop(
-            settings.wtb.max_price_drop,
-            settings.wtb.min_listings_below,
+        // Price the order: strategy price, max price drop, price caps, knapsack budget and profit checks.
+        // Items not selected by the knapsack get deferred deletion (cleaned up by the global pass in process_items).
+        let strategy =
+            build_pricing_strategy(settings.wtb.pricing.resolve(&entry.wfm_id, &item_info.tags));
+        let decision = decide_buy(
+            &settings,
+            strategy.as_ref(),
+            entry,
+            price,
             current_order_price,
-            post_price,
             live_orders.get_price_list(OrderType::Buy, None),
-            OrderType::Buy,
-        ) {
-            post_price = current_order_price;
-            trade_operations.add(reason);
-        }
-
-        // How far above (positive) or below (negative) our post price is from the market average.
-        // Used to gauge whether we're overpaying relative to recent trades.
-        let closed_avg_metric = closed_avg as i64 - post_price;
-
-        // Rough expected profit: the margin between our buy price and the average sell price, minus 1 plat buffer.
-        let potential_profit = closed_avg_metric - 1;
-
-        // Per-item max price cap — if this item has a specific max configured, clamp the post price.
-        let item_max_price = settings.general.get_item_max_price(&item_info.wfm_id);
-        if item_max_price > 0 && post_price > item_max_price {
-            trade_operations.add("AboveMaxBuyPrice");
-            post_price = item_max_price;
-        }
-
-        // Global average price cap — if post price exceeds it, mark for deletion regardless of other checks.
-        if !is_disabled(avg_price_cap) && post_price > avg_price_cap {
-            trade_operations.add("AboveAvgPrice");
-            trade_operations.add("Delete");
-
-            log(&format!(
-                "Item {} is above the average price cap.",
-                item_info.name
-            ));
-        }
-
-        // Knapsack solver: enforces a total platinum budget across all buy orders.
-        // Given the cap and all existing orders + this item, it selects the most profitable subset.
-        // Items not selected get deferred deletion (cleaned up by the global pass in process_items).
-        if !is_disabled(max_total_price_cap) {
-            let mut all_orders = wfm_client
+            wfm_client
                 .order()
                 .cache_orders()
-                .extract_order_summary(OrderType::Buy);
-            if !all_orders.iter().any(|i| i.2 == item_info.wfm_id) {
-                all_orders.push((
-                    post_price,
-                    potential_profit as f64,
-                    item_info.wfm_id.clone(),
-                    String::new(),
-                ));
-            }
+                .extract_order_summary(OrderType::Buy),
+            trade_operations,
+        );
+        let BuyDecision {
+            post_price,
+            closed_avg_metric,
+            potential_profit,
+            operations: trade_operations,
+            deferred,
+        } = decision;
 
+        if trade_operations.has("AboveAvgPrice") {
             log(&format!(
-                "Knapsack Input: {} | Buy orders | Cap: {}",
-                all_orders.len(),
-                max_total_price_cap
-            ));
-            let (selected, unselected) = knapsack(all_orders, max_total_price_cap);
-            log(&format!(
-                "Knapsack selected {}/{} buy orders for {}.",
-                selected.len(),
-                selected.len() + unselected.len(),
+                "Item {} is above the average price cap.",
                 item_info.name
             ));
-
-            let selected_ids: HashSet<_> = selected.iter().map(|o| &o.2).collect();
-
-            if !selected_ids.contains(&item_info.wfm_id) {
-                log(&format!("{} was not selected.", item_info.name));
-                trade_operations.add("Skip");
-                trade_operations.add("Delete");
-                return Ok(());
-            }
-
-            for order in &unselected {
-                log(&format!(
-                    "Deferred deletion of unselected order {} for item {} (will delete after all items processed).",
-                    order.3, order.2
-                ));
-            }
         }
-
-        // If our post price is higher than the market average, flag as overpriced.
-        if closed_avg_metric < 0 {
-            trade_operations.add("Delete");
-            trade_operations.add("Overpriced");
+        if trade_operations.has("Skip") {
+            log(&format!("{} was not selected.", item_info.name));
+            return Ok(());
         }
-
-        // If the spread between lowest and highest buy orders is too narrow, not worth competing.
-        if market_info.price_range < profit_threshold {
-            trade_operations.add("Delete");
-            trade_operations.add("Underpriced");
+        for order in &deferred {
+            log(&format!(
+                "Deferred deletion of unselected order {} for item {} (will delete after all items processed).",
+                order.3, order.2
+            ));
         }
 
-        // Warframe Market prices cannot be below 1 platinum.
-        post_price = post_price.max(1);
-
         log_summary(
             &component,
             format!(
-                "Item {} | Post: {} | CurOrder: {} ({}) \
+                "Item {} | Post: {} | CurOrder: {} ({}) | Strategy: {} \
                  | Market: {} \
                  | Price: ClosedAvg: {} | MovingAvg: {} | Avg: {} | Min: {} | Max: {} \
                  | Profit: Metric: {} | Potential: {} | Threshold: {} \
@@ -546,6 +563,7 @@ impl ItemModule {
                 post_price,
                 current_order_price,
                 order_id,
+                strategy.name(),
                 market_info,
                 closed_avg,
                 price.moving_avg.unwrap_or(0.0),
@@ -637,21 +655,11 @@ impl ItemModule {
             get_order_info(&entry, OrderType::Sell, &wfm_client);
 
         // Per-item overrides stored on the stock item (optional)
-        let (min_price, min_profit, min_sma) = (
-            stock_item
-                .properties
-                .get_property_value("min_price", None::<i64>),
-            stock_item
-                .properties
-                .get_property_value("min_profit", None::<i64>),
-            stock_item
-                .properties
-                .get_property_value("min_sma", None::<i64>),
-        );
+        let overrides = StockOverrides::from_properties(&stock_item.properties);
 
         log(&format!(
             "Item {}: Overrides — min_price={:?}, min_profit={:?}, min_sma={:?}",
-            item_info.name, min_price, min_profit, min_sma
+            item_info.name, overrides.min_price, overrides.min_profit, overrides.min_sma
         ));
 
         // Hidden + inactive → nothing to do; hidden + active → deactivate and delete order
@@ -672,95 +680,48 @@ impl ItemModule {
             trade_operations.add("Delete");
         }
 
-        // Determine lowest competitor price. Fall back to 0 if no sellers exist and no min_price override is set.
-        let lowest_price = if market_info.volume >= 2 {
-            market_info.lowest_price
-        } else if min_price.is_none() {
-            log(&format!(
-                "Item {} has no sellers and no minimum price. Skipping.",
-                item_info.name
-            ));
-            trade_operations.add("Delete");
-            trade_operations.add("NoSellers");
-            stock_item.set_status(StockStatus::NoSellers);
-            stock_item.set_list_price(None);
-            stock_item.locked = true;
-            0
-        } else {
-            log(&format!(
-                "Item {} has no sellers but has min_price override. Using bought price as fallback.",
-                item_info.name
-            ));
-            0
-        };
-
-        // Start with the lowest competitor price as the initial post price
-        let mut post_price = lowest_price;
-
-        // Clamp to per-item minimum price if set
-        if let Some(min_price) = min_price {
-            let capped_price = post_price.max(min_price);
-            if capped_price != post_price {
-                log(&format!(
-                    "Item {} price capped to minimum price {}.",
-                    item_info.name, capped_price
-                ));
-                post_price = capped_price;
-                trade_operations.add("MinimumPrice");
-            }
-        }
-
-        // Prevent prices from dropping too fast relative to existing order
-        if let Some(reason) = should_apply_max_price_drop(
-            settings.wtb.max_price_drop,
-            settings.wtb.min_listings_below,
-            current_order_price,
+        // Price the order: strategy price, minimum price, max price drop, SMA floor and minimum profit.
+        let strategy =
+            build_pricing_strategy(settings.wts.pricing.resolve(&entry.wfm_id, &item_info.tags));
+        let SellDecision {
             post_price,
+            lowest_price,
+            profit,
+            minimum_sma,
+            minimum_profit,
+            operations: trade_operations,
+            stock_status,
+        } = decide_sell(
+            &settings,
+            strategy.as_ref(),
+            entry,
+            price,
+            bought_price,
+            current_order_price,
             live_orders.get_price_list(OrderType::Sell, None),
-            OrderType::Sell,
-        ) {
+            &overrides,
+            trade_operations,
+        );
+        let min_price = overrides.min_price;
+
+        if trade_operations.has("NoSellers") {
             log(&format!(
-                "Item {} max price drop applied ({}).",
-                item_info.name, reason
+                "Item {} has no sellers and no minimum price. Skipping.",
+                item_info.name
             ));
-            post_price = current_order_price;
-            trade_operations.add(reason);
         }
-
-        let minimum_sma = min_sma.unwrap_or(settings.wts.min_sma);
-
-        // Enforce SMA floor: don't sell below (closed average - min_sma) if there's competition
-        if !is_disabled(minimum_sma)
-            && post_price < (closed_avg - minimum_sma)
-            && lowest_price > bought_price
-        {
+        if trade_operations.has("MaxPriceDrop") {
             log(&format!(
-                "Item {} price adjusted to SMA limit (closed avg: {}, min_sma: {}).",
-                item_info.name, closed_avg, minimum_sma
+                "Item {} max price drop applied (MaxPriceDrop).",
+                item_info.name
             ));
-            post_price = closed_avg;
-            trade_operations.add("SMALimit");
-            stock_item.set_list_price(Some(post_price));
-            stock_item.set_status(StockStatus::SMALimit);
-            stock_item.locked = true;
         }
 
-        // Ensure profit meets the minimum threshold by raising the price if needed
-        let mut profit = post_price - bought_price;
-        let minimum_profit = min_profit.unwrap_or(settings.wts.min_profit);
-
-        if !is_disabled(minimum_profit) && profit < minimum_profit {
-            let adjustment = minimum_profit - profit;
-            log(&format!(
-                "Item {} profit too low ({}) < min ({}), adjusting by {}.",
-                item_info.name, profit, minimum_profit, adjustment
-            ));
-            post_price += adjustment;
-            stock_item.set_status(StockStatus::ToLowProfit);
-            stock_item.set_list_price(Some(post_price));
+        // Lock the stock item to the first rule that changed its status
+        if let Some((status, list_price)) = stock_status {
+            stock_item.set_status(status);
+            stock_item.set_list_price(list_price);
             stock_item.locked = true;
-            trade_operations.add("LowProfit");
-            profit = post_price - bought_price;
         }
 
         // Persist final price, mark as live, and record price history
@@ -771,13 +732,10 @@ impl ItemModule {
             post_price,
         ));
 
-        // Warframe Market prices cannot be below 1 platinum.
-        post_price = post_price.max(1);
-
         log_summary(
             &component,
             format!(
-                "Item {} | Post: {} | CurOrder: {} | Lowest: {} | ClosedAvg: {} | Bought: {} | Profit: {} \
+                "Item {} | Post: {} | CurOrder: {} | Strategy: {} | Lowest: {} | ClosedAvg: {} | Bought: {} | Profit: {} \
                  | Market: {} \
                  | Price: AVG: {} | Min: {} | Max: {} | Median: {} | WeekShift: {} \
                  | MinSMA: {} | MinProfit: {} | MinPrice: {:?} \
@@ -785,6 +743,7 @@ impl ItemModule {
                 item_info.name,
                 post_price,
                 current_order_price,
+                strategy.name(),
                 lowest_price,
                 closed_avg,
                 bought_price,
@@ -841,10 +800,12 @@ impl ItemModule {
             }
         }
 
-        // Flush stock-item changes to the database
-        entry
-            .finalize_stock_item(conn, &component, &mut stock_item, log_options)
-            .await?;
+        // Flush stock-item changes to the database, simulation mode never touches the stock
+        if !is_simulation() {
+            entry
+                .finalize_stock_item(conn, &component, &mut stock_item, log_options)
+                .await?;
+        }
         Ok(())
     }
 
@@ -1002,10 +963,12 @@ impl ItemModule {
                 .with_context(entry.to_json())
         })?;
 
-        // Persist any changes made to the wishlist item.
-        entry
-            .finalize_wishlist_item(conn, &component, &mut wishlist_item, &log_options)
-            .await?;
+        // Persist any changes made to the wishlist item, simulation mode never touches the wishlist.
+        if !is_simulation() {
+            entry
+                .finalize_wishlist_item(conn, &component, &mut wishlist_item, &log_options)
+                .await?;
+        }
 
         Ok(())
     }
@@ -1045,8 +1008,22 @@ impl ItemModule {
         // Merge any existing properties from the entry into the order properties.
         properties.merge_properties(entry.properties.properties.clone(), true, true);
 
-        // Start by matching the lowest active market price.
-        let mut post_price = market.lowest_price;
+        // Syndicate price data attached when the entry was collected.
+        let standing_cost = entry.properties.get_property_value("standingCost", 0i64);
+        let standing_per_platinum = entry.standing_per_platinum().unwrap_or(0.0);
+        let syndicate_max_price = entry.properties.get_property_value("maxPrice", 0.0f64) as i64;
+
+        // Start by matching the lowest active market price, fall back to the syndicate max price when nobody is selling.
+        let mut post_price = if market.volume == 0 {
+            log(&format!(
+                "Item {} has no sellers. Using syndicate max price {}.",
+                item_info.name, syndicate_max_price
+            ));
+            trade_operations.add("NoSellers");
+            syndicate_max_price
+        } else {
+            market.lowest_price
+        };
 
         // Prevent large price drops that would undercut the existing order too aggressively.
         if let Some(reason) = should_apply_max_price_drop(
@@ -1066,6 +1043,17 @@ impl ItemModule {
             trade_operations.add(reason);
         }
 
+        // Never list below the configured minimum price.
+        let min_price = syndicate_settings.wts.min_price;
+        if !is_disabled(min_price) && post_price < min_price {
+            log(&format!(
+                "Item {} price capped to minimum price {}.",
+                item_info.name, min_price
+            ));
+            post_price = min_price;
+            trade_operations.add("MinimumPrice");
+        }
+
         // Warframe Market prices cannot be below 1 platinum.
         post_price = post_price.max(1);
 
@@ -1075,12 +1063,15 @@ impl ItemModule {
             format!(
                 "Item {} | Post: {} | CurOrder: {} \
                  | Market: {} \
+                 | Standing: Cost: {} | PerPlatinum: {:.2} \
                  | Price: Avg: {} | Min: {} | Max: {} | MovingAvg: {} | Median: {} \
                  | Ops: {:?}",
                 item_info.name,
                 post_price,
                 current_order_price,
                 market,
+                standing_cost,
+                standing_per_platinum,
                 price.avg_price,
                 price.min_price,
                 price.max_price,
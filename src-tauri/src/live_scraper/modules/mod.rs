@@ -4,5 +4,20 @@ pub use item::*;
 pub mod riven;
 pub use riven::*;
 
+pub mod riven_wtb;
+pub use riven_wtb::*;
+
+pub mod price_alert;
+pub use price_alert::*;
+
 pub mod helpers;
 pub use helpers::*;
+
+pub mod decision;
+pub use decision::*;
+
+pub mod pricing;
+pub use pricing::*;
+
+pub mod backtest;
+pub use backtest::*;
@@ -0,0 +1,390 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{atomic::Ordering, Arc, Mutex, Weak},
+};
+
+use serde::Serialize;
+use serde_json::json;
+use utils::{get_location, info, warning, Error, LoggerOptions, SubType};
+use wf_market::{
+    enums::{OrderType, StatusType},
+    types::{OrderList, OrderWithUser},
+};
+
+use crate::{
+    app::{PriceAlertCondition, PriceAlertRule, WebhookEvent},
+    cache::ItemPriceInfo,
+    live_scraper::{load_orders, LiveScraperState},
+    notify_gui,
+    utils::modules::states,
+};
+
+static COMPONENT: &str = "LiveScraper:PriceAlertModule";
+static LOG_FILE: &str = "price_alert.log";
+
+/// A rule whose condition turned true, with the market it was checked against.
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceAlertHit {
+    pub rule_id: String,
+    pub rule: String,
+    pub wfm_url: String,
+    pub item_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<SubType>,
+    pub threshold: f64,
+    pub value: f64,
+    pub lowest_sell: Option<i64>,
+    pub highest_buy: Option<i64>,
+    pub moving_avg: Option<f64>,
+    pub week_price_shift: Option<f64>,
+}
+
+impl PriceAlertHit {
+    pub fn get_notify_variables(&self) -> HashMap<String, String> {
+        let display = |v: Option<i64>| v.map(|v| v.to_string()).unwrap_or("-".to_string());
+        let item_name = match &self.sub_type {
+            Some(sub_type) => format!("{} ({})", self.item_name, sub_type.shot_display()),
+            None => self.item_name.clone(),
+        };
+        HashMap::from([
+            ("<ITEM_NAME>".to_string(), item_name),
+            ("<WFM_URL>".to_string(), self.wfm_url.clone()),
+            ("<RULE>".to_string(), self.rule.clone()),
+            ("<THRESHOLD>".to_string(), self.threshold.to_string()),
+            ("<VALUE>".to_string(), format!("{:.1}", self.value)),
+            ("<LOWEST_SELL>".to_string(), display(self.lowest_sell)),
+            ("<HIGHEST_BUY>".to_string(), display(self.highest_buy)),
+            (
+                "<WEEK_SHIFT>".to_string(),
+                self.week_price_shift
+                    .map(|v| format!("{:.1}", v))
+                    .unwrap_or("-".to_string()),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug)]
+pub struct PriceAlertModule {
+    client: Weak<LiveScraperState>,
+    // Rules whose condition held on the last check, a rule fires again only after it went false
+    active: Mutex<HashSet<String>>,
+}
+
+impl PriceAlertModule {
+    /**
+     * Creates a new `PriceAlertModule` with no active rules.
+     * The `client` parameter is an `Arc<LiveScraperState>` that allows the module
+     * to access the live scraper state.
+     */
+    pub fn new(client: Arc<LiveScraperState>) -> Arc<Self> {
+        Arc::new(Self {
+            client: Arc::downgrade(&client),
+            active: Mutex::new(HashSet::new()),
+        })
+    }
+
+    pub async fn check(&self) -> Result<(), Error> {
+        let cache = states::cache_client()?;
+        let app = states::app_state()?;
+        let settings = states::get_settings()?;
+        let client = self.client.upgrade().expect("Client should not be dropped");
+        let log_options = &LoggerOptions::default().set_file(LOG_FILE);
+
+        // One order book request per item, whatever the number of rules on it
+        let mut groups: HashMap<String, Vec<&PriceAlertRule>> = HashMap::new();
+        for rule in settings
+            .notifications
+            .price_alerts
+            .rules
+            .iter()
+            .filter(|r| r.enabled && !r.wfm_url.is_empty())
+        {
+            groups.entry(rule.wfm_url.clone()).or_default().push(rule);
+        }
+        info(
+            format!("{}:Check", COMPONENT),
+            format!("Checking price alerts for {} items...", groups.len()),
+            log_options,
+        );
+
+        for (wfm_url, rules) in groups {
+            if !client.is_running.load(Ordering::SeqCst) {
+                return Ok(());
+            }
+            let item_name = cache
+                .tradable_item()
+                .get_by(&wfm_url)
+                .map(|item| item.name)
+                .unwrap_or(wfm_url.clone());
+
+            let orders = if rules.iter().any(|r| r.condition.needs_live_orders()) {
+                match load_orders(COMPONENT, &app.wfm_client, &wfm_url, None).await {
+                    Ok(mut orders) => {
+                        orders.filter_username(&app.user.wfm_username, true);
+                        orders.filter_user_status(StatusType::InGame, false);
+                        Some(orders)
+                    }
+                    Err(e) if e.properties.get_property_value("rate_limited", false) => {
+                        warning(
+                            format!("{}:Check", COMPONENT),
+                            &format!(
+                                "Rate limited when getting live orders for {}. Skipping it for now.",
+                                wfm_url
+                            ),
+                            log_options,
+                        );
+                        continue;
+                    }
+                    Err(e) => return Err(e.with_location(get_location!())),
+                }
+            } else {
+                None
+            };
+
+            for rule in rules {
+                let price_info = match cache.item_price().find_by(&wfm_url, rule.sub_type.clone()) {
+                    Ok(price_info) => price_info,
+                    Err(e) => {
+                        e.with_location(get_location!()).log(LOG_FILE);
+                        continue;
+                    }
+                };
+                let orders = orders.clone().map(|mut orders| {
+                    orders.filter_by_sub_type(
+                        wf_market::types::SubType::from_entity(rule.sub_type.clone()),
+                        false,
+                    );
+                    orders.sort_by_platinum();
+                    orders
+                });
+                let hit = evaluate_rule(rule, &item_name, price_info.as_ref(), orders.as_ref());
+                if self.should_fire(&rule.id, hit.is_some()) {
+                    if let Some(hit) = hit {
+                        self.fire(&hit, log_options);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn should_fire(&self, rule_id: &str, triggered: bool) -> bool {
+        let mut active = self
+            .active
+            .lock()
+            .expect("Failed to lock active price alerts");
+        if triggered {
+            active.insert(rule_id.to_string())
+        } else {
+            active.remove(rule_id);
+            false
+        }
+    }
+
+    fn fire(&self, hit: &PriceAlertHit, log_options: &LoggerOptions) {
+        info(
+            format!("{}:Alert", COMPONENT),
+            format!(
+                "{}: {} {} | Value: {:.1}",
+                hit.item_name, hit.rule, hit.threshold, hit.value
+            ),
+            log_options,
+        );
+        notify_gui!(
+            "price_alert",
+            "blue.7",
+            "triggered",
+            json!({
+                "item_name": hit.item_name,
+                "rule": hit.rule,
+                "threshold": hit.threshold,
+                "value": format!("{:.1}", hit.value),
+            }),
+            json!({})
+        );
+        match states::get_settings() {
+            Ok(settings) => settings.notifications.on_price_alert.send(
+                WebhookEvent::PriceAlert,
+                &hit.get_notify_variables(),
+                Some(json!(hit)),
+            ),
+            Err(e) => {
+                e.log(LOG_FILE);
+            }
+        }
+    }
+}
+
+/// Checks one rule against the cached price info and the live orders of its item.
+/// Returns the hit when the condition holds, rules without the data they need never fire.
+pub fn evaluate_rule(
+    rule: &PriceAlertRule,
+    item_name: &str,
+    price_info: Option<&ItemPriceInfo>,
+    orders: Option<&OrderList<OrderWithUser>>,
+) -> Option<PriceAlertHit> {
+    let lowest_sell = orders
+        .filter(|o| !o.sell_orders.is_empty())
+        .map(|o| o.lowest_price(OrderType::Sell));
+    let highest_buy = orders
+        .filter(|o| !o.buy_orders.is_empty())
+        .map(|o| o.highest_price(OrderType::Buy));
+    evaluate_market(rule, item_name, price_info, lowest_sell, highest_buy)
+}
+
+/// Same as `evaluate_rule` once the top of the order book is known.
+fn evaluate_market(
+    rule: &PriceAlertRule,
+    item_name: &str,
+    price_info: Option<&ItemPriceInfo>,
+    lowest_sell: Option<i64>,
+    highest_buy: Option<i64>,
+) -> Option<PriceAlertHit> {
+    let week_price_shift = price_info.map(|p| p.week_price_shift);
+
+    let value = match &rule.condition {
+        PriceAlertCondition::LowestSellBelow { price } => {
+            lowest_sell.filter(|v| v < price).map(|v| v as f64)
+        }
+        PriceAlertCondition::HighestBuyAbove { price } => {
+            highest_buy.filter(|v| v > price).map(|v| v as f64)
+        }
+        PriceAlertCondition::SpreadAbove { spread } => match (lowest_sell, highest_buy) {
+            (Some(sell), Some(buy)) if sell - buy > *spread => Some((sell - buy) as f64),
+            _ => None,
+        },
+        PriceAlertCondition::MovingAvgShift { percent } => week_price_shift.filter(|shift| {
+            if *percent >= 0.0 {
+                *shift >= *percent
+            } else {
+                *shift <= *percent
+            }
+        }),
+    }?;
+
+    Some(PriceAlertHit {
+        rule_id: rule.id.clone(),
+        rule: rule.condition.as_str().to_string(),
+        wfm_url: rule.wfm_url.clone(),
+        item_name: item_name.to_string(),
+        sub_type: rule.sub_type.clone(),
+        threshold: rule.condition.threshold(),
+        value,
+        lowest_sell,
+        highest_buy,
+        moving_avg: price_info.and_then(|p| p.moving_avg),
+        week_price_shift,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(condition: PriceAlertCondition) -> PriceAlertRule {
+        PriceAlertRule {
+            id: "rule".to_string(),
+            enabled: true,
+            wfm_url: "ash_prime_set".to_string(),
+            sub_type: None,
+            condition,
+        }
+    }
+
+    fn price_info(week_price_shift: f64) -> ItemPriceInfo {
+        ItemPriceInfo {
+            moving_avg: Some(100.0),
+            week_price_shift,
+            ..Default::default()
+        }
+    }
+
+    fn module() -> PriceAlertModule {
+        PriceAlertModule {
+            client: Weak::new(),
+            active: Mutex::new(HashSet::new()),
+        }
+    }
+
+    #[test]
+    fn lowest_sell_below_fires_under_the_price() {
+        let rule = rule(PriceAlertCondition::LowestSellBelow { price: 50 });
+        let hit = evaluate_market(&rule, "Ash Prime Set", None, Some(45), Some(30)).unwrap();
+        assert_eq!(hit.rule, "lowest_sell_below");
+        assert_eq!(hit.threshold, 50.0);
+        assert_eq!(hit.value, 45.0);
+        assert_eq!(hit.lowest_sell, Some(45));
+        assert_eq!(hit.highest_buy, Some(30));
+
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, Some(50), None).is_none());
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, None, Some(30)).is_none());
+    }
+
+    #[test]
+    fn highest_buy_above_fires_over_the_price() {
+        let rule = rule(PriceAlertCondition::HighestBuyAbove { price: 40 });
+        let hit = evaluate_market(&rule, "Ash Prime Set", None, None, Some(41)).unwrap();
+        assert_eq!(hit.value, 41.0);
+
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, Some(60), Some(40)).is_none());
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, Some(60), None).is_none());
+    }
+
+    #[test]
+    fn spread_above_needs_both_sides() {
+        let rule = rule(PriceAlertCondition::SpreadAbove { spread: 10 });
+        let hit = evaluate_market(&rule, "Ash Prime Set", None, Some(60), Some(45)).unwrap();
+        assert_eq!(hit.value, 15.0);
+
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, Some(60), Some(50)).is_none());
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, Some(60), None).is_none());
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, None, Some(45)).is_none());
+    }
+
+    #[test]
+    fn moving_avg_shift_fires_on_a_rise() {
+        let rule = rule(PriceAlertCondition::MovingAvgShift { percent: 10.0 });
+        let info = price_info(12.5);
+        let hit = evaluate_market(&rule, "Ash Prime Set", Some(&info), None, None).unwrap();
+        assert_eq!(hit.value, 12.5);
+        assert_eq!(hit.moving_avg, Some(100.0));
+        assert_eq!(hit.week_price_shift, Some(12.5));
+
+        let info = price_info(-12.5);
+        assert!(evaluate_market(&rule, "Ash Prime Set", Some(&info), None, None).is_none());
+        assert!(evaluate_market(&rule, "Ash Prime Set", None, None, None).is_none());
+    }
+
+    #[test]
+    fn negative_moving_avg_shift_fires_on_a_drop() {
+        let rule = rule(PriceAlertCondition::MovingAvgShift { percent: -10.0 });
+        let info = price_info(-10.0);
+        let hit = evaluate_market(&rule, "Ash Prime Set", Some(&info), None, None).unwrap();
+        assert_eq!(hit.value, -10.0);
+
+        let info = price_info(-5.0);
+        assert!(evaluate_market(&rule, "Ash Prime Set", Some(&info), None, None).is_none());
+        let info = price_info(15.0);
+        assert!(evaluate_market(&rule, "Ash Prime Set", Some(&info), None, None).is_none());
+    }
+
+    #[test]
+    fn fires_once_until_the_condition_goes_false() {
+        let module = module();
+        assert!(module.should_fire("rule", true));
+        assert!(!module.should_fire("rule", true));
+        assert!(!module.should_fire("rule", false));
+        assert!(module.should_fire("rule", true));
+    }
+
+    #[test]
+    fn rules_latch_independently() {
+        let module = module();
+        assert!(module.should_fire("a", true));
+        assert!(module.should_fire("b", true));
+        assert!(!module.should_fire("a", false));
+        assert!(!module.should_fire("b", true));
+        assert!(module.should_fire("a", true));
+    }
+}
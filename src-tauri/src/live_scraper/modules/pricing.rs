@@ -0,0 +1,126 @@
+use std::fmt::Debug;
+
+use wf_market::enums::OrderType;
+
+use crate::{
+    app::PricingStrategySetting, cache::types::ItemPriceInfo, live_scraper::ItemMarketInfo,
+};
+
+/// Market data a pricing strategy can look at.
+#[derive(Debug)]
+pub struct PricingContext<'a> {
+    pub order_type: OrderType,
+    // Market info of the side we post on (buy orders for WTB, sell orders for WTS)
+    pub market_info: &'a ItemMarketInfo,
+    pub price: &'a ItemPriceInfo,
+    // Prices of the live orders on the same side
+    pub prices: &'a [i64],
+}
+
+impl PricingContext<'_> {
+    /// Best prices first: highest buy orders or lowest sell orders.
+    pub fn best_prices(&self) -> Vec<i64> {
+        let mut prices = self.prices.to_vec();
+        match self.order_type {
+            OrderType::Buy => prices.sort_by(|a, b| b.cmp(a)),
+            _ => prices.sort(),
+        }
+        prices
+    }
+
+    /// The best price on our side of the order book.
+    pub fn top_price(&self) -> i64 {
+        match self.order_type {
+            OrderType::Buy => self.market_info.highest_price,
+            _ => self.market_info.lowest_price,
+        }
+    }
+}
+
+/// Picks the starting price of an order, caps and profit checks are applied afterwards.
+pub trait PricingStrategy: Debug + Send + Sync {
+    fn name(&self) -> String;
+    fn price(&self, context: &PricingContext) -> i64;
+}
+
+/// Matches the best order on our side, the original live scraper behaviour.
+#[derive(Debug, Default)]
+pub struct MatchTop;
+
+impl PricingStrategy for MatchTop {
+    fn name(&self) -> String {
+        "MatchTop".to_string()
+    }
+    fn price(&self, context: &PricingContext) -> i64 {
+        context.top_price()
+    }
+}
+
+/// Beats the best order by N platinum: outbids on WTB, undercuts on WTS.
+#[derive(Debug)]
+pub struct UndercutBy(pub i64);
+
+impl PricingStrategy for UndercutBy {
+    fn name(&self) -> String {
+        format!("UndercutBy({})", self.0)
+    }
+    fn price(&self, context: &PricingContext) -> i64 {
+        let top = context.top_price();
+        if top <= 0 {
+            return top;
+        }
+        match context.order_type {
+            OrderType::Buy => top + self.0,
+            _ => top - self.0,
+        }
+    }
+}
+
+/// Prices at a percentage of the closed moving average, falls back to the average price.
+#[derive(Debug)]
+pub struct PercentOfMovingAvg(pub f64);
+
+impl PricingStrategy for PercentOfMovingAvg {
+    fn name(&self) -> String {
+        format!("PercentOfMovingAvg({}%)", self.0)
+    }
+    fn price(&self, context: &PricingContext) -> i64 {
+        let average = context.price.moving_avg.unwrap_or(context.price.avg_price);
+        (average * self.0 / 100.0).round() as i64
+    }
+}
+
+/// Prices at the median of the K best orders, ignores a single outlier at the top.
+#[derive(Debug)]
+pub struct MedianOfTopK(pub usize);
+
+impl PricingStrategy for MedianOfTopK {
+    fn name(&self) -> String {
+        format!("MedianOfTopK({})", self.0)
+    }
+    fn price(&self, context: &PricingContext) -> i64 {
+        let prices = context.best_prices();
+        let top: Vec<i64> = prices.into_iter().take(self.0.max(1)).collect();
+        if top.is_empty() {
+            return context.top_price();
+        }
+        let mid = top.len() / 2;
+        if top.len() % 2 == 0 {
+            (top[mid - 1] + top[mid]) / 2
+        } else {
+            top[mid]
+        }
+    }
+}
+
+/// Creates the strategy configured in the settings.
+pub fn build_pricing_strategy(setting: &PricingStrategySetting) -> Box<dyn PricingStrategy> {
+    match setting {
+        PricingStrategySetting::MatchTop => Box::new(MatchTop),
+        PricingStrategySetting::Undercut { amount } => Box::new(UndercutBy(*amount)),
+        PricingStrategySetting::PercentOfMovingAvg { percent } => {
+            Box::new(PercentOfMovingAvg(*percent))
+        }
+        PricingStrategySetting::MedianOfTopK { k } => Box::new(MedianOfTopK(*k)),
+    }
+}
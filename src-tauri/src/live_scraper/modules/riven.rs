@@ -4,7 +4,8 @@ use entity::{dto::PriceHistory, enums::*, stock_riven::*};
 use serde_json::json;
 use service::{StockRivenMutation, StockRivenQuery};
 use utils::{
-    average_filtered_lowest_prices, get_location, info, warning, Error, LoggerOptions, OperationSet,
+    average_filtered_lowest_prices, get_location, info, warning, Error, LoggerOptions,
+    OperationSet, Properties,
 };
 use wf_market::{
     enums::{AuctionType, Polarity, StatusType},
@@ -15,7 +16,10 @@ use wf_market::{
 };
 static COMPONENT: &str = "LiveScraper:RivenModule";
 use crate::{
-    live_scraper::{is_disabled, LiveScraperState},
+    cache::{find_comparable_auctions, riven_attribute_ranges, RivenRollProfile},
+    live_scraper::{
+        is_disabled, is_simulation, order_action, record_decision, DecisionRecord, LiveScraperState,
+    },
     send_event,
     types::*,
     utils::{modules::states, ErrorFromExt},
@@ -144,14 +148,22 @@ impl RivenModule {
             let mut live_auctions = if stock_riven.is_hidden {
                 AuctionList::<AuctionWithOwner>::new(vec![])
             } else {
+                let scraper = states::live_scraper();
+                scraper.acquire_request().await;
                 match wfm_client
                     .auction()
                     .search_auctions(get_filter(&stock_riven))
                     .await
                 {
-                    Ok(auctions) => auctions,
+                    Ok(auctions) => {
+                        scraper.scheduler().on_success();
+                        auctions
+                    }
                     Err(e) => match e {
                         wf_market::errors::ApiError::TooManyRequests(err) => {
+                            scraper
+                                .scheduler()
+                                .on_rate_limited(json!(err.retry_after).as_f64());
                             warning(
                                     format!("{}:Check", COMPONENT),
                                     &format!(
@@ -184,9 +196,33 @@ impl RivenModule {
             live_auctions.filter_username(&app.user.wfm_username, true);
             live_auctions.sort_by_platinum();
 
+            // Price from the auctions most similar to this riven, rolls on the same weapon can differ a lot in value
+            let profile = RivenRollProfile::from_stock_riven(&stock_riven);
+            let ranges = riven_attribute_ranges(&cache, &weapon_info, &profile).unwrap_or_default();
+            let comparables = find_comparable_auctions(
+                &profile,
+                &live_auctions.auctions,
+                &ranges,
+                stock_riven
+                    .filter
+                    .min_similarity
+                    .filter(|_| stock_riven.filter.enabled.unwrap_or(false))
+                    .unwrap_or(settings.wts.min_similarity),
+                settings.wts.max_results,
+            );
+            let prices = if comparables.is_empty() {
+                if live_auctions.total_auctions() > 0 {
+                    operations.add("NoComparables");
+                }
+                live_auctions.prices()
+            } else {
+                operations.add("Comparables");
+                comparables.iter().map(|c| c.price).collect()
+            };
+
             // Get the price the item was bought for.
             let mut post_price = average_filtered_lowest_prices(
-                live_auctions.prices(),
+                prices,
                 settings.wts.max_results,
                 settings.wts.threshold_percentage,
             );
@@ -234,6 +270,21 @@ impl RivenModule {
             ),
             &log_options,
             );
+            if !comparables.is_empty() {
+                info(
+                    format!("{}:Comparables", COMPONENT),
+                    format!(
+                        "Auction {}: {}",
+                        stock_riven.weapon_name,
+                        comparables
+                            .iter()
+                            .map(|c| format!("{} {}p ({})", c.id, c.price, c.similarity.score))
+                            .collect::<Vec<_>>()
+                            .join(" | ")
+                    ),
+                    &log_options,
+                );
+            }
 
             // Prepare properties for auction creation or update
             let properties = json!({
@@ -242,10 +293,36 @@ impl RivenModule {
                 "highest_price": highest_price,
                 "lowest_price": lowest_price,
                 "profit": profit,
-                "operations": json!(operations.operations)
+                "operations": json!(operations.operations),
+                "comparables": comparables
+                    .iter()
+                    .map(|c| json!({"id": c.id, "price": c.price, "score": c.similarity.score}))
+                    .collect::<Vec<_>>()
             });
 
             let can_create = wfm_client.auction().can_create_auction();
+
+            // Simulation mode: record the decision and leave the auction and stock untouched
+            if is_simulation() {
+                let mut properties = Properties::from(properties);
+                properties.set_property_value("id", auction_id.clone());
+                record_decision(
+                    DecisionRecord::new(
+                        COMPONENT,
+                        "auction",
+                        order_action(&operations, can_create),
+                        &stock_riven.wfm_weapon_id,
+                        "sell",
+                        post_price,
+                        &operations,
+                        &properties,
+                    )
+                    .set_sub_type(stock_riven.sub_type.clone()),
+                );
+                current_index += 1;
+                continue;
+            }
+
             if operations.has("Create") && !operations.has("Delete") && can_create {
                 match wfm_client
                     .auction()
@@ -428,44 +505,7 @@ fn get_filter(entity: &Model) -> AuctionFilter {
         .with_user_activity(StatusType::InGame)
         .with_sort_by("price_asc");
     if entity.filter.enabled.unwrap_or(false) {
-        if let Some(attributes) = &entity.filter.attributes {
-            let positive_stats = attributes
-                .iter()
-                .filter(|a| a.positive && a.is_required)
-                .map(|a| a.url_name.clone())
-                .collect::<Vec<_>>();
-            let negative_stats = attributes
-                .iter()
-                .filter(|a| !a.positive && a.is_required)
-                .map(|a| a.url_name.clone())
-                .collect::<Vec<_>>();
-            if !positive_stats.is_empty() {
-                auction_filter = auction_filter.with_positive_stats(positive_stats);
-            }
-            if !negative_stats.is_empty() {
-                auction_filter = auction_filter.with_negative_stats(negative_stats);
-            }
-        }
-        if let Some(mastery_rank) = &entity.filter.mastery_rank {
-            if mastery_rank.min != 0 {
-                auction_filter = auction_filter.with_mastery_rank_min(mastery_rank.min as u32);
-            }
-            if let Some(max) = mastery_rank.max {
-                auction_filter = auction_filter.with_mastery_rank_max(max as u32);
-            }
-        }
-        if let Some(re_rolls) = &entity.filter.re_rolls {
-            if re_rolls.min != 0 {
-                auction_filter = auction_filter.with_re_rolls_min(re_rolls.min as u32);
-            }
-            if let Some(max) = re_rolls.max {
-                auction_filter = auction_filter.with_re_rolls_max(max as u32);
-            }
-        }
-        if let Some(polarity) = &entity.filter.polarity {
-            auction_filter = auction_filter
-                .with_polarity(wf_market::enums::Polarity::from_str(polarity).unwrap());
-        }
+        auction_filter = apply_match_filter(auction_filter, &entity.filter);
         if let Some(similarity) = entity.filter.similarity {
             let attributes = entity
                 .attributes
@@ -478,3 +518,49 @@ fn get_filter(entity: &Model) -> AuctionFilter {
     }
     auction_filter
 }
+
+/// Narrows an auction search to the required stats, mastery rank, re-rolls and polarity of a match filter.
+pub fn apply_match_filter(
+    mut auction_filter: AuctionFilter,
+    filter: &MatchRivenStruct,
+) -> AuctionFilter {
+    if let Some(attributes) = &filter.attributes {
+        let positive_stats = attributes
+            .iter()
+            .filter(|a| a.positive && a.is_required)
+            .map(|a| a.url_name.clone())
+            .collect::<Vec<_>>();
+        let negative_stats = attributes
+            .iter()
+            .filter(|a| !a.positive && a.is_required)
+            .map(|a| a.url_name.clone())
+            .collect::<Vec<_>>();
+        if !positive_stats.is_empty() {
+            auction_filter = auction_filter.with_positive_stats(positive_stats);
+        }
+        if !negative_stats.is_empty() {
+            auction_filter = auction_filter.with_negative_stats(negative_stats);
+        }
+    }
+    if let Some(mastery_rank) = &filter.mastery_rank {
+        if mastery_rank.min != 0 {
+            auction_filter = auction_filter.with_mastery_rank_min(mastery_rank.min as u32);
+        }
+        if let Some(max) = mastery_rank.max {
+            auction_filter = auction_filter.with_mastery_rank_max(max as u32);
+        }
+    }
+    if let Some(re_rolls) = &filter.re_rolls {
+        if re_rolls.min != 0 {
+            auction_filter = auction_filter.with_re_rolls_min(re_rolls.min as u32);
+        }
+        if let Some(max) = re_rolls.max {
+            auction_filter = auction_filter.with_re_rolls_max(max as u32);
+        }
+    }
+    if let Some(polarity) = &filter.polarity {
+        auction_filter =
+            auction_filter.with_polarity(wf_market::enums::Polarity::from_str(polarity).unwrap());
+    }
+    auction_filter
+}
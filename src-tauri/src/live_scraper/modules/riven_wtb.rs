@@ -0,0 +1,481 @@
+use std::{
+    collections::HashMap,
+    sync::{atomic::Ordering, Arc, Mutex, Weak},
+};
+
+use entity::{
+    enums::RivenGrade,
+    stock_riven::{MatchRivenStruct, RivenAttribute},
+};
+use serde::Serialize;
+use serde_json::json;
+use utils::{get_location, info, warning, Error, LoggerOptions};
+use wf_market::{
+    enums::{AuctionType, StatusType},
+    types::{AuctionFilter, AuctionWithOwner},
+};
+
+use crate::{
+    app::{RivenWatchSetting, WebhookEvent},
+    cache::{derive_riven_summary_attributes, grade_riven, CacheState},
+    live_scraper::{apply_match_filter, LiveScraperState},
+    notify_gui, send_event,
+    types::*,
+    utils::{modules::states, ErrorFromExt},
+};
+
+static COMPONENT: &str = "LiveScraper:RivenWtbModule";
+
+// Parts of a deal score, they add up to 1
+const CRITERIA_WEIGHT: f64 = 0.6;
+const ROLL_WEIGHT: f64 = 0.4;
+
+/// An auction that fits a riven watch and is listed at or under its target price.
+#[derive(Debug, Clone, Serialize)]
+pub struct RivenDeal {
+    pub watch_id: String,
+    pub auction_id: String,
+    pub seller: String,
+    pub weapon_name: String,
+    pub mod_name: String,
+    pub price: i64,
+    pub target_price: i64,
+    /// 0-100, how well the auction fits the watch filter and the good rolls of the weapon
+    pub score: f64,
+    pub criteria_score: f64,
+    pub grade: RivenGrade,
+    pub attributes: Vec<RivenAttribute>,
+}
+
+impl RivenDeal {
+    pub fn get_notify_variables(&self) -> HashMap<String, String> {
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|a| {
+                format!(
+                    "{}{} {}",
+                    if a.positive { "+" } else { "-" },
+                    a.value,
+                    a.wfm_url
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        HashMap::from([
+            ("<WEAPON_NAME>".to_string(), self.weapon_name.clone()),
+            ("<MOD_NAME>".to_string(), self.mod_name.clone()),
+            ("<SELLER>".to_string(), self.seller.clone()),
+            ("<PRICE>".to_string(), self.price.to_string()),
+            ("<TARGET_PRICE>".to_string(), self.target_price.to_string()),
+            ("<SCORE>".to_string(), format!("{:.1}", self.score)),
+            ("<GRADE>".to_string(), self.grade.to_string()),
+            ("<ATTRIBUTES>".to_string(), attributes),
+            (
+                "<AUCTION_URL>".to_string(),
+                format!("https://warframe.market/auction/{}", self.auction_id),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug)]
+pub struct RivenWtbModule {
+    client: Weak<LiveScraperState>,
+    // Watch id to the auctions reported for it and the price they were reported at,
+    // a listing is reported again only once it gets cheaper
+    reported: Mutex<HashMap<String, HashMap<String, i64>>>,
+}
+
+impl RivenWtbModule {
+    /**
+     * Creates a new `RivenWtbModule` with nothing reported yet.
+     * The `client` parameter is an `Arc<LiveScraperState>` that allows the module
+     * to access the live scraper state.
+     */
+    pub fn new(client: Arc<LiveScraperState>) -> Arc<Self> {
+        Arc::new(Self {
+            client: Arc::downgrade(&client),
+            reported: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn send_event(&self, i18nKey: &str, values: Option<serde_json::Value>) {
+        send_event!(
+            UIEvent::SendLiveScraperMessage,
+            json!({"i18nKey": format!("riven_wtb.{}", i18nKey), "values": values})
+        );
+    }
+
+    pub async fn check(&self) -> Result<(), Error> {
+        let cache = states::cache_client()?;
+        let app = states::app_state()?;
+        let settings = states::get_settings()?;
+        let wfm_client = app.wfm_client;
+        let client = self.client.upgrade().expect("Client should not be dropped");
+        let log_options = &LoggerOptions::default().set_file("riven_wtb.log");
+
+        let watches = settings
+            .live_scraper
+            .rivens
+            .wtb
+            .watches
+            .iter()
+            .filter(|w| w.enabled && !w.wfm_weapon_url.is_empty())
+            .collect::<Vec<_>>();
+        info(
+            format!("{}:Check", COMPONENT),
+            format!("Checking {} riven watches...", watches.len()),
+            log_options,
+        );
+        // Forget watches that were removed or turned off
+        self.reported
+            .lock()
+            .expect("Failed to lock reported deals")
+            .retain(|id, _| watches.iter().any(|w| w.id == *id));
+
+        let total = watches.len();
+        for (index, watch) in watches.into_iter().enumerate() {
+            if !client.is_running.load(Ordering::SeqCst) || app.user.is_banned() {
+                return Ok(());
+            }
+            self.send_event(
+                "checking",
+                Some(json!({
+                    "current": index + 1,
+                    "total": total,
+                    "name": watch.wfm_weapon_url,
+                })),
+            );
+
+            if !client.acquire_request().await {
+                return Ok(());
+            }
+            let mut auctions = match wfm_client
+                .auction()
+                .search_auctions(get_filter(watch))
+                .await
+            {
+                Ok(auctions) => {
+                    client.scheduler().on_success();
+                    auctions
+                }
+                Err(wf_market::errors::ApiError::TooManyRequests(err)) => {
+                    client
+                        .scheduler()
+                        .on_rate_limited(json!(err.retry_after).as_f64());
+                    warning(
+                        format!("{}:Check", COMPONENT),
+                        &format!(
+                            "Rate limited when searching auctions for {}. Skipping this watch for now.",
+                            watch.wfm_weapon_url
+                        ),
+                        log_options,
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    return Err(Error::from_wfm(
+                        format!("{}:Check", COMPONENT),
+                        &format!("Failed to search auctions for {}", watch.wfm_weapon_url),
+                        e,
+                        get_location!(),
+                    ))
+                }
+            };
+            auctions.filter_username(&app.user.wfm_username, true);
+            auctions.sort_by_platinum();
+            let listed = auctions
+                .auctions
+                .iter()
+                .map(|a| a.auction.id.clone())
+                .collect::<Vec<_>>();
+            self.forget_missing(&watch.id, &listed);
+
+            for auction in auctions.auctions.iter() {
+                if auction.auction.starting_price as i64 > watch.target_price {
+                    break;
+                }
+                let deal = match score_auction(&cache, watch, auction) {
+                    Ok(deal) => deal,
+                    Err(e) => {
+                        e.with_location(get_location!()).log("riven_wtb.log");
+                        continue;
+                    }
+                };
+                if deal.score < watch.min_score || !self.should_report(&deal) {
+                    continue;
+                }
+                self.report(&deal, log_options);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the reported auctions of a watch that are no longer listed, a relisting is reported again.
+    fn forget_missing(&self, watch_id: &str, listed: &[String]) {
+        let mut reported = self.reported.lock().expect("Failed to lock reported deals");
+        if let Some(reported) = reported.get_mut(watch_id) {
+            reported.retain(|id, _| listed.contains(id));
+        }
+    }
+
+    fn should_report(&self, deal: &RivenDeal) -> bool {
+        let mut reported = self.reported.lock().expect("Failed to lock reported deals");
+        let reported = reported.entry(deal.watch_id.clone()).or_default();
+        match reported.get(&deal.auction_id) {
+            Some(price) if *price <= deal.price => false,
+            _ => {
+                reported.insert(deal.auction_id.clone(), deal.price);
+                true
+            }
+        }
+    }
+
+    fn report(&self, deal: &RivenDeal, log_options: &LoggerOptions) {
+        info(
+            format!("{}:Deal", COMPONENT),
+            format!(
+                "{} {} from {} for {}p (target {}p) | Score: {:.1} | Grade: {}",
+                deal.weapon_name,
+                deal.mod_name,
+                deal.seller,
+                deal.price,
+                deal.target_price,
+                deal.score,
+                deal.grade.to_string()
+            ),
+            log_options,
+        );
+        notify_gui!(
+            "riven_wtb_deal",
+            "green.7",
+            "success",
+            json!({
+                "weapon_name": deal.weapon_name,
+                "mod_name": deal.mod_name,
+                "seller": deal.seller,
+                "price": deal.price,
+                "score": format!("{:.1}", deal.score),
+            }),
+            json!({})
+        );
+        match states::get_settings() {
+            Ok(settings) => settings.notifications.on_riven_deal.send(
+                WebhookEvent::RivenDeal,
+                &deal.get_notify_variables(),
+                Some(json!(deal)),
+            ),
+            Err(e) => {
+                e.log("riven_wtb.log");
+            }
+        }
+    }
+}
+
+fn get_filter(watch: &RivenWatchSetting) -> AuctionFilter {
+    let auction_filter = AuctionFilter::new(AuctionType::Riven, &watch.wfm_weapon_url)
+        .with_buyout_policy("direct")
+        .with_user_activity(StatusType::InGame)
+        .with_sort_by("price_asc");
+    apply_match_filter(auction_filter, &watch.filter)
+}
+
+/// Scores an auction against the watch filter and the good rolls of the weapon.
+fn score_auction(
+    cache: &CacheState,
+    watch: &RivenWatchSetting,
+    auction: &AuctionWithOwner,
+) -> Result<RivenDeal, Error> {
+    let item = &auction.auction.item;
+    let weapon = cache
+        .weapon()
+        .get_by(&watch.wfm_weapon_url)
+        .map_err(|e| e.with_location(get_location!()))?;
+    let raw_attributes = item
+        .attributes
+        .as_deref()
+        .unwrap_or(&[])
+        .iter()
+        .map(|a| (a.url_name.clone(), a.value, a.positive))
+        .collect::<Vec<_>>();
+    let attributes = derive_riven_summary_attributes(
+        cache,
+        &weapon,
+        &raw_attributes,
+        item.mod_rank.unwrap_or(0) as i32,
+    )?;
+    let grade = match cache.riven_good_roll().get_by(&weapon.unique_name) {
+        Ok(roll) => grade_riven(&roll, &attributes, "tag").0,
+        Err(_) => RivenGrade::Unknown,
+    };
+
+    let criteria_score = score_criteria(&watch.filter, &raw_attributes);
+    let roll_score = match grade {
+        RivenGrade::Perfect => 1.0,
+        RivenGrade::Good => 0.75,
+        RivenGrade::HasPotential => 0.5,
+        RivenGrade::Unknown => 0.25,
+        RivenGrade::Bad => 0.0,
+    };
+    let score = (criteria_score * CRITERIA_WEIGHT + roll_score * ROLL_WEIGHT) * 100.0;
+
+    Ok(RivenDeal {
+        watch_id: watch.id.clone(),
+        auction_id: auction.auction.id.clone(),
+        seller: auction.owner.ingame_name.clone(),
+        weapon_name: weapon.name.clone(),
+        mod_name: item.mod_name.clone().unwrap_or_default(),
+        price: auction.auction.starting_price as i64,
+        target_price: watch.target_price,
+        score: (score * 10.0).round() / 10.0,
+        criteria_score,
+        grade,
+        attributes,
+    })
+}
+
+/// Share of the wanted stats the riven has with the wanted sign, required stats are already enforced by the search.
+fn score_criteria(filter: &MatchRivenStruct, attributes: &[(String, f64, bool)]) -> f64 {
+    let wanted = filter.attributes.as_deref().unwrap_or(&[]);
+    if wanted.is_empty() {
+        return 1.0;
+    }
+    let matched = wanted
+        .iter()
+        .filter(|w| {
+            attributes
+                .iter()
+                .any(|(url, _, positive)| *url == w.url_name && *positive == w.positive)
+        })
+        .count();
+    let mut score = matched as f64 / wanted.len() as f64;
+    // A riven without a curse is worth less when the filter asks for one
+    if filter.required_negative.unwrap_or(false) && !attributes.iter().any(|(_, _, p)| !p) {
+        score *= 0.5;
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use entity::stock_riven::MatchRivenAttributeStruct;
+
+    use super::*;
+
+    fn module() -> RivenWtbModule {
+        RivenWtbModule {
+            client: Weak::new(),
+            reported: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn deal(watch_id: &str, auction_id: &str, price: i64) -> RivenDeal {
+        RivenDeal {
+            watch_id: watch_id.to_string(),
+            auction_id: auction_id.to_string(),
+            seller: "Seller".to_string(),
+            weapon_name: "Rubico".to_string(),
+            mod_name: "Crita-satiata".to_string(),
+            price,
+            target_price: 500,
+            score: 80.0,
+            criteria_score: 1.0,
+            grade: RivenGrade::Good,
+            attributes: vec![],
+        }
+    }
+
+    fn filter(wanted: &[(&str, bool)], required_negative: bool) -> MatchRivenStruct {
+        let mut filter = MatchRivenStruct::new();
+        filter.required_negative = Some(required_negative);
+        filter.attributes = Some(
+            wanted
+                .iter()
+                .map(|(url, positive)| MatchRivenAttributeStruct {
+                    url_name: url.to_string(),
+                    positive: *positive,
+                    is_required: false,
+                })
+                .collect(),
+        );
+        filter
+    }
+
+    fn attributes(stats: &[(&str, bool)]) -> Vec<(String, f64, bool)> {
+        stats
+            .iter()
+            .map(|(url, positive)| (url.to_string(), 100.0, *positive))
+            .collect()
+    }
+
+    #[test]
+    fn criteria_score_is_the_share_of_wanted_stats() {
+        let filter = filter(&[("critical_chance", true), ("multishot", true)], false);
+        let both = attributes(&[("critical_chance", true), ("multishot", true)]);
+        let one = attributes(&[("critical_chance", true), ("zoom", false)]);
+        assert_eq!(score_criteria(&filter, &both), 1.0);
+        assert_eq!(score_criteria(&filter, &one), 0.5);
+        assert_eq!(score_criteria(&filter, &[]), 0.0);
+    }
+
+    #[test]
+    fn criteria_score_checks_the_sign() {
+        let filter = filter(&[("zoom", false)], false);
+        assert_eq!(
+            score_criteria(&filter, &attributes(&[("zoom", false)])),
+            1.0
+        );
+        assert_eq!(score_criteria(&filter, &attributes(&[("zoom", true)])), 0.0);
+    }
+
+    #[test]
+    fn criteria_score_halves_without_a_wanted_curse() {
+        let filter = filter(&[("critical_chance", true)], true);
+        let cursed = attributes(&[("critical_chance", true), ("zoom", false)]);
+        let clean = attributes(&[("critical_chance", true)]);
+        assert_eq!(score_criteria(&filter, &cursed), 1.0);
+        assert_eq!(score_criteria(&filter, &clean), 0.5);
+    }
+
+    #[test]
+    fn criteria_score_without_wanted_stats_is_full() {
+        let clean = attributes(&[("critical_chance", true)]);
+        assert_eq!(score_criteria(&MatchRivenStruct::new(), &clean), 1.0);
+        assert_eq!(score_criteria(&filter(&[], true), &clean), 1.0);
+    }
+
+    #[test]
+    fn reports_a_listing_again_only_when_cheaper() {
+        let module = module();
+        assert!(module.should_report(&deal("watch", "a", 300)));
+        assert!(!module.should_report(&deal("watch", "a", 300)));
+        assert!(!module.should_report(&deal("watch", "a", 350)));
+        assert!(module.should_report(&deal("watch", "a", 250)));
+        assert!(!module.should_report(&deal("watch", "a", 300)));
+    }
+
+    #[test]
+    fn watches_keep_their_own_reports() {
+        let module = module();
+        assert!(module.should_report(&deal("first", "a", 300)));
+        assert!(module.should_report(&deal("second", "a", 300)));
+        assert!(module.should_report(&deal("first", "b", 300)));
+    }
+
+    #[test]
+    fn delisted_auctions_are_reported_again_when_relisted() {
+        let module = module();
+        assert!(module.should_report(&deal("watch", "a", 300)));
+        assert!(module.should_report(&deal("watch", "b", 300)));
+
+        module.forget_missing("watch", &["b".to_string()]);
+        assert!(module.should_report(&deal("watch", "a", 300)));
+        assert!(!module.should_report(&deal("watch", "b", 300)));
+
+        // Other watches keep what they reported
+        assert!(module.should_report(&deal("other", "a", 300)));
+        module.forget_missing("watch", &[]);
+        assert!(!module.should_report(&deal("other", "a", 300)));
+    }
+}
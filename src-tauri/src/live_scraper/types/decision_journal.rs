@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use utils::{get_location, info, Error, LoggerOptions, OperationSet, Properties, SubType};
+
+/// Market metrics copied from the order properties into each journal record.
+static METRIC_KEYS: &[&str] = &[
+    "closed_avg",
+    "potential_profit",
+    "sell_highest_price",
+    "sell_lowest_price",
+    "buy_highest_price",
+    "buy_lowest_price",
+    "supply",
+    "demand",
+    "spread",
+    "spread_percent",
+    "highest_price",
+    "lowest_price",
+    "profit",
+];
+
+//
+// Journal Record
+//
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub timestamp: String,
+    pub component: String,
+    // "order" or "auction"
+    pub kind: String,
+    // "Create", "Update", "Delete" or "Skip"
+    pub action: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    pub wfm_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<SubType>,
+    pub order_type: String,
+    pub post_price: i64,
+    pub quantity: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_trade: Option<i64>,
+    pub operations: Vec<String>,
+    pub metrics: Properties,
+}
+
+impl DecisionRecord {
+    pub fn new(
+        component: impl Into<String>,
+        kind: impl Into<String>,
+        action: impl Into<String>,
+        wfm_id: impl Into<String>,
+        order_type: impl Into<String>,
+        post_price: i64,
+        operations: &OperationSet,
+        properties: &Properties,
+    ) -> Self {
+        let mut metrics = properties.clone();
+        metrics.keep_property_values(METRIC_KEYS);
+        Self {
+            timestamp: chrono::Local::now().naive_local().to_string(),
+            component: component.into(),
+            kind: kind.into(),
+            action: action.into(),
+            name: properties.get_property_value("name", String::new()),
+            id: properties.get_property_value("id", String::new()),
+            wfm_id: wfm_id.into(),
+            sub_type: None,
+            order_type: order_type.into(),
+            post_price,
+            quantity: 1,
+            per_trade: None,
+            operations: operations.operations.clone(),
+            metrics,
+        }
+    }
+
+    pub fn set_sub_type(mut self, sub_type: Option<SubType>) -> Self {
+        self.sub_type = sub_type;
+        self
+    }
+
+    pub fn set_quantity(mut self, quantity: i64, per_trade: Option<i64>) -> Self {
+        self.quantity = quantity;
+        self.per_trade = per_trade;
+        self
+    }
+}
+
+//
+// Decision Journal
+//
+
+/// Collects every order/auction change the live scraper would have made during one cycle
+/// while the simulation mode is enabled.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DecisionJournal {
+    pub cycle: u64,
+    pub started_at: String,
+    pub finished_at: String,
+    pub records: Vec<DecisionRecord>,
+}
+
+impl DecisionJournal {
+    pub fn start_cycle(&mut self) {
+        self.cycle += 1;
+        self.started_at = chrono::Local::now().naive_local().to_string();
+        self.finished_at = String::new();
+        self.records.clear();
+    }
+
+    pub fn record(&mut self, record: DecisionRecord) {
+        info(
+            "LiveScraper:Simulation:Record",
+            &format!(
+                "[Cycle {}] {} {} {} | Price: {} | Ops: {:?}",
+                self.cycle,
+                record.action,
+                record.kind,
+                record.name,
+                record.post_price,
+                record.operations
+            ),
+            &LoggerOptions::default().set_file("live_scraper_simulation.log"),
+        );
+        self.records.push(record);
+    }
+
+    pub fn folder() -> PathBuf {
+        PathBuf::from(utils::get_base_path()).join("simulation")
+    }
+
+    /// Writes the current cycle to `simulation/journal_<cycle>_<timestamp>.json`, empty cycles are skipped.
+    pub fn flush(&mut self) -> Result<Option<PathBuf>, Error> {
+        if self.records.is_empty() {
+            return Ok(None);
+        }
+        let now = chrono::Local::now();
+        self.finished_at = now.naive_local().to_string();
+        let path = Self::folder().join(format!(
+            "journal_{}_{}.json",
+            self.cycle,
+            now.format("%Y%m%d_%H%M%S")
+        ));
+        utils::write_json_file(&path, &self).map_err(|e| {
+            Error::from_io(
+                "LiveScraper:Simulation:Flush",
+                &path,
+                "writing the decision journal",
+                e,
+                get_location!(),
+            )
+        })?;
+        Ok(Some(path))
+    }
+}
@@ -172,6 +172,17 @@ impl ItemEntry {
         self.clone()
     }
 
+    /// Standing spent per platinum for syndicate entries, `None` for every other entry.
+    pub fn standing_per_platinum(&self) -> Option<f64> {
+        if !self.properties.has_property("standingPerPlatinum") {
+            return None;
+        }
+        Some(
+            self.properties
+                .get_property_value("standingPerPlatinum", f64::MAX),
+        )
+    }
+
     pub fn to_json(&self) -> serde_json::Value {
         serde_json::to_value(self).unwrap_or_default()
     }
@@ -378,6 +389,9 @@ impl From<&qf_api::types::SyndicateItemPrice> for ItemEntry {
             Properties::from(json!({
                 "syndicate": item.syndicate,
                 "standingCost": item.standing_cost,
+                "standingPerPlatinum": crate::live_scraper::standing_per_platinum(item),
+                "minPrice": item.min_price,
+                "maxPrice": item.max_price,
             })),
         )
     }
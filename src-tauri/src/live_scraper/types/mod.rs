@@ -1,2 +1,8 @@
 pub mod item_entry;
 pub use item_entry::*;
+
+pub mod decision_journal;
+pub use decision_journal::*;
+
+pub mod request_scheduler;
+pub use request_scheduler::*;
@@ -0,0 +1,252 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::live_scraper::ItemEntry;
+
+// Staleness of an item that was never checked, and the cap for items that were.
+static MAX_STALENESS_SECS: f64 = 3600.0;
+// Each rate limit in a row doubles the wait on top of `retry_after`, up to this factor.
+static MAX_BACKOFF_MULTIPLIER: u32 = 8;
+
+//
+// Token Bucket
+//
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self) {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = Instant::now();
+    }
+
+    /// Takes a token, or returns how long until one is available.
+    fn take(&mut self) -> Option<Duration> {
+        self.refill();
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return None;
+        }
+        let missing = 1.0 - self.tokens;
+        Some(Duration::from_secs_f64(missing / self.refill_per_sec))
+    }
+}
+
+//
+// Queue
+//
+
+#[derive(Debug)]
+struct QueuedItem {
+    score: f64,
+    // Original position, keeps the incoming order for equal scores
+    index: usize,
+    entry: ItemEntry,
+}
+
+impl PartialEq for QueuedItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for QueuedItem {}
+impl PartialOrd for QueuedItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for QueuedItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+//
+// Stats
+//
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SchedulerStats {
+    pub tokens: f64,
+    pub capacity: f64,
+    pub requests_per_second: f64,
+    pub total_requests: u64,
+    pub rate_limited: u64,
+    pub backoff_multiplier: u32,
+    pub backoff_remaining_secs: f64,
+    pub queued_items: usize,
+    pub tracked_items: usize,
+    // Staleness of the item that waited the longest when the queue was built
+    pub max_staleness_secs: f64,
+}
+
+//
+// Scheduler
+//
+
+/// Shared request budget for the Warframe Market API and the item processing order.
+#[derive(Debug)]
+pub struct RequestScheduler {
+    bucket: TokenBucket,
+    backoff_until: Option<Instant>,
+    backoff_multiplier: u32,
+    last_checked: HashMap<String, Instant>,
+    total_requests: u64,
+    rate_limited: u64,
+    queued_items: usize,
+    max_staleness_secs: f64,
+}
+
+impl Default for RequestScheduler {
+    fn default() -> Self {
+        Self::new(3.0, 2.0)
+    }
+}
+
+impl RequestScheduler {
+    pub fn new(capacity: f64, requests_per_second: f64) -> Self {
+        Self {
+            bucket: TokenBucket::new(capacity.max(1.0), requests_per_second.max(0.1)),
+            backoff_until: None,
+            backoff_multiplier: 0,
+            last_checked: HashMap::new(),
+            total_requests: 0,
+            rate_limited: 0,
+            queued_items: 0,
+            max_staleness_secs: 0.0,
+        }
+    }
+
+    /// Applies new budget settings, the current tokens are kept.
+    pub fn configure(&mut self, capacity: f64, requests_per_second: f64) {
+        self.bucket.capacity = capacity.max(1.0);
+        self.bucket.refill_per_sec = requests_per_second.max(0.1);
+        self.bucket.tokens = self.bucket.tokens.min(self.bucket.capacity);
+    }
+
+    /// Takes a request token, or returns how long to wait before asking again.
+    pub fn try_acquire(&mut self) -> Option<Duration> {
+        if let Some(until) = self.backoff_until {
+            let now = Instant::now();
+            if until > now {
+                return Some(until - now);
+            }
+            self.backoff_until = None;
+        }
+        let wait = self.bucket.take();
+        if wait.is_none() {
+            self.total_requests += 1;
+        }
+        wait
+    }
+
+    /// Pauses all requests for `retry_after` seconds, longer when rate limits keep coming.
+    pub fn on_rate_limited(&mut self, retry_after: Option<f64>) {
+        self.rate_limited += 1;
+        self.backoff_multiplier = (self.backoff_multiplier * 2).clamp(1, MAX_BACKOFF_MULTIPLIER);
+        let base = retry_after.unwrap_or(1.0).max(1.0);
+        let wait = Duration::from_secs_f64(base * self.backoff_multiplier as f64);
+        self.backoff_until = Some(Instant::now() + wait);
+        // Start refilling from an empty bucket after the pause
+        self.bucket.tokens = 0.0;
+    }
+
+    /// Relaxes the adaptive backoff after a successful request.
+    pub fn on_success(&mut self) {
+        self.backoff_multiplier /= 2;
+    }
+
+    /// How long the live scraper should wait before the next cycle.
+    pub fn cycle_delay(&self, minimum: Duration) -> Duration {
+        match self.backoff_until {
+            Some(until) => until.saturating_duration_since(Instant::now()).max(minimum),
+            None => minimum,
+        }
+    }
+
+    pub fn mark_checked(&mut self, uuid: &str) {
+        self.last_checked.insert(uuid.to_string(), Instant::now());
+    }
+
+    fn staleness_secs(&self, uuid: &str) -> f64 {
+        self.last_checked
+            .get(uuid)
+            .map(|at| at.elapsed().as_secs_f64().min(MAX_STALENESS_SECS))
+            .unwrap_or(MAX_STALENESS_SECS)
+    }
+
+    /// Orders the items by priority and staleness, the most urgent first.
+    /// An item checked long ago eventually beats a fresh item with a higher priority,
+    /// so large stock lists can no longer starve the tail of the list.
+    pub fn schedule(&mut self, entries: Vec<ItemEntry>) -> Vec<ItemEntry> {
+        let mut queue = BinaryHeap::with_capacity(entries.len());
+        self.max_staleness_secs = 0.0;
+        for (index, entry) in entries.into_iter().enumerate() {
+            let staleness = self.staleness_secs(&entry.uuid());
+            self.max_staleness_secs = self.max_staleness_secs.max(staleness);
+            let score = (entry.priority.max(0) + 1) as f64 * staleness;
+            queue.push(QueuedItem {
+                score,
+                index,
+                entry,
+            });
+        }
+        // Forget items that are no longer interesting
+        let keep: std::collections::HashSet<String> =
+            queue.iter().map(|item| item.entry.uuid()).collect();
+        self.last_checked.retain(|uuid, _| keep.contains(uuid));
+
+        self.queued_items = queue.len();
+        let mut ordered = Vec::with_capacity(queue.len());
+        while let Some(item) = queue.pop() {
+            ordered.push(item.entry);
+        }
+        ordered
+    }
+
+    pub fn stats(&mut self) -> SchedulerStats {
+        self.bucket.refill();
+        SchedulerStats {
+            tokens: self.bucket.tokens,
+            capacity: self.bucket.capacity,
+            requests_per_second: self.bucket.refill_per_sec,
+            total_requests: self.total_requests,
+            rate_limited: self.rate_limited,
+            backoff_multiplier: self.backoff_multiplier,
+            backoff_remaining_secs: self
+                .backoff_until
+                .map(|until| {
+                    until
+                        .saturating_duration_since(Instant::now())
+                        .as_secs_f64()
+                })
+                .unwrap_or(0.0),
+            queued_items: self.queued_items,
+            tracked_items: self.last_checked.len(),
+            max_staleness_secs: self.max_staleness_secs,
+        }
+    }
+}
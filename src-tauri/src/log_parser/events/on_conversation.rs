@@ -3,14 +3,20 @@ use std::collections::HashMap;
 use serde_json::json;
 use utils::{info, DetectionStatus, Error, LineEntry, LineHandler, LoggerOptions};
 
-use crate::{add_metric, utils::modules::states};
+use crate::{add_metric, app::WebhookEvent, utils::modules::states};
 
 #[derive(Clone, Debug)]
-pub struct OnConversationEvent {}
+pub struct OnConversationEvent {
+    // Log replays only keep the handler chain intact, nothing is sent
+    replay: bool,
+}
 
 impl OnConversationEvent {
     pub fn new() -> Self {
-        OnConversationEvent {}
+        OnConversationEvent { replay: false }
+    }
+    pub fn replay() -> Self {
+        OnConversationEvent { replay: true }
     }
 }
 
@@ -44,6 +50,9 @@ impl LineHandler for OnConversationEvent {
                     if !player_name.is_empty() {
                         player_name = player_name.chars().skip(1).collect();
                     }
+                    if self.replay {
+                        return Ok((false, DetectionStatus::None));
+                    }
                     add_metric!("on_conversation_event", "new_conversation");
                     notify(&player_name);
                 }
@@ -62,8 +71,9 @@ fn notify(player_name: &str) {
     );
     let mut variables = HashMap::new();
     variables.insert("<PLAYER_NAME>".to_string(), player_name.to_string());
-    settings
-        .notifications
-        .on_new_conversation
-        .send(&variables, Some(json!({ "playerName": player_name })));
+    settings.notifications.on_new_conversation.send(
+        WebhookEvent::NewConversation,
+        &variables,
+        Some(json!({ "playerName": player_name })),
+    );
 }
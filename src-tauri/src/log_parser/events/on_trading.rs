@@ -2,7 +2,7 @@ use std::sync::{LazyLock, Mutex};
 
 use crate::{
     add_metric,
-    app::Settings,
+    app::{Settings, WebhookEvent},
     enums::TradeItemType,
     handlers::{
         handle_item, handle_riven_by_name, handle_transaction, handle_wish_list, is_wish_list_item,
@@ -39,12 +39,18 @@ fn get_component(component: &str) -> String {
 //----------------------------
 
 pub struct OnTradeEvent {
+    // Client language of the last detected trade, picks the detection pack
+    language: String,
     detection: TradeDetection,
     logs: Vec<LineEntry>,
     current_trade: PlayerTrade,
     operations: OperationSet,
     watcher: FileWatcher,
     logger: ZipLogger,
+    // Item lines that could not be resolved, reported by log replays
+    unresolved_items: Vec<String>,
+    // Set for log replays: finished trades go here instead of being processed
+    replay: Option<ReplaySink>,
 }
 
 //----------------------------
@@ -53,23 +59,48 @@ pub struct OnTradeEvent {
 
 impl OnTradeEvent {
     pub fn new(base_component: &str, watcher: FileWatcher) -> Self {
-        let detections = DETECTIONS.get().unwrap();
         delete_log("trade.log").ok();
 
         *COMPONENT.lock().unwrap() = format!("{}:OnTradeEvent", base_component);
 
         Self {
-            detection: detections.get("en").unwrap().clone(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            detection: get_detection(DEFAULT_LANGUAGE),
             logs: Vec::new(),
             current_trade: PlayerTrade::default(),
             operations: OperationSet::new(),
             watcher,
             logger: ZipLogger::new(),
+            unresolved_items: Vec::new(),
+            replay: None,
+        }
+    }
+
+    /// Trade handler for log replays, it sends no metrics or notifications, writes no trade logs
+    /// and does not process the trades. Every finished trade is pushed to `sink` instead.
+    pub fn replay(watcher: FileWatcher, sink: ReplaySink) -> Self {
+        Self {
+            language: DEFAULT_LANGUAGE.to_string(),
+            detection: get_detection(DEFAULT_LANGUAGE),
+            logs: Vec::new(),
+            current_trade: PlayerTrade::default(),
+            operations: OperationSet::new(),
+            watcher,
+            logger: ZipLogger::new(),
+            unresolved_items: Vec::new(),
+            replay: Some(sink),
+        }
+    }
+
+    fn add_metric(&self, value: &str) {
+        if self.replay.is_none() {
+            add_metric!("on_trade_event", value);
         }
     }
 
     pub fn reset(&mut self) {
         self.logs.clear();
+        self.unresolved_items.clear();
         self.current_trade = PlayerTrade::default();
         self.operations = OperationSet::new();
     }
@@ -133,6 +164,10 @@ impl OnTradeEvent {
         self.current_trade.finalize_items();
         self.log_trade_items();
 
+        if self.replay.is_some() {
+            return Ok(());
+        }
+
         let settings = states::get_settings()?.clone();
 
         let (trade_type, order_type) = match self.current_trade.trade_type {
@@ -145,7 +180,7 @@ impl OnTradeEvent {
 
         self.spawn_trade_processor(settings, trade_type, order_type);
 
-        add_metric!("on_trade_event", "trade_accepted");
+        self.add_metric("trade_accepted");
         Ok(())
     }
 }
@@ -219,6 +254,8 @@ impl OnTradeEvent {
 
             if status.is_found() {
                 self.add_item(item, &status);
+            } else {
+                self.unresolved_items.push(item.raw.clone());
             }
 
             i += advance(status);
@@ -512,7 +549,12 @@ fn process_operations(trade: &PlayerTrade, operations: OperationSet) {
         );
     }
 
-    settings.send(&trade.get_notify_variables(), Some(json!(trade)));
+    settings.send_with_lists(
+        WebhookEvent::NewTrade,
+        &trade.get_notify_variables(),
+        &trade.get_notify_lists(),
+        Some(json!(trade)),
+    );
 
     info(
         get_component("TradeAccepted"),
@@ -659,30 +701,54 @@ impl LineHandler for OnTradeEvent {
             }
         }
         // Detect start of trade
-        let trade_start = self.detection.is_beginning_of_trade(
-            &entry.line,
-            &entry.prev_line,
-            &[entry
-                .prev_detection
-                .replace_if_matches(&[DetectionStatus::Line], DetectionStatus::Combined)],
-        );
+        let ignored = [entry
+            .prev_detection
+            .replace_if_matches(&[DetectionStatus::Line], DetectionStatus::Combined)];
+        let mut trade_start =
+            self.detection
+                .is_beginning_of_trade(&entry.line, &entry.prev_line, &ignored);
+        let mut switched_language = None;
+        if !trade_start.is_found() {
+            // The client language may have changed, try the other packs
+            if let Some((language, detection, status)) =
+                detect_trade_language(&entry.line, &entry.prev_line, &ignored)
+            {
+                if language != self.language {
+                    switched_language = Some(format!("{} -> {}", self.language, language));
+                }
+                self.language = language;
+                self.detection = detection;
+                trade_start = status;
+            }
+        }
         if trade_start.is_found() {
             self.logger = ZipLogger::new();
-            self.logger.operations.add("DumpLog:trade.log");
+            if self.replay.is_none() {
+                self.logger.operations.add("DumpLog:trade.log");
+            }
             self.logger.add_log("Initialized Trade Logger");
             self.logger.add_log("Trade Started With:");
             self.logger
                 .add_log(format!("       {entry} | Detection: {:?}", trade_start));
+            if let Some(switch) = switched_language {
+                self.logger
+                    .add_log(format!("Switched trade detection language: {}", switch));
+            }
+            self.logger
+                .add_log(format!("Trade detection language: {}", self.language));
 
             self.operations.add("TradeStarted");
 
-            self.current_trade.trade_time = chrono::Local::now().with_timezone(&chrono::Utc);
+            self.current_trade.trade_time = match self.replay {
+                Some(_) => log_line_time(&entry.line),
+                None => chrono::Local::now().with_timezone(&chrono::Utc),
+            };
 
             self.operations.add("GettingTradeMessage");
             self.logger
                 .add_log("Switching to mode: Collecting Trade Message");
 
-            add_metric!("on_trade_event", "trade_started");
+            self.add_metric("trade_started");
 
             return Ok((true, trade_start));
         }
@@ -707,7 +773,7 @@ impl LineHandler for OnTradeEvent {
             .add_log(format!("Trade {} With:", result.display()));
         self.logger
             .add_log(format!("       {entry} | Detection: {:?}", status));
-        add_metric!("on_trade_event", result.metric_name());
+        self.add_metric(result.metric_name());
         self.logger.add_log(format!(
             "Switching to mode: Processing Trade Logs {} message lines collected",
             self.logs.len()
@@ -733,7 +799,16 @@ impl LineHandler for OnTradeEvent {
             }
             _ => {}
         }
-        self.create_log_file()?;
+        match &self.replay {
+            Some(sink) => sink.lock().unwrap().push(ReplayedTrade {
+                result: result.display().to_string(),
+                language: self.language.clone(),
+                lines: self.logs.iter().map(|l| l.line.clone()).collect(),
+                unresolved_items: self.unresolved_items.clone(),
+                trade: self.current_trade.clone(),
+            }),
+            None => self.create_log_file()?,
+        }
         self.reset();
         Ok((false, DetectionStatus::None))
     }
@@ -0,0 +1,215 @@
+use chrono::Duration;
+use entity::{
+    enums::TransactionSource,
+    transaction::{dto::TransactionPaginationQueryDto, transaction},
+};
+use serde::Serialize;
+use serde_json::json;
+use service::{TransactionMutation, TransactionQuery};
+use utils::*;
+
+use crate::{log_parser::*, send_event, types::UIEvent, DATABASE};
+
+static COMPONENT: &str = "GDPRImport";
+// The export has the server time of the trade, the app records it when the log line is read
+const MATCH_WINDOW_MINUTES: i64 = 10;
+
+/// What an import of the GDPR trades did to the ledger.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GdprImportSummary {
+    pub trades: usize,
+    pub imported: usize,
+    // Rows already in the ledger, recorded by the app or by an earlier import
+    pub duplicates: usize,
+    // Item for item trades and trades whose items are not in the cache
+    pub skipped_trades: usize,
+}
+
+/// Adds the sales and purchases of the accounts to the transaction ledger in one database transaction.
+/// A row matches an existing one with the same type, item and partner (or price and quantity) within a few minutes, those are left alone.
+pub async fn import_gdpr_trades(accounts: &[Account]) -> Result<GdprImportSummary, Error> {
+    let conn = DATABASE.get().unwrap();
+    let mut summary = GdprImportSummary::default();
+
+    let mut rows = vec![];
+    for account in accounts {
+        for trade in &account.trades {
+            summary.trades += 1;
+            let transactions = trade.to_transactions();
+            if transactions.is_empty() {
+                summary.skipped_trades += 1;
+            }
+            for mut transaction in transactions {
+                transaction.set_source(TransactionSource::Gdpr);
+                transaction.properties = Some(json!({ "gdpr_account": account.display_name }));
+                rows.push(transaction);
+            }
+        }
+    }
+    let (Some(from), Some(to)) = (
+        rows.iter().map(|t| t.created_at).min(),
+        rows.iter().map(|t| t.created_at).max(),
+    ) else {
+        return Ok(summary);
+    };
+
+    let window = Duration::minutes(MATCH_WINDOW_MINUTES);
+    let existing = TransactionQuery::get_all(
+        conn,
+        TransactionPaginationQueryDto::new(1, -1)
+            .set_from_date(from - window)
+            .set_to_date(to + window),
+    )
+    .await
+    .map_err(|e| e.with_location(get_location!()))?
+    .results;
+
+    let (new_rows, duplicates) = dedupe(rows, &existing, window);
+    summary.duplicates = duplicates;
+    summary.imported = TransactionMutation::create_all(conn, &new_rows)
+        .await
+        .map_err(|e| e.with_location(get_location!()))?
+        .len();
+
+    info(
+        format!("{}:Import", COMPONENT),
+        format!(
+            "Imported {} transactions from {} trades | Duplicates: {} | Skipped trades: {}",
+            summary.imported, summary.trades, summary.duplicates, summary.skipped_trades
+        ),
+        &LoggerOptions::default(),
+    );
+    if summary.imported > 0 {
+        send_event!(UIEvent::RefreshTransactions, json!({"source": COMPONENT}));
+    }
+    Ok(summary)
+}
+
+/// Splits the rows into the ones to insert and the count of those already in the ledger.
+/// Each existing row matches at most one imported row, so repeated trades are kept.
+fn dedupe(
+    rows: Vec<transaction::Model>,
+    existing: &[transaction::Model],
+    window: Duration,
+) -> (Vec<transaction::Model>, usize) {
+    let mut matched = vec![false; existing.len()];
+    let mut new_rows = vec![];
+    let mut duplicates = 0;
+    for row in rows {
+        let duplicate = (0..existing.len())
+            .find(|&i| !matched[i] && is_same_transaction(&existing[i], &row, window));
+        match duplicate {
+            Some(i) => {
+                matched[i] = true;
+                duplicates += 1;
+            }
+            None => new_rows.push(row),
+        }
+    }
+    (new_rows, duplicates)
+}
+
+// The export does not name the trade partner, without one the price and quantity have to match instead
+fn is_same_transaction(
+    existing: &transaction::Model,
+    row: &transaction::Model,
+    window: Duration,
+) -> bool {
+    let same_partner = if row.user_name.is_empty() {
+        existing.price == row.price && existing.quantity == row.quantity
+    } else {
+        existing.user_name.eq_ignore_ascii_case(&row.user_name)
+    };
+    existing.transaction_type == row.transaction_type
+        && existing.item_unique_name == row.item_unique_name
+        && same_partner
+        && (existing.created_at - row.created_at).abs() <= window
+}
+
+#[cfg(test)]
+mod tests {
+    use entity::enums::{TransactionItemType, TransactionType};
+
+    use super::*;
+
+    fn row(user_name: &str, price: i64, minute: i64) -> transaction::Model {
+        let mut row = transaction::Model::new(
+            "5a2feeb2c2c9e90cbdaa23e4",
+            "ash_prime_systems_blueprint",
+            "Ash Prime Systems Blueprint",
+            TransactionItemType::Item,
+            "/Lotus/Types/Recipes/WarframeRecipes/AshPrimeSystemsBlueprint",
+            None,
+            vec![],
+            TransactionType::Sale,
+            1,
+            user_name,
+            price,
+            0,
+            None,
+        );
+        row.created_at = chrono::DateTime::from_timestamp(minute * 60, 0).unwrap();
+        row
+    }
+
+    fn window() -> Duration {
+        Duration::minutes(MATCH_WINDOW_MINUTES)
+    }
+
+    #[test]
+    fn a_named_partner_has_to_match() {
+        assert!(is_same_transaction(
+            &row("Buyer", 25, 0),
+            &row("buyer", 30, 5),
+            window()
+        ));
+        assert!(!is_same_transaction(
+            &row("Other", 25, 0),
+            &row("Buyer", 25, 0),
+            window()
+        ));
+        // A row the app recorded without a partner no longer matches any name
+        assert!(!is_same_transaction(
+            &row("", 25, 0),
+            &row("Buyer", 25, 0),
+            window()
+        ));
+    }
+
+    #[test]
+    fn without_a_partner_the_price_and_quantity_have_to_match() {
+        assert!(is_same_transaction(
+            &row("Buyer", 25, 0),
+            &row("", 25, 3),
+            window()
+        ));
+        assert!(!is_same_transaction(
+            &row("Buyer", 25, 0),
+            &row("", 30, 3),
+            window()
+        ));
+
+        let mut more = row("", 25, 3);
+        more.quantity = 2;
+        assert!(!is_same_transaction(&row("Buyer", 25, 0), &more, window()));
+    }
+
+    #[test]
+    fn rows_outside_the_window_do_not_match() {
+        assert!(!is_same_transaction(
+            &row("Buyer", 25, 0),
+            &row("", 25, 11),
+            window()
+        ));
+    }
+
+    #[test]
+    fn an_existing_row_matches_one_import_row() {
+        let existing = vec![row("Buyer", 25, 0)];
+        let (new_rows, duplicates) =
+            dedupe(vec![row("", 25, 1), row("", 25, 2)], &existing, window());
+        assert_eq!(duplicates, 1);
+        assert_eq!(new_rows.len(), 1);
+        assert_eq!(new_rows[0].created_at, row("", 25, 2).created_at);
+    }
+}
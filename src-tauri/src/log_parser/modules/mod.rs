@@ -1,4 +1,8 @@
 pub mod warframe_gdpr;
 pub use warframe_gdpr::*;
 pub mod helpers;
-pub use helpers::*;
\ No newline at end of file
+pub use helpers::*;
+pub mod trade_replay;
+pub use trade_replay::*;
+pub mod gdpr_import;
+pub use gdpr_import::*;
@@ -0,0 +1,241 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utils::*;
+
+use crate::log_parser::*;
+
+static COMPONENT: &str = "TradeReplay";
+// Raw EE.log lines stored inside the `*_TRADE.zip` files written by `OnTradeEvent`
+static RAW_LOGS_FILE: &str = "RawEELogs.txt";
+
+/// A trade found while replaying a log, successful or not.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayedTrade {
+    pub result: String,
+    pub language: String,
+    // Trade message lines collected between the trade dialog and the end of the message
+    pub lines: Vec<String>,
+    // Item lines that were not resolved, every item line when the item cache is not available
+    pub unresolved_items: Vec<String>,
+    pub trade: PlayerTrade,
+}
+
+pub type ReplaySink = Arc<Mutex<Vec<ReplayedTrade>>>;
+
+/// Time of an EE.log line, the log counts the seconds since the game started.
+/// Replays use it instead of the clock so the same log always gives the same trades.
+pub fn log_line_time(line: &str) -> DateTime<Utc> {
+    let seconds = line
+        .split_whitespace()
+        .next()
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    DateTime::from_timestamp_millis((seconds * 1000.0).round() as i64).unwrap_or_default()
+}
+
+/// Splits log content into lines, `RawEELogs.txt` and the EE log export are JSON arrays of entries.
+pub fn parse_replay_lines(content: &[u8]) -> Vec<String> {
+    if let Ok(entries) = serde_json::from_slice::<Vec<LineEntry>>(content) {
+        return entries.into_iter().map(|entry| entry.line).collect();
+    }
+    split_lines_lossy(content)
+}
+
+/// Reads the lines of an EE.log file, a `RawEELogs.txt` file or a `*_TRADE.zip` file.
+pub fn read_replay_lines(path: &Path) -> Result<Vec<String>, Error> {
+    let component = format!("{}:ReadLines", COMPONENT);
+    let path_buf = path.to_path_buf();
+    let content = if path.extension().map_or(false, |ext| ext == "zip") {
+        let file = std::fs::File::open(path).map_err(|e| {
+            Error::from_io(
+                &component,
+                &path_buf,
+                "opening the trade archive",
+                e,
+                get_location!(),
+            )
+        })?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| {
+            Error::from_zip(
+                &component,
+                path.display().to_string(),
+                "reading the trade archive",
+                e,
+                get_location!(),
+            )
+        })?;
+        let mut entry = archive.by_name(RAW_LOGS_FILE).map_err(|e| {
+            Error::from_zip(
+                &component,
+                path.display().to_string(),
+                "finding the raw EE logs",
+                e,
+                get_location!(),
+            )
+        })?;
+        let mut content = vec![];
+        entry.read_to_end(&mut content).map_err(|e| {
+            Error::from_io(
+                &component,
+                &PathBuf::from(RAW_LOGS_FILE),
+                "reading the raw EE logs",
+                e,
+                get_location!(),
+            )
+        })?;
+        content
+    } else {
+        std::fs::read(path).map_err(|e| {
+            Error::from_io(
+                &component,
+                &path_buf,
+                "reading the log file",
+                e,
+                get_location!(),
+            )
+        })?
+    };
+    Ok(parse_replay_lines(&content))
+}
+
+/// Feeds the lines through the same handler chain as the live log parser, one line at a time.
+pub fn replay_lines(lines: Vec<String>) -> Vec<ReplayedTrade> {
+    let sink = ReplaySink::default();
+    let watcher = FileWatcher::new("");
+    watcher.add_handler(Box::new(OnTradeEvent::replay(
+        watcher.clone(),
+        sink.clone(),
+    )));
+    watcher.add_handler(Box::new(OnConversationEvent::replay()));
+    watcher.replay(lines);
+    // The trade handler keeps a clone of the watcher, drop the cached lines with it
+    watcher.reset();
+    let trades = sink.lock().unwrap().clone();
+    trades
+}
+
+pub fn replay_file(path: &Path) -> Result<Vec<ReplayedTrade>, Error> {
+    let lines = read_replay_lines(path)?;
+    let trades = replay_lines(lines);
+    info(
+        format!("{}:ReplayFile", COMPONENT),
+        format!(
+            "Replayed {} and found {} trades",
+            path.display(),
+            trades.len()
+        ),
+        &LoggerOptions::default(),
+    );
+    Ok(trades)
+}
+
+/// Loads the built-in detections and the small item cache of the replay fixtures, once per test run.
+#[cfg(test)]
+pub(crate) fn load_fixture_cache() {
+    use std::sync::Once;
+
+    use crate::{
+        cache::client::CacheState, log_parser::types::trade_detection::init_builtin_detections,
+        utils::modules::states,
+    };
+
+    static LOAD_CACHE: Once = Once::new();
+    LOAD_CACHE.call_once(|| {
+        init_builtin_detections();
+        let folder =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/trade_replay/cache");
+        states::set_replay_cache(CacheState::from_folder(folder, "en").unwrap());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/trade_replay")
+    }
+
+    /// Replays `<case>.log` and compares the trades with `<case>.golden.json`.
+    /// Set `UPDATE_GOLDEN=1` to rewrite the golden file after an intended change.
+    ///
+    /// Items are resolved with the small item cache in `cache/`,
+    /// add the items of a new case there or they end up in `unresolved_items`.
+    fn check_golden(case: &str) {
+        load_fixture_cache();
+        let log = fixtures().join(format!("{}.log", case));
+        let golden = fixtures().join(format!("{}.golden.json", case));
+        let trades = serde_json::to_value(replay_file(&log).unwrap()).unwrap();
+
+        if std::env::var("UPDATE_GOLDEN").is_ok() {
+            let content = serde_json::to_string_pretty(&trades).unwrap();
+            std::fs::write(&golden, content + "\n").unwrap();
+            return;
+        }
+        let expected: Value =
+            serde_json::from_str(&std::fs::read_to_string(&golden).unwrap()).unwrap();
+        assert_eq!(
+            trades,
+            expected,
+            "{} does not match {}",
+            log.display(),
+            golden.display()
+        );
+    }
+
+    #[test]
+    fn golden_sale() {
+        check_golden("sale");
+    }
+
+    #[test]
+    fn golden_purchase() {
+        check_golden("purchase");
+    }
+
+    #[test]
+    fn golden_multi_item() {
+        check_golden("multi_item");
+    }
+
+    #[test]
+    fn golden_riven() {
+        check_golden("riven");
+    }
+
+    #[test]
+    fn golden_credits() {
+        check_golden("credits");
+    }
+
+    #[test]
+    fn golden_cancelled() {
+        check_golden("cancelled");
+    }
+
+    #[test]
+    fn golden_failed() {
+        check_golden("failed");
+    }
+
+    #[test]
+    fn raw_ee_logs_are_read_as_lines() {
+        let content = br#"[{"index":1,"line":"1.000 Sys [Info]: a","prev_line":"","prev_detection":"None","date":0},{"index":2,"line":"b","prev_line":"1.000 Sys [Info]: a","prev_detection":"None","date":0}]"#;
+        assert_eq!(
+            parse_replay_lines(content),
+            vec!["1.000 Sys [Info]: a".to_string(), "b".to_string()]
+        );
+        assert_eq!(
+            parse_replay_lines(b"1.000 Sys [Info]: a\r\nb\n"),
+            vec!["1.000 Sys [Info]: a".to_string(), "b".to_string()]
+        );
+    }
+}
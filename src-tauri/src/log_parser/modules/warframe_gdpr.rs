@@ -1,6 +1,10 @@
 use chrono::{NaiveDateTime, Utc};
+use entity::gdpr::{GdprAccountRecords, GdprWatermark};
 use regex::Regex;
+use serde::Serialize;
+use service::{GdprMutation, GdprQuery};
 use std::{
+    collections::HashMap,
     sync::{Arc, Mutex},
     time::Instant,
 };
@@ -12,24 +16,228 @@ use crate::{
     log_parser::*,
     notify_gui,
     utils::modules::states,
+    DATABASE,
 };
 
 static COMPONENT: &str = "WarframeGDPRModule";
+
+/// What a load of a GDPR export found, only records newer than the stored ones are counted as new.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct GdprParseStats {
+    pub accounts: usize,
+    pub new_trades: usize,
+    pub new_logins: usize,
+    pub new_purchases: usize,
+    pub new_transactions: usize,
+    // Records already stored by an earlier load
+    pub skipped_records: usize,
+    pub warnings: Vec<String>,
+    pub elapsed_ms: u64,
+}
+
+impl GdprParseStats {
+    fn warn(&mut self, warning: impl Into<String>) {
+        let warning = warning.into();
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WarframeGDPRModule {
     pub was_initialized: Mutex<bool>,
+    pub was_restored: Mutex<bool>,
     pub accounts: Mutex<Vec<Account>>,
 }
 impl WarframeGDPRModule {
     pub fn new() -> Arc<Self> {
         Arc::new(Self {
             was_initialized: Mutex::new(false),
+            was_restored: Mutex::new(false),
             accounts: Mutex::new(Vec::new()),
         })
     }
 
-    pub fn load(&self, file_path: &str) -> Result<(), Error> {
+    /// Parses the export and stores the records newer than the last load of each account.
+    pub async fn load(&self, file_path: &str) -> Result<GdprParseStats, Error> {
+        let conn = DATABASE.get().unwrap();
+        // Start Time
+        let start = Instant::now();
+        let watermarks = GdprQuery::get_watermarks(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+
         enable_logging(false);
+        let result = Self::parse(file_path, &watermarks);
+        enable_logging(true);
+        let (accounts, mut stats) = result?;
+
+        let mut records = vec![];
+        for account in &accounts {
+            if account.oid.is_empty() {
+                stats.warn(format!(
+                    "Account {} has no OID and was not stored",
+                    account.display_name
+                ));
+                continue;
+            }
+            let oid = account.oid.as_str();
+            records.push(GdprAccountRecords {
+                account: account.to_model(),
+                trades: account.trades.iter().map(|t| t.to_model(oid)).collect(),
+                logins: account.logins.iter().map(|l| l.to_model(oid)).collect(),
+                purchases: account.purchases.iter().map(|p| p.to_model(oid)).collect(),
+                transactions: account
+                    .transactions
+                    .iter()
+                    .map(|t| t.to_model(oid))
+                    .collect(),
+            });
+        }
+        let counts = GdprMutation::import(conn, records)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        stats.new_trades = counts.trades;
+        stats.new_logins = counts.logins;
+        stats.new_purchases = counts.purchases;
+        stats.new_transactions = counts.transactions;
+        self.restore()
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        stats.elapsed_ms = start.elapsed().as_millis() as u64;
+
+        info(
+            format!("{}:Load", COMPONENT),
+            format!(
+                "Finished loading Warframe GDPR data in: {}ms | Accounts: {} | New Trades: {} | New Logins: {} | New Purchases: {} | New Transactions: {} | Skipped: {} | Warnings: {}",
+                stats.elapsed_ms,
+                stats.accounts,
+                stats.new_trades,
+                stats.new_logins,
+                stats.new_purchases,
+                stats.new_transactions,
+                stats.skipped_records,
+                stats.warnings.len(),
+            ),
+            &LoggerOptions::default(),
+        );
+        notify_gui!(
+            "warframe_gdpr_data_loaded",
+            "green",
+            "success",
+            json!({
+                "accounts": stats.accounts,
+                "trades": stats.new_trades,
+                "logins": stats.new_logins,
+                "purchases": stats.new_purchases,
+                "transactions": stats.new_transactions,
+                "skipped": stats.skipped_records,
+                "warnings": stats.warnings.len(),
+            }),
+            json!({})
+        );
+        Ok(stats)
+    }
+
+    /// Rebuilds the accounts from the stored records.
+    pub async fn restore(&self) -> Result<(), Error> {
+        let conn = DATABASE.get().unwrap();
+        let mut accounts: Vec<Account> = GdprQuery::get_accounts(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+            .into_iter()
+            .map(Account::from)
+            .collect();
+        let by_oid: HashMap<String, usize> = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.oid.clone(), i))
+            .collect();
+        let account_for = |oid: &str| by_oid.get(oid).copied();
+
+        for trade in GdprQuery::get_trades(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+        {
+            if let Some(i) = account_for(&trade.account_oid) {
+                accounts[i].trades.extend(PlayerTrade::from_model(trade));
+            }
+        }
+        for login in GdprQuery::get_logins(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+        {
+            if let Some(i) = account_for(&login.account_oid) {
+                accounts[i].logins.push(login.into());
+            }
+        }
+        for purchase in GdprQuery::get_purchases(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+        {
+            if let Some(i) = account_for(&purchase.account_oid) {
+                accounts[i].purchases.push(purchase.into());
+            }
+        }
+        for transaction in GdprQuery::get_transactions(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?
+        {
+            if let Some(i) = account_for(&transaction.account_oid) {
+                accounts[i].transactions.push(transaction.into());
+            }
+        }
+
+        *self.was_initialized.lock().unwrap() = !accounts.is_empty();
+        *self.was_restored.lock().unwrap() = true;
+        *self.accounts.lock().unwrap() = accounts;
+        Ok(())
+    }
+
+    /// Restores the stored accounts the first time they are needed after a start.
+    pub async fn ensure_restored(&self) -> Result<(), Error> {
+        if *self.was_restored.lock().unwrap() {
+            return Ok(());
+        }
+        self.restore()
+            .await
+            .map_err(|e| e.with_location(get_location!()))
+    }
+
+    pub async fn update_trade_notes(&self, id: i64, notes: Option<String>) -> Result<(), Error> {
+        let conn = DATABASE.get().unwrap();
+        let model = GdprMutation::update_trade_notes(conn, id, notes)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        let mut accounts = self.accounts.lock().unwrap();
+        for trade in accounts.iter_mut().flat_map(|a| a.trades.iter_mut()) {
+            if trade.properties.get_property_value("gdpr_id", 0i64) == model.id {
+                trade
+                    .properties
+                    .set_property_value("notes", model.notes.clone().unwrap_or_default());
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes every stored account and record.
+    pub async fn clear(&self) -> Result<(), Error> {
+        let conn = DATABASE.get().unwrap();
+        GdprMutation::delete_all(conn)
+            .await
+            .map_err(|e| e.with_location(get_location!()))?;
+        self.accounts.lock().unwrap().clear();
+        *self.was_initialized.lock().unwrap() = false;
+        *self.was_restored.lock().unwrap() = true;
+        Ok(())
+    }
+
+    /// Reads the accounts of the export, records at or before the watermark of their account are skipped.
+    fn parse(
+        file_path: &str,
+        watermarks: &HashMap<String, GdprWatermark>,
+    ) -> Result<(Vec<Account>, GdprParseStats), Error> {
         let cache = states::cache_client()?;
         // Read the file content
         let content = std::fs::read_to_string(&file_path)?;
@@ -43,8 +251,8 @@ impl WarframeGDPRModule {
             &LoggerOptions::default(),
         );
 
-        let detections = DETECTIONS.get().unwrap();
-        let detection = detections.get("en").unwrap();
+        // Switched to the pack of the account language once it is read, see `LANGUAGE :`
+        let mut detection = get_detection(DEFAULT_LANGUAGE);
         let mut current_trade: Option<PlayerTrade> = None;
         let mut current_login: Option<Login> = None;
         let mut current_purchase: Option<Purchase> = None;
@@ -64,12 +272,9 @@ impl WarframeGDPRModule {
         let platinum_re = Regex::new(r"^PREMIUM CREDITS\s*:\s*(\d+)").unwrap();
         let transaction_index_re = Regex::new(r"^(\d+)\s*:\s*$").unwrap();
 
-        let mut accounts = self.accounts.lock().unwrap();
-        accounts.clear();
-        let mut was_initialized = self.was_initialized.lock().unwrap();
-        *was_initialized = true;
-        // Start Time
-        let start = Instant::now();
+        let mut accounts: Vec<Account> = Vec::new();
+        let mut stats = GdprParseStats::default();
+        let watermark = |acc: &Account| watermarks.get(&acc.oid).cloned().unwrap_or_default();
         let mut previous_line = String::new();
         for line in lines {
             /* =======================
@@ -213,6 +418,7 @@ impl WarframeGDPRModule {
                     }
                     if line.starts_with("LANGUAGE :") {
                         acc.language = line.replace("LANGUAGE :", "").trim().to_string();
+                        detection = get_detection(&acc.language.to_lowercase());
                         continue;
                     }
                 }
@@ -227,7 +433,12 @@ impl WarframeGDPRModule {
                             if let Some(trade) = current_trade.take() {
                                 acc.add_trade(trade);
                             }
-                            current_trade = Some(PlayerTrade::default().set_time(to_date(&line)));
+                            let date = to_date(&line);
+                            if GdprWatermark::is_new(watermark(acc).trades, date) {
+                                current_trade = Some(PlayerTrade::default().set_time(date));
+                            } else {
+                                stats.skipped_records += 1;
+                            }
                         }
                     }
 
@@ -236,11 +447,16 @@ impl WarframeGDPRModule {
                             if let Some(login) = current_login.take() {
                                 acc.logins.push(login);
                             }
-                            current_login = Some(Login {
-                                date: to_date(&line),
-                                ip: None,
-                                client_type: None,
-                            });
+                            let date = to_date(&line);
+                            if GdprWatermark::is_new(watermark(acc).logins, date) {
+                                current_login = Some(Login {
+                                    date,
+                                    ip: None,
+                                    client_type: None,
+                                });
+                            } else {
+                                stats.skipped_records += 1;
+                            }
                         }
                     }
 
@@ -249,13 +465,19 @@ impl WarframeGDPRModule {
                             if let Some(purchase) = current_purchase.take() {
                                 acc.purchases.push(purchase);
                             }
-                            current_purchase = Some(Purchase {
-                                date: to_date(&line),
-                                shop_id: String::new(),
-                                price: 0,
-                                items_received: vec![],
-                            });
-                            awaiting_purchase_shop_id = true;
+                            let date = to_date(&line);
+                            if GdprWatermark::is_new(watermark(acc).purchases, date) {
+                                current_purchase = Some(Purchase {
+                                    date,
+                                    shop_id: String::new(),
+                                    price: 0,
+                                    items_received: vec![],
+                                });
+                                awaiting_purchase_shop_id = true;
+                            } else {
+                                stats.skipped_records += 1;
+                                awaiting_purchase_shop_id = false;
+                            }
                             log_section = None;
                         }
                     }
@@ -359,13 +581,13 @@ impl WarframeGDPRModule {
                                 }
                             }
                             Err(e) => {
-                                println!("Validation error: {}", e);
+                                stats.warn(format!("Validation error for {}: {}", raw, e));
                             }
                         }
                     }
 
                     if item.item_type == TradeItemType::Unknown {
-                        println!("Item not found in cache: {}", item.raw);
+                        stats.warn(format!("Item not found in cache: {}", raw));
                         item.properties.set_property_value("item_name", &item.raw);
                     }
                 }
@@ -546,38 +768,17 @@ impl WarframeGDPRModule {
             accounts.push(acc);
         }
 
-        let total_trades: usize = accounts.iter().map(|a| a.trades.len()).sum();
-        let total_logins: usize = accounts.iter().map(|a| a.logins.len()).sum();
-        let total_purchases: usize = accounts.iter().map(|a| a.purchases.len()).sum();
-        let total_transactions: usize = accounts.iter().map(|a| a.transactions.len()).sum();
-        info(
-            format!("{}:Load", COMPONENT),
-            format!(
-                "Finished loading Warframe GDPR data in: {:.2?} | Accounts: {} | Trades: {} | Logins: {} | Purchases: {} | Transactions: {}",
-                start.elapsed(),
-                accounts.len(),
-                total_trades,
-                total_logins,
-                total_purchases,
-                total_transactions,
-            ),
-            &LoggerOptions::default(),
-        );
-        notify_gui!(
-            "warframe_gdpr_data_loaded",
-            "green",
-            "success",
-            json!({
-                "accounts": accounts.len(),
-                "trades": total_trades,
-                "logins": total_logins,
-                "purchases": total_purchases,
-                "transactions": total_transactions,
-            }),
-            json!({})
-        );
-        enable_logging(true);
-        Ok(())
+        // Transactions carry their date after the index line, so they are filtered once complete
+        for acc in accounts.iter_mut() {
+            let last = watermark(acc).transactions;
+            let before = acc.transactions.len();
+            acc.transactions
+                .retain(|t| GdprWatermark::is_new(last, t.date));
+            stats.skipped_records += before - acc.transactions.len();
+        }
+
+        stats.accounts = accounts.len();
+        Ok((accounts, stats))
     }
 
     pub fn was_initialized(&self) -> bool {
@@ -588,3 +789,99 @@ impl WarframeGDPRModule {
         self.accounts.lock().unwrap().clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use entity::enums::{TransactionItemType, TransactionType};
+
+    use super::*;
+
+    fn parse_fixture() -> Vec<Account> {
+        load_fixture_cache();
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gdpr/export.txt");
+        let (accounts, _) =
+            WarframeGDPRModule::parse(path.to_str().unwrap(), &HashMap::new()).unwrap();
+        accounts
+    }
+
+    #[test]
+    fn parses_the_trades_of_the_account() {
+        let accounts = parse_fixture();
+        assert_eq!(accounts.len(), 1);
+        let account = &accounts[0];
+        assert_eq!(account.oid, "5f00000000000000000000a1");
+        assert_eq!(account.display_name, "FixtureTrader");
+
+        let types = account
+            .trades
+            .iter()
+            .map(|t| t.trade_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                TradeClassification::Sale,
+                TradeClassification::Purchase,
+                TradeClassification::Trade
+            ]
+        );
+        // The export does not name the partner
+        assert!(account.trades.iter().all(|t| t.player_name.is_empty()));
+    }
+
+    #[test]
+    fn a_sale_becomes_one_ledger_row() {
+        let accounts = parse_fixture();
+        let rows = accounts[0].trades[0].to_transactions();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].transaction_type, TransactionType::Sale);
+        assert_eq!(rows[0].item_type, TransactionItemType::Item);
+        assert_eq!(rows[0].wfm_url, "ash_prime_systems_blueprint");
+        assert_eq!(rows[0].price, 25);
+        assert_eq!(rows[0].created_at.to_rfc3339(), "2026-03-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn a_purchase_splits_the_platinum_between_the_items() {
+        let accounts = parse_fixture();
+        let rows = accounts[0].trades[1].to_transactions();
+        let prices = rows
+            .iter()
+            .map(|r| (r.wfm_url.as_str(), r.transaction_type.clone(), r.price))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            prices,
+            vec![
+                ("ash_prime_blueprint", TransactionType::Purchase, 21),
+                ("ash_prime_chassis_blueprint", TransactionType::Purchase, 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_item_for_item_trade_is_not_recorded() {
+        let accounts = parse_fixture();
+        assert!(accounts[0].trades[2].to_transactions().is_empty());
+    }
+
+    #[test]
+    fn a_riven_is_recorded_under_its_weapon() {
+        load_fixture_cache();
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/trade_replay/riven.golden.json");
+        let replayed: Vec<ReplayedTrade> =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let rows = replayed[0].trade.to_transactions();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].item_type, TransactionItemType::Riven);
+        assert_eq!(rows[0].item_name, "Torid");
+        assert_eq!(rows[0].wfm_url, "torid");
+        assert_eq!(
+            rows[0].item_unique_name,
+            "/Lotus/Weapons/Grineer/LongGuns/GrineerLauncher/GLauncher"
+        );
+        assert_eq!(rows[0].price, 300);
+    }
+}
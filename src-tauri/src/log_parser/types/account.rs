@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
+use entity::gdpr::gdpr_account;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 use crate::log_parser::{apply_item_info, TradeItem};
 
@@ -47,4 +49,25 @@ impl Account {
         }
         self.trades.push(trade);
     }
+
+    /// The account row, its records are stored in their own tables.
+    pub fn to_model(&self) -> gdpr_account::Model {
+        let mut details = json!(self);
+        if let Some(map) = details.as_object_mut() {
+            for key in ["trades", "logins", "purchases", "transactions"] {
+                map.insert(key.to_string(), json!([]));
+            }
+        }
+        gdpr_account::Model::new(&self.oid, &self.display_name, &self.email, details)
+    }
+}
+
+impl From<gdpr_account::Model> for Account {
+    fn from(model: gdpr_account::Model) -> Self {
+        let mut account: Account = serde_json::from_value(model.details).unwrap_or_default();
+        account.oid = model.oid;
+        account.email = model.email;
+        account.display_name = model.display_name;
+        account
+    }
 }
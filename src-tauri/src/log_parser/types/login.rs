@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use entity::gdpr::gdpr_login;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,3 +8,24 @@ pub struct Login {
     pub ip: Option<String>,
     pub client_type: Option<String>,
 }
+
+impl Login {
+    pub fn to_model(&self, account_oid: &str) -> gdpr_login::Model {
+        gdpr_login::Model::new(
+            account_oid,
+            self.date,
+            self.ip.clone(),
+            self.client_type.clone(),
+        )
+    }
+}
+
+impl From<gdpr_login::Model> for Login {
+    fn from(model: gdpr_login::Model) -> Self {
+        Self {
+            date: model.date,
+            ip: model.ip,
+            client_type: model.client_type,
+        }
+    }
+}
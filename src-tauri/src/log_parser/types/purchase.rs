@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use entity::gdpr::gdpr_purchase;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,4 +18,27 @@ pub struct Purchase {
     pub date: DateTime<Utc>,
     pub price: i64,
     pub items_received: Vec<PurchaseItem>,
-}
\ No newline at end of file
+}
+
+impl Purchase {
+    pub fn to_model(&self, account_oid: &str) -> gdpr_purchase::Model {
+        gdpr_purchase::Model::new(
+            account_oid,
+            self.date,
+            &self.shop_id,
+            self.price,
+            serde_json::to_value(&self.items_received).unwrap_or_default(),
+        )
+    }
+}
+
+impl From<gdpr_purchase::Model> for Purchase {
+    fn from(model: gdpr_purchase::Model) -> Self {
+        Self {
+            shop_id: model.shop_id,
+            date: model.date,
+            price: model.price,
+            items_received: serde_json::from_value(model.items).unwrap_or_default(),
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::{collections::HashMap, fmt::Display, vec};
 
-use crate::cache::CacheItemBase;
+use crate::app::NotifyLists;
+use crate::cache::{CacheItemBase, CacheState};
 use crate::enums::TradeItemType;
 use crate::{log_parser::*, utils::modules::states};
 use chrono::{DateTime, Local};
+use entity::enums::{TransactionItemType, TransactionType};
 use serde::{Deserialize, Serialize};
 use utils::Properties;
 
@@ -224,8 +226,18 @@ impl PlayerTrade {
             .collect::<Vec<String>>()
             .join("\n");
 
+        let item_icon = self
+            .offered_items
+            .iter()
+            .chain(self.received_items.iter())
+            .filter(|x| x.is_valid())
+            .find_map(|x| x.get_trade_item_info().ok())
+            .map(|info| format!("https://warframe.market/static/assets/{}", info.icon))
+            .unwrap_or_default();
+
         return HashMap::from([
             ("<TR_TYPE>".to_string(), self.trade_type.to_string()),
+            ("<ITEM_ICON>".to_string(), item_icon),
             ("<PLAYER_NAME>".to_string(), self.player_name.clone()),
             (
                 "<OF_COUNT>".to_string(),
@@ -248,11 +260,156 @@ impl PlayerTrade {
         ]);
     }
 
+    /// Items of each side of the trade for the `{{#each OF_ITEMS}}` and `{{#each RE_ITEMS}}` template sections.
+    pub fn get_notify_lists(&self) -> NotifyLists {
+        let to_entries = |classification: &TradeClassification| {
+            self.get_valid_items(classification, vec![])
+                .iter()
+                .map(|x| {
+                    let icon = x
+                        .get_trade_item_info()
+                        .map(|info| format!("https://warframe.market/static/assets/{}", info.icon))
+                        .unwrap_or_default();
+                    HashMap::from([
+                        ("<ITEM_NAME>".to_string(), x.item_name()),
+                        ("<QUANTITY>".to_string(), x.quantity.to_string()),
+                        ("<ITEM_ICON>".to_string(), icon),
+                    ])
+                })
+                .collect::<Vec<_>>()
+        };
+        HashMap::from([
+            (
+                "OF_ITEMS".to_string(),
+                to_entries(&TradeClassification::Purchase),
+            ),
+            (
+                "RE_ITEMS".to_string(),
+                to_entries(&TradeClassification::Sale),
+            ),
+        ])
+    }
+
+    /// Ledger rows of a sale or purchase, one per item with the platinum split evenly between them.
+    /// Rivens are recorded under their weapon like the stock rivens.
+    /// Item for item trades and items missing from the cache give no rows.
+    pub fn to_transactions(&self) -> Vec<entity::transaction::Model> {
+        let (side, transaction_type) = match self.trade_type {
+            TradeClassification::Sale => (TradeClassification::Purchase, TransactionType::Sale),
+            TradeClassification::Purchase => (TradeClassification::Sale, TransactionType::Purchase),
+            _ => return vec![],
+        };
+        let Ok(cache) = states::cache_client() else {
+            return vec![];
+        };
+        let items = self
+            .get_valid_items(&side, vec![TradeItemType::Platinum, TradeItemType::Credits])
+            .into_iter()
+            .filter_map(|item| LedgerItem::new(&cache, &item).map(|info| (item, info)))
+            .collect::<Vec<_>>();
+        let count = items.len() as i64;
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, (item, info))| {
+                // The first row takes the remainder and the credits so the totals stay exact
+                let first = i == 0;
+                let mut transaction = entity::transaction::Model::new(
+                    info.wfm_id,
+                    info.wfm_url,
+                    info.name,
+                    info.item_type,
+                    info.unique_name,
+                    item.sub_type,
+                    info.tags,
+                    transaction_type.clone(),
+                    item.quantity,
+                    &self.player_name,
+                    self.platinum / count + if first { self.platinum % count } else { 0 },
+                    if first { self.credits } else { 0 },
+                    None,
+                );
+                transaction.created_at = self.trade_time;
+                transaction.updated_at = self.trade_time;
+                transaction
+            })
+            .collect()
+    }
+
+    pub fn to_model(&self, account_oid: &str) -> entity::gdpr::gdpr_trade::Model {
+        entity::gdpr::gdpr_trade::Model::new(
+            account_oid,
+            self.trade_time,
+            &self.player_name,
+            self.trade_type.to_string(),
+            self.platinum,
+            self.credits,
+            serde_json::to_value(self).unwrap_or_default(),
+        )
+    }
+
+    /// Rebuilds a stored GDPR trade, its row id and notes are kept in the properties.
+    pub fn from_model(model: entity::gdpr::gdpr_trade::Model) -> Option<PlayerTrade> {
+        let mut trade: PlayerTrade = serde_json::from_value(model.trade).ok()?;
+        trade.properties.set_property_value("gdpr_id", model.id);
+        trade
+            .properties
+            .set_property_value("notes", model.notes.unwrap_or_default());
+        Some(trade)
+    }
+
     pub fn set_time(&mut self, time: DateTime<chrono::Utc>) -> PlayerTrade {
         self.trade_time = time;
         self.clone()
     }
 }
+/// What the ledger records a traded item under.
+struct LedgerItem {
+    wfm_id: String,
+    wfm_url: String,
+    name: String,
+    item_type: TransactionItemType,
+    unique_name: String,
+    tags: Vec<String>,
+}
+
+impl LedgerItem {
+    fn new(cache: &CacheState, item: &TradeItem) -> Option<Self> {
+        let is_riven = matches!(
+            item.item_type,
+            TradeItemType::RivenVeiled
+                | TradeItemType::RivenPreVeiled
+                | TradeItemType::RivenUnVeiled
+        );
+        if is_riven {
+            if let Ok(weapon) = cache.weapon().get_by(&item.unique_name) {
+                return Some(Self {
+                    wfm_id: weapon.wfm_riven_id,
+                    wfm_url: weapon.wfm_riven_url,
+                    name: weapon.name,
+                    item_type: TransactionItemType::Riven,
+                    unique_name: weapon.unique_name,
+                    tags: vec![weapon.riven_type],
+                });
+            }
+        }
+        // Veiled rivens are listed as items of their own
+        let info = cache.tradable_item().get_by(&item.unique_name).ok()?;
+        Some(Self {
+            wfm_id: info.wfm_id,
+            wfm_url: info.wfm_url,
+            name: info.name,
+            item_type: if is_riven {
+                TransactionItemType::Riven
+            } else {
+                TransactionItemType::Item
+            },
+            unique_name: item.unique_name.clone(),
+            tags: info.tags,
+        })
+    }
+}
+
 impl Display for PlayerTrade {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.player_name.is_empty() {
@@ -1,11 +1,17 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, path::PathBuf, sync::OnceLock};
 
 use regex::Regex;
-use utils::{combine_and_detect_match, DetectionStatus};
+use serde::{Deserialize, Serialize};
+use utils::{combine_and_detect_match, info, read_json_file, DetectionStatus, LoggerOptions};
 
-use crate::{enums::TradeItemType, log_parser::TradeResult};
+use crate::{enums::TradeItemType, helper, log_parser::TradeResult};
 
-#[derive(Clone, Debug)]
+pub static DEFAULT_LANGUAGE: &str = "en";
+
+/// Localized EE.log strings of one Warframe client language.
+///
+/// Packs are stored as `detections/<language>.json` with one key per field below.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TradeDetection {
     pub start: String,
     pub confirmation_line: String,
@@ -334,38 +340,209 @@ impl TradeDetection {
 
 pub static DETECTIONS: OnceLock<HashMap<String, TradeDetection>> = OnceLock::new();
 
+// Packs checked against EE.log lines of their client, see `tests/fixtures/detections`.
+// Other languages can be added as `<language>.json` in one of the `detection_folders`.
+static BUILTIN_PACKS: [(&str, &str); 2] = [
+    ("en", include_str!("../../../resources/detections/en.json")),
+    ("ru", include_str!("../../../resources/detections/ru.json")),
+];
+
+fn builtin_detections() -> HashMap<String, TradeDetection> {
+    BUILTIN_PACKS
+        .iter()
+        .map(|(language, content)| {
+            let detection = serde_json::from_str::<TradeDetection>(content)
+                .expect("Built-in trade detection packs should be valid");
+            (language.to_string(), detection)
+        })
+        .collect()
+}
+
+/// Folders searched for detection packs, later folders override earlier ones.
+pub fn detection_folders() -> Vec<PathBuf> {
+    let storage = helper::get_app_storage_path();
+    vec![
+        storage.join("cache").join("detections"),
+        storage.join("detections"),
+    ]
+}
+
+/// Loads every `<language>.json` pack in `folder`, broken packs are logged and skipped.
+fn load_detection_packs(folder: &PathBuf, detections: &mut HashMap<String, TradeDetection>) {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != "json") {
+            continue;
+        }
+        let Some(language) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match read_json_file::<TradeDetection>(&path) {
+            Ok(detection) => {
+                detections.insert(language.to_lowercase(), detection);
+            }
+            Err(e) => {
+                e.log("trade_detection.log");
+            }
+        }
+    }
+}
+
+/// Loads the built-in packs and the packs of the `detection_folders`.
+/// Has to run before the log parser starts, see `detections`.
 pub fn init_detections() {
-    DETECTIONS.get_or_init(|| {
-        let mut detections = HashMap::new();
-        detections.insert(
-            "en".to_string(),
-            TradeDetection::new(
-                "description=Are you sure you want to accept this trade? You are offering"
-                    .to_string(),
-                "description=The trade was successful!",
-                "description=The trade failed.",
-                "description=The trade was cancelled",
-                "and will receive from ",
-                " the following:",
-                "Platinum",
-                "Credits",
-                "imprint of",
-            ),
+    let mut detections = builtin_detections();
+    for folder in detection_folders() {
+        load_detection_packs(&folder, &mut detections);
+    }
+    let mut languages: Vec<&String> = detections.keys().collect();
+    languages.sort();
+    info(
+        "TradeDetection:Init",
+        format!(
+            "Loaded trade detections for: {}",
+            languages
+                .iter()
+                .map(|l| l.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        &LoggerOptions::default(),
+    );
+    if DETECTIONS.set(detections).is_err() {
+        panic!("Trade detections are already initialized");
+    }
+}
+
+/// Only the built-in packs, for tests that run without the app.
+#[cfg(test)]
+pub fn init_builtin_detections() {
+    DETECTIONS.get_or_init(builtin_detections);
+}
+
+/// The loaded packs, a lookup before `init_detections` would miss the user packs.
+fn detections() -> &'static HashMap<String, TradeDetection> {
+    DETECTIONS
+        .get()
+        .expect("init_detections has to run before trade detection")
+}
+
+/// Returns the pack of `language`, falls back to English when it is not loaded.
+pub fn get_detection(language: &str) -> TradeDetection {
+    let detections = detections();
+    detections
+        .get(language)
+        .or_else(|| detections.get(DEFAULT_LANGUAGE))
+        .cloned()
+        .expect("The English trade detection should always exist")
+}
+
+/// Finds the pack whose trade confirmation dialog matches the line.
+/// Returns the language, the pack and the detection status of the trade start.
+pub fn detect_trade_language(
+    line: &str,
+    prev_line: &str,
+    ignored_combinations: &[DetectionStatus],
+) -> Option<(String, TradeDetection, DetectionStatus)> {
+    let detections = detections();
+    // The dialog prefix is the same in every language, skip the packs for other lines.
+    let any = detections.values().next()?;
+    if !any
+        .is_dialog_line(line, prev_line, ignored_combinations)
+        .is_found()
+    {
+        return None;
+    }
+    detections.iter().find_map(|(language, detection)| {
+        let status = detection.is_beginning_of_trade(line, prev_line, ignored_combinations);
+        status
+            .is_found()
+            .then(|| (language.clone(), detection.clone(), status))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    /// Reads a trade of `tests/fixtures/detections` as (line, previous line) pairs.
+    fn fixture_lines(language: &str) -> Vec<(String, String)> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/detections")
+            .join(format!("{}.log", language));
+        let content = std::fs::read_to_string(path).unwrap();
+        let mut prev_line = String::new();
+        let mut lines = vec![];
+        for line in content.lines() {
+            lines.push((line.to_string(), prev_line.clone()));
+            prev_line = line.to_string();
+        }
+        lines
+    }
+
+    #[test]
+    fn every_builtin_pack_has_a_fixture() {
+        for (language, _) in BUILTIN_PACKS {
+            assert!(!fixture_lines(language).is_empty(), "{}", language);
+        }
+    }
+
+    #[test]
+    fn detects_the_language_of_the_fixtures() {
+        init_builtin_detections();
+        for (language, _) in BUILTIN_PACKS {
+            let detected = fixture_lines(language)
+                .iter()
+                .find_map(|(line, prev_line)| detect_trade_language(line, prev_line, &[]))
+                .unwrap_or_else(|| panic!("no trade start found in {}.log", language));
+            assert_eq!(detected.0, language);
+            assert_eq!(detected.2, DetectionStatus::Line);
+        }
+    }
+
+    #[test]
+    fn reads_the_partner_and_the_result_of_the_fixtures() {
+        init_builtin_detections();
+        for (language, partner) in [("en", "Buyer123"), ("ru", "Продавец_77")] {
+            let detection = get_detection(language);
+            let lines = fixture_lines(language);
+            let player_name = lines
+                .iter()
+                .map(|(line, prev_line)| detection.is_offer_line(line, prev_line, &[]))
+                .find(|(_, status)| status.is_found())
+                .map(|(name, _)| name)
+                .unwrap_or_else(|| panic!("no offer line found in {}.log", language));
+            assert_eq!(player_name, partner, "{}", language);
+            let result = lines
+                .iter()
+                .map(|(line, prev_line)| detection.get_trade_result(line, prev_line, &[]).1)
+                .find(|result| *result != TradeResult::Unknown);
+            assert_eq!(result, Some(TradeResult::Success), "{}", language);
+        }
+    }
+
+    #[test]
+    fn ignores_lines_that_are_not_trade_dialogs() {
+        init_builtin_detections();
+        let line = "1234.567 Sys [Info]: Dialog.lua: Dialog::CreateOk(description=Welcome back)";
+        assert!(detect_trade_language(line, "", &[]).is_none());
+        let start = get_detection("ru").start;
+        assert!(
+            detect_trade_language(&format!("1234.567 Sys [Info]: {}", start), "", &[]).is_none()
         );
-        detections.insert(
-            "ru".to_string(),
-            TradeDetection::new(
-                "description=Вы хотите принять условия сделки? Вы предлагаете",
-                "description=Обмен успешно завершён!",
-                "description=Обмен не удался.",
-                "description=Обмен был отменён",
-                "и получите от ",
-                " следующее:",
-                "Платина",
-                "Кредиты",
-                "оттиск от",
-            ),
+    }
+
+    #[test]
+    fn unknown_languages_fall_back_to_english() {
+        init_builtin_detections();
+        assert_eq!(
+            get_detection("xx").start,
+            get_detection(DEFAULT_LANGUAGE).start
         );
-        detections
-    });
+    }
 }
@@ -107,7 +107,6 @@ impl TradeItem {
         prev_line: &str,
         detection: &TradeDetection,
         ignored_combinations: &[DetectionStatus],
-        _: &CacheState,
     ) -> Result<DetectionStatus, Error> {
         // Check if the item is platinum
         let (combine, status, item_type) =
@@ -586,9 +585,18 @@ impl TradeItem {
         detection: &TradeDetection,
         ignored_combinations: &[DetectionStatus],
     ) -> Result<DetectionStatus, Error> {
-        let cache = states::cache_client().expect("Cache not found");
+        // Currencies only need the detection pack
+        let status = self.is_platinum_or_credits(
+            &self.raw.clone(),
+            prev_line,
+            detection,
+            ignored_combinations,
+        )?;
+        if status.is_found() {
+            return Ok(status);
+        }
+        let cache = states::cache_client()?;
         for check in [
-            Self::is_platinum_or_credits,
             Self::is_relic,
             Self::is_recipe,
             Self::is_arcane,
@@ -624,7 +632,7 @@ impl TradeItem {
         Trade Item Info Retrieval
     ------------------------------------------------------------- */
     pub fn get_trade_item_info(&self) -> Result<CacheTradableItem, Error> {
-        let cache = states::cache_client().expect("Cache not found");
+        let cache = states::cache_client()?;
         let info = cache.tradable_item().get_by(&self.unique_name)?;
         Ok(info)
     }
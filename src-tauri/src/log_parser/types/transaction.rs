@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use entity::gdpr::gdpr_transaction;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,3 +11,30 @@ pub struct Transaction {
     pub date: DateTime<Utc>,
     pub account: String,
 }
+
+impl Transaction {
+    pub fn to_model(&self, account_oid: &str) -> gdpr_transaction::Model {
+        gdpr_transaction::Model::new(
+            account_oid,
+            self.date,
+            &self.sku,
+            self.price,
+            &self.currency,
+            &self.vendor,
+            &self.account,
+        )
+    }
+}
+
+impl From<gdpr_transaction::Model> for Transaction {
+    fn from(model: gdpr_transaction::Model) -> Self {
+        Self {
+            sku: model.sku,
+            price: model.price,
+            currency: model.currency,
+            vendor: model.vendor,
+            date: model.date,
+            account: model.account,
+        }
+    }
+}
@@ -6,7 +6,12 @@ macro_rules! emit_event {
         use ::utils::*;
         use tauri::Emitter; // Bring the trait with `emit` into scope
         let app = APP.get().expect("App not initialized");
-        match app.emit($event_name, $payload) {
+        let payload = $payload;
+        // Mirror the event to the HTTP server event streams
+        if let Ok(value) = serde_json::to_value(&payload) {
+            crate::http_server::publish_event(&$event_name, &value);
+        }
+        match app.emit($event_name, payload) {
             Ok(_) => {
                 info(
                     &format!("Emit:{}", $log_context),
@@ -0,0 +1,702 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+use tokio::sync::Notify;
+use utils::{get_location, info, warning, BackoffStrategy, Error, LoggerOptions, RetryConfig};
+
+use crate::{helper, notify_queue::*, utils::modules::states};
+
+const COMPONENT: &str = "NotifyQueue";
+static LOG_FILE: &str = "notify_queue.log";
+// Finished messages kept for the delivery history
+const MAX_HISTORY: usize = 200;
+// How long the worker sleeps when nothing is waiting
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+
+fn get_path() -> PathBuf {
+    helper::get_app_storage_path().join("notify_queue.json")
+}
+
+/// Outbound notification messages, kept on disk until delivered.
+/// A worker posts the due messages, retries failures with backoff and waits out the rate limits the endpoints report.
+pub struct NotifyQueueState {
+    entries: Mutex<Vec<QueuedNotification>>,
+    // Url to the time the endpoint allows the next request
+    blocked_until: Mutex<HashMap<String, DateTime<Utc>>>,
+    retry: RetryConfig,
+    wake: Notify,
+    client: reqwest::Client,
+}
+
+impl NotifyQueueState {
+    pub fn new() -> Arc<Self> {
+        let entries = match Self::load() {
+            Ok(entries) => entries,
+            Err(e) => {
+                e.log(LOG_FILE);
+                vec![]
+            }
+        };
+        let state = Arc::new(Self::with_entries(entries));
+        state.clone().start_worker();
+        state
+    }
+
+    fn with_entries(entries: Vec<QueuedNotification>) -> Self {
+        Self {
+            entries: Mutex::new(entries),
+            blocked_until: Mutex::new(HashMap::new()),
+            retry: RetryConfig::new(10, 5_000, BackoffStrategy::Exponential),
+            wake: Notify::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn load() -> Result<Vec<QueuedNotification>, Error> {
+        let path = &get_path();
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let content = fs::read_to_string(path).map_err(|e| {
+            Error::from_io(
+                COMPONENT,
+                path,
+                "Failed to read notification queue",
+                e,
+                get_location!(),
+            )
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            Error::from_json(
+                COMPONENT,
+                path,
+                &content,
+                "Failed to parse notification queue",
+                e,
+                get_location!(),
+            )
+        })
+    }
+
+    fn save(&self) {
+        let path = &get_path();
+        let entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue");
+        let result = serde_json::to_string(&*entries)
+            .map_err(|e| {
+                Error::from_json(
+                    COMPONENT,
+                    path,
+                    "N/A",
+                    "Failed to serialize notification queue",
+                    e,
+                    get_location!(),
+                )
+            })
+            .and_then(|content| {
+                fs::write(path, content).map_err(|e| {
+                    Error::from_io(
+                        COMPONENT,
+                        path,
+                        "Failed to write notification queue",
+                        e,
+                        get_location!(),
+                    )
+                })
+            });
+        if let Err(e) = result {
+            e.log(LOG_FILE);
+        }
+    }
+
+    /// Queues a JSON POST and wakes the worker, returns the message id.
+    pub fn enqueue(
+        &self,
+        channel: NotifyChannel,
+        url: impl Into<String>,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> String {
+        self.enqueue_message(QueuedNotification::new(
+            channel,
+            HttpMethod::Post,
+            url,
+            headers,
+            body,
+        ))
+    }
+
+    pub fn enqueue_message(&self, message: QueuedNotification) -> String {
+        let id = message.id.clone();
+        self.entries
+            .lock()
+            .expect("Failed to lock notification queue")
+            .push(message);
+        self.save();
+        self.wake.notify_one();
+        id
+    }
+
+    /// Every queued and finished message, newest first.
+    pub fn get_all(&self) -> Vec<QueuedNotification> {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue")
+            .clone();
+        entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        entries
+    }
+
+    /// Queues a finished message again, or every failed one when no id is given.
+    /// Returns how many messages were queued.
+    pub fn resend(&self, id: Option<&str>) -> Result<usize, Error> {
+        let count = {
+            let mut entries = self.entries.lock()?;
+            match id {
+                Some(id) => {
+                    let Some(message) = entries.iter_mut().find(|m| m.id == id) else {
+                        return Err(Error::new(
+                            format!("{}:Resend", COMPONENT),
+                            format!("Notification {} not found", id),
+                            get_location!(),
+                        ));
+                    };
+                    if !message.is_finished() {
+                        return Ok(0);
+                    }
+                    message.resend();
+                    1
+                }
+                None => {
+                    let mut count = 0;
+                    for message in entries
+                        .iter_mut()
+                        .filter(|m| m.status == DeliveryStatus::Failed)
+                    {
+                        message.resend();
+                        count += 1;
+                    }
+                    count
+                }
+            }
+        };
+        if count > 0 {
+            self.save();
+            self.wake.notify_one();
+        }
+        Ok(count)
+    }
+
+    fn start_worker(self: Arc<Self>) {
+        tauri::async_runtime::spawn(async move {
+            loop {
+                self.process_due().await;
+                let wait = self.next_wait();
+                tokio::select! {
+                    _ = self.wake.notified() => {}
+                    _ = tokio::time::sleep(wait) => {}
+                }
+            }
+        });
+    }
+
+    fn next_wait(&self) -> Duration {
+        let now = Utc::now();
+        let entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue");
+        entries
+            .iter()
+            .filter(|m| !m.is_finished())
+            .map(|m| (m.next_attempt_at - now).to_std().unwrap_or(Duration::ZERO))
+            .min()
+            .unwrap_or(IDLE_WAIT)
+    }
+
+    async fn process_due(&self) {
+        let now = Utc::now();
+        let due = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue")
+            .iter()
+            .filter(|m| m.is_due(now))
+            .cloned()
+            .collect::<Vec<_>>();
+        if due.is_empty() {
+            return;
+        }
+
+        for message in due {
+            match self.get_blocked_until(&message.url) {
+                Some(until) => self.postpone(&message, until),
+                None => {
+                    let (attempt, wait) = self.post(&message).await;
+                    self.record_attempt(&message, attempt, wait);
+                }
+            }
+        }
+        self.prune();
+        self.save();
+    }
+
+    /// Moves a due message behind the rate limit of its endpoint, unless it was resent meanwhile.
+    fn postpone(&self, sent: &QueuedNotification, until: DateTime<Utc>) {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue");
+        if let Some(entry) = entries
+            .iter_mut()
+            .find(|m| m.id == sent.id && m.next_attempt_at == sent.next_attempt_at)
+        {
+            entry.next_attempt_at = until;
+        }
+    }
+
+    /// Applies an attempt to the queued message, not to the copy that was posted.
+    /// A message resent while its request was in flight keeps its new schedule and only gets the attempt in its history.
+    fn record_attempt(
+        &self,
+        sent: &QueuedNotification,
+        attempt: DeliveryAttempt,
+        wait: Option<Duration>,
+    ) {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue");
+        let Some(entry) = entries.iter_mut().find(|m| m.id == sent.id) else {
+            return;
+        };
+        if entry.status != sent.status
+            || entry.tries != sent.tries
+            || entry.next_attempt_at != sent.next_attempt_at
+        {
+            entry.attempts.push(attempt);
+            return;
+        }
+        self.apply_attempt(entry, attempt, wait);
+    }
+
+    fn get_blocked_until(&self, url: &str) -> Option<DateTime<Utc>> {
+        let blocked = self
+            .blocked_until
+            .lock()
+            .expect("Failed to lock rate limits");
+        blocked
+            .get(url)
+            .copied()
+            .filter(|until| *until > Utc::now())
+    }
+
+    async fn post(&self, message: &QueuedNotification) -> (DeliveryAttempt, Option<Duration>) {
+        let mut attempt = DeliveryAttempt {
+            at: Utc::now(),
+            status_code: None,
+            error: None,
+            retry_after_ms: None,
+        };
+        let (url, headers) = match message.resolve(saved_secret) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                attempt.error = Some(e);
+                return (attempt, None);
+            }
+        };
+        let method = match message.method {
+            HttpMethod::Post => reqwest::Method::POST,
+            HttpMethod::Put => reqwest::Method::PUT,
+        };
+        let mut request = self.client.request(method, &url).json(&message.body);
+        for (key, value) in headers.iter() {
+            request = request.header(key, value);
+        }
+        match request.send().await {
+            Ok(res) => {
+                let status = res.status();
+                let headers = res.headers().clone();
+                let body = res.text().await.unwrap_or_default();
+                let wait = rate_limit_wait(status.as_u16(), &headers, &body);
+                attempt.status_code = Some(status.as_u16());
+                attempt.retry_after_ms = wait.map(|w| w.as_millis() as u64);
+                if !status.is_success() {
+                    attempt.error = Some(body.chars().take(500).collect());
+                }
+                (attempt, wait)
+            }
+            Err(e) => {
+                attempt.error = Some(e.to_string());
+                (attempt, None)
+            }
+        }
+    }
+
+    fn apply_attempt(
+        &self,
+        message: &mut QueuedNotification,
+        attempt: DeliveryAttempt,
+        wait: Option<Duration>,
+    ) {
+        let now = Utc::now();
+        if let Some(wait) = wait {
+            self.blocked_until
+                .lock()
+                .expect("Failed to lock rate limits")
+                .insert(message.url.clone(), now + to_chrono(wait));
+        }
+        let status_code = attempt.status_code;
+        let error = attempt.error.clone().unwrap_or_default();
+        message.attempts.push(attempt);
+
+        // Other client errors mean the request itself is wrong, sending it again will not help
+        let retryable = match status_code {
+            Some(code) if (200..300).contains(&code) => {
+                message.status = DeliveryStatus::Delivered;
+                message.delivered_at = Some(now);
+                info(
+                    format!("{}:Deliver", COMPONENT),
+                    format!("Notification {} delivered", message.id),
+                    &LoggerOptions::default().set_file(LOG_FILE),
+                );
+                return;
+            }
+            Some(code) => code == 408 || code == 429 || code >= 500,
+            None => true,
+        };
+
+        message.tries += 1;
+        if !retryable || message.tries >= self.retry.max_retries {
+            message.status = DeliveryStatus::Failed;
+            Error::new(
+                format!("{}:Deliver", COMPONENT),
+                format!(
+                    "Notification {} failed after {} tries | Status: {} | {}",
+                    message.id,
+                    message.tries,
+                    status_code
+                        .map(|c| c.to_string())
+                        .unwrap_or("-".to_string()),
+                    error
+                ),
+                get_location!(),
+            )
+            .log(message.channel.log_file());
+            return;
+        }
+        let delay = wait.unwrap_or(self.retry.delay_duration(message.tries));
+        message.status = DeliveryStatus::Retrying;
+        message.next_attempt_at = now + to_chrono(delay);
+        warning(
+            format!("{}:Deliver", COMPONENT),
+            format!(
+                "Notification {} failed ({}/{}), retrying in {}s",
+                message.id,
+                message.tries,
+                self.retry.max_retries,
+                delay.as_secs()
+            ),
+            &LoggerOptions::default().set_file(LOG_FILE),
+        );
+    }
+
+    fn prune(&self) {
+        let mut entries = self
+            .entries
+            .lock()
+            .expect("Failed to lock notification queue");
+        let mut finished = entries
+            .iter()
+            .filter(|m| m.is_finished())
+            .map(|m| (m.created_at, m.id.clone()))
+            .collect::<Vec<_>>();
+        if finished.len() <= MAX_HISTORY {
+            return;
+        }
+        finished.sort();
+        let drop = finished[..finished.len() - MAX_HISTORY]
+            .iter()
+            .map(|(_, id)| id.clone())
+            .collect::<Vec<_>>();
+        entries.retain(|m| !drop.contains(&m.id));
+    }
+}
+
+/// How long the endpoint wants us to wait, from the `Retry-After` or Discord `X-RateLimit-*` headers or the error body.
+/// A successful response that used up the bucket blocks it until the reset too.
+fn rate_limit_wait(status: u16, headers: &HeaderMap, body: &str) -> Option<Duration> {
+    let header_secs = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|secs| secs.is_finite())
+    };
+    let secs = if status == 429 {
+        header_secs("retry-after")
+            .or_else(|| {
+                // Discord and Telegram put it in the body in seconds, Matrix in milliseconds
+                let body = serde_json::from_str::<Value>(body).ok()?;
+                body["retry_after"]
+                    .as_f64()
+                    .or_else(|| body["parameters"]["retry_after"].as_f64())
+                    .or_else(|| body["retry_after_ms"].as_f64().map(|ms| ms / 1000.0))
+            })
+            .or_else(|| header_secs("x-ratelimit-reset-after"))
+    } else if header_secs("x-ratelimit-remaining") == Some(0.0) {
+        header_secs("x-ratelimit-reset-after")
+    } else {
+        None
+    }?;
+    Some(Duration::from_secs_f64(secs.clamp(0.0, 3600.0)))
+}
+
+/// Finds a credential of the notification settings by the fingerprint stored with a queued message.
+fn saved_secret(fingerprint: &str) -> Option<String> {
+    let settings = states::get_settings().ok()?;
+    settings
+        .notifications
+        .secrets()
+        .into_iter()
+        .find(|secret| QueuedNotification::fingerprint(secret) == fingerprint)
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    fn message() -> QueuedNotification {
+        QueuedNotification::new(
+            NotifyChannel::Webhook,
+            HttpMethod::Post,
+            "https://example.com/hook",
+            HashMap::new(),
+            json!({ "content": "test" }),
+        )
+    }
+
+    fn attempt(status_code: Option<u16>) -> DeliveryAttempt {
+        DeliveryAttempt {
+            at: Utc::now(),
+            status_code,
+            error: None,
+            retry_after_ms: None,
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn success_marks_the_message_delivered() {
+        let queue = NotifyQueueState::with_entries(vec![]);
+        let mut message = message();
+        queue.apply_attempt(&mut message, attempt(Some(204)), None);
+        assert_eq!(message.status, DeliveryStatus::Delivered);
+        assert!(message.delivered_at.is_some());
+        assert_eq!(message.tries, 0);
+        assert_eq!(message.attempts.len(), 1);
+    }
+
+    #[test]
+    fn client_errors_fail_without_retrying() {
+        let queue = NotifyQueueState::with_entries(vec![]);
+        let mut message = message();
+        queue.apply_attempt(&mut message, attempt(Some(400)), None);
+        assert_eq!(message.status, DeliveryStatus::Failed);
+        assert_eq!(message.tries, 1);
+    }
+
+    #[test]
+    fn server_and_network_errors_are_retried_with_backoff() {
+        let queue = NotifyQueueState::with_entries(vec![]);
+        let mut message = message();
+        queue.apply_attempt(&mut message, attempt(Some(503)), None);
+        assert_eq!(message.status, DeliveryStatus::Retrying);
+        assert!(message.next_attempt_at > Utc::now());
+        let first = message.next_attempt_at;
+
+        queue.apply_attempt(&mut message, attempt(None), None);
+        assert_eq!(message.status, DeliveryStatus::Retrying);
+        assert_eq!(message.tries, 2);
+        assert!(message.next_attempt_at > first);
+    }
+
+    #[test]
+    fn gives_up_after_the_max_retries() {
+        let queue = NotifyQueueState::with_entries(vec![]);
+        let mut message = message();
+        for _ in 0..queue.retry.max_retries {
+            queue.apply_attempt(&mut message, attempt(Some(500)), None);
+        }
+        assert_eq!(message.status, DeliveryStatus::Failed);
+        assert_eq!(message.tries, queue.retry.max_retries);
+        assert!(message.is_finished());
+    }
+
+    #[test]
+    fn rate_limits_block_the_endpoint_until_the_reset() {
+        let queue = NotifyQueueState::with_entries(vec![]);
+        let mut message = message();
+        let wait = Duration::from_secs(30);
+        queue.apply_attempt(&mut message, attempt(Some(429)), Some(wait));
+        assert_eq!(message.status, DeliveryStatus::Retrying);
+        let until = queue
+            .get_blocked_until(&message.url)
+            .expect("endpoint should be blocked");
+        assert!(until > Utc::now() + chrono::Duration::seconds(25));
+        assert!(message.next_attempt_at >= until - chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    fn resend_resets_the_tries_and_keeps_the_history() {
+        let mut message = message();
+        message.status = DeliveryStatus::Failed;
+        message.tries = 3;
+        message.attempts.push(attempt(Some(500)));
+        let queue = NotifyQueueState::with_entries(vec![message.clone()]);
+        assert_eq!(queue.resend(Some(&message.id)).unwrap(), 1);
+
+        let entries = queue.get_all();
+        let entry = &entries[0];
+        assert_eq!(entry.status, DeliveryStatus::Pending);
+        assert_eq!(entry.tries, 0);
+        assert_eq!(entry.attempts.len(), 1);
+        assert!(queue.resend(Some("missing")).is_err());
+    }
+
+    #[test]
+    fn a_resend_during_the_post_keeps_its_schedule() {
+        let sent = message();
+        let queue = NotifyQueueState::with_entries(vec![sent.clone()]);
+        {
+            let mut entries = queue.entries.lock().unwrap();
+            entries[0].status = DeliveryStatus::Failed;
+            entries[0].tries = 10;
+            std::thread::sleep(Duration::from_millis(2));
+            entries[0].resend();
+        }
+        queue.record_attempt(&sent, attempt(Some(500)), None);
+
+        let entries = queue.get_all();
+        let entry = &entries[0];
+        assert_eq!(entry.status, DeliveryStatus::Pending);
+        assert_eq!(entry.tries, 0);
+        assert_eq!(entry.attempts.len(), 1);
+    }
+
+    #[test]
+    fn attempts_apply_to_the_queued_message() {
+        let sent = message();
+        let queue = NotifyQueueState::with_entries(vec![sent.clone()]);
+        queue.record_attempt(&sent, attempt(Some(200)), None);
+        assert_eq!(queue.get_all()[0].status, DeliveryStatus::Delivered);
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_finished_and_every_queued_message() {
+        let now = Utc::now();
+        let mut entries = (0..MAX_HISTORY + 5)
+            .map(|i| {
+                let mut message = message();
+                message.status = DeliveryStatus::Delivered;
+                message.created_at = now + chrono::Duration::seconds(i as i64);
+                message
+            })
+            .collect::<Vec<_>>();
+        let mut pending = message();
+        pending.created_at = now - chrono::Duration::days(1);
+        entries.push(pending.clone());
+        let oldest = entries[0].id.clone();
+        let queue = NotifyQueueState::with_entries(entries);
+
+        queue.prune();
+        let entries = queue.get_all();
+        assert_eq!(entries.len(), MAX_HISTORY + 1);
+        assert!(entries.iter().any(|m| m.id == pending.id));
+        assert!(!entries.iter().any(|m| m.id == oldest));
+    }
+
+    #[test]
+    fn reads_the_wait_from_headers_and_bodies() {
+        assert_eq!(
+            rate_limit_wait(429, &headers(&[("retry-after", "2")]), ""),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            rate_limit_wait(429, &HeaderMap::new(), r#"{"retry_after": 1.5}"#),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            rate_limit_wait(
+                429,
+                &HeaderMap::new(),
+                r#"{"parameters": {"retry_after": 7}}"#
+            ),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            rate_limit_wait(429, &HeaderMap::new(), r#"{"retry_after_ms": 250}"#),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            rate_limit_wait(
+                200,
+                &headers(&[
+                    ("x-ratelimit-remaining", "0"),
+                    ("x-ratelimit-reset-after", "3")
+                ]),
+                ""
+            ),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            rate_limit_wait(200, &headers(&[("x-ratelimit-remaining", "4")]), ""),
+            None
+        );
+    }
+
+    #[test]
+    fn ignores_waits_that_are_not_numbers() {
+        assert_eq!(
+            rate_limit_wait(429, &headers(&[("retry-after", "NaN")]), ""),
+            None
+        );
+        assert_eq!(
+            rate_limit_wait(
+                429,
+                &headers(&[("retry-after", "inf")]),
+                r#"{"retry_after": 4}"#
+            ),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(
+            rate_limit_wait(429, &headers(&[("retry-after", "-5")]), ""),
+            Some(Duration::ZERO)
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub use client::*;
+
+pub mod types;
+pub use types::*;
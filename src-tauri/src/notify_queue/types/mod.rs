@@ -0,0 +1,2 @@
+pub mod queued_notification;
+pub use queued_notification::*;
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyChannel {
+    Discord,
+    Webhook,
+    Telegram,
+    Ntfy,
+    Matrix,
+}
+
+impl NotifyChannel {
+    pub fn log_file(&self) -> &'static str {
+        match self {
+            NotifyChannel::Discord => "discord_notification.log",
+            NotifyChannel::Webhook => "webhook_notification.log",
+            NotifyChannel::Telegram => "telegram_notification.log",
+            NotifyChannel::Ntfy => "ntfy_notification.log",
+            NotifyChannel::Matrix => "matrix_notification.log",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    Post,
+    Put,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Retrying,
+    Delivered,
+    Failed,
+}
+
+/// One POST of a queued notification.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // Wait asked by the endpoint through its rate limit headers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
+}
+
+/// A credential left out of the queue file and the delivery history, e.g. a bot token.
+/// The url and headers hold the placeholder, only the SHA-256 fingerprint of the value is stored.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SecretRef {
+    pub placeholder: String,
+    pub fingerprint: String,
+}
+
+/// An outbound notification and every attempt made to deliver it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueuedNotification {
+    pub id: String,
+    pub channel: NotifyChannel,
+    #[serde(default)]
+    pub method: HttpMethod,
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<SecretRef>,
+    // Values of the secrets, never written to disk
+    #[serde(skip)]
+    secret_values: HashMap<String, String>,
+    pub status: DeliveryStatus,
+    // Failed attempts since the message was queued or resent
+    pub tries: u32,
+    pub attempts: Vec<DeliveryAttempt>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl QueuedNotification {
+    pub fn new(
+        channel: NotifyChannel,
+        method: HttpMethod,
+        url: impl Into<String>,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            channel,
+            method,
+            url: url.into(),
+            headers,
+            body,
+            secrets: vec![],
+            secret_values: HashMap::new(),
+            status: DeliveryStatus::Pending,
+            tries: 0,
+            attempts: vec![],
+            created_at: now,
+            next_attempt_at: now,
+            delivered_at: None,
+        }
+    }
+
+    /// Keeps `value` out of what is stored, `placeholder` stands for it in the url and headers.
+    pub fn with_secret(mut self, placeholder: &str, value: &str) -> Self {
+        self.secrets.push(SecretRef {
+            placeholder: placeholder.to_string(),
+            fingerprint: Self::fingerprint(value),
+        });
+        self.secret_values
+            .insert(placeholder.to_string(), value.to_string());
+        self
+    }
+
+    pub fn fingerprint(value: &str) -> String {
+        format!("{:x}", Sha256::digest(value.as_bytes()))
+    }
+
+    /// Url and headers with the secrets filled in.
+    /// After a restart the values are gone, `lookup` then finds them again by their fingerprint.
+    pub fn resolve(
+        &self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(String, HashMap<String, String>), String> {
+        let mut url = self.url.clone();
+        let mut headers = self.headers.clone();
+        for secret in self.secrets.iter() {
+            let value = self
+                .secret_values
+                .get(&secret.placeholder)
+                .cloned()
+                .or_else(|| lookup(&secret.fingerprint))
+                .ok_or_else(|| {
+                    format!(
+                        "{} is no longer in the notification settings",
+                        secret.placeholder
+                    )
+                })?;
+            url = url.replace(&secret.placeholder, &value);
+            for header in headers.values_mut() {
+                *header = header.replace(&secret.placeholder, &value);
+            }
+        }
+        Ok((url, headers))
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        matches!(
+            self.status,
+            DeliveryStatus::Pending | DeliveryStatus::Retrying
+        ) && self.next_attempt_at <= now
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            DeliveryStatus::Delivered | DeliveryStatus::Failed
+        )
+    }
+
+    /// Puts the message back in the queue, the earlier attempts stay in its history.
+    pub fn resend(&mut self) {
+        self.status = DeliveryStatus::Pending;
+        self.tries = 0;
+        self.next_attempt_at = Utc::now();
+        self.delivered_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn telegram() -> QueuedNotification {
+        QueuedNotification::new(
+            NotifyChannel::Telegram,
+            HttpMethod::Post,
+            "https://api.telegram.org/bot{bot_token}/sendMessage",
+            HashMap::from([("X-Token".to_string(), "{bot_token}".to_string())]),
+            Value::Null,
+        )
+        .with_secret("{bot_token}", "123:secret")
+    }
+
+    #[test]
+    fn secrets_are_not_serialized() {
+        let json = serde_json::to_string(&telegram()).unwrap();
+        assert!(!json.contains("123:secret"));
+        assert!(json.contains(&QueuedNotification::fingerprint("123:secret")));
+    }
+
+    #[test]
+    fn resolve_fills_in_the_secrets() {
+        let (url, headers) = telegram().resolve(|_| None).unwrap();
+        assert_eq!(url, "https://api.telegram.org/bot123:secret/sendMessage");
+        assert_eq!(headers["X-Token"], "123:secret");
+    }
+
+    #[test]
+    fn resolve_finds_lost_secrets_by_their_fingerprint() {
+        let json = serde_json::to_string(&telegram()).unwrap();
+        let loaded: QueuedNotification = serde_json::from_str(&json).unwrap();
+        let saved = ["other".to_string(), "123:secret".to_string()];
+        let lookup = |fingerprint: &str| {
+            saved
+                .iter()
+                .find(|s| QueuedNotification::fingerprint(s) == fingerprint)
+                .cloned()
+        };
+        let (url, _) = loaded.resolve(lookup).unwrap();
+        assert_eq!(url, "https://api.telegram.org/bot123:secret/sendMessage");
+        assert!(loaded.resolve(|_| None).is_err());
+    }
+}
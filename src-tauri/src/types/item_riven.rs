@@ -1,5 +1,5 @@
 use crate::cache::CacheState;
-use entity::stock_riven::RivenAttribute;
+use entity::stock_riven::{CreateStockRiven, RivenAttribute};
 use serde::{Deserialize, Serialize};
 use utils::SubType;
 use utils::{generate_uuid_from_list, get_location, Error, Properties};
@@ -73,6 +73,21 @@ impl ItemRivenBase {
         self.uuid = uuid.0.clone();
         (uuid.0, uuid.1)
     }
+    pub fn to_create(&self) -> CreateStockRiven {
+        CreateStockRiven::new(
+            self.wfm_url.clone(),
+            self.mod_name.clone(),
+            self.mastery_rank,
+            self.re_rolls,
+            self.polarity.clone(),
+            self.attributes.clone(),
+            self.sub_type
+                .clone()
+                .unwrap_or_default()
+                .rank
+                .unwrap_or_default(),
+        )
+    }
     pub fn matches_query(&self, query: &str) -> bool {
         let query = query.to_lowercase();
         self.name.to_lowercase().contains(&query) || self.mod_name.to_lowercase().contains(&query)
@@ -8,6 +8,7 @@ pub enum UIEvent {
     OnError,
     UpdateLiveScraperRunningState,
     SendLiveScraperMessage,
+    UpdateLiveScraperSchedulerStats,
     RefreshStockItems,
     RefreshCache,
     RefreshStockRivens,
@@ -28,6 +29,7 @@ impl UIEvent {
             UIEvent::OnError => "App:Error",
             UIEvent::UpdateLiveScraperRunningState => "LiveScraper:UpdateRunningState",
             UIEvent::SendLiveScraperMessage => "LiveScraper:OnMessage",
+            UIEvent::UpdateLiveScraperSchedulerStats => "LiveScraper:UpdateSchedulerStats",
             UIEvent::RefreshStockItems => "LiveScraper:RefreshStockItems",
             UIEvent::RefreshStockRivens => "LiveScraper:RefreshStockRivens",
             UIEvent::RefreshCache => "Cache:Refresh",
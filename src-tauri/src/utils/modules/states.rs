@@ -1,13 +1,15 @@
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use crate::{
     app::{AppState, Settings},
     cache::client::CacheState,
+    live_scraper::LiveScraperState,
     log_parser::LogParserState,
+    notify_queue::NotifyQueueState,
     APP, APP_ERROR,
 };
 use tauri::Manager;
-use utils::Error;
+use utils::{get_location, Error};
 
 pub fn app_state() -> Result<AppState, Error> {
     let app = APP.get().expect("APP not initialized");
@@ -22,12 +24,48 @@ pub fn get_settings() -> Result<Settings, Error> {
     Ok(settings)
 }
 
+// Item cache of the tests, they run without the app
+#[cfg(test)]
+static REPLAY_CACHE: std::sync::OnceLock<CacheState> = std::sync::OnceLock::new();
+
+#[cfg(test)]
+pub fn set_replay_cache(cache: CacheState) {
+    let _ = REPLAY_CACHE.set(cache);
+}
+
 pub fn cache_client() -> Result<CacheState, Error> {
-    let app = APP.get().expect("APP not initialized");
+    let Some(app) = APP.get() else {
+        #[cfg(test)]
+        if let Some(cache) = REPLAY_CACHE.get() {
+            return Ok(cache.clone());
+        }
+        return Err(Error::new(
+            "States:CacheClient",
+            "APP not initialized",
+            get_location!(),
+        ));
+    };
     let state = app.state::<Mutex<CacheState>>();
     let guard = state.lock()?;
     Ok(guard.clone())
 }
+pub fn live_scraper() -> Arc<LiveScraperState> {
+    let app = APP.get().expect("APP not initialized");
+    app.state::<Arc<LiveScraperState>>().inner().clone()
+}
+pub fn notify_queue() -> Result<Arc<NotifyQueueState>, Error> {
+    let app = APP
+        .get()
+        .ok_or_else(|| Error::new("States:NotifyQueue", "APP not initialized", get_location!()))?;
+    match app.try_state::<Arc<NotifyQueueState>>() {
+        Some(state) => Ok(state.inner().clone()),
+        None => Err(Error::new(
+            "States:NotifyQueue",
+            "Notification queue not initialized",
+            get_location!(),
+        )),
+    }
+}
 pub fn get_app_error() -> Option<Error> {
     let app_error = APP_ERROR.get_or_init(|| Mutex::new(None));
     let guard = app_error.lock().expect("Failed to lock APP_ERROR");
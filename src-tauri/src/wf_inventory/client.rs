@@ -126,12 +126,12 @@ impl WFInventoryState {
             .get_or_init(|| RivenModule::new(self.clone()));
     }
 
-    // pub fn item(&self) -> Arc<ItemModule> {
-    //     self.item_module
-    //         .get()
-    //         .expect("ItemModule not initialized")
-    //         .clone()
-    // }
+    pub fn item(&self) -> Arc<ItemModule> {
+        self.item_module
+            .get()
+            .expect("ItemModule not initialized")
+            .clone()
+    }
 
     pub fn riven(&self) -> Arc<RivenModule> {
         self.riven_module
@@ -139,6 +139,19 @@ impl WFInventoryState {
             .expect("RivenModule not initialized")
             .clone()
     }
+    /// Reads the data file again, the watcher is off so callers that need fresh data load it on demand.
+    pub async fn reload(&self) -> Result<(), Error> {
+        if !self.path.exists() {
+            return Err(Error::new(
+                format!("{}:Reload", COMPONENT),
+                format!("Inventory data file not found at: {}", self.path.display()),
+                get_location!(),
+            ));
+        }
+        self.on_data_file_modified(&self.path, SystemTime::now())
+            .await
+            .map_err(|e| e.with_location(get_location!()))
+    }
     async fn on_data_file_modified(&self, path: &Path, _modified: SystemTime) -> Result<(), Error> {
         let bytes = read_file(path)?;
         let data = decrypt_lastdata(&bytes).await?;
@@ -1,6 +1,15 @@
-use std::sync::{Arc, Weak};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
 
-use crate::wf_inventory::WFInventoryState;
+use utils::{Error, SubType};
+
+use crate::{
+    cache::{CacheState, CacheTradableItem},
+    utils::modules::states,
+    wf_inventory::{item_key, types::item_base::WFInvItemBase, WFInvItemRaw, WFInventoryState},
+};
 
 #[derive(Debug)]
 pub struct ItemModule {
@@ -18,4 +27,74 @@ impl ItemModule {
             client: Arc::downgrade(&client),
         })
     }
+
+    /// Returns the tradable items of the inventory, one entry per `wfm_url` and sub type.
+    /// Entries that are not on warframe.market and rivens are left out.
+    pub fn get_tradable_items(&self) -> Result<Vec<WFInvItemBase>, Error> {
+        let client = self.client.upgrade().unwrap();
+        let root = client.get_root();
+        let cache = states::cache_client()?;
+
+        let mut items: HashMap<String, WFInvItemBase> = HashMap::new();
+        let raw_items = root
+            .misc_items
+            .iter()
+            .chain(root.recipes.iter())
+            .chain(root.raw_upgrades.iter())
+            .chain(root.upgrades.iter())
+            .filter(|raw| !raw.is_riven());
+
+        for raw in raw_items {
+            let Some(item) = to_tradable_item(raw, &cache) else {
+                continue;
+            };
+            items
+                .entry(item_key(&item.wfm_url, &item.sub_type))
+                .and_modify(|entry| entry.quantity += item.quantity)
+                .or_insert(item);
+        }
+        Ok(items.into_values().collect())
+    }
+}
+
+fn to_tradable_item(raw: &WFInvItemRaw, cache: &CacheState) -> Option<WFInvItemBase> {
+    let cache_item = cache.tradable_item().get_by(&raw.unique_name).ok()?;
+    // Ranked mods and arcanes are stored one per entry without a count
+    let quantity = match raw.quantity {
+        0 if raw.id.id.is_some() => 1,
+        quantity => quantity,
+    };
+    if quantity <= 0 {
+        return None;
+    }
+
+    let mut item = WFInvItemBase {
+        id: raw.id.id.clone().unwrap_or_default(),
+        name: cache_item.name.clone(),
+        unique_name: raw.unique_name.clone(),
+        wfm_url: cache_item.wfm_url.clone(),
+        quantity,
+        sub_type: sub_type_of(raw, &cache_item),
+        ..Default::default()
+    };
+    item.properties
+        .set_property_value("wfm_id", cache_item.wfm_id.clone());
+    Some(item)
+}
+
+fn sub_type_of(raw: &WFInvItemRaw, cache_item: &CacheTradableItem) -> Option<SubType> {
+    // Relic refinements and other variants have their own unique name
+    if let Some((variant, _)) = cache_item
+        .variant_to_unique_name
+        .iter()
+        .find(|(_, unique_name)| **unique_name == raw.unique_name)
+    {
+        return Some(SubType::variant(variant));
+    }
+    match &cache_item.sub_type {
+        Some(sub_type) if sub_type.max_rank.is_some() => {
+            Some(SubType::rank(raw.get_upgrade_fingerprint().mod_rank))
+        }
+        _ => None,
+    }
 }
@@ -21,31 +21,7 @@ impl RivenModule {
         &self,
         query: WFItemPaginationDto,
     ) -> Result<PaginatedResult<WFInvItemRiven>, Error> {
-        let client = self.client.upgrade().unwrap();
-        let root = client.get_root();
-        let cache = states::cache_client()?;
-
-        let items: Vec<_> = root
-            .raw_upgrades
-            .iter()
-            .chain(root.upgrades.iter())
-            .filter(|item| item.is_riven())
-            .cloned()
-            .collect();
-
-        let mut rivens: Vec<WFInvItemRiven> = vec![];
-        for item in items.iter() {
-            match WFInvItemRiven::try_from_raw(item, &cache) {
-                Ok(riven) => rivens.push(riven),
-                Err(e) => {
-                    warning(
-                        format!("{}:ParseRiven", COMPONENT),
-                        format!("Failed to parse riven from raw item: {}", e),
-                        &LoggerOptions::default(),
-                    );
-                }
-            }
-        }
+        let mut rivens = self.get_all_rivens()?;
 
         match query.query {
             FieldChange::Value(query) => {
@@ -151,6 +127,36 @@ impl RivenModule {
         Ok(paginate)
     }
 
+    /// Parses every riven of the inventory, the ones that fail to parse are logged and skipped.
+    pub fn get_all_rivens(&self) -> Result<Vec<WFInvItemRiven>, Error> {
+        let client = self.client.upgrade().unwrap();
+        let root = client.get_root();
+        let cache = states::cache_client()?;
+
+        let items: Vec<_> = root
+            .raw_upgrades
+            .iter()
+            .chain(root.upgrades.iter())
+            .filter(|item| item.is_riven())
+            .cloned()
+            .collect();
+
+        let mut rivens: Vec<WFInvItemRiven> = vec![];
+        for item in items.iter() {
+            match WFInvItemRiven::try_from_raw(item, &cache) {
+                Ok(riven) => rivens.push(riven),
+                Err(e) => {
+                    warning(
+                        format!("{}:ParseRiven", COMPONENT),
+                        format!("Failed to parse riven from raw item: {}", e),
+                        &LoggerOptions::default(),
+                    );
+                }
+            }
+        }
+        Ok(rivens)
+    }
+
     pub fn new(client: Arc<WFInventoryState>) -> Arc<Self> {
         Arc::new(Self {
             client: Arc::downgrade(&client),
@@ -1,9 +1,13 @@
-use entity::stock_riven::RivenAttribute;
+use entity::{enums::RivenGrade, stock_riven::RivenAttribute};
 use serde::{Deserialize, Serialize};
-use utils::{get_location, Error};
+use utils::{get_location, Error, SubType};
 
 use crate::{
-    cache::CacheState,
+    cache::{
+        build_riven_attributes_from_fingerprint, build_riven_mod_name, compute_riven_endo_cost,
+        compute_riven_kuva_cost, grade_riven, lookup_riven_multipliers, normalize_polarity,
+        normalize_weapon_unique_name, CacheState,
+    },
     types::ItemRivenBase,
     wf_inventory::*,
 };
@@ -127,64 +131,59 @@ impl WFInvItemRiven {
     }
     fn populate_veiled(
         &mut self,
-        _raw: &WFInvItemRaw,
-        _fingerprint: &UpgradeFingerprint,
-        _cache: &CacheState,
+        raw: &WFInvItemRaw,
+        fingerprint: &UpgradeFingerprint,
+        cache: &CacheState,
     ) -> Result<(), Error> {
-        // let riven_cache = cache.riven();
-
-        // let weapon_key = normalize_weapon_unique_name(fingerprint.compatibility.clone());
+        let weapon_key = normalize_weapon_unique_name(fingerprint.compatibility.clone());
 
-        // let weapon = riven_cache
-        //     .get_weapon_by(&weapon_key)
-        //     .map_err(|e| e.with_location(get_location!()))?;
+        let weapon = cache
+            .weapon()
+            .get_by(&weapon_key)
+            .map_err(|e| e.with_location(get_location!()))?;
 
-        // self.base.name = weapon.name.clone();
-        // self.base.wfm_url = weapon.wfm_url_name.clone();
-        // self.base.unique_name = raw.unique_name.clone();
-        // self.base.sub_type = Some(SubType::rank(fingerprint.mod_rank));
-        // self.base
-        //     .properties
-        //     .set_property_value("disposition", weapon.disposition);
+        self.base.name = weapon.name.clone();
+        self.base.wfm_url = weapon.wfm_riven_url.clone();
+        self.base.unique_name = raw.unique_name.clone();
+        self.base.sub_type = Some(SubType::rank(fingerprint.mod_rank));
+        self.base
+            .properties
+            .set_property_value("disposition", weapon.disposition);
 
-        // let (buffs_total, curses_total) = fingerprint.riven_stat_totals();
-        // let multipliers = lookup_riven_multipliers(buffs_total, curses_total)?;
+        let (buffs_total, curses_total) = fingerprint.riven_stat_totals();
+        let multipliers = lookup_riven_multipliers(buffs_total, curses_total)?;
 
-        // self.base.attributes = build_riven_attributes_from_fingerprint(
-        //     &riven_cache,
-        //     &weapon,
-        //     fingerprint,
-        //     multipliers,
-        // )?;
+        self.base.attributes =
+            build_riven_attributes_from_fingerprint(cache, &weapon, fingerprint, multipliers)?;
 
-        // sort_attributes_for_display(&mut self.base.attributes, "raw_value");
+        sort_attributes_for_display(&mut self.base.attributes, "raw_value");
 
-        // self.base.mod_name = build_riven_mod_name(&self.base.attributes, fingerprint.buffs.len());
+        self.base.mod_name = build_riven_mod_name(&self.base.attributes, fingerprint.buffs.len());
 
-        // sort_attributes_by_polarity(&mut self.base.attributes);
+        sort_attributes_by_polarity(&mut self.base.attributes);
 
-        // let grade = weapon
-        //     .god_roll
-        //     .as_ref()
-        //     .map(|rolls| grade_riven(rolls, &self.base.attributes, "tag").0)
-        //     .unwrap_or(RivenGrade::Unknown);
-        // self.base.properties.set_property_value("grade", grade);
-        // self.base.polarity = normalize_polarity(fingerprint.polarity.clone());
+        let grade = cache
+            .riven_good_roll()
+            .get_by(&weapon.unique_name)
+            .map(|rolls| grade_riven(&rolls, &self.base.attributes, "tag").0)
+            .unwrap_or(RivenGrade::Unknown);
+        self.base.properties.set_property_value("grade", grade);
+        self.base.polarity = normalize_polarity(fingerprint.polarity.clone());
 
-        // self.base.mastery_rank = fingerprint.mastery_rank;
-        // self.base.re_rolls = fingerprint.rerolls;
+        self.base.mastery_rank = fingerprint.mastery_rank;
+        self.base.re_rolls = fingerprint.rerolls;
 
-        // let endo = compute_riven_endo_cost(
-        //     fingerprint.mastery_rank,
-        //     fingerprint.rerolls,
-        //     fingerprint.mod_rank as i32,
-        // );
-        // self.base.properties.set_property_value("endo_cost", endo);
+        let endo = compute_riven_endo_cost(
+            fingerprint.mastery_rank,
+            fingerprint.rerolls,
+            fingerprint.mod_rank as i32,
+        );
+        self.base.properties.set_property_value("endo_cost", endo);
 
-        // let kuva = compute_riven_kuva_cost(fingerprint.rerolls);
-        // self.base.properties.set_property_value("kuva_cost", kuva);
+        let kuva = compute_riven_kuva_cost(fingerprint.rerolls);
+        self.base.properties.set_property_value("kuva_cost", kuva);
 
-        // self.base.update_uuid();
+        self.base.update_uuid();
         Ok(())
     }
 }
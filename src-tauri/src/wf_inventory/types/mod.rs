@@ -20,3 +20,6 @@ pub use item_riven::*;
 
 pub mod affiliation;
 pub use affiliation::*;
+
+pub mod reconciliation;
+pub use reconciliation::*;
@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use entity::{stock_item, stock_riven};
+use serde::{Deserialize, Serialize};
+use utils::SubType;
+
+use crate::{
+    types::ItemRivenBase,
+    wf_inventory::{types::item_base::WFInvItemBase, RivenState, WFInvItemRiven},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationKind {
+    /// In the inventory but not in stock
+    MissingInStock,
+    /// Stock holds more than the inventory, or the stock entry is gone from the inventory
+    OverCounted,
+    /// Stock holds less than the inventory
+    UnderCounted,
+    /// A riven with revealed stats that is not in the stock rivens,
+    /// `RivenState::Veiled` in the inventory data
+    UnveiledNotInStock,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ItemReconciliation {
+    pub kind: ReconciliationKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_id: Option<i64>,
+    pub wfm_url: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_type: Option<SubType>,
+    pub inventory_quantity: i64,
+    pub stock_quantity: i64,
+}
+
+impl ItemReconciliation {
+    /// Units to add to the stock, negative when the stock has to shrink.
+    pub fn difference(&self) -> i64 {
+        self.inventory_quantity - self.stock_quantity
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RivenReconciliation {
+    pub kind: ReconciliationKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_id: Option<i64>,
+    pub uuid: String,
+    pub weapon_name: String,
+    pub mod_name: String,
+    /// The inventory riven, set for `UnveiledNotInStock`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub riven: Option<ItemRivenBase>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct ReconciliationReport {
+    pub items: Vec<ItemReconciliation>,
+    pub rivens: Vec<RivenReconciliation>,
+    /// Rivens of the inventory that could not be compared, e.g. not unveiled yet
+    pub skipped_rivens: i64,
+}
+
+impl ReconciliationReport {
+    pub fn new(
+        inventory_items: &[WFInvItemBase],
+        inventory_rivens: &[WFInvItemRiven],
+        stock_items: &[stock_item::Model],
+        stock_rivens: &[stock_riven::Model],
+    ) -> Self {
+        let mut report = Self::default();
+        report.compare_items(inventory_items, stock_items);
+        report.compare_rivens(inventory_rivens, stock_rivens);
+        report
+    }
+
+    fn compare_items(&mut self, inventory: &[WFInvItemBase], stock: &[stock_item::Model]) {
+        let mut stock_by_key: HashMap<String, &stock_item::Model> = stock
+            .iter()
+            .map(|item| (item_key(&item.wfm_url, &item.sub_type), item))
+            .collect();
+
+        for item in inventory {
+            let stock_item = stock_by_key.remove(&item_key(&item.wfm_url, &item.sub_type));
+            let stock_quantity = stock_item.map(|s| s.owned).unwrap_or(0);
+            let kind = match stock_item {
+                None => ReconciliationKind::MissingInStock,
+                Some(_) if stock_quantity > item.quantity => ReconciliationKind::OverCounted,
+                Some(_) if stock_quantity < item.quantity => ReconciliationKind::UnderCounted,
+                Some(_) => continue,
+            };
+            self.items.push(ItemReconciliation {
+                kind,
+                stock_id: stock_item.map(|s| s.id),
+                wfm_url: item.wfm_url.clone(),
+                name: item.name.clone(),
+                sub_type: item.sub_type.clone(),
+                inventory_quantity: item.quantity,
+                stock_quantity,
+            });
+        }
+
+        // Whatever is left is in stock but no longer in the inventory
+        let mut leftovers: Vec<_> = stock_by_key.into_values().filter(|s| s.owned > 0).collect();
+        leftovers.sort_by_key(|s| s.id);
+        for item in leftovers {
+            self.items.push(ItemReconciliation {
+                kind: ReconciliationKind::OverCounted,
+                stock_id: Some(item.id),
+                wfm_url: item.wfm_url.clone(),
+                name: item.item_name.clone(),
+                sub_type: item.sub_type.clone(),
+                inventory_quantity: 0,
+                stock_quantity: item.owned,
+            });
+        }
+    }
+
+    fn compare_rivens(&mut self, inventory: &[WFInvItemRiven], stock: &[stock_riven::Model]) {
+        // Only rolled rivens carry the stats the stock uuid is built from
+        let (rolled, skipped): (Vec<_>, Vec<_>) = inventory
+            .iter()
+            .partition(|r| r.riven_type == RivenState::Veiled && !r.base.uuid.is_empty());
+        self.skipped_rivens = skipped.len() as i64;
+
+        let mut stock_by_uuid: HashMap<&str, &stock_riven::Model> =
+            stock.iter().map(|r| (r.uuid.as_str(), r)).collect();
+
+        for riven in rolled {
+            if stock_by_uuid.remove(riven.base.uuid.as_str()).is_some() {
+                continue;
+            }
+            self.rivens.push(RivenReconciliation {
+                kind: ReconciliationKind::UnveiledNotInStock,
+                stock_id: None,
+                uuid: riven.base.uuid.clone(),
+                weapon_name: riven.base.name.clone(),
+                mod_name: riven.base.mod_name.clone(),
+                riven: Some(riven.base.clone()),
+            });
+        }
+
+        // A rolled riven without uuid could be any stock riven of its weapon, those are not reported.
+        // Without a weapon it could be any of them.
+        let unmatched: Vec<&str> = skipped
+            .iter()
+            .filter(|r| r.riven_type == RivenState::Veiled)
+            .map(|r| r.base.wfm_url.as_str())
+            .collect();
+        if unmatched.contains(&"") {
+            return;
+        }
+        let unmatched: HashSet<&str> = unmatched.into_iter().collect();
+        let mut leftovers: Vec<_> = stock_by_uuid
+            .into_values()
+            .filter(|r| !unmatched.contains(r.wfm_weapon_url.as_str()))
+            .collect();
+        leftovers.sort_by_key(|r| r.id);
+        for riven in leftovers {
+            self.rivens.push(RivenReconciliation {
+                kind: ReconciliationKind::OverCounted,
+                stock_id: Some(riven.id),
+                uuid: riven.uuid.clone(),
+                weapon_name: riven.weapon_name.clone(),
+                mod_name: riven.mod_name.clone(),
+                riven: None,
+            });
+        }
+    }
+}
+
+/// Stock items are unique per `wfm_url` and sub type.
+pub(crate) fn item_key(wfm_url: &str, sub_type: &Option<SubType>) -> String {
+    format!(
+        "{}|{}",
+        wfm_url,
+        sub_type.clone().unwrap_or_default().shot_display()
+    )
+}
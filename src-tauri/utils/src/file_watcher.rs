@@ -189,40 +189,7 @@ impl FileWatcher {
                     if self.skip_empty_lines && line.is_empty() {
                         continue;
                     }
-                    // Build the entry and immediately drop both locks so that
-                    // handlers calling back into the watcher (e.g. get_all_cached_lines,
-                    // set_path) do not deadlock.
-                    let entry = {
-                        let cache = self.cache.lock().unwrap();
-                        let prev = self.prev_line.lock().unwrap();
-                        let prev_line_str = prev.as_deref().unwrap_or("");
-                        LineEntry::new(
-                            cache.len() + 1,
-                            line.clone(),
-                            prev_line_str.to_string(),
-                            prev_detection.clone(),
-                        )
-                    }; // cache and prev locks released here
-
-                    let mut handlers = self.handlers.lock().unwrap();
-                    for handler in handlers.iter_mut() {
-                        match handler.process_line(&entry) {
-                            Ok((break_loop, combined)) => {
-                                prev_detection = combined;
-                                if break_loop {
-                                    break;
-                                }
-                            }
-                            Err(e) => {
-                                println!("Error processing line in handler: {}", e);
-                            }
-                        }
-                    }
-                    drop(handlers); // release handlers lock before re-locking cache
-
-                    // Add line to cache
-                    self.cache.lock().unwrap().push(entry);
-                    *self.prev_line.lock().unwrap() = Some(line);
+                    self.dispatch_line(line, &mut prev_detection);
                 }
                 if current_file_size != 0 {
                     *pos = current_file_size;
@@ -240,6 +207,56 @@ impl FileWatcher {
             thread::sleep(Duration::from_millis(1));
         }
     }
+    /// Runs one line through the handler chain, then caches it.
+    fn dispatch_line(&self, line: String, prev_detection: &mut DetectionStatus) {
+        // Build the entry and immediately drop both locks so that
+        // handlers calling back into the watcher (e.g. get_all_cached_lines,
+        // set_path) do not deadlock.
+        let entry = {
+            let cache = self.cache.lock().unwrap();
+            let prev = self.prev_line.lock().unwrap();
+            let prev_line_str = prev.as_deref().unwrap_or("");
+            LineEntry::new(
+                cache.len() + 1,
+                line.clone(),
+                prev_line_str.to_string(),
+                prev_detection.clone(),
+            )
+        }; // cache and prev locks released here
+
+        let mut handlers = self.handlers.lock().unwrap();
+        for handler in handlers.iter_mut() {
+            match handler.process_line(&entry) {
+                Ok((break_loop, combined)) => {
+                    *prev_detection = combined;
+                    if break_loop {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    e.with_location(get_location!()).log("file_watcher.log");
+                }
+            }
+        }
+        drop(handlers); // release handlers lock before re-locking cache
+
+        // Add line to cache
+        self.cache.lock().unwrap().push(entry);
+        *self.prev_line.lock().unwrap() = Some(line);
+    }
+
+    /// Feeds recorded lines through the handlers synchronously, exactly like `watch` does
+    /// for lines appended to the file. The watcher should be fresh, its cache is kept.
+    pub fn replay(&self, lines: impl IntoIterator<Item = String>) {
+        let mut prev_detection = DetectionStatus::None;
+        for line in lines {
+            if self.skip_empty_lines && line.is_empty() {
+                continue;
+            }
+            self.dispatch_line(line, &mut prev_detection);
+        }
+    }
+
     pub fn get_cached_lines_between(&self, mut start: usize, mut end: usize) -> Vec<LineEntry> {
         let cache = self.cache.lock().unwrap();
         if start < 1 {
@@ -264,7 +281,6 @@ impl FileWatcher {
     fn read_lines_lossy(&self, mut reader: BufReader<File>) -> Result<Vec<String>, Error> {
         use std::io::Read;
 
-        let mut lines = Vec::new();
         let mut buffer = Vec::new();
 
         // Read all remaining bytes
@@ -280,29 +296,7 @@ impl FileWatcher {
             }
         }
 
-        if buffer.is_empty() {
-            return Ok(lines);
-        }
-
-        // Convert to string with lossy UTF-8 conversion
-        // This will replace invalid UTF-8 sequences with � (replacement character)
-        let content = String::from_utf8_lossy(&buffer);
-
-        // Split into lines
-        for line in content.lines() {
-            // Skip lines that contain only replacement characters (likely corrupted)
-            if !line.chars().all(|c| c == '�') || line.is_empty() {
-                lines.push(line.to_string());
-            } else {
-                trace(
-                    "FileWatcher",
-                    &format!("Skipping corrupted line with invalid UTF-8: {}", line),
-                    &LoggerOptions::default(),
-                );
-            }
-        }
-
-        Ok(lines)
+        Ok(split_lines_lossy(&buffer))
     }
 
     /// Alternative method for reading lines in chunks (more memory efficient for large files)
@@ -372,6 +366,33 @@ impl FileWatcher {
     }
 }
 
+/// Splits raw log bytes into lines, replacing invalid UTF-8 and dropping corrupted lines.
+pub fn split_lines_lossy(buffer: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    if buffer.is_empty() {
+        return lines;
+    }
+
+    // Convert to string with lossy UTF-8 conversion
+    // This will replace invalid UTF-8 sequences with � (replacement character)
+    let content = String::from_utf8_lossy(buffer);
+
+    // Split into lines
+    for line in content.lines() {
+        // Skip lines that contain only replacement characters (likely corrupted)
+        if !line.chars().all(|c| c == '�') || line.is_empty() {
+            lines.push(line.to_string());
+        } else {
+            trace(
+                "FileWatcher",
+                &format!("Skipping corrupted line with invalid UTF-8: {}", line),
+                &LoggerOptions::default(),
+            );
+        }
+    }
+    lines
+}
+
 impl Display for LineEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut items: Vec<String> = vec![];
@@ -396,3 +417,53 @@ impl Display for LineEntry {
         write!(f, "{}", items.join(" | "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Recorder(Arc<Mutex<Vec<(String, String, DetectionStatus)>>>);
+
+    impl LineHandler for Recorder {
+        fn process_line(&mut self, entry: &LineEntry) -> Result<(bool, DetectionStatus), Error> {
+            self.0.lock().unwrap().push((
+                entry.line.clone(),
+                entry.prev_line.clone(),
+                entry.prev_detection.clone(),
+            ));
+            let status = if entry.line.starts_with("match") {
+                DetectionStatus::Line
+            } else {
+                DetectionStatus::None
+            };
+            Ok((false, status))
+        }
+    }
+
+    #[test]
+    fn replay_feeds_lines_like_watch() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let watcher = FileWatcher::new("");
+        watcher.add_handler(Box::new(Recorder(seen.clone())));
+        watcher.replay(split_lines_lossy(b"first\n\nmatch\nlast\n"));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(
+            *seen,
+            vec![
+                ("first".to_string(), "".to_string(), DetectionStatus::None),
+                (
+                    "match".to_string(),
+                    "first".to_string(),
+                    DetectionStatus::None
+                ),
+                (
+                    "last".to_string(),
+                    "match".to_string(),
+                    DetectionStatus::Line
+                ),
+            ]
+        );
+        assert_eq!(watcher.get_all_cached_lines().len(), 3);
+    }
+}
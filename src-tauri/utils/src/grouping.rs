@@ -1,4 +1,8 @@
-use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
 use std::{collections::HashMap, hash::Hash};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
@@ -119,6 +123,20 @@ fn generate_date_key(dt: NaiveDateTime, levels: &[GroupByDate]) -> String {
     parts.join("-")
 }
 
+/// SQLite `strftime` format producing the same keys as `group_by_date` for the levels
+pub fn sql_date_format(levels: &[GroupByDate]) -> String {
+    levels
+        .iter()
+        .map(|level| match level {
+            GroupByDate::Year => "%Y",
+            GroupByDate::Month => "%m",
+            GroupByDate::Day => "%d",
+            GroupByDate::Hour => "%H:00",
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 pub fn get_start_end_of(
     datetime: DateTime<Utc>,
     group: GroupByDate,
@@ -180,3 +198,311 @@ pub fn get_start_end_of(
         Utc.from_utc_datetime(&end_naive),
     )
 }
+
+/// Timezone reporting periods are cut in and graph labels are produced in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportTimezone {
+    /// The timezone of the system, following its daylight saving time.
+    #[default]
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+    /// An IANA timezone like `Europe/Berlin`, following its daylight saving time.
+    Named(Tz),
+}
+impl ReportTimezone {
+    /// Parses `local` (or nothing), `utc`, an offset like `-05:00` or an IANA name like `Europe/Berlin`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        match value.to_lowercase().as_str() {
+            "" | "local" => return Ok(ReportTimezone::Local),
+            "utc" => return Ok(ReportTimezone::Utc),
+            _ => {}
+        }
+        if let Ok(offset) = value.parse::<FixedOffset>() {
+            return Ok(ReportTimezone::Fixed(offset));
+        }
+        value.parse::<Tz>().map(ReportTimezone::Named).map_err(|_| {
+            format!(
+                "Unknown timezone '{}', use local, utc, an offset like -05:00 or a name like Europe/Berlin",
+                value
+            )
+        })
+    }
+
+    /// Wall clock time of `datetime` in this timezone.
+    pub fn to_naive(&self, datetime: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            ReportTimezone::Local => datetime.with_timezone(&chrono::Local).naive_local(),
+            ReportTimezone::Utc => datetime.naive_utc(),
+            ReportTimezone::Fixed(offset) => datetime.with_timezone(offset).naive_local(),
+            ReportTimezone::Named(tz) => datetime.with_timezone(tz).naive_local(),
+        }
+    }
+
+    /// Instant of a wall clock time in this timezone, the earliest one around daylight saving changes.
+    pub fn to_utc(&self, naive: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            ReportTimezone::Local => earliest_utc(&chrono::Local, naive),
+            ReportTimezone::Utc => Utc.from_utc_datetime(&naive),
+            ReportTimezone::Fixed(offset) => {
+                Utc.from_utc_datetime(&(naive - Duration::seconds(offset.local_minus_utc() as i64)))
+            }
+            ReportTimezone::Named(tz) => earliest_utc(tz, naive),
+        }
+    }
+
+    /// SQLite date modifier moving a UTC timestamp into this timezone.
+    /// None for a named timezone, SQLite has no timezone database to follow its daylight saving time.
+    pub fn sql_modifier(&self) -> Option<String> {
+        match self {
+            ReportTimezone::Local => Some("localtime".to_string()),
+            ReportTimezone::Utc => Some("+0 seconds".to_string()),
+            ReportTimezone::Fixed(offset) => {
+                Some(format!("{:+} seconds", offset.local_minus_utc()))
+            }
+            ReportTimezone::Named(_) => None,
+        }
+    }
+
+    /// `datetime` with its wall clock time in this timezone stored as UTC, for the naive helpers above.
+    fn wall_clock(&self, datetime: DateTime<Utc>) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&self.to_naive(datetime))
+    }
+}
+
+/// Earliest instant of a wall clock time, a time skipped by daylight saving is taken an hour later.
+fn earliest_utc<T: TimeZone>(timezone: &T, naive: NaiveDateTime) -> DateTime<Utc> {
+    timezone
+        .from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+/// How reports cut time into periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportPeriods {
+    pub timezone: ReportTimezone,
+    pub week_start: Weekday,
+    /// Days start at the Warframe daily reset (00:00 UTC) instead of midnight in `timezone`.
+    pub daily_reset: bool,
+}
+impl Default for ReportPeriods {
+    fn default() -> Self {
+        ReportPeriods {
+            timezone: ReportTimezone::Local,
+            week_start: Weekday::Mon,
+            daily_reset: false,
+        }
+    }
+}
+impl ReportPeriods {
+    /// `week_start` counts days from Sunday like JavaScript's `Date.getDay()`.
+    pub fn new(timezone: &str, week_start: u32, daily_reset: bool) -> Self {
+        let mut weekday = Weekday::Sun;
+        for _ in 0..week_start % 7 {
+            weekday = weekday.succ();
+        }
+        ReportPeriods {
+            timezone: ReportTimezone::parse(timezone).unwrap_or_default(),
+            week_start: weekday,
+            daily_reset,
+        }
+    }
+
+    /// Timezone a period of `group` is cut in, days follow the daily reset when enabled.
+    pub fn timezone_of(&self, group: GroupByDate) -> ReportTimezone {
+        if self.daily_reset && group == GroupByDate::Day {
+            ReportTimezone::Utc
+        } else {
+            self.timezone
+        }
+    }
+
+    /// Timezone of date keys, decided by their most granular level.
+    pub fn key_timezone(&self, levels: &[GroupByDate]) -> ReportTimezone {
+        self.timezone_of(levels.iter().max().copied().unwrap_or(GroupByDate::Year))
+    }
+
+    /// Like `get_start_end_of`, with the boundaries in the reporting timezone.
+    pub fn start_end_of(
+        &self,
+        datetime: DateTime<Utc>,
+        group: GroupByDate,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let timezone = self.timezone_of(group);
+        let (start, end) = get_start_end_of(timezone.wall_clock(datetime), group);
+        (
+            timezone.to_utc(start.naive_utc()),
+            timezone.to_utc(end.naive_utc()),
+        )
+    }
+
+    /// Start and end of the week around `datetime`, its days cut like `GroupByDate::Day`.
+    pub fn week_of(&self, datetime: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let timezone = self.timezone_of(GroupByDate::Day);
+        let date = timezone.to_naive(datetime).date();
+        let days = (7 + date.weekday().num_days_from_monday()
+            - self.week_start.num_days_from_monday())
+            % 7;
+        let start = (date - Duration::days(days as i64))
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let end = start + Duration::days(7) - Duration::seconds(1);
+        (timezone.to_utc(start), timezone.to_utc(end))
+    }
+
+    /// Date key of `datetime` in the reporting timezone.
+    pub fn date_key(&self, datetime: DateTime<Utc>, levels: &[GroupByDate]) -> String {
+        generate_date_key(self.key_timezone(levels).to_naive(datetime), levels)
+    }
+
+    /// Like `fill_missing_date_keys`, with the keys in the reporting timezone.
+    pub fn fill_missing_date_keys<T>(
+        &self,
+        grouped: &mut HashMap<String, Vec<T>>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        levels: &[GroupByDate],
+    ) {
+        let timezone = self.key_timezone(levels);
+        fill_missing_date_keys(
+            grouped,
+            timezone.wall_clock(start),
+            timezone.wall_clock(end),
+            levels,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(ReportTimezone::parse(""), Ok(ReportTimezone::Local));
+        assert_eq!(ReportTimezone::parse(" Local "), Ok(ReportTimezone::Local));
+        assert_eq!(ReportTimezone::parse("UTC"), Ok(ReportTimezone::Utc));
+        assert_eq!(
+            ReportTimezone::parse("-05:00"),
+            Ok(ReportTimezone::Fixed(
+                FixedOffset::west_opt(5 * 3600).unwrap()
+            ))
+        );
+        assert_eq!(
+            ReportTimezone::parse("Europe/Berlin"),
+            Ok(ReportTimezone::Named(Tz::Europe__Berlin))
+        );
+        assert!(ReportTimezone::parse("Mars/Olympus_Mons").is_err());
+        assert!(ReportTimezone::parse("+25:00").is_err());
+    }
+
+    #[test]
+    fn test_invalid_timezone_falls_back_to_local() {
+        let periods = ReportPeriods::new("nowhere", 1, false);
+        assert_eq!(periods.timezone, ReportTimezone::Local);
+    }
+
+    #[test]
+    fn test_start_end_of_fixed_offset() {
+        let periods = ReportPeriods::new("+02:00", 1, false);
+        // 01:30 on the 11th at +02:00
+        let (start, end) = periods.start_end_of(utc(2026, 3, 10, 23, 30, 0), GroupByDate::Day);
+        assert_eq!(start, utc(2026, 3, 10, 22, 0, 0));
+        assert_eq!(end, utc(2026, 3, 11, 21, 59, 59));
+    }
+
+    #[test]
+    fn test_start_end_of_named_across_daylight_saving() {
+        let periods = ReportPeriods::new("Europe/Berlin", 1, false);
+        // March starts in winter time (+01:00) and ends in summer time (+02:00)
+        let (start, end) = periods.start_end_of(utc(2026, 3, 15, 12, 0, 0), GroupByDate::Month);
+        assert_eq!(start, utc(2026, 2, 28, 23, 0, 0));
+        assert_eq!(end, utc(2026, 3, 31, 21, 59, 59));
+        // The clocks move from 02:00 to 03:00 on the 29th, the day is 23 hours long
+        let (start, end) = periods.start_end_of(utc(2026, 3, 29, 12, 0, 0), GroupByDate::Day);
+        assert_eq!(start, utc(2026, 3, 28, 23, 0, 0));
+        assert_eq!(end, utc(2026, 3, 29, 21, 59, 59));
+    }
+
+    #[test]
+    fn test_week_of_week_start() {
+        // 2026-10-18 is a Sunday
+        let sunday = utc(2026, 10, 18, 12, 0, 0);
+
+        let monday_weeks = ReportPeriods::new("utc", 1, false);
+        assert_eq!(
+            monday_weeks.week_of(sunday),
+            (utc(2026, 10, 12, 0, 0, 0), utc(2026, 10, 18, 23, 59, 59))
+        );
+
+        let sunday_weeks = ReportPeriods::new("utc", 0, false);
+        assert_eq!(
+            sunday_weeks.week_of(sunday),
+            (utc(2026, 10, 18, 0, 0, 0), utc(2026, 10, 24, 23, 59, 59))
+        );
+    }
+
+    #[test]
+    fn test_daily_reset() {
+        // 22:00 on the 17th at -05:00, already the 18th at the daily reset
+        let datetime = utc(2026, 10, 18, 3, 0, 0);
+
+        let midnight = ReportPeriods::new("-05:00", 1, false);
+        assert_eq!(
+            midnight.start_end_of(datetime, GroupByDate::Day),
+            (utc(2026, 10, 17, 5, 0, 0), utc(2026, 10, 18, 4, 59, 59))
+        );
+        assert_eq!(midnight.date_key(datetime, &[GroupByDate::Day]), "17");
+
+        let reset = ReportPeriods::new("-05:00", 1, true);
+        assert_eq!(
+            reset.start_end_of(datetime, GroupByDate::Day),
+            (utc(2026, 10, 18, 0, 0, 0), utc(2026, 10, 18, 23, 59, 59))
+        );
+        assert_eq!(reset.date_key(datetime, &[GroupByDate::Day]), "18");
+        // Weeks are made of reset days, months stay in the timezone
+        assert_eq!(reset.week_of(datetime).0, utc(2026, 10, 12, 0, 0, 0));
+        assert_eq!(
+            reset.start_end_of(datetime, GroupByDate::Month).0,
+            utc(2026, 10, 1, 5, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_sql_modifier() {
+        assert_eq!(
+            ReportTimezone::Utc.sql_modifier().as_deref(),
+            Some("+0 seconds")
+        );
+        assert_eq!(
+            ReportTimezone::parse("-05:00")
+                .unwrap()
+                .sql_modifier()
+                .as_deref(),
+            Some("-18000 seconds")
+        );
+        assert_eq!(
+            ReportTimezone::Local.sql_modifier().as_deref(),
+            Some("localtime")
+        );
+        assert_eq!(
+            ReportTimezone::parse("Europe/Berlin")
+                .unwrap()
+                .sql_modifier(),
+            None
+        );
+    }
+}
@@ -13,6 +13,7 @@ pub mod retry_utils;
 pub mod sorting;
 pub mod stop_watch;
 pub mod sub_type;
+pub mod table_export;
 pub mod zip_folder;
 pub mod zip_logger;
 
@@ -30,5 +31,6 @@ pub use retry_utils::*;
 pub use sorting::*;
 pub use stop_watch::*;
 pub use sub_type::*;
+pub use table_export::*;
 pub use zip_folder::*;
 pub use zip_logger::*;
@@ -36,7 +36,8 @@ impl RetryConfig {
         }
     }
 
-    fn delay_duration(&self, attempt: u32) -> Duration {
+    /// Time to wait after the given failed attempt, attempts start at 1.
+    pub fn delay_duration(&self, attempt: u32) -> Duration {
         Duration::from_millis(self.delay_ms(attempt))
     }
 
@@ -0,0 +1,507 @@
+use crate::{Error, get_location};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::io::{Cursor, Write};
+use zip::{CompressionMethod, ZipWriter, write::FileOptions};
+
+/// File format of a data export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Xlsx,
+    Ods,
+}
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Ods => "ods",
+        }
+    }
+
+    /// MIME type of the exported file.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            ExportFormat::Ods => ODS_MIMETYPE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Flattened column names like `sub_type.rank`, every column when empty.
+    #[serde(default)]
+    pub columns: Vec<String>,
+}
+
+/// Rows of flattened items, nested objects become `parent.child` columns.
+#[derive(Debug, Clone, Default)]
+pub struct ExportTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl ExportTable {
+    /// Flattens the serialized items, keeping `columns` in their order or every column in order of appearance.
+    pub fn from_items<T: Serialize>(items: &[T], columns: &[String]) -> Result<Self, Error> {
+        let mut flattened = Vec::with_capacity(items.len());
+        for item in items {
+            let value = serde_json::to_value(item).map_err(|e| {
+                Error::new(
+                    "ExportTable",
+                    format!("Failed to serialize item: {}", e),
+                    get_location!(),
+                )
+            })?;
+            let mut row = Map::new();
+            flatten_value("", value, &mut row);
+            flattened.push(row);
+        }
+
+        let columns = if columns.is_empty() {
+            let mut all: Vec<String> = Vec::new();
+            for row in &flattened {
+                for key in row.keys() {
+                    if !all.contains(key) {
+                        all.push(key.clone());
+                    }
+                }
+            }
+            all
+        } else {
+            columns.to_vec()
+        };
+        let rows = flattened
+            .into_iter()
+            .map(|mut row| {
+                columns
+                    .iter()
+                    .map(|column| row.remove(column).unwrap_or(Value::Null))
+                    .collect()
+            })
+            .collect();
+        Ok(ExportTable { columns, rows })
+    }
+
+    /// Array of flat objects with the selected columns.
+    pub fn to_json(&self) -> Result<String, Error> {
+        let items: Vec<Map<String, Value>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned())
+                    .collect()
+            })
+            .collect();
+        serde_json::to_string_pretty(&items).map_err(|e| {
+            Error::new(
+                "ExportTable",
+                format!("Failed to serialize rows to JSON: {}", e),
+                get_location!(),
+            )
+        })
+    }
+
+    /// RFC 4180 CSV, with a byte order mark so spreadsheet apps read it as UTF-8.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("\u{feff}");
+        let header: Vec<String> = self.columns.iter().map(|c| csv_field(c)).collect();
+        csv.push_str(&header.join(","));
+        csv.push_str("\r\n");
+        for row in &self.rows {
+            let fields: Vec<String> = row.iter().map(|v| csv_field(&cell_text(v))).collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+
+    /// Office Open XML workbook with a single sheet.
+    pub fn to_xlsx(&self, sheet_name: &str) -> Result<Vec<u8>, Error> {
+        let mut sheet = String::from(XML_HEADER);
+        sheet.push_str(r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#);
+        let header: Vec<Value> = self.columns.iter().cloned().map(Value::String).collect();
+        for (index, row) in std::iter::once(&header).chain(&self.rows).enumerate() {
+            let number = index + 1;
+            sheet.push_str(&format!(r#"<row r="{}">"#, number));
+            for (column, value) in row.iter().enumerate() {
+                let reference = format!("{}{}", column_letters(column), number);
+                match value {
+                    Value::Null => {}
+                    Value::Bool(b) => sheet.push_str(&format!(
+                        r#"<c r="{}" t="b"><v>{}</v></c>"#,
+                        reference, *b as u8
+                    )),
+                    Value::Number(n) => {
+                        sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, n))
+                    }
+                    _ => sheet.push_str(&format!(
+                        r#"<c r="{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#,
+                        reference,
+                        xml_escape(&cell_text(value))
+                    )),
+                }
+            }
+            sheet.push_str("</row>");
+        }
+        sheet.push_str("</sheetData></worksheet>");
+
+        let workbook = format!(
+            r#"{}<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+            XML_HEADER,
+            xml_escape(&xlsx_sheet_name(sheet_name))
+        );
+        write_zip(&[
+            ("[Content_Types].xml", XLSX_CONTENT_TYPES, true),
+            ("_rels/.rels", XLSX_RELS, true),
+            ("xl/workbook.xml", &workbook, true),
+            ("xl/_rels/workbook.xml.rels", XLSX_WORKBOOK_RELS, true),
+            ("xl/worksheets/sheet1.xml", &sheet, true),
+        ])
+    }
+
+    /// OpenDocument spreadsheet with a single table.
+    pub fn to_ods(&self, sheet_name: &str) -> Result<Vec<u8>, Error> {
+        let mut content = String::from(XML_HEADER);
+        content.push_str(r#"<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2"><office:body><office:spreadsheet>"#);
+        content.push_str(&format!(
+            r#"<table:table table:name="{}">"#,
+            xml_escape(sheet_name)
+        ));
+        let header: Vec<Value> = self.columns.iter().cloned().map(Value::String).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            content.push_str("<table:table-row>");
+            for value in row {
+                match value {
+                    Value::Null => content.push_str("<table:table-cell/>"),
+                    Value::Bool(b) => content.push_str(&format!(
+                        r#"<table:table-cell office:value-type="boolean" office:boolean-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                        b, b
+                    )),
+                    Value::Number(n) => content.push_str(&format!(
+                        r#"<table:table-cell office:value-type="float" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                        n, n
+                    )),
+                    _ => content.push_str(&format!(
+                        r#"<table:table-cell office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+                        xml_escape(&cell_text(value))
+                    )),
+                }
+            }
+            content.push_str("</table:table-row>");
+        }
+        content.push_str(
+            "</table:table></office:spreadsheet></office:body></office:document-content>",
+        );
+
+        // The mimetype has to be the first entry and stored uncompressed
+        write_zip(&[
+            ("mimetype", ODS_MIMETYPE, false),
+            ("META-INF/manifest.xml", ODS_MANIFEST, true),
+            ("content.xml", &content, true),
+        ])
+    }
+}
+
+/// Serializes `items` in the format of the options, `sheet_name` names the sheet of spreadsheets.
+/// JSON without selected columns keeps the items as they are.
+pub fn export_items<T: Serialize>(
+    items: &[T],
+    options: &ExportOptions,
+    sheet_name: &str,
+) -> Result<Vec<u8>, Error> {
+    if options.format == ExportFormat::Json && options.columns.is_empty() {
+        return serde_json::to_vec_pretty(items).map_err(|e| {
+            Error::new(
+                "ExportTable",
+                format!("Failed to serialize items to JSON: {}", e),
+                get_location!(),
+            )
+        });
+    }
+    let table = ExportTable::from_items(items, &options.columns)?;
+    match options.format {
+        ExportFormat::Json => table.to_json().map(String::into_bytes),
+        ExportFormat::Csv => Ok(table.to_csv().into_bytes()),
+        ExportFormat::Xlsx => table.to_xlsx(sheet_name),
+        ExportFormat::Ods => table.to_ods(sheet_name),
+    }
+}
+
+const XML_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
+const XLSX_CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#;
+const XLSX_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+const XLSX_WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#;
+const ODS_MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+const ODS_MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?><manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2"><manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/><manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/></manifest:manifest>"#;
+
+fn flatten_value(prefix: &str, value: Value, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(map) if !prefix.is_empty() || !map.is_empty() => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_value(&key, value, row);
+            }
+        }
+        value => {
+            row.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+/// Text of a cell, lists of plain values are joined and other lists are kept as JSON.
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(|v| !v.is_array() && !v.is_object()) => {
+            items.iter().map(cell_text).collect::<Vec<_>>().join(", ")
+        }
+        value => value.to_string(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // Control characters are not allowed in XML 1.0
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `A`, `B`, ..., `Z`, `AA`, ... for a zero based column.
+fn column_letters(mut column: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push((b'A' + (column % 26) as u8) as char);
+        if column < 26 {
+            break;
+        }
+        column = column / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Excel sheet names are limited to 31 characters without `[]:*?/\`.
+fn xlsx_sheet_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(31)
+        .collect();
+    if name.is_empty() {
+        "Sheet1".to_string()
+    } else {
+        name
+    }
+}
+
+fn write_zip(files: &[(&str, &str, bool)]) -> Result<Vec<u8>, Error> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content, compressed) in files {
+        let method = if *compressed {
+            CompressionMethod::Deflated
+        } else {
+            CompressionMethod::Stored
+        };
+        zip.start_file(*name, FileOptions::default().compression_method(method))
+            .map_err(|e| {
+                Error::from_zip(
+                    "ExportTable",
+                    *name,
+                    "Failed to add file to export",
+                    e,
+                    get_location!(),
+                )
+            })?;
+        zip.write_all(content.as_bytes()).map_err(|e| {
+            Error::new(
+                "ExportTable",
+                format!("Failed to write {} to export: {}", name, e),
+                get_location!(),
+            )
+        })?;
+    }
+    let cursor = zip.finish().map_err(|e| {
+        Error::from_zip(
+            "ExportTable",
+            "export",
+            "Failed to finish export",
+            e,
+            get_location!(),
+        )
+    })?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use zip::ZipArchive;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|c| c.to_string()).collect()
+    }
+
+    fn zip_entries(content: Vec<u8>) -> Vec<(String, CompressionMethod)> {
+        let mut archive = ZipArchive::new(Cursor::new(content)).unwrap();
+        (0..archive.len())
+            .map(|i| {
+                let file = archive.by_index(i).unwrap();
+                (file.name().to_string(), file.compression())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+    }
+
+    #[test]
+    fn test_column_letters() {
+        assert_eq!(column_letters(0), "A");
+        assert_eq!(column_letters(25), "Z");
+        assert_eq!(column_letters(26), "AA");
+        assert_eq!(column_letters(701), "ZZ");
+        assert_eq!(column_letters(702), "AAA");
+    }
+
+    #[test]
+    fn test_flatten_nested_objects() {
+        let mut row = Map::new();
+        flatten_value(
+            "",
+            json!({
+                "name": "Arcane Energize",
+                "sub_type": { "rank": 5, "variant": null },
+                "properties": { "trade": { "user": "seller" }, "tags": ["arcane"] },
+            }),
+            &mut row,
+        );
+        assert_eq!(row["name"], json!("Arcane Energize"));
+        assert_eq!(row["sub_type.rank"], json!(5));
+        assert_eq!(row["sub_type.variant"], Value::Null);
+        assert_eq!(row["properties.trade.user"], json!("seller"));
+        assert_eq!(row["properties.tags"], json!(["arcane"]));
+        assert_eq!(row.len(), 5);
+    }
+
+    #[test]
+    fn test_selected_columns_keep_their_order() {
+        let items = vec![
+            json!({ "name": "Serration", "price": 15, "sub_type": { "rank": 10 } }),
+            json!({ "name": "Ash Prime Set", "price": 95 }),
+        ];
+        let table =
+            ExportTable::from_items(&items, &columns(&["sub_type.rank", "price", "name"])).unwrap();
+        assert_eq!(table.columns, columns(&["sub_type.rank", "price", "name"]));
+        assert_eq!(
+            table.rows,
+            vec![
+                vec![json!(10), json!(15), json!("Serration")],
+                vec![Value::Null, json!(95), json!("Ash Prime Set")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_columns_in_order_of_appearance() {
+        let items = vec![
+            json!({ "name": "Serration" }),
+            json!({ "name": "Ash", "amount": 2 }),
+        ];
+        let table = ExportTable::from_items(&items, &[]).unwrap();
+        assert_eq!(table.columns, columns(&["name", "amount"]));
+        assert_eq!(table.rows[0], vec![json!("Serration"), Value::Null]);
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let items = vec![json!({ "name": "Ash, Prime", "tags": ["set", "prime"] })];
+        let table = ExportTable::from_items(&items, &columns(&["name", "tags"])).unwrap();
+        assert_eq!(
+            table.to_csv(),
+            "\u{feff}name,tags\r\n\"Ash, Prime\",\"set, prime\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_xlsx_entries() {
+        let table = ExportTable::from_items(&[json!({ "name": "Serration" })], &[]).unwrap();
+        let entries = zip_entries(table.to_xlsx("Items").unwrap());
+        assert_eq!(
+            entries.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(),
+            vec![
+                "[Content_Types].xml",
+                "_rels/.rels",
+                "xl/workbook.xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/worksheets/sheet1.xml",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ods_mimetype_is_first_and_stored() {
+        let table = ExportTable::from_items(&[json!({ "name": "Serration" })], &[]).unwrap();
+        let entries = zip_entries(table.to_ods("Items").unwrap());
+        assert_eq!(
+            entries,
+            vec![
+                ("mimetype".to_string(), CompressionMethod::Stored),
+                (
+                    "META-INF/manifest.xml".to_string(),
+                    CompressionMethod::Deflated
+                ),
+                ("content.xml".to_string(), CompressionMethod::Deflated),
+            ]
+        );
+
+        let mut archive = ZipArchive::new(Cursor::new(table.to_ods("Items").unwrap())).unwrap();
+        let mut mimetype = String::new();
+        std::io::Read::read_to_string(&mut archive.by_index(0).unwrap(), &mut mimetype).unwrap();
+        assert_eq!(mimetype, ODS_MIMETYPE);
+    }
+}
@@ -34,6 +34,15 @@ export class AppModule {
   notify_reset(id: string): Promise<TauriTypes.NotificationSetting> {
     return this.client.sendInvoke<TauriTypes.NotificationSetting>("app_notify_reset", { id });
   }
+  notify_get_deliveries(): Promise<TauriTypes.QueuedNotification[]> {
+    return this.client.sendInvoke<TauriTypes.QueuedNotification[]>("notification_get_deliveries");
+  }
+  notify_resend(id?: string): Promise<number> {
+    return this.client.sendInvoke<number>("notification_resend", { id });
+  }
+  notify_get_webhook_schemas(): Promise<Record<string, any>> {
+    return this.client.sendInvoke<Record<string, any>>("notification_get_webhook_schemas");
+  }
   getDefaultSettings(): Promise<TauriTypes.Settings> {
     return this.client.sendInvoke<TauriTypes.Settings>("app_get_default_settings");
   }
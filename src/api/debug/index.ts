@@ -13,4 +13,7 @@ export class DebugModule {
   async ee_logs_exportJson(query: TauriTypes.EELogControllerGetListParams): Promise<string> {
     return await this.client.sendInvoke<string>("debug_export_ee_logs", { query });
   }
+  async replay_ee_log(path: string): Promise<TauriTypes.ReplayedTrade[]> {
+    return await this.client.sendInvoke<TauriTypes.ReplayedTrade[]>("debug_replay_ee_log", { path });
+  }
 }
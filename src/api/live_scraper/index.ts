@@ -9,6 +9,12 @@ export class LiveScraperModule {
   async get_interesting_wtb_items(settings: TauriTypes.ItemSettings): Promise<TauriTypes.ItemPriceInfo[]> {
     return await this.client.sendInvoke("live_scraper_get_interesting_wtb_items", { settings });
   }
+  async backtest(settings: TauriTypes.ItemSettings, path?: string): Promise<TauriTypes.BacktestReport> {
+    return await this.client.sendInvoke("live_scraper_backtest", { settings, path });
+  }
+  async get_scheduler_stats(): Promise<TauriTypes.SchedulerStats> {
+    return await this.client.sendInvoke("live_scraper_get_scheduler_stats");
+  }
   async get_state(): Promise<{ is_running: boolean }> {
     return await this.client.sendInvoke("live_scraper_get_state");
   }
@@ -27,8 +27,8 @@ export interface Values {
 export class LogParserModule {
   constructor(private readonly client: TauriClient) {}
 
-  async load(path: string): Promise<{ [key: string]: number[] }> {
-    return await this.client.sendInvoke("wfgdpr_load", { filePath: path });
+  async load(path: string): Promise<TauriTypes.GdprParseStats> {
+    return await this.client.sendInvoke<TauriTypes.GdprParseStats>("wfgdpr_load", { filePath: path });
   }
 
   async getState(): Promise<{ was_initialized: boolean; trade_years: string[] }> {
@@ -38,4 +38,16 @@ export class LogParserModule {
   async getAccounts(): Promise<TauriTypes.WFGDPRAccount[]> {
     return await this.client.sendInvoke<TauriTypes.WFGDPRAccount[]>("wfgdpr_get_accounts");
   }
+
+  async importTransactions(): Promise<TauriTypes.GdprImportSummary> {
+    return await this.client.sendInvoke<TauriTypes.GdprImportSummary>("wfgdpr_import_transactions");
+  }
+
+  async updateTradeNotes(id: number, notes?: string): Promise<void> {
+    return await this.client.sendInvoke<void>("wfgdpr_update_trade_notes", { id, notes });
+  }
+
+  async clear(): Promise<void> {
+    return await this.client.sendInvoke<void>("wfgdpr_clear");
+  }
 }
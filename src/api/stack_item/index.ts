@@ -42,12 +42,18 @@ export class StockItemModule {
     return await this.client.sendInvoke<TauriTypes.StockItem>("stock_item_sell", { ...entry, by });
   }
 
+  async getLots(wfm_url: string, sub_type?: TauriTypes.SubType, include_closed?: boolean): Promise<TauriTypes.StockLot[]> {
+    return await this.client.sendInvoke<TauriTypes.StockLot[]>("get_stock_item_lots", { wfm_url, sub_type, include_closed });
+  }
+
   async getById<T = any>(id: number, operations?: string[]): Promise<TauriTypes.StockItem<T>> {
     return await this.client.sendInvoke<TauriTypes.StockItem<T>>("stock_item_get_by_id", { id, operations });
   }
-  exportJson = async (query: TauriTypes.StockItemControllerGetListParams): Promise<string> => {
-    return await this.client.sendInvoke<string>("export_stock_item_json", {
+  exportData = async (query: TauriTypes.StockItemControllerGetListParams, options: TauriTypes.ExportOptions, path?: string): Promise<string> => {
+    return await this.client.sendInvoke<string>("export_stock_item", {
       query: this.client.convertToTauriQuery(query),
+      options,
+      path,
     });
   };
 }
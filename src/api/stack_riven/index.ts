@@ -43,9 +43,11 @@ export class StockRivenModule {
   async getById<T = any>(id: number, operations?: string[]): Promise<TauriTypes.StockRiven<T>> {
     return await this.client.sendInvoke<TauriTypes.StockRiven<T>>("stock_riven_get_by_id", { id, operations });
   }
-  exportJson = async (query: TauriTypes.StockRivenControllerGetListParams): Promise<string> => {
-    return await this.client.sendInvoke<string>("export_stock_riven_json", {
+  exportData = async (query: TauriTypes.StockRivenControllerGetListParams, options: TauriTypes.ExportOptions, path?: string): Promise<string> => {
+    return await this.client.sendInvoke<string>("export_stock_riven", {
       query: this.client.convertToTauriQuery(query),
+      options,
+      path,
     });
   };
 }
@@ -33,6 +33,14 @@ export class TransactionModule {
     });
   }
 
+  async getHistoryReport(query: TauriTypes.TransactionControllerGetListParams): Promise<TauriTypes.TradeHistoryReport> {
+    if (query.from_date) query.from_date = dayjs(query.from_date).utc().toISOString();
+    if (query.to_date) query.to_date = dayjs(query.to_date).utc().toISOString();
+    return await this.client.sendInvoke<TauriTypes.TradeHistoryReport>("get_transaction_history_report", {
+      query: this.client.convertToTauriQuery(query),
+    });
+  }
+
   async update(input: TauriTypes.UpdateTransaction): Promise<TauriTypes.TransactionDto> {
     return await this.client.sendInvoke<TauriTypes.TransactionDto>("transaction_update", { input });
   }
@@ -43,9 +51,11 @@ export class TransactionModule {
   deleteBulk = async (ids: number[]): Promise<{ deleted_count: number }> => {
     return await this.client.sendInvoke<{ deleted_count: number }>("transaction_delete_bulk", { ids });
   };
-  exportJson = async (query: TauriTypes.TransactionControllerGetListParams): Promise<string> => {
-    return await this.client.sendInvoke<string>("export_transaction_json", {
+  exportData = async (query: TauriTypes.TransactionControllerGetListParams, options: TauriTypes.ExportOptions, path?: string): Promise<string> => {
+    return await this.client.sendInvoke<string>("export_transaction", {
       query: this.client.convertToTauriQuery(query),
+      options,
+      path,
     });
   };
   calculateTax = async (): Promise<void> => {
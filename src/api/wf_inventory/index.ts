@@ -7,4 +7,16 @@ export class WfInventoryModule {
       query: this.client.convertToTauriQuery(query),
     });
   }
+  async reconcile(): Promise<TauriTypes.WFInvReconciliationReport> {
+    return await this.client.sendInvoke<TauriTypes.WFInvReconciliationReport>("wf_inventory_reconcile");
+  }
+  async applyReconciliation(
+    items: TauriTypes.WFInvItemReconciliation[],
+    rivens: TauriTypes.WFInvRivenReconciliation[]
+  ): Promise<TauriTypes.WFInvReconciliationReport> {
+    return await this.client.sendInvoke<TauriTypes.WFInvReconciliationReport>("wf_inventory_apply_reconciliation", { items, rivens });
+  }
+  async importRivens(uuids: string[], bought?: number, suggest_price?: boolean): Promise<TauriTypes.StockRiven[]> {
+    return await this.client.sendInvoke<TauriTypes.StockRiven[]>("wf_inventory_import_rivens", { uuids, bought, suggest_price });
+  }
 }
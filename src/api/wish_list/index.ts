@@ -42,9 +42,11 @@ export class WishListModule {
   async getById<T>(id: number, operations?: string[]): Promise<TauriTypes.WishListItem<T>> {
     return await this.client.sendInvoke<TauriTypes.WishListItem<T>>("wish_list_get_by_id", { id, operations });
   }
-  exportJson = async (query: TauriTypes.WishListControllerGetListParams): Promise<string> => {
-    return await this.client.sendInvoke<string>("export_wish_list_json", {
+  exportData = async (query: TauriTypes.WishListControllerGetListParams, options: TauriTypes.ExportOptions, path?: string): Promise<string> => {
+    return await this.client.sendInvoke<string>("export_wish_list", {
       query: this.client.convertToTauriQuery(query),
+      options,
+      path,
     });
   };
 }
@@ -0,0 +1,57 @@
+import { TauriTypes } from "$types";
+import api from "@api/index";
+import { useTauriEvent } from "@hooks/useTauriEvent.hook";
+import { useTranslateComponent } from "@hooks/useTranslate.hook";
+import { Badge, Group, Tooltip } from "@mantine/core";
+import { useQuery } from "@tanstack/react-query";
+import { useEffect, useState } from "react";
+
+export type SchedulerStatsProps = {
+  isRunning: boolean;
+};
+
+export function SchedulerStats({ isRunning }: SchedulerStatsProps) {
+  // State
+  const [stats, setStats] = useState<TauriTypes.SchedulerStats | undefined>(undefined);
+
+  // Translate general
+  const useTranslate = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateComponent(`scheduler_stats.${key}`, { ...context }, i18Key);
+
+  // Fetch data from rust side
+  const { data } = useQuery({
+    queryKey: ["live_scraper_scheduler_stats"],
+    queryFn: () => api.live_scraper.get_scheduler_stats(),
+    enabled: isRunning,
+  });
+
+  useEffect(() => {
+    if (data) setStats(data);
+  }, [data]);
+
+  // Use the custom hook for Tauri events
+  useTauriEvent<TauriTypes.SchedulerStats>(TauriTypes.Events.UpdateLiveScraperSchedulerStats, (value) => setStats(value), []);
+
+  if (!isRunning || !stats) return null;
+  const isBackingOff = stats.backoff_remaining_secs > 0;
+  return (
+    <Group justify="center" gap="xs">
+      <Tooltip label={useTranslate("tokens.tooltip", { rps: stats.requests_per_second })}>
+        <Badge variant="light">{useTranslate("tokens.label", { tokens: stats.tokens.toFixed(1), capacity: stats.capacity })}</Badge>
+      </Tooltip>
+      <Tooltip label={useTranslate("backoff.tooltip", { rate_limited: stats.rate_limited })}>
+        <Badge variant="light" color={isBackingOff ? "red.7" : "green.7"}>
+          {isBackingOff
+            ? useTranslate("backoff.active", { seconds: stats.backoff_remaining_secs.toFixed(0), multiplier: stats.backoff_multiplier })
+            : useTranslate("backoff.idle")}
+        </Badge>
+      </Tooltip>
+      <Tooltip label={useTranslate("queue.tooltip", { total_requests: stats.total_requests })}>
+        <Badge variant="light">{useTranslate("queue.label", { queued: stats.queued_items, tracked: stats.tracked_items })}</Badge>
+      </Tooltip>
+      <Tooltip label={useTranslate("staleness.tooltip")}>
+        <Badge variant="light">{useTranslate("staleness.label", { seconds: stats.max_staleness_secs.toFixed(0) })}</Badge>
+      </Tooltip>
+    </Group>
+  );
+}
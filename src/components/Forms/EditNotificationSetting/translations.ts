@@ -13,6 +13,8 @@ export const createEditNotificationSettingTranslations = () => {
     systemFields: createTranslator("edit_notification_setting.system.fields"),
     discordFields: createTranslator("edit_notification_setting.discord.fields"),
     webhookFields: createTranslator("edit_notification_setting.webhook.fields"),
+    channels: createTranslator("edit_notification_setting.channels"),
+    channelFields: createTranslator("edit_notification_setting.channels.fields"),
     manageSounds: createTranslator("edit_notification_setting.manage_sounds"),
   };
 };
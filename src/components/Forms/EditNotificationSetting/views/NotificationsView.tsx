@@ -1,8 +1,11 @@
-import { Collapse, Group, TextInput, Title, Divider, Textarea, ScrollAreaAutosize, Button, Stack, Select, NumberInput } from "@mantine/core";
+import { Collapse, Group, TextInput, Title, Divider, Textarea, ScrollAreaAutosize, Button, Stack, Select, NumberInput, PasswordInput, Menu, Switch, Paper } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
+import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { UseFormReturnType } from "@mantine/form";
+import { useState } from "react";
 import { TauriTypes } from "$types";
 import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
-import { faBell } from "@fortawesome/free-solid-svg-icons";
+import { faBell, faTrashCan } from "@fortawesome/free-solid-svg-icons";
 import { faDiscord } from "@fortawesome/free-brands-svg-icons";
 import { TooltipIcon } from "@components/Shared/TooltipIcon";
 import api from "@api/index";
@@ -27,6 +30,22 @@ const buildSystemSoundOptions = (translateSystemFields: (key: string, context?:
   })),
 ];
 
+const EMBED_TEMPLATE_EXAMPLE = JSON.stringify(
+  {
+    title: "<TR_TYPE> with <PLAYER_NAME>",
+    description: "<TIME>",
+    color: "#58B9FF",
+    thumbnail: "<ITEM_ICON>",
+    fields: [
+      { name: "Platinum", value: "<TOTAL_PLAT>", inline: true },
+      { name: "Offered Items", value: "{{#each OF_ITEMS}}<ITEM_NAME> x<QUANTITY>\n{{/each}}", inline: false },
+      { name: "Received Items", value: "{{#if RE_ITEMS}}{{#each RE_ITEMS}}<ITEM_NAME> x<QUANTITY>\n{{/each}}{{else}}None{{/if}}", inline: false },
+    ],
+  },
+  null,
+  2,
+);
+
 const normalizeVolume = (value: number | string | null | undefined) => (typeof value === "number" && !Number.isNaN(value) ? value : 1.0);
 
 type EditNotificationSettingTranslations = ReturnType<typeof createEditNotificationSettingTranslations>;
@@ -165,6 +184,33 @@ const DiscordNotificationSection = ({
     form.setFieldValue("discord_notify.content", defaultNotification.discord_notify.content.replace(/\\n/g, "\n"));
   };
 
+  const [embedText, setEmbedText] = useState(() =>
+    form.values.discord_notify.embed ? JSON.stringify(form.values.discord_notify.embed, null, 2) : "",
+  );
+  const [embedError, setEmbedError] = useState<string | undefined>(undefined);
+
+  const handleEmbedChange = (value: string) => {
+    setEmbedText(value);
+    if (value.trim() === "") {
+      setEmbedError(undefined);
+      form.setFieldValue("discord_notify.embed", undefined);
+      return;
+    }
+    try {
+      const embed = JSON.parse(value) as Partial<TauriTypes.DiscordEmbedTemplate>;
+      form.setFieldValue("discord_notify.embed", {
+        title: embed.title ?? "",
+        description: embed.description ?? "",
+        color: embed.color ?? "",
+        thumbnail: embed.thumbnail ?? "",
+        fields: embed.fields ?? [],
+      });
+      setEmbedError(undefined);
+    } catch {
+      setEmbedError(t.discordFields("embed.error_invalid_json"));
+    }
+  };
+
   const handleUserIdsChange = (value: string) =>
     form.setFieldValue(
       "discord_notify.user_ids",
@@ -206,32 +252,231 @@ const DiscordNotificationSection = ({
         onChange={(event) => handleUserIdsChange(event.currentTarget.value)}
         radius="md"
       />
+      <Textarea
+        label={t.discordFields("embed.label")}
+        placeholder={EMBED_TEMPLATE_EXAMPLE}
+        description={t.discordFields("embed.description")}
+        value={embedText}
+        error={embedError}
+        onChange={(event) => handleEmbedChange(event.currentTarget.value)}
+        radius="md"
+        rows={7}
+        maxRows={7}
+        styles={{ input: { fontFamily: "monospace" } }}
+      />
       <Divider my="sm" />
     </Collapse>
   );
 };
 
+const generateSecret = () => Array.from(crypto.getRandomValues(new Uint8Array(32)), (b) => b.toString(16).padStart(2, "0")).join("");
+
 const WebhookNotificationSection = ({
+  id,
+  form,
+  t,
+}: {
+  id: string;
+  form: UseFormReturnType<TauriTypes.NotificationSetting>;
+  t: EditNotificationSettingTranslations;
+}) => {
+  // Notification ids are the event names with an "on_" prefix
+  const handleCopySchema = async () => {
+    const schemas = await api.app.notify_get_webhook_schemas();
+    const schema = schemas[id.replace(/^on_/, "")];
+    if (!schema) return;
+    await writeText(JSON.stringify(schema, null, 2));
+    notifications.show({ title: t.webhookFields("schema.copied"), message: "", color: "green.7" });
+  };
+
+  return (
+    <Collapse expanded={form.values.webhook_notify.enabled}>
+      <Title order={4} mb="xs" mt="sm">
+        {t.form("webhook.title")}
+      </Title>
+      <TextInput
+        label={t.webhookFields("url.label")}
+        placeholder={t.webhookFields("url.placeholder")}
+        value={form.values.webhook_notify.url}
+        onChange={(event) => form.setFieldValue("webhook_notify.url", event.currentTarget.value)}
+        radius="md"
+      />
+      <PasswordInput
+        label={t.webhookFields("secret.label")}
+        placeholder={t.webhookFields("secret.placeholder")}
+        description={t.webhookFields("secret.description")}
+        value={form.values.webhook_notify.secret}
+        onChange={(event) => form.setFieldValue("webhook_notify.secret", event.currentTarget.value)}
+        radius="md"
+      />
+      <Group gap="xs" mt={5}>
+        <Button onClick={() => form.setFieldValue("webhook_notify.secret", generateSecret())}>{t.webhookFields("secret.generate_button")}</Button>
+        <Button variant="light" onClick={handleCopySchema}>
+          {t.webhookFields("schema.copy_button")}
+        </Button>
+      </Group>
+      <Divider my="sm" />
+    </Collapse>
+  );
+};
+
+type ChannelType = TauriTypes.NotificationChannelSetting["type"];
+const CHANNEL_TYPES: ChannelType[] = ["telegram", "ntfy", "matrix"];
+
+// New channels start from the system notification texts, they use the same variables
+const createChannel = (type: ChannelType, system: TauriTypes.SystemNotify): TauriTypes.NotificationChannelSetting => {
+  switch (type) {
+    case "telegram":
+      return { type, enabled: true, bot_token: "", chat_id: "", content: system.content };
+    case "ntfy":
+      return { type, enabled: true, server: "https://ntfy.sh", topic: "", token: "", title: system.title, content: system.content, priority: 3, tags: [] };
+    case "matrix":
+      return { type, enabled: true, homeserver: "https://matrix.org", room_id: "", access_token: "", content: system.content };
+  }
+};
+
+const ChannelFields = ({
+  channel,
+  path,
   form,
   t,
 }: {
+  channel: TauriTypes.NotificationChannelSetting;
+  path: string;
   form: UseFormReturnType<TauriTypes.NotificationSetting>;
   t: EditNotificationSettingTranslations;
-}) => (
-  <Collapse expanded={form.values.webhook_notify.enabled}>
-    <Title order={4} mb="xs" mt="sm">
-      {t.form("webhook.title")}
-    </Title>
-    <TextInput
-      label={t.webhookFields("url.label")}
-      placeholder={t.webhookFields("url.placeholder")}
-      value={form.values.webhook_notify.url}
-      onChange={(event) => form.setFieldValue("webhook_notify.url", event.currentTarget.value)}
+}) => {
+  const textInput = (key: string, value: string, secret?: boolean) => {
+    const Input = secret ? PasswordInput : TextInput;
+    return (
+      <Input
+        label={t.channelFields(`${key}.label`)}
+        placeholder={t.channelFields(`${key}.placeholder`)}
+        value={value}
+        onChange={(event) => form.setFieldValue(`${path}.${key}`, event.currentTarget.value)}
+        radius="md"
+      />
+    );
+  };
+  const contentInput = (
+    <Textarea
+      label={t.channelFields("content.label")}
+      placeholder={t.channelFields("content.placeholder")}
+      value={channel.content}
+      onChange={(event) => form.setFieldValue(`${path}.content`, event.currentTarget.value)}
       radius="md"
+      rows={5}
+      maxRows={5}
     />
-    <Divider my="sm" />
-  </Collapse>
-);
+  );
+
+  switch (channel.type) {
+    case "telegram":
+      return (
+        <>
+          {textInput("bot_token", channel.bot_token, true)}
+          {textInput("chat_id", channel.chat_id)}
+          {contentInput}
+        </>
+      );
+    case "ntfy":
+      return (
+        <>
+          <Group grow>
+            {textInput("server", channel.server)}
+            {textInput("topic", channel.topic)}
+          </Group>
+          {textInput("token", channel.token, true)}
+          <Group grow align="flex-end">
+            <NumberInput
+              label={t.channelFields("priority.label")}
+              radius="md"
+              min={1}
+              max={5}
+              value={channel.priority}
+              onChange={(value) => form.setFieldValue(`${path}.priority`, typeof value === "number" ? value : 3)}
+            />
+            <TextInput
+              label={t.channelFields("tags.label")}
+              placeholder={t.channelFields("tags.placeholder")}
+              value={channel.tags.join(", ")}
+              onChange={(event) =>
+                form.setFieldValue(
+                  `${path}.tags`,
+                  event.currentTarget.value
+                    .split(",")
+                    .map((entry) => entry.trim())
+                    .filter(Boolean),
+                )
+              }
+              radius="md"
+            />
+          </Group>
+          {textInput("title", channel.title)}
+          {contentInput}
+        </>
+      );
+    case "matrix":
+      return (
+        <>
+          <Group grow>
+            {textInput("homeserver", channel.homeserver)}
+            {textInput("room_id", channel.room_id)}
+          </Group>
+          {textInput("access_token", channel.access_token, true)}
+          {contentInput}
+        </>
+      );
+  }
+};
+
+const ChannelsSection = ({ form, t }: { form: UseFormReturnType<TauriTypes.NotificationSetting>; t: EditNotificationSettingTranslations }) => {
+  const channels = form.values.channels ?? [];
+  return (
+    <>
+      <Group justify="space-between" mt="sm" mb="xs">
+        <Title order={4}>{t.channels("title")}</Title>
+        <Menu shadow="md" position="bottom-end">
+          <Menu.Target>
+            <Button size="xs">{t.channels("add_button")}</Button>
+          </Menu.Target>
+          <Menu.Dropdown>
+            {CHANNEL_TYPES.map((type) => (
+              <Menu.Item key={type} onClick={() => form.setFieldValue("channels", [...channels, createChannel(type, form.values.system_notify)])}>
+                {t.channels(`types.${type}`)}
+              </Menu.Item>
+            ))}
+          </Menu.Dropdown>
+        </Menu>
+      </Group>
+      <Stack gap="xs">
+        {channels.map((channel, index) => (
+          <Paper key={index} withBorder p="xs" radius="md">
+            <Group justify="space-between">
+              <Title order={5}>{t.channels(`types.${channel.type}`)}</Title>
+              <Group gap={5}>
+                <Switch
+                  label={t.channels("enabled")}
+                  checked={channel.enabled}
+                  onChange={(event) => form.setFieldValue(`channels.${index}.enabled`, event.currentTarget.checked)}
+                />
+                <ActionWithTooltip
+                  tooltip={t.channels("remove_tooltip")}
+                  icon={faTrashCan}
+                  color="red.7"
+                  onClick={() => form.removeListItem("channels", index)}
+                />
+              </Group>
+            </Group>
+            <Collapse expanded={channel.enabled}>
+              <ChannelFields channel={channel} path={`channels.${index}`} form={form} t={t} />
+            </Collapse>
+          </Paper>
+        ))}
+      </Stack>
+    </>
+  );
+};
 
 export const NotificationsView = ({ id, form, customSounds, onManageSounds }: NotificationsViewProps) => {
   const t = createEditNotificationSettingTranslations();
@@ -248,7 +493,8 @@ export const NotificationsView = ({ id, form, customSounds, onManageSounds }: No
         <Stack gap={5}>
           <SystemNotificationSection form={form} t={t} customSoundOptions={customSoundOptions} onManageSounds={onManageSounds} />
           <DiscordNotificationSection id={id} form={form} t={t} />
-          <WebhookNotificationSection form={form} t={t} />
+          <WebhookNotificationSection id={id} form={form} t={t} />
+          <ChannelsSection form={form} t={t} />
         </Stack>
       </ScrollAreaAutosize>
     </>
@@ -0,0 +1,86 @@
+import { Box, Button, Group, Select, TagsInput } from "@mantine/core";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { useForm } from "@mantine/form";
+import { TauriTypes } from "$types";
+import { DatePickerInput } from "@mantine/dates";
+
+export type ExportDataValues = {
+  options: TauriTypes.ExportOptions;
+  from_date?: string;
+  to_date?: string;
+};
+
+export type ExportDataProps = {
+  columns?: string[];
+  from_date?: string;
+  to_date?: string;
+  onSubmit: (values: ExportDataValues) => void;
+};
+
+const FORMATS: TauriTypes.ExportFormat[] = ["json", "csv", "xlsx", "ods"];
+
+export function ExportData({ columns, from_date, to_date, onSubmit }: ExportDataProps) {
+  // Translate general
+  const useTranslateForm = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`export_data.${key}`, { ...context }, i18Key);
+  const useTranslateFormFields = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForm(`fields.${key}`, { ...context }, i18Key);
+
+  // User form
+  const form = useForm<ExportDataValues>({
+    initialValues: {
+      options: { format: "csv", columns: [] },
+      from_date,
+      to_date,
+    },
+    validate: {},
+  });
+
+  return (
+    <Box w={"100%"}>
+      <form onSubmit={form.onSubmit((data) => onSubmit(data))}>
+        <Select
+          label={useTranslateFormFields("format.label")}
+          description={useTranslateFormFields("format.description")}
+          data={FORMATS.map((format) => ({ value: format, label: useTranslateFormFields(`format.options.${format}`) }))}
+          value={form.values.options.format}
+          onChange={(value) => form.setFieldValue("options.format", (value as TauriTypes.ExportFormat) || "json")}
+          allowDeselect={false}
+          radius="md"
+        />
+        <TagsInput
+          label={useTranslateFormFields("columns.label")}
+          description={useTranslateFormFields("columns.description")}
+          placeholder={useTranslateFormFields("columns.placeholder")}
+          data={columns || []}
+          value={form.values.options.columns}
+          onChange={(value) => form.setFieldValue("options.columns", value)}
+          clearable
+          radius="md"
+          mt="md"
+        />
+        <DatePickerInput
+          clearable
+          label={useTranslateFormFields("date_range.label")}
+          description={useTranslateFormFields("date_range.description")}
+          placeholder={useTranslateFormFields("date_range.placeholder")}
+          type="range"
+          valueFormat="YYYY MMM DD"
+          value={[form.values.from_date ? new Date(form.values.from_date) : null, form.values.to_date ? new Date(form.values.to_date) : null]}
+          onChange={(value) => {
+            let [start, end] = value || [undefined, undefined];
+            form.setFieldValue("from_date", start || undefined);
+            form.setFieldValue("to_date", end || undefined);
+          }}
+          radius="md"
+          mt="md"
+        />
+        <Group justify="flex-end" mt="md">
+          <Button type="submit" variant="light">
+            {useTranslateForm("buttons.export")}
+          </Button>
+        </Group>
+      </form>
+    </Box>
+  );
+}
@@ -0,0 +1,75 @@
+import { TauriTypes } from "$types";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { Group, NumberInput, Select } from "@mantine/core";
+
+export type PricingStrategyInputProps = {
+  value: TauriTypes.PricingStrategySetting;
+  label?: string;
+  onChange(value: TauriTypes.PricingStrategySetting): void;
+};
+
+const STRATEGIES: TauriTypes.PricingStrategy[] = ["match_top", "undercut", "percent_of_moving_avg", "median_of_top_k"];
+
+// Default parameter of each strategy when it gets selected
+const withDefaults = (type: TauriTypes.PricingStrategy): TauriTypes.PricingStrategySetting => {
+  switch (type) {
+    case "undercut":
+      return { type, amount: 1 };
+    case "percent_of_moving_avg":
+      return { type, percent: 100 };
+    case "median_of_top_k":
+      return { type, k: 3 };
+    default:
+      return { type: "match_top" };
+  }
+};
+
+export function PricingStrategyInput({ value, label, onChange }: PricingStrategyInputProps) {
+  // Translate general
+  const useTranslate = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`pricing_strategy.${key}`, { ...context }, i18Key);
+  const useTranslateFormFields = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslate(`fields.${key}`, { ...context }, i18Key);
+
+  return (
+    <Group gap="xs" align="flex-end">
+      <Select
+        w={220}
+        label={label ?? useTranslateFormFields("type.label")}
+        data={STRATEGIES.map((type) => ({ value: type, label: useTranslateFormFields(`type.options.${type}`) }))}
+        value={value.type}
+        allowDeselect={false}
+        onChange={(type) => type && onChange(withDefaults(type as TauriTypes.PricingStrategy))}
+      />
+      {value.type == "undercut" && (
+        <NumberInput
+          w={120}
+          label={useTranslateFormFields("amount.label")}
+          min={0}
+          value={value.amount}
+          onChange={(amount) => onChange({ ...value, amount: Number(amount) || 0 })}
+        />
+      )}
+      {value.type == "percent_of_moving_avg" && (
+        <NumberInput
+          w={120}
+          label={useTranslateFormFields("percent.label")}
+          min={1}
+          suffix="%"
+          value={value.percent}
+          onChange={(percent) => onChange({ ...value, percent: Number(percent) || 0 })}
+        />
+      )}
+      {value.type == "median_of_top_k" && (
+        <NumberInput
+          w={120}
+          label={useTranslateFormFields("k.label")}
+          min={1}
+          max={20}
+          value={value.k}
+          onChange={(k) => onChange({ ...value, k: Number(k) || 1 })}
+        />
+      )}
+    </Group>
+  );
+}
@@ -81,6 +81,20 @@ export function RivenFilter({ value, onSubmit }: RivenFilterProps) {
                 suffix=" %"
                 mt="md"
               />
+              <NumberInput
+                label={useTranslateFormFields("min_similarity.label")}
+                description={useTranslateFormFields("min_similarity.description")}
+                placeholder="0"
+                value={form.values.min_similarity || 0}
+                min={0}
+                max={100}
+                onChange={(event) => {
+                  const value = Number(event);
+                  if (value == 0) form.setFieldValue("min_similarity", undefined);
+                  else form.setFieldValue("min_similarity", value);
+                }}
+                mt="md"
+              />
               <Stack mt="md" gap="md">
                 {(form.values.attributes ?? []).map((attribute, index) => (
                   <RivenFilterAttribute
@@ -0,0 +1,143 @@
+import { TauriTypes } from "$types";
+import api from "@api/index";
+import { MinMax } from "@components/Forms/MinMax";
+import { RivenFilterAttribute } from "@components/Forms/RivenFilterAttribute";
+import { SelectRivenWeapon } from "@components/Forms/SelectRivenWeapon";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { Box, Button, Grid, Group, MultiSelect, NumberInput, Stack, Switch } from "@mantine/core";
+import { useForm } from "@mantine/form";
+import { useQuery } from "@tanstack/react-query";
+
+export type RivenWatchProps = {
+  value: TauriTypes.RivenWatchSetting;
+  onSubmit: (values: TauriTypes.RivenWatchSetting) => void;
+};
+
+export function RivenWatch({ value, onSubmit }: RivenWatchProps) {
+  // Translate general
+  const useTranslateForm = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`riven_watch.${key}`, { ...context }, i18Key);
+  const useTranslateFormFields = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForm(`fields.${key}`, { ...context }, i18Key);
+  const useTranslateButtons = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForm(`buttons.${key}`, { ...context }, i18Key);
+
+  // Fetch data from rust side
+  const { data: attributes } = useQuery({
+    queryKey: ["cache_riven_attributes"],
+    queryFn: () => api.cache.getRivenAttributes(),
+  });
+
+  // User form
+  const form = useForm({
+    initialValues: {
+      ...value,
+      filter: { ...value.filter, attributes: value.filter.attributes ?? [] },
+    },
+    validate: {
+      wfm_weapon_url: (value) => (value ? null : useTranslateFormFields("weapon.error")),
+      target_price: (value) => (value > 0 ? null : useTranslateFormFields("target_price.error")),
+    },
+  });
+
+  const getAttributes = (positive: boolean) => (form.values.filter.attributes ?? []).filter((a) => a.positive == positive);
+  // Keeps the required flag of the stats that stay picked
+  const setAttributes = (positive: boolean, urls: string[]) => {
+    const current = getAttributes(positive);
+    form.setFieldValue("filter.attributes", [
+      ...getAttributes(!positive),
+      ...urls.map((url_name) => current.find((a) => a.url_name == url_name) ?? { url_name, positive, is_required: false }),
+    ]);
+  };
+  const getAttributeOptions = (positive: boolean) =>
+    (attributes || [])
+      .filter((a) => (positive ? !a.negativeOnly : !a.positiveOnly))
+      .map((a) => ({ label: a.label, value: a.url_name }));
+
+  return (
+    <Box w={"100%"} p={"sm"}>
+      <form onSubmit={form.onSubmit((data) => onSubmit(data))}>
+        <Group gap="md" align="flex-end">
+          <SelectRivenWeapon
+            value={form.values.wfm_weapon_url}
+            onChange={(weapon) => form.setFieldValue("wfm_weapon_url", weapon.wfmRivenUrl)}
+          />
+          <NumberInput
+            w={150}
+            label={useTranslateFormFields("target_price.label")}
+            description={useTranslateFormFields("target_price.description")}
+            min={1}
+            suffix="p"
+            {...form.getInputProps("target_price")}
+          />
+          <NumberInput
+            w={150}
+            label={useTranslateFormFields("min_score.label")}
+            description={useTranslateFormFields("min_score.description")}
+            min={0}
+            max={100}
+            {...form.getInputProps("min_score")}
+          />
+        </Group>
+        <Grid mt="md">
+          <Grid.Col span={6}>
+            <MultiSelect
+              searchable
+              label={useTranslateFormFields("positive.label")}
+              description={useTranslateFormFields("positive.description")}
+              data={getAttributeOptions(true)}
+              value={getAttributes(true).map((a) => a.url_name)}
+              onChange={(urls) => setAttributes(true, urls)}
+              maxValues={3}
+            />
+            <MultiSelect
+              searchable
+              mt="md"
+              label={useTranslateFormFields("negative.label")}
+              data={getAttributeOptions(false)}
+              value={getAttributes(false).map((a) => a.url_name)}
+              onChange={(urls) => setAttributes(false, urls)}
+              maxValues={1}
+            />
+            <Stack mt="md" gap="md">
+              {(form.values.filter.attributes ?? []).map((attribute, index) => (
+                <RivenFilterAttribute
+                  key={attribute.url_name}
+                  value={attribute}
+                  onChange={(value) => {
+                    const attributes = [...(form.values.filter.attributes ?? [])];
+                    attributes[index] = value;
+                    form.setFieldValue("filter.attributes", attributes);
+                  }}
+                />
+              ))}
+            </Stack>
+          </Grid.Col>
+          <Grid.Col span={6}>
+            <MinMax
+              label={useTranslateFormFields("re_rolls.label")}
+              value={form.values.filter.re_rolls}
+              onChange={(re_rolls) => form.setFieldValue("filter.re_rolls", re_rolls)}
+            />
+            <MinMax
+              label={useTranslateFormFields("mastery_rank.label")}
+              maxAllowed={16}
+              minAllowed={7}
+              value={form.values.filter.mastery_rank}
+              onChange={(mastery_rank) => form.setFieldValue("filter.mastery_rank", mastery_rank)}
+            />
+            <Switch
+              mt="md"
+              label={useTranslateFormFields("required_negative.label")}
+              checked={form.values.filter.required_negative ?? false}
+              onChange={(event) => form.setFieldValue("filter.required_negative", event.currentTarget.checked)}
+            />
+          </Grid.Col>
+        </Grid>
+        <Button mt={15} type="submit" color="blue" radius="md" fullWidth>
+          {useTranslateButtons("save.label")}
+        </Button>
+      </form>
+    </Box>
+  );
+}
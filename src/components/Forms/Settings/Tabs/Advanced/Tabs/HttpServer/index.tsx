@@ -1,7 +1,7 @@
 import { TauriTypes } from "$types";
 import { TooltipIcon } from "@components/Shared/TooltipIcon";
 import { useTranslateForms } from "@hooks/useTranslate.hook";
-import { Box, Checkbox, Collapse, NumberInput, Stack, TextInput, Tooltip } from "@mantine/core";
+import { Box, Checkbox, Collapse, NumberInput, PasswordInput, Stack, TextInput, Tooltip } from "@mantine/core";
 import { UseFormReturnType } from "@mantine/form";
 export type HttpServerPanelProps = {
   form: UseFormReturnType<TauriTypes.Settings>;
@@ -37,6 +37,12 @@ export const HttpServerPanel = ({ form }: HttpServerPanelProps) => {
             {...form.getInputProps(getFieldPath("port"))}
             rightSection={<TooltipIcon label={useTranslateFormFields("port.tooltip")} />}
           />
+          <PasswordInput
+            label={useTranslateFormFields("token.label")}
+            placeholder={useTranslateFormFields("token.placeholder")}
+            description={useTranslateFormFields("token.description")}
+            {...form.getInputProps(getFieldPath("token"))}
+          />
         </Collapse>
       </Stack>
     </Box>
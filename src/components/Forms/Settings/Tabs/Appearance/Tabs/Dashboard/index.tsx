@@ -5,10 +5,11 @@ import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
 import { TooltipIcon } from "@components/Shared/TooltipIcon";
 import { faEdit, faTrashCan } from "@fortawesome/free-solid-svg-icons";
 import { useTranslateForms } from "@hooks/useTranslate.hook";
-import { Box, Button, Divider, Group, Image, NumberInput, Stack, Text } from "@mantine/core";
+import { Box, Button, Checkbox, Divider, Group, Image, NumberInput, Select, Stack, Text, TextInput, Tooltip } from "@mantine/core";
 import { UseFormReturnType } from "@mantine/form";
 import { DataTable } from "mantine-datatable";
 import { useEffect, useState } from "react";
+import i18next from "i18next";
 
 enum Mode {
   None = "none",
@@ -67,6 +68,30 @@ export const DashboardPanel = ({ form, setHideTab, setHideButtons }: DashboardPa
               {...form.getInputProps(getFieldPath("recent_transactions"))}
             />
           </Group>
+          <Group gap="xs" grow align="flex-end">
+            <TextInput
+              label={useTranslateFormFields("timezone.label")}
+              placeholder={useTranslateFormFields("timezone.placeholder")}
+              rightSection={<TooltipIcon label={useTranslateFormFields("timezone.tooltip")} />}
+              radius="md"
+              {...form.getInputProps(getFieldPath("timezone"))}
+            />
+            <Select
+              allowDeselect={false}
+              label={useTranslateFormFields("week_start.label")}
+              data={(i18next.t("weekdays", { returnObjects: true }) as string[]).map((day, index) => ({ value: index.toString(), label: day }))}
+              value={form.values.summary_settings.week_start.toString()}
+              onChange={(value) => form.setFieldValue(getFieldPath("week_start"), Number(value))}
+              radius="md"
+            />
+          </Group>
+          <Tooltip label={useTranslateFormFields("daily_reset.tooltip")}>
+            <Checkbox
+              label={useTranslateFormFields("daily_reset.label")}
+              checked={form.values.summary_settings.daily_reset}
+              onChange={(event) => form.setFieldValue(getFieldPath("daily_reset"), event.currentTarget.checked)}
+            />
+          </Tooltip>
           <Divider />
           <Button onClick={() => setMode(Mode.EditCategories)}>{useTranslateFormButtons("edit_categories")}</Button>
         </Stack>
@@ -0,0 +1,121 @@
+import { TauriTypes } from "$types";
+import api from "@api/index";
+import { PricingStrategyInput } from "@components/Forms/PricingStrategyInput";
+import { SelectTradableItem } from "@components/Forms/SelectTradableItem";
+import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
+import { faTrashCan } from "@fortawesome/free-solid-svg-icons";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { Box, Button, Divider, Group, Select, Stack, Title } from "@mantine/core";
+import { UseFormReturnType } from "@mantine/form";
+import { upperFirst } from "@mantine/hooks";
+import { useQuery } from "@tanstack/react-query";
+
+export type PricingItemAccordionProps = {
+  form: UseFormReturnType<TauriTypes.Settings>;
+};
+
+type Side = "wtb" | "wts";
+
+export const PricingItemAccordion = ({ form }: PricingItemAccordionProps) => {
+  // Translate general
+  const useTranslateForm = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`settings.tabs.live_scraper.item.pricing.${key}`, { ...context }, i18Key);
+
+  // Fetch data from rust side
+  const { data: items } = useQuery({
+    queryKey: ["cache_items"],
+    queryFn: () => api.cache.getTradableItems(),
+  });
+  const tags = Array.from(new Set((items || []).flatMap((item) => item.tags))).map((tag) => ({
+    label: upperFirst(tag.replace("_", " ")),
+    value: tag,
+  }));
+  const getWfmUrl = (wfmId: string) => items?.find((item) => item.wfmId === wfmId)?.wfmUrl || "";
+
+  const getFieldPath = (side: Side) => `live_scraper.items.${side}.pricing`;
+  const getPricing = (side: Side): TauriTypes.PricingSettings => form.values.live_scraper.items[side].pricing;
+  const setPricing = (side: Side, pricing: Partial<TauriTypes.PricingSettings>) =>
+    form.setFieldValue(getFieldPath(side), { ...getPricing(side), ...pricing });
+
+  const renderSide = (side: Side) => {
+    const pricing = getPricing(side);
+    return (
+      <Stack gap="xs">
+        <Title order={5}>{useTranslateForm(`titles.${side}`)}</Title>
+        <PricingStrategyInput
+          label={useTranslateForm("fields.default.label")}
+          value={pricing.default}
+          onChange={(strategy) => setPricing(side, { default: strategy })}
+        />
+        <Divider label={useTranslateForm("titles.categories")} labelPosition="left" />
+        {pricing.categories.map((category, index) => (
+          <Group key={`${side}-category-${index}`} gap="xs" align="flex-end">
+            <Select
+              w={200}
+              searchable
+              label={useTranslateForm("fields.tag.label")}
+              data={tags}
+              value={category.tag}
+              onChange={(tag) =>
+                setPricing(side, { categories: pricing.categories.map((c, i) => (i === index ? { ...c, tag: tag || "" } : c)) })
+              }
+            />
+            <PricingStrategyInput
+              value={category.strategy}
+              onChange={(strategy) => setPricing(side, { categories: pricing.categories.map((c, i) => (i === index ? { ...c, strategy } : c)) })}
+            />
+            <ActionWithTooltip
+              tooltip={useTranslateForm("buttons.delete_tooltip")}
+              icon={faTrashCan}
+              color="red.7"
+              actionProps={{ size: "lg" }}
+              onClick={() => setPricing(side, { categories: pricing.categories.filter((_, i) => i !== index) })}
+            />
+          </Group>
+        ))}
+        <Group>
+          <Button variant="light" onClick={() => setPricing(side, { categories: [...pricing.categories, { tag: "", strategy: { type: "match_top" } }] })}>
+            {useTranslateForm("buttons.add_category_label")}
+          </Button>
+        </Group>
+        <Divider label={useTranslateForm("titles.items")} labelPosition="left" />
+        {pricing.items.map((item, index) => (
+          <Group key={`${side}-item-${index}`} gap="xs" align="flex-end">
+            <SelectTradableItem
+              hideSubType
+              value={getWfmUrl(item.wfmId)}
+              onChange={(selected) =>
+                setPricing(side, { items: pricing.items.map((it, i) => (i === index ? { ...it, wfmId: selected.wfmId } : it)) })
+              }
+            />
+            <PricingStrategyInput
+              value={item.strategy}
+              onChange={(strategy) => setPricing(side, { items: pricing.items.map((it, i) => (i === index ? { ...it, strategy } : it)) })}
+            />
+            <ActionWithTooltip
+              tooltip={useTranslateForm("buttons.delete_tooltip")}
+              icon={faTrashCan}
+              color="red.7"
+              actionProps={{ size: "lg" }}
+              onClick={() => setPricing(side, { items: pricing.items.filter((_, i) => i !== index) })}
+            />
+          </Group>
+        ))}
+        <Group>
+          <Button variant="light" onClick={() => setPricing(side, { items: [...pricing.items, { wfmId: "", strategy: { type: "match_top" } }] })}>
+            {useTranslateForm("buttons.add_item_label")}
+          </Button>
+        </Group>
+      </Stack>
+    );
+  };
+
+  return (
+    <Box h="100%">
+      <Stack gap="lg">
+        {renderSide("wtb")}
+        {renderSide("wts")}
+      </Stack>
+    </Box>
+  );
+};
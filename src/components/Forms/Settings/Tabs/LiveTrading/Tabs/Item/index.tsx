@@ -3,6 +3,7 @@ import { useTranslateForms } from "@hooks/useTranslate.hook";
 import { Accordion } from "@mantine/core";
 import { WTBItemAccordion } from "./Accordion/WTB";
 import { WTSItemAccordion } from "./Accordion/WTS";
+import { PricingItemAccordion } from "./Accordion/Pricing";
 import { SummaryAccordion } from "./Accordion/Summary";
 import { UseFormReturnType } from "@mantine/form";
 
@@ -28,6 +29,11 @@ export const ItemPanel = ({ form }: ItemPanelProps) => {
       component: <WTSItemAccordion form={form} />,
       id: "wts",
     },
+    {
+      label: useTranslateTabs("live_scraper.item.pricing.title"),
+      component: <PricingItemAccordion form={form} />,
+      id: "pricing",
+    },
     {
       label: useTranslateTabs("live_scraper.item.summary.title"),
       component: <SummaryAccordion value={form.values.live_scraper.items} />,
@@ -1,8 +1,14 @@
-import { Box, Group, NumberInput } from "@mantine/core";
+import { Box, Button, Checkbox, Divider, Group, NumberInput, Stack, Switch, Text, Tooltip } from "@mantine/core";
 import { TauriTypes } from "$types";
+import api from "@api/index";
 import { useTranslateForms } from "@hooks/useTranslate.hook";
 import { UseFormReturnType } from "@mantine/form";
+import { modals } from "@mantine/modals";
+import { useQuery } from "@tanstack/react-query";
 import { TooltipIcon } from "@components/Shared/TooltipIcon";
+import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
+import { RivenWatch } from "@components/Forms/RivenWatch";
+import { faEdit, faTrashCan } from "@fortawesome/free-solid-svg-icons";
 
 export type RivenPanelProps = {
   form: UseFormReturnType<TauriTypes.Settings>;
@@ -14,9 +20,47 @@ export const RivenPanel = ({ form }: RivenPanelProps) => {
     useTranslateForms(`settings.tabs.live_scraper.riven.${key}`, { ...context }, i18Key);
   const useTranslateFormFields = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
     useTranslateForm(`fields.${key}`, { ...context }, i18Key);
+  const useTranslateWatches = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForm(`watches.${key}`, { ...context }, i18Key);
+
+  // Fetch data from rust side
+  const { data: weapons } = useQuery({
+    queryKey: ["cache_riven_weapons"],
+    queryFn: () => api.cache.getRivenWeapons(),
+  });
+  const getWeaponName = (url: string) => weapons?.find((weapon) => weapon.wfmRivenUrl === url)?.name || url;
 
   // Form
   const getFieldPath = (field: string) => `live_scraper.rivens.${field}`;
+  const watches = form.values.live_scraper.rivens.wtb.watches;
+  const setWatches = (watches: TauriTypes.RivenWatchSetting[]) => form.setFieldValue(getFieldPath("wtb.watches"), watches);
+
+  const OpenWatchModal = (watch?: TauriTypes.RivenWatchSetting) => {
+    const id = modals.open({
+      title: useTranslateWatches(watch ? "edit_title" : "add_title"),
+      size: "75vw",
+      children: (
+        <RivenWatch
+          value={
+            watch ?? {
+              id: crypto.randomUUID(),
+              enabled: true,
+              wfm_weapon_url: "",
+              filter: { enabled: true, attributes: [] },
+              target_price: 100,
+              min_score: 50,
+            }
+          }
+          onSubmit={(data) => {
+            if (watch) setWatches(watches.map((w) => (w.id === data.id ? data : w)));
+            else setWatches([...watches, data]);
+            modals.close(id);
+          }}
+        />
+      ),
+    });
+  };
+
   return (
     <Box h="100%" p={"md"}>
       <Group gap={"md"}>
@@ -46,6 +90,15 @@ export const RivenPanel = ({ form }: RivenPanelProps) => {
           radius="md"
           {...form.getInputProps(getFieldPath("wts.max_results"))}
         />
+        <NumberInput
+          label={useTranslateFormFields("min_similarity.label")}
+          min={0}
+          max={100}
+          placeholder={useTranslateFormFields("min_similarity.placeholder")}
+          rightSection={<TooltipIcon label={useTranslateFormFields("min_similarity.tooltip")} />}
+          radius="md"
+          {...form.getInputProps(getFieldPath("wts.min_similarity"))}
+        />
         <NumberInput
           label={useTranslateFormFields("update_interval.label")}
           min={-1}
@@ -56,7 +109,48 @@ export const RivenPanel = ({ form }: RivenPanelProps) => {
           radius="md"
           {...form.getInputProps(getFieldPath("general.update_interval"))}
         />
+        <Tooltip label={useTranslateFormFields("wtb_enabled.tooltip")}>
+          <Checkbox
+            label={useTranslateFormFields("wtb_enabled.label")}
+            checked={form.values.live_scraper.rivens.wtb.enabled}
+            onChange={(event) => form.setFieldValue(getFieldPath("wtb.enabled"), event.currentTarget.checked)}
+          />
+        </Tooltip>
+        <NumberInput
+          label={useTranslateFormFields("wtb_update_interval.label")}
+          min={1}
+          placeholder={useTranslateFormFields("wtb_update_interval.placeholder")}
+          rightSection={<TooltipIcon label={useTranslateFormFields("wtb_update_interval.tooltip")} />}
+          radius="md"
+          {...form.getInputProps(getFieldPath("wtb.update_interval"))}
+        />
       </Group>
+      <Divider mt="md" label={useTranslateWatches("title")} labelPosition="left" />
+      <Stack gap="xs">
+        {watches.map((watch, index) => (
+          <Group key={watch.id} gap="md">
+            <Switch
+              checked={watch.enabled}
+              onChange={(event) => form.setFieldValue(getFieldPath(`wtb.watches.${index}.enabled`), event.currentTarget.checked)}
+            />
+            <Text w={200}>{getWeaponName(watch.wfm_weapon_url)}</Text>
+            <Text w={150}>{useTranslateWatches("target_price", { price: watch.target_price })}</Text>
+            <Text w={150}>{useTranslateWatches("min_score", { score: watch.min_score })}</Text>
+            <ActionWithTooltip tooltip={useTranslateWatches("edit_tooltip")} icon={faEdit} onClick={() => OpenWatchModal(watch)} />
+            <ActionWithTooltip
+              tooltip={useTranslateWatches("delete_tooltip")}
+              icon={faTrashCan}
+              color="red.7"
+              onClick={() => setWatches(watches.filter((w) => w.id !== watch.id))}
+            />
+          </Group>
+        ))}
+        <Group>
+          <Button variant="light" onClick={() => OpenWatchModal()}>
+            {useTranslateWatches("add_label")}
+          </Button>
+        </Group>
+      </Stack>
     </Box>
   );
 };
@@ -0,0 +1,89 @@
+import { TauriTypes } from "$types";
+import api from "@api/index";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { Badge, Button, Group, ScrollAreaAutosize, Table, Text, Tooltip } from "@mantine/core";
+import { useMutation, useQuery } from "@tanstack/react-query";
+import dayjs from "dayjs";
+
+const STATUS_COLORS: Record<TauriTypes.DeliveryStatus, string> = {
+  pending: "blue.7",
+  retrying: "yellow.7",
+  delivered: "green.7",
+  failed: "red.7",
+};
+
+export const DeliveryHistory = () => {
+  const t = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`settings.tabs.notifications.delivery_history.${key}`, { ...context }, i18Key);
+
+  // Fetch data from rust side
+  const { data, refetch } = useQuery({
+    queryKey: ["notification_deliveries"],
+    queryFn: () => api.app.notify_get_deliveries(),
+    refetchInterval: 5000,
+  });
+
+  // Mutations
+  const resendMutation = useMutation({
+    mutationFn: (id?: string) => api.app.notify_resend(id),
+    onSuccess: () => refetch(),
+  });
+
+  const lastAttempt = (message: TauriTypes.QueuedNotification) => message.attempts[message.attempts.length - 1];
+
+  return (
+    <ScrollAreaAutosize mah={"78vh"} scrollbarSize={6}>
+      <Group justify="flex-end" m="xs">
+        <Button
+          size="xs"
+          loading={resendMutation.isPending}
+          disabled={!data?.some((message) => message.status === "failed")}
+          onClick={() => resendMutation.mutate(undefined)}
+        >
+          {t("buttons.resend_failed")}
+        </Button>
+      </Group>
+      <Table striped>
+        <Table.Thead>
+          <Table.Tr>
+            <Table.Th>{t("columns.created_at")}</Table.Th>
+            <Table.Th>{t("columns.channel")}</Table.Th>
+            <Table.Th>{t("columns.status")}</Table.Th>
+            <Table.Th>{t("columns.tries")}</Table.Th>
+            <Table.Th>{t("columns.last_result")}</Table.Th>
+            <Table.Th />
+          </Table.Tr>
+        </Table.Thead>
+        <Table.Tbody>
+          {data?.map((message) => {
+            const attempt = lastAttempt(message);
+            return (
+              <Table.Tr key={message.id}>
+                <Table.Td>{dayjs(message.created_at).format("DD-MM-YYYY HH:mm:ss")}</Table.Td>
+                <Table.Td>{t(`channels.${message.channel}`)}</Table.Td>
+                <Table.Td>
+                  <Badge color={STATUS_COLORS[message.status]}>{t(`status.${message.status}`)}</Badge>
+                </Table.Td>
+                <Table.Td>{message.attempts.length}</Table.Td>
+                <Table.Td>
+                  {attempt && (
+                    <Tooltip label={attempt.error || "-"} multiline maw={400}>
+                      <Text size="sm">{attempt.status_code ?? t("no_response")}</Text>
+                    </Tooltip>
+                  )}
+                </Table.Td>
+                <Table.Td>
+                  {(message.status === "failed" || message.status === "delivered") && (
+                    <Button size="xs" variant="light" onClick={() => resendMutation.mutate(message.id)}>
+                      {t("buttons.resend")}
+                    </Button>
+                  )}
+                </Table.Td>
+              </Table.Tr>
+            );
+          })}
+        </Table.Tbody>
+      </Table>
+    </ScrollAreaAutosize>
+  );
+};
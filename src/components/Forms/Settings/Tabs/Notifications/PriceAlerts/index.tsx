@@ -0,0 +1,129 @@
+import { TauriTypes } from "$types";
+import { SelectTradableItem } from "@components/Forms/SelectTradableItem";
+import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
+import { TooltipIcon } from "@components/Shared/TooltipIcon";
+import { faTrashCan } from "@fortawesome/free-solid-svg-icons";
+import { useTranslateForms } from "@hooks/useTranslate.hook";
+import { Alert, Box, Button, Checkbox, Divider, Group, NumberInput, ScrollAreaAutosize, Select, Stack, Switch } from "@mantine/core";
+import { UseFormReturnType } from "@mantine/form";
+
+export type PriceAlertsProps = {
+  form: UseFormReturnType<TauriTypes.Settings>;
+};
+
+type ConditionType = TauriTypes.PriceAlertCondition["type"];
+const CONDITIONS: ConditionType[] = ["lowest_sell_below", "highest_buy_above", "spread_above", "moving_avg_shift"];
+
+// Default threshold of each condition when it gets selected
+const withDefaults = (type: ConditionType): TauriTypes.PriceAlertCondition => {
+  switch (type) {
+    case "highest_buy_above":
+      return { type, price: 50 };
+    case "spread_above":
+      return { type, spread: 10 };
+    case "moving_avg_shift":
+      return { type, percent: -10 };
+    default:
+      return { type: "lowest_sell_below", price: 10 };
+  }
+};
+
+const getThreshold = (condition: TauriTypes.PriceAlertCondition): number => {
+  switch (condition.type) {
+    case "spread_above":
+      return condition.spread;
+    case "moving_avg_shift":
+      return condition.percent;
+    default:
+      return condition.price;
+  }
+};
+
+const setThreshold = (condition: TauriTypes.PriceAlertCondition, value: number): TauriTypes.PriceAlertCondition => {
+  switch (condition.type) {
+    case "spread_above":
+      return { ...condition, spread: value };
+    case "moving_avg_shift":
+      return { ...condition, percent: value };
+    default:
+      return { ...condition, price: value };
+  }
+};
+
+export const PriceAlerts = ({ form }: PriceAlertsProps) => {
+  const t = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslateForms(`settings.tabs.notifications.price_alerts.${key}`, { ...context }, i18Key);
+
+  const getFieldPath = (field: string) => `notifications.price_alerts.${field}`;
+  const rules = form.values.notifications.price_alerts.rules;
+  const updateRule = (index: number, rule: Partial<TauriTypes.PriceAlertRule>) =>
+    form.setFieldValue(getFieldPath("rules"), rules.map((r, i) => (i === index ? { ...r, ...rule } : r)));
+
+  return (
+    <ScrollAreaAutosize mah={"78vh"} scrollbarSize={6}>
+      <Box p={"md"}>
+        <Alert color="blue" variant="light">
+          {t("live_trading_notice")}
+        </Alert>
+        <Group gap="md" mt="md" align="flex-end">
+          <Checkbox
+            label={t("fields.enabled.label")}
+            checked={form.values.notifications.price_alerts.enabled}
+            onChange={(event) => form.setFieldValue(getFieldPath("enabled"), event.currentTarget.checked)}
+          />
+          <NumberInput
+            label={t("fields.update_interval.label")}
+            min={1}
+            rightSection={<TooltipIcon label={t("fields.update_interval.tooltip")} />}
+            radius="md"
+            {...form.getInputProps(getFieldPath("update_interval"))}
+          />
+        </Group>
+        <Divider mt="md" label={t("rules_title")} labelPosition="left" />
+        <Stack gap="xs">
+          {rules.map((rule, index) => (
+            <Group key={rule.id} gap="xs" align="flex-end">
+              <Switch mb={8} checked={rule.enabled} onChange={(event) => updateRule(index, { enabled: event.currentTarget.checked })} />
+              <SelectTradableItem value={rule.wfm_url} onChange={(item) => updateRule(index, { wfm_url: item.wfmUrl, subType: item.sub_type })} />
+              <Select
+                w={220}
+                label={t("fields.condition.label")}
+                data={CONDITIONS.map((type) => ({ value: type, label: t(`fields.condition.options.${type}`) }))}
+                value={rule.condition.type}
+                allowDeselect={false}
+                onChange={(type) => type && updateRule(index, { condition: withDefaults(type as ConditionType) })}
+              />
+              <NumberInput
+                w={120}
+                label={t(`fields.threshold.${rule.condition.type}`)}
+                suffix={rule.condition.type == "moving_avg_shift" ? "%" : "p"}
+                value={getThreshold(rule.condition)}
+                onChange={(value) => updateRule(index, { condition: setThreshold(rule.condition, Number(value) || 0) })}
+              />
+              <ActionWithTooltip
+                tooltip={t("buttons.delete_tooltip")}
+                icon={faTrashCan}
+                color="red.7"
+                actionProps={{ size: "lg" }}
+                onClick={() => form.setFieldValue(getFieldPath("rules"), rules.filter((_, i) => i !== index))}
+              />
+            </Group>
+          ))}
+          <Group>
+            <Button
+              variant="light"
+              onClick={() =>
+                form.setFieldValue(getFieldPath("rules"), [
+                  ...rules,
+                  { id: crypto.randomUUID(), enabled: true, wfm_url: "", condition: withDefaults("lowest_sell_below") },
+                ])
+              }
+            >
+              {t("buttons.add_rule_label")}
+            </Button>
+          </Group>
+        </Stack>
+      </Box>
+    </ScrollAreaAutosize>
+  );
+};
@@ -4,6 +4,8 @@ import { useTranslateForms } from "@hooks/useTranslate.hook";
 import { Tabs, Text } from "@mantine/core";
 import { UseFormReturnType } from "@mantine/form";
 import { useState } from "react";
+import { DeliveryHistory } from "./DeliveryHistory";
+import { PriceAlerts } from "./PriceAlerts";
 export type NotificationsPanelProps = {
   form: UseFormReturnType<TauriTypes.Settings>;
   onHideButtons?: (value: boolean) => void;
@@ -27,6 +29,8 @@ export const NotificationsPanel = ({ form, onHideButtons }: NotificationsPanelPr
     { id: "on_new_conversation", labelKey: "on_new_conversation_title" },
     { id: "on_new_trade", labelKey: "on_new_trade_title" },
     { id: "on_wfm_chat_message", labelKey: "on_wfm_chat_message_title" },
+    { id: "on_riven_deal", labelKey: "on_riven_deal_title" },
+    { id: "on_price_alert", labelKey: "on_price_alert_title" },
   ] as const satisfies Array<{ id: NotificationKey; labelKey: string }>;
 
   const panels = tabs.map(({ id, labelKey }) => {
@@ -57,12 +61,26 @@ export const NotificationsPanel = ({ form, onHideButtons }: NotificationsPanelPr
             </Text>
           </Tabs.Tab>
         ))}
+        <Tabs.Tab value="price_alerts">
+          <Text size="sm" c={notificationValues.price_alerts.enabled ? "green.7" : "red.7"}>
+            {t("price_alerts_title")}
+          </Text>
+        </Tabs.Tab>
+        <Tabs.Tab value="delivery_history">
+          <Text size="sm">{t("delivery_history_title")}</Text>
+        </Tabs.Tab>
       </Tabs.List>
       {panels.map((panel) => (
         <Tabs.Panel value={panel.id} key={panel.id}>
           {panel.component}
         </Tabs.Panel>
       ))}
+      <Tabs.Panel value="price_alerts">
+        <PriceAlerts form={form} />
+      </Tabs.Panel>
+      <Tabs.Panel value="delivery_history">
+        <DeliveryHistory />
+      </Tabs.Panel>
     </Tabs>
   );
 };
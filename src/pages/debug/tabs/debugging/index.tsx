@@ -64,6 +64,36 @@ export const DebuggingPanel = ({}: DebuggingPanelProps) => {
             }}
           />
         </Tooltip>
+        <Tooltip label={useTranslateTabDebugging("simulation_tooltip")}>
+          <Checkbox
+            label={useTranslateTabDebugging("simulation_label")}
+            checked={settings?.debugging.live_scraper.simulation || false}
+            size="sm"
+            onChange={async (e) => {
+              if (!settings) return;
+              await api.app.updateSettings({
+                ...settings,
+                debugging: { ...settings.debugging, live_scraper: { ...settings.debugging.live_scraper, simulation: e.currentTarget.checked } },
+              });
+              SendTauriEvent(TauriTypes.Events.RefreshSettings);
+            }}
+          />
+        </Tooltip>
+        <Tooltip label={useTranslateTabDebugging("record_snapshots_tooltip")}>
+          <Checkbox
+            label={useTranslateTabDebugging("record_snapshots_label")}
+            checked={settings?.debugging.live_scraper.record_snapshots || false}
+            size="sm"
+            onChange={async (e) => {
+              if (!settings) return;
+              await api.app.updateSettings({
+                ...settings,
+                debugging: { ...settings.debugging, live_scraper: { ...settings.debugging.live_scraper, record_snapshots: e.currentTarget.checked } },
+              });
+              SendTauriEvent(TauriTypes.Events.RefreshSettings);
+            }}
+          />
+        </Tooltip>
       </Group>
       <DataTable
         className={`${classes.dataTableLogging}`}
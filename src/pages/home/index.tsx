@@ -1,7 +1,7 @@
 import { Container, Text, Divider, Grid, Group, Paper, ScrollArea, useMantineTheme } from "@mantine/core";
 import api from "@api/index";
 import { FontAwesomeIcon } from "@fortawesome/react-fontawesome";
-import { faBoxOpen, faCalendarAlt, faMoneyBill } from "@fortawesome/free-solid-svg-icons";
+import { faBoxOpen, faCalendarAlt, faCalendarWeek, faMoneyBill } from "@fortawesome/free-solid-svg-icons";
 import { useTranslatePages } from "@hooks/useTranslate.hook";
 import { TextTranslate } from "@components/Shared/TextTranslate";
 import i18next from "i18next";
@@ -32,7 +32,7 @@ export default function HomePage() {
   return (
     <Container size={"100%"}>
       <Grid className={classes.wrapper} data-has-alert={useHasAlert()}>
-        <Grid.Col span={4}>
+        <Grid.Col span={3}>
           <StatsWithIcon
             count={summary?.total.total_profit || 0}
             color={theme.other.chartStyles.total.bgColor}
@@ -51,7 +51,7 @@ export default function HomePage() {
             }
           />
         </Grid.Col>
-        <Grid.Col span={4}>
+        <Grid.Col span={3}>
           <StatsWithIcon
             count={summary?.today.summary.total_profit || 0}
             color={theme.other.chartStyles.today.bgColor}
@@ -70,7 +70,26 @@ export default function HomePage() {
             }
           />
         </Grid.Col>
-        <Grid.Col span={4}>
+        <Grid.Col span={3}>
+          <StatsWithIcon
+            count={summary?.this_week.summary.total_profit || 0}
+            color={theme.other.chartStyles.lastDays.bgColor}
+            title={useTranslateCards("this_week.title")}
+            icon={<FontAwesomeIcon size="2x" icon={faCalendarWeek} />}
+            footer={
+              <TextTranslate
+                i18nKey={useTranslateCards("this_week.footer")}
+                values={{
+                  sales: summary?.this_week.summary.sale_count || 0,
+                  purchases: summary?.this_week.summary.purchases_count || 0,
+                  quantity: summary?.this_week.summary.total_transactions || 0,
+                  profit_margin: (summary?.this_week.summary.profit_margin || 0).toFixed(2),
+                }}
+              />
+            }
+          />
+        </Grid.Col>
+        <Grid.Col span={3}>
           <StatsWithIcon
             count={summary?.best_seller.total_profit || 0}
             color={theme.other.chartStyles.lastDays.bgColor}
@@ -115,7 +134,7 @@ export default function HomePage() {
         <Grid.Col span={4}>
           <BarCardChart
             title={useTranslateCards("today.bar_chart.title")}
-            labels={summary?.today.chart.labels || []}
+            labels={(summary?.today.chart.labels || []).map((label) => label.slice(-5))}
             chartStyle={{ background: theme.other.chartStyles.today.bgColor, height: "200px" }}
             datasets={[
               {
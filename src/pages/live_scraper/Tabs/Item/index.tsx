@@ -68,14 +68,23 @@ export const ItemPanel = ({ isActive }: ItemPanelProps = {}) => {
       setLoadingRows,
     });
   // Modals
-  const { OpenMinimumPriceModal, OpenWTSModal, OpenDeleteMultipleModal, OpenUpdateMultipleModal, OpenSellModal, OpenInfoModal, OpenDeleteModal } =
-    useModals({
-      updateMutation,
-      updateMultipleMutation,
-      sellStockMutation,
-      deleteMutation,
-      deleteMultipleMutation,
-    });
+  const {
+    OpenMinimumPriceModal,
+    OpenWTSModal,
+    OpenDeleteMultipleModal,
+    OpenUpdateMultipleModal,
+    OpenSellModal,
+    OpenInfoModal,
+    OpenDeleteModal,
+    OpenExportModal,
+  } = useModals({
+    exportMutation,
+    updateMutation,
+    updateMultipleMutation,
+    sellStockMutation,
+    deleteMutation,
+    deleteMultipleMutation,
+  });
   const handleRefresh = (data: { id: string }) => {
     if (data.id) setSelectedRecords((prev) => prev.filter((record) => record.id !== Number(data.id)));
     refetchQueries(true);
@@ -153,7 +162,7 @@ export const ItemPanel = ({ isActive }: ItemPanelProps = {}) => {
               icon={faDownload}
               iconProps={{ size: "xs" }}
               actionProps={{ size: "sm", disabled: !canExport }}
-              onClick={() => exportMutation.mutate(queryData)}
+              onClick={() => OpenExportModal(queryData)}
             />
             <ActionWithTooltip
               tooltip={useTranslate("update_multiple_tooltip")}
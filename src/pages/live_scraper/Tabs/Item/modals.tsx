@@ -1,11 +1,30 @@
 import { TauriTypes } from "$types";
 import { GenerateTradeMessageModal, GenerateTradeMessageModalProps } from "@components/Modals/GenerateTradeMessage";
 import { ItemDetailsModal, Operations } from "@components/Modals/ItemDetails";
-import { useTranslateCommon, useTranslateModals } from "@hooks/useTranslate.hook";
+import { useTranslateCommon, useTranslateModals, useTranslateForms } from "@hooks/useTranslate.hook";
 import { Text } from "@mantine/core";
 import { modals } from "@mantine/modals";
+import { ExportData } from "@components/Forms/ExportData";
+
+const EXPORT_COLUMNS = [
+  "id",
+  "item_name",
+  "item_unique_name",
+  "sub_type.rank",
+  "sub_type.variant",
+  "bought",
+  "minimum_price",
+  "list_price",
+  "owned",
+  "status",
+  "created_at",
+  "updated_at",
+];
 
 interface ModalHooks {
+  exportMutation: {
+    mutateAsync: (data: { query: TauriTypes.StockItemControllerGetListParams; options: TauriTypes.ExportOptions }) => Promise<any>;
+  };
   updateMutation: {
     mutateAsync: (data: TauriTypes.UpdateStockItem) => Promise<any>;
   };
@@ -23,7 +42,14 @@ interface ModalHooks {
   };
 }
 
-export const useModals = ({ updateMutation, updateMultipleMutation, sellStockMutation, deleteMutation, deleteMultipleMutation }: ModalHooks) => {
+export const useModals = ({
+  exportMutation,
+  updateMutation,
+  updateMultipleMutation,
+  sellStockMutation,
+  deleteMutation,
+  deleteMultipleMutation,
+}: ModalHooks) => {
   const OpenMinimumPriceModal = (id: number, minimum_price: number) => {
     modals.openContextModal({
       modal: "prompt",
@@ -150,7 +176,25 @@ export const useModals = ({ updateMutation, updateMultipleMutation, sellStockMut
     });
   };
 
+  const OpenExportModal = (query: TauriTypes.StockItemControllerGetListParams) => {
+    modals.open({
+      title: useTranslateForms("export_data.title"),
+      children: (
+        <ExportData
+          columns={EXPORT_COLUMNS}
+          from_date={query.from_date}
+          to_date={query.to_date}
+          onSubmit={async ({ options, from_date, to_date }) => {
+            modals.closeAll();
+            await exportMutation.mutateAsync({ query: { ...query, from_date, to_date }, options });
+          }}
+        />
+      ),
+    });
+  };
+
   return {
+    OpenExportModal,
     OpenMinimumPriceModal,
     OpenSellModal,
     OpenInfoModal,
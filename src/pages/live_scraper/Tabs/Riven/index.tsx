@@ -84,8 +84,10 @@ export const RivenPanel = ({ isActive }: RivenPanelProps = {}) => {
     OpenUpdateMultipleModal,
     OpenDeleteMultipleModal,
     OpenWTSModal,
+    OpenExportModal,
   } = useStockModals({
     useTranslateBasePrompt,
+    exportMutation,
     createMutation,
     updateMutation,
     updateMultipleMutation,
@@ -149,7 +151,7 @@ export const RivenPanel = ({ isActive }: RivenPanelProps = {}) => {
               icon={faDownload}
               iconProps={{ size: "xs" }}
               actionProps={{ size: "sm", disabled: !canExport }}
-              onClick={() => exportMutation.mutate(queryData)}
+              onClick={() => OpenExportModal(queryData)}
             />
             <ActionWithTooltip
               tooltip={useTranslate("update_multiple_tooltip")}
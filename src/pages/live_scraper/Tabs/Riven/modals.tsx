@@ -3,10 +3,32 @@ import { CreateRiven } from "@components/Forms/CreateRiven";
 import { RivenFilter } from "@components/Forms/RivenFilter";
 import { GenerateTradeMessageModal, GenerateTradeMessageModalProps } from "@components/Modals/GenerateTradeMessage";
 import { Operations, RivenDetailsModal } from "@components/Modals/RivenDetails";
-import { useTranslateCommon, useTranslateModals } from "@hooks/useTranslate.hook";
+import { useTranslateCommon, useTranslateModals, useTranslateForms } from "@hooks/useTranslate.hook";
 import { Text } from "@mantine/core";
 import { modals } from "@mantine/modals";
+import { ExportData } from "@components/Forms/ExportData";
+
+const EXPORT_COLUMNS = [
+  "id",
+  "weapon_name",
+  "weapon_type",
+  "mod_name",
+  "sub_type.rank",
+  "mastery_rank",
+  "re_rolls",
+  "polarity",
+  "bought",
+  "minimum_price",
+  "list_price",
+  "status",
+  "created_at",
+  "updated_at",
+];
+
 interface ModalHooks {
+  exportMutation: {
+    mutateAsync: (data: { query: TauriTypes.StockRivenControllerGetListParams; options: TauriTypes.ExportOptions }) => Promise<any>;
+  };
   useTranslateBasePrompt: (key: string, context?: { [key: string]: any }) => string;
   updateMutation: {
     mutateAsync: (data: TauriTypes.UpdateStockRiven) => Promise<any>;
@@ -29,6 +51,7 @@ interface ModalHooks {
 }
 
 export const useStockModals = ({
+  exportMutation,
   useTranslateBasePrompt,
   updateMutation,
   updateMultipleMutation,
@@ -199,7 +222,25 @@ export const useStockModals = ({
       ),
     });
   };
+  const OpenExportModal = (query: TauriTypes.StockRivenControllerGetListParams) => {
+    modals.open({
+      title: useTranslateForms("export_data.title"),
+      children: (
+        <ExportData
+          columns={EXPORT_COLUMNS}
+          from_date={query.from_date}
+          to_date={query.to_date}
+          onSubmit={async ({ options, from_date, to_date }) => {
+            modals.closeAll();
+            await exportMutation.mutateAsync({ query: { ...query, from_date, to_date }, options });
+          }}
+        />
+      ),
+    });
+  };
+
   return {
+    OpenExportModal,
     OpenMinimumPriceModal,
     OpenSellModal,
     OpenFilterModal,
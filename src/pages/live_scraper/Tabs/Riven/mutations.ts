@@ -6,7 +6,8 @@ export const useStockMutations = ({ refetchQueries, setLoadingRows }: MutationHo
   const hooks = { refetchQueries, setLoadingRows };
   const exportMutation = createGenericMutation(
     {
-      mutationFn: (data: TauriTypes.StockItemControllerGetListParams) => api.stock_riven.exportJson(data),
+      mutationFn: (data: { query: TauriTypes.StockRivenControllerGetListParams; options: TauriTypes.ExportOptions }) =>
+        api.stock_riven.exportData(data.query, data.options),
       successKey: "export_data",
       errorKey: "export_data",
       getSuccessMessage: (data: any) => ({ path: data }),
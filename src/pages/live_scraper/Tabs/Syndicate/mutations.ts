@@ -6,7 +6,8 @@ export const useMutations = ({ refetchQueries, setLoadingRows }: MutationHooks)
   const hooks = { refetchQueries, setLoadingRows };
   const exportMutation = createGenericMutation(
     {
-      mutationFn: (data: TauriTypes.StockItemControllerGetListParams) => api.stock_item.exportJson(data),
+      mutationFn: (data: { query: TauriTypes.StockItemControllerGetListParams; options: TauriTypes.ExportOptions }) =>
+        api.stock_item.exportData(data.query, data.options),
       successKey: "export_data",
       errorKey: "export_data",
       getSuccessMessage: (data: any) => ({ path: data }),
@@ -64,14 +64,23 @@ export const WishListPanel = ({ isActive }: WishListPanelProps = {}) => {
       setLoadingRows,
     });
   // Modals
-  const { OpenMinimumPriceModal, OpenWTBModal, OpenUpdateMultipleModal, OpenDeleteModal, OpenDeleteMultipleModal, OpenBoughtModal, OpenInfoModal } =
-    useStockModals({
-      updateMutation,
-      updateMultipleMutation,
-      boughtMutation,
-      deleteMutation,
-      deleteMultipleMutation,
-    });
+  const {
+    OpenMinimumPriceModal,
+    OpenWTBModal,
+    OpenUpdateMultipleModal,
+    OpenDeleteModal,
+    OpenDeleteMultipleModal,
+    OpenBoughtModal,
+    OpenInfoModal,
+    OpenExportModal,
+  } = useStockModals({
+    exportMutation,
+    updateMutation,
+    updateMultipleMutation,
+    boughtMutation,
+    deleteMutation,
+    deleteMultipleMutation,
+  });
   const handleRefresh = (data: { id: string }) => {
     if (data.id) setSelectedRecords((prev) => prev.filter((record) => record.id !== Number(data.id)));
     refetchQueries(true);
@@ -131,7 +140,7 @@ export const WishListPanel = ({ isActive }: WishListPanelProps = {}) => {
               icon={faDownload}
               iconProps={{ size: "xs" }}
               actionProps={{ size: "sm", disabled: !canExport }}
-              onClick={() => exportMutation.mutate(queryData)}
+              onClick={() => OpenExportModal(queryData)}
             />
             <ActionWithTooltip
               tooltip={useTranslate("update_multiple_tooltip")}
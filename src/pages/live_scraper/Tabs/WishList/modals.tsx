@@ -1,10 +1,29 @@
 import { TauriTypes } from "$types";
 import { GenerateTradeMessageModal, GenerateTradeMessageModalProps } from "@components/Modals/GenerateTradeMessage";
 import { ItemDetailsModal, Operations } from "@components/Modals/ItemDetails";
-import { useTranslateCommon, useTranslateModals } from "@hooks/useTranslate.hook";
+import { useTranslateCommon, useTranslateModals, useTranslateForms } from "@hooks/useTranslate.hook";
 import { Text } from "@mantine/core";
 import { modals } from "@mantine/modals";
+import { ExportData } from "@components/Forms/ExportData";
+
+const EXPORT_COLUMNS = [
+  "id",
+  "item_name",
+  "item_unique_name",
+  "sub_type.rank",
+  "sub_type.variant",
+  "quantity",
+  "maximum_price",
+  "list_price",
+  "status",
+  "created_at",
+  "updated_at",
+];
+
 interface ModalHooks {
+  exportMutation: {
+    mutateAsync: (data: { query: TauriTypes.WishListControllerGetListParams; options: TauriTypes.ExportOptions }) => Promise<any>;
+  };
   updateMutation: {
     mutateAsync: (data: TauriTypes.UpdateWishListItem) => Promise<any>;
   };
@@ -22,7 +41,14 @@ interface ModalHooks {
   };
 }
 
-export const useStockModals = ({ updateMutation, deleteMutation, boughtMutation, updateMultipleMutation, deleteMultipleMutation }: ModalHooks) => {
+export const useStockModals = ({
+  exportMutation,
+  updateMutation,
+  deleteMutation,
+  boughtMutation,
+  updateMultipleMutation,
+  deleteMultipleMutation,
+}: ModalHooks) => {
   const OpenMinimumPriceModal = (id: number, max_price: number) => {
     modals.openContextModal({
       modal: "prompt",
@@ -139,7 +165,25 @@ export const useStockModals = ({ updateMutation, deleteMutation, boughtMutation,
     });
   };
 
+  const OpenExportModal = (query: TauriTypes.WishListControllerGetListParams) => {
+    modals.open({
+      title: useTranslateForms("export_data.title"),
+      children: (
+        <ExportData
+          columns={EXPORT_COLUMNS}
+          from_date={query.from_date}
+          to_date={query.to_date}
+          onSubmit={async ({ options, from_date, to_date }) => {
+            modals.closeAll();
+            await exportMutation.mutateAsync({ query: { ...query, from_date, to_date }, options });
+          }}
+        />
+      ),
+    });
+  };
+
   return {
+    OpenExportModal,
     OpenMinimumPriceModal,
     OpenInfoModal,
     OpenUpdateMultipleModal,
@@ -7,7 +7,8 @@ export const useWishListMutations = ({ refetchQueries, setLoadingRows }: Mutatio
 
   const exportMutation = createGenericMutation(
     {
-      mutationFn: (data: TauriTypes.WishListControllerGetListParams) => api.wish_list.exportJson(data),
+      mutationFn: (data: { query: TauriTypes.WishListControllerGetListParams; options: TauriTypes.ExportOptions }) =>
+        api.wish_list.exportData(data.query, data.options),
       successKey: "export_data",
       errorKey: "export_data",
       getSuccessMessage: (data: any) => ({ path: data }),
@@ -1,4 +1,6 @@
 import { LiveScraperControl } from "@components/Forms/LiveScraperControl";
+import { SchedulerStats } from "@components/DataDisplay/SchedulerStats";
+import { useLiveScraperContext } from "@contexts/liveScraper.context";
 import { useHasAlert } from "@hooks/useHasAlert.hook";
 import { useTranslatePages } from "@hooks/useTranslate.hook";
 import { Box, Container, Tabs } from "@mantine/core";
@@ -7,6 +9,9 @@ import classes from "./LiveScraper.module.css";
 import { ItemPanel, RivenPanel, SyndicatePanel, WishListPanel } from "./Tabs";
 
 export default function LiveScraperPage() {
+  // States
+  const { is_running } = useLiveScraperContext();
+
   // Translate general
   const useTranslateForm = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
     useTranslatePages(`live_scraper.${key}`, { ...context }, i18Key);
@@ -46,6 +51,7 @@ export default function LiveScraperPage() {
     <Container size={"100%"}>
       <Box data-has-alert={useHasAlert()} className={classes.liveScraper}>
         <LiveScraperControl />
+        <SchedulerStats isRunning={is_running} />
       </Box>
       <Tabs value={activeTab} onChange={(value) => setActiveTab(value || tabs[0].id)}>
         <Tabs.List>
@@ -81,7 +81,8 @@ export const TransactionPanel = ({ isActive }: TransactionPanelProps = {}) => {
   });
 
   // Modals
-  const { OpenDeleteModal, OpenUpdateModal, OpenDeleteBulkModal } = useModals({
+  const { OpenDeleteModal, OpenUpdateModal, OpenDeleteBulkModal, OpenExportModal } = useModals({
+    exportMutation,
     refetchQueries,
     deleteMutation,
     setLoadingRows,
@@ -145,7 +146,7 @@ export const TransactionPanel = ({ isActive }: TransactionPanelProps = {}) => {
               icon={faDownload}
               iconProps={{ size: "xs" }}
               actionProps={{ size: "sm", disabled: !canExport }}
-              onClick={() => exportMutation.mutate(queryData)}
+              onClick={() => OpenExportModal(queryData)}
             />
             <ActionWithTooltip
               tooltip={useTranslateButtons("show_financial_report_tooltip")}
@@ -1,10 +1,32 @@
 import { modals } from "@mantine/modals";
+import { ExportData } from "@components/Forms/ExportData";
 import { Text } from "@mantine/core";
 import { TauriTypes } from "$types";
-import { useTranslateCommon } from "@hooks/useTranslate.hook";
+import { useTranslateCommon, useTranslateForms } from "@hooks/useTranslate.hook";
 import { UpdateTransaction } from "@components/Forms/UpdateTransaction";
 
+const EXPORT_COLUMNS = [
+  "id",
+  "item_name",
+  "item_type",
+  "item_unique_name",
+  "sub_type.rank",
+  "sub_type.variant",
+  "tags",
+  "transaction_type",
+  "quantity",
+  "user_name",
+  "price",
+  "profit",
+  "credits",
+  "source",
+  "created_at",
+];
+
 interface ModalHooks {
+  exportMutation: {
+    mutateAsync: (data: { query: TauriTypes.TransactionControllerGetListParams; options: TauriTypes.ExportOptions }) => Promise<any>;
+  };
   useTranslateBasePrompt: (key: string, context?: { [key: string]: any }) => string;
   useTranslatePrompt: (key: string, context?: { [key: string]: any }) => string;
   refetchQueries: () => void;
@@ -20,7 +42,14 @@ interface ModalHooks {
   };
 }
 
-export const useModals = ({ useTranslatePrompt, setLoadingRows, updateMutation, deleteMutation, deleteMultipleMutation }: ModalHooks) => {
+export const useModals = ({
+  exportMutation,
+  useTranslatePrompt,
+  setLoadingRows,
+  updateMutation,
+  deleteMutation,
+  deleteMultipleMutation,
+}: ModalHooks) => {
   const OpenUpdateModal = (item: TauriTypes.TransactionDto) => {
     modals.open({
       title: useTranslatePrompt("update_title"),
@@ -60,7 +89,25 @@ export const useModals = ({ useTranslatePrompt, setLoadingRows, updateMutation,
     });
   };
 
+  const OpenExportModal = (query: TauriTypes.TransactionControllerGetListParams) => {
+    modals.open({
+      title: useTranslateForms("export_data.title"),
+      children: (
+        <ExportData
+          columns={EXPORT_COLUMNS}
+          from_date={query.from_date}
+          to_date={query.to_date}
+          onSubmit={async ({ options, from_date, to_date }) => {
+            modals.closeAll();
+            await exportMutation.mutateAsync({ query: { ...query, from_date, to_date }, options });
+          }}
+        />
+      ),
+    });
+  };
+
   return {
+    OpenExportModal,
     OpenUpdateModal,
     OpenDeleteModal,
     OpenDeleteBulkModal,
@@ -7,7 +7,8 @@ export const useMutations = ({ refetchQueries, setLoadingRows }: MutationHooks)
 
   const exportMutation = createGenericMutation(
     {
-      mutationFn: (data: TauriTypes.TransactionControllerGetListParams) => api.transaction.exportJson(data),
+      mutationFn: (data: { query: TauriTypes.TransactionControllerGetListParams; options: TauriTypes.ExportOptions }) =>
+        api.transaction.exportData(data.query, data.options),
       successKey: "export_data",
       errorKey: "export_data",
       getSuccessMessage: (data: any) => ({ path: data }),
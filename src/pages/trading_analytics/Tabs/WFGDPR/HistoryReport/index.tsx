@@ -0,0 +1,138 @@
+import { Grid, NumberFormatter, ScrollArea, SegmentedControl, Stack, Table, Text, Title, useMantineTheme } from "@mantine/core";
+import { useQuery } from "@tanstack/react-query";
+import { useState } from "react";
+import dayjs from "dayjs";
+import api from "@api/index";
+import { TauriTypes } from "$types";
+import { useTranslatePages } from "@hooks/useTranslate.hook";
+import { BarCardChart } from "@components/Shared/BarCardChart";
+import { Loading } from "@components/Shared/Loading";
+
+export const HistoryReportPanel = () => {
+  const theme = useMantineTheme();
+  // Translate general
+  const useTranslate = (key: string, context?: { [key: string]: any }, i18Key?: boolean) =>
+    useTranslatePages(`trading_analytics.tabs.wfgdpr.history_report.${key}`, { ...context }, i18Key);
+
+  const [source, setSource] = useState<string>("all");
+  const { data, isFetching } = useQuery({
+    queryKey: ["transaction_history_report", source],
+    queryFn: () =>
+      api.transaction.getHistoryReport({
+        page: 1,
+        limit: -1,
+        source: source === "all" ? undefined : (source as TauriTypes.TransactionSource),
+      }),
+    retry: false,
+  });
+
+  const flow = data?.platinum_flow;
+  return (
+    <ScrollArea h="85vh" pos="relative">
+      {isFetching && <Loading />}
+      <Stack p="xs" gap="md">
+        <SegmentedControl
+          w={300}
+          value={source}
+          onChange={setSource}
+          data={[
+            { value: "all", label: useTranslate("sources.all") },
+            { value: "app", label: useTranslate("sources.app") },
+            { value: "gdpr", label: useTranslate("sources.gdpr") },
+          ]}
+        />
+        <Text c="dimmed">
+          {useTranslate("range", {
+            count: data?.total_transactions ?? 0,
+            from: data?.first_trade ? dayjs(data.first_trade).format("DD MMM YYYY") : "-",
+            to: data?.last_trade ? dayjs(data.last_trade).format("DD MMM YYYY") : "-",
+          })}
+        </Text>
+        <BarCardChart
+          title={useTranslate("platinum_flow.title")}
+          boxHeight={400}
+          showDatasetLabels
+          labels={flow?.labels ?? []}
+          chartStyle={{ background: theme.colors.dark[7], height: "200px" }}
+          datasets={[
+            {
+              label: useTranslate("platinum_flow.revenue"),
+              data: flow?.values.revenue ?? [],
+              backgroundColor: theme.other.transactionType.sale,
+            },
+            {
+              label: useTranslate("platinum_flow.expenses"),
+              data: flow?.values.expenses ?? [],
+              backgroundColor: theme.other.transactionType.purchase,
+            },
+            {
+              label: useTranslate("platinum_flow.profit"),
+              data: flow?.values.profit ?? [],
+              backgroundColor: theme.other.transactionType.trade,
+            },
+          ]}
+        />
+        <Grid>
+          <Grid.Col span={6}>
+            <Title order={4}>{useTranslate("top_partners.title")}</Title>
+            <Table striped>
+              <Table.Thead>
+                <Table.Tr>
+                  <Table.Th>{useTranslate("columns.user_name")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.transactions")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.revenue")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.expenses")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.last_trade")}</Table.Th>
+                </Table.Tr>
+              </Table.Thead>
+              <Table.Tbody>
+                {data?.top_partners.map((partner) => (
+                  <Table.Tr key={partner.user_name}>
+                    <Table.Td>{partner.user_name}</Table.Td>
+                    <Table.Td>{partner.transactions}</Table.Td>
+                    <Table.Td>
+                      <NumberFormatter value={partner.revenue} thousandSeparator="," thousandsGroupStyle="thousand" />
+                    </Table.Td>
+                    <Table.Td>
+                      <NumberFormatter value={partner.expenses} thousandSeparator="," thousandsGroupStyle="thousand" />
+                    </Table.Td>
+                    <Table.Td>{dayjs(partner.last_trade).format("DD MMM YYYY")}</Table.Td>
+                  </Table.Tr>
+                ))}
+              </Table.Tbody>
+            </Table>
+          </Grid.Col>
+          <Grid.Col span={6}>
+            <Title order={4}>{useTranslate("most_traded_items.title")}</Title>
+            <Table striped>
+              <Table.Thead>
+                <Table.Tr>
+                  <Table.Th>{useTranslate("columns.item_name")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.quantity_sold")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.quantity_bought")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.revenue")}</Table.Th>
+                  <Table.Th>{useTranslate("columns.expenses")}</Table.Th>
+                </Table.Tr>
+              </Table.Thead>
+              <Table.Tbody>
+                {data?.most_traded_items.map((item) => (
+                  <Table.Tr key={item.item_unique_name}>
+                    <Table.Td>{item.item_name}</Table.Td>
+                    <Table.Td>{item.quantity_sold}</Table.Td>
+                    <Table.Td>{item.quantity_bought}</Table.Td>
+                    <Table.Td>
+                      <NumberFormatter value={item.revenue} thousandSeparator="," thousandsGroupStyle="thousand" />
+                    </Table.Td>
+                    <Table.Td>
+                      <NumberFormatter value={item.expenses} thousandSeparator="," thousandsGroupStyle="thousand" />
+                    </Table.Td>
+                  </Table.Tr>
+                ))}
+              </Table.Tbody>
+            </Table>
+          </Grid.Col>
+        </Grid>
+      </Stack>
+    </ScrollArea>
+  );
+};
@@ -1,4 +1,4 @@
-import { Box, Grid, Group, NumberFormatter, Paper, Select, Table, Text, Title, useMantineTheme } from "@mantine/core";
+import { Box, Grid, Group, NumberFormatter, Paper, Select, Table, Text, TextInput, Title, useMantineTheme } from "@mantine/core";
 import { TauriTypes } from "$types";
 import { useTranslateEnums, useTranslatePages } from "@hooks/useTranslate.hook";
 import { ActionWithTooltip } from "@components/Shared/ActionWithTooltip";
@@ -18,6 +18,7 @@ import { useAppContext } from "@contexts/app.context";
 import { BarCardChart } from "@components/Shared/BarCardChart";
 import i18next from "i18next";
 import { BarChartFinancialSummary } from "@components/DataDisplay/BarChartFinancialSummary";
+import api from "@api/index";
 interface TradePanelProps {
   value: TauriTypes.WFGDPRAccount | null;
 }
@@ -165,6 +166,23 @@ export const TradePanel = ({ value }: TradePanelProps) => {
             sortable: true,
             render: ({ credits }) => <NumberFormatter value={credits} thousandSeparator="," thousandsGroupStyle="thousand" />,
           },
+          {
+            accessor: "notes",
+            title: useTranslateDataGridColumns("notes"),
+            render: (record) => (
+              <TextInput
+                size="xs"
+                defaultValue={record.notes}
+                disabled={record.gdpr_id == undefined}
+                onClick={(e) => e.stopPropagation()}
+                onBlur={(e) => {
+                  if (record.gdpr_id == undefined || e.currentTarget.value == (record.notes || "")) return;
+                  record.notes = e.currentTarget.value;
+                  api.log_parser.updateTradeNotes(record.gdpr_id, record.notes);
+                }}
+              />
+            ),
+          },
         ]}
       />
       {showReport && (
@@ -1,21 +1,26 @@
-import { Center, Container, Group, Overlay, Table, Tabs, Text } from "@mantine/core";
+import { Button, Center, Container, Group, Overlay, Table, Tabs, Text } from "@mantine/core";
+import { notifications } from "@mantine/notifications";
 import { useTranslatePages } from "@hooks/useTranslate.hook";
-import { invoke } from "@tauri-apps/api/core";
 import { Loading } from "@components/Shared/Loading";
 import { useTauriDragDrop } from "@hooks/useTauriDragDrop.hook";
 import { useState } from "react";
-import { useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery } from "@tanstack/react-query";
 import api from "@api/index";
 import { TauriTypes } from "$types";
 import { TradePanel, PurchasePanel, TransactionPanel } from "./Tabs";
 import { TimerStamp } from "@components/Shared/TimerStamp";
 import { OverviewPanel } from "./Tabs/Overview";
+import { HistoryReportPanel } from "./HistoryReport";
 
 enum View {
   Details = "details",
   Accounts = "accounts",
+  History = "history",
 }
 
+// Keeps the notification readable when many items are missing from the cache
+const MAX_SHOWN_WARNINGS = 5;
+
 interface WarframeGDPRParserProps {
   isActive?: boolean;
 }
@@ -31,13 +36,37 @@ export const WarframeGDPRParser = ({}: WarframeGDPRParserProps = {}) => {
     queryFn: () => api.log_parser.getState(),
     retry: false,
   });
-  const { data: accounts } = useQuery({
+  const { data: accounts, refetch: refetchAccounts } = useQuery({
     queryKey: ["get_accounts"],
     queryFn: () => api.log_parser.getAccounts(),
     retry: false,
     enabled: !!data?.was_initialized,
   });
 
+  const importMutation = useMutation({
+    mutationFn: () => api.log_parser.importTransactions(),
+    onSuccess: (summary) =>
+      notifications.show({
+        title: useTranslateTabs("import.success.title"),
+        message: useTranslateTabs("import.success.message", { ...summary }),
+        color: "green.7",
+      }),
+    onError: () =>
+      notifications.show({
+        title: useTranslateTabs("import.error.title"),
+        message: useTranslateTabs("import.error.message"),
+        color: "red.7",
+      }),
+  });
+
+  const clearMutation = useMutation({
+    mutationFn: () => api.log_parser.clear(),
+    onSuccess: async () => {
+      setSelectedAccount(null);
+      await refetch();
+    },
+  });
+
   const [loading, setLoading] = useState(false);
   const [selectedAccount, setSelectedAccount] = useState<TauriTypes.WFGDPRAccount | null>(null);
   const [view, setView] = useState<View>(View.Accounts);
@@ -67,10 +96,28 @@ export const WarframeGDPRParser = ({}: WarframeGDPRParserProps = {}) => {
     onDrop: async (path) => {
       try {
         setLoading(true);
-        await invoke("wfgdpr_load", { filePath: path });
+        const stats = await api.log_parser.load(path);
         await refetch();
+        await refetchAccounts();
+        if (stats.warnings.length > 0)
+          notifications.show({
+            title: useTranslateTabs("load.warnings.title", { count: stats.warnings.length }),
+            message: [
+              ...stats.warnings.slice(0, MAX_SHOWN_WARNINGS),
+              ...(stats.warnings.length > MAX_SHOWN_WARNINGS
+                ? [useTranslateTabs("load.warnings.more", { count: stats.warnings.length - MAX_SHOWN_WARNINGS })]
+                : []),
+            ].join("\n"),
+            color: "yellow.7",
+            style: { whiteSpace: "pre-line" },
+          });
       } catch (err) {
         console.error("Error parsing WFGDPR data:", err);
+        notifications.show({
+          title: useTranslateTabs("load.error.title"),
+          message: useTranslateTabs("load.error.message"),
+          color: "red.7",
+        });
       } finally {
         setLoading(false);
       }
@@ -88,7 +135,24 @@ export const WarframeGDPRParser = ({}: WarframeGDPRParserProps = {}) => {
           </Center>
         </Overlay>
       )}
-      {loading && <Loading />}
+      {(loading || importMutation.isPending || clearMutation.isPending) && <Loading />}
+      {view !== View.Details && (
+        <Group p="xs" gap="xs">
+          <Button variant={view === View.Accounts ? "filled" : "light"} onClick={() => setView(View.Accounts)}>
+            {useTranslateTabs("buttons.accounts")}
+          </Button>
+          <Button variant={view === View.History ? "filled" : "light"} onClick={() => setView(View.History)}>
+            {useTranslateTabs("buttons.history_report")}
+          </Button>
+          <Button color="green.7" disabled={!data?.was_initialized} onClick={() => importMutation.mutate()}>
+            {useTranslateTabs("buttons.import")}
+          </Button>
+          <Button color="red.7" variant="light" disabled={!data?.was_initialized} onClick={() => clearMutation.mutate()}>
+            {useTranslateTabs("buttons.clear")}
+          </Button>
+        </Group>
+      )}
+      {view === View.History && <HistoryReportPanel />}
       {view === View.Accounts && (
         <Table.ScrollContainer minWidth={800}>
           <Table verticalSpacing="sm">
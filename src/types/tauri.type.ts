@@ -30,6 +30,7 @@ export namespace TauriTypes {
     RefreshSettings = "Settings:Refresh",
     UpdateLiveScraperRunningState = "LiveScraper:UpdateRunningState",
     OnLiveScraperMessage = "LiveScraper:OnMessage",
+    UpdateLiveScraperSchedulerStats = "LiveScraper:UpdateSchedulerStats",
     RefreshCache = "Cache:Refresh",
     RefreshStockItems = "LiveScraper:RefreshStockItems",
     RefreshStockRiven = "LiveScraper:RefreshStockRiven",
@@ -109,6 +110,10 @@ export namespace TauriTypes {
     stock_mode: StockMode;
     trade_modes: TradeMode[];
     delete_conflicting_orders: boolean;
+    market_snapshots: boolean;
+    market_snapshot_retention_days: number;
+    requests_per_second: number;
+    request_burst: number;
   }
   export interface WFInventorySettings {
     inv_path: string;
@@ -127,7 +132,9 @@ export namespace TauriTypes {
   export interface ItemGeneralSettings {
     blacklist: BlackListItemSetting[];
     buy_list: BuyListItemSetting[];
+    lot_method: LotMethod;
   }
+  export type LotMethod = "fifo" | "lifo" | "specific";
   export interface BlackListItemSetting {
     wfmId: string;
     subType?: SubType;
@@ -137,6 +144,17 @@ export namespace TauriTypes {
     wfmId: string;
     max_price: number;
   }
+  export type PricingStrategy = "match_top" | "undercut" | "percent_of_moving_avg" | "median_of_top_k";
+  export type PricingStrategySetting =
+    | { type: "match_top" }
+    | { type: "undercut"; amount: number }
+    | { type: "percent_of_moving_avg"; percent: number }
+    | { type: "median_of_top_k"; k: number };
+  export interface PricingSettings {
+    default: PricingStrategySetting;
+    items: { wfmId: string; strategy: PricingStrategySetting }[];
+    categories: { tag: string; strategy: PricingStrategySetting }[];
+  }
   export interface SyndicateWtsSettings {
     max_standing_cost: number;
     syndicates: string[];
@@ -150,6 +168,7 @@ export namespace TauriTypes {
     min_profit: number;
     max_price_drop: number;
     min_listings_below: number;
+    pricing: PricingSettings;
   }
   export interface ItemWtsSettings {
     volume_threshold: number;
@@ -164,10 +183,12 @@ export namespace TauriTypes {
     max_stock_quantity: number;
     max_price_drop: number;
     min_listings_below: number;
+    pricing: PricingSettings;
   }
   export interface RivenSettings {
     general: RivenGeneralSettings;
     wts: RivenWtsSettings;
+    wtb: RivenWtbSettings;
   }
   export interface RivenGeneralSettings {
     update_interval: number;
@@ -176,11 +197,28 @@ export namespace TauriTypes {
     min_profit: number;
     threshold_percentage: number;
     max_results: number;
+    min_similarity: number;
+  }
+  export interface RivenWtbSettings {
+    enabled: boolean;
+    update_interval: number;
+    watches: RivenWatchSetting[];
+  }
+  export interface RivenWatchSetting {
+    id: string;
+    enabled: boolean;
+    wfm_weapon_url: string;
+    filter: StockRivenFilter;
+    target_price: number;
+    min_score: number;
   }
   export interface SummarySettings {
     recent_days: number;
     recent_transactions: number;
     categories: SummaryCategorySetting[];
+    timezone: string;
+    week_start: number;
+    daily_reset: boolean;
   }
   export interface SummaryCategorySetting {
     icon: string;
@@ -197,6 +235,8 @@ export namespace TauriTypes {
   export interface DebuggingLiveScraperSettings {
     entries: DebuggingLiveItemEntry[];
     fake_orders: boolean;
+    simulation: boolean;
+    record_snapshots: boolean;
   }
   export interface DebuggingLiveItemEntry {
     stock_id?: number | null;
@@ -215,11 +255,59 @@ export namespace TauriTypes {
     on_new_conversation: NotificationSetting;
     on_wfm_chat_message: NotificationSetting;
     on_new_trade: NotificationSetting;
+    on_riven_deal: NotificationSetting;
+    on_price_alert: NotificationSetting;
+    price_alerts: PriceAlertSettings;
+  }
+  export interface PriceAlertSettings {
+    enabled: boolean;
+    update_interval: number;
+    rules: PriceAlertRule[];
+  }
+  export type PriceAlertCondition =
+    | { type: "lowest_sell_below"; price: number }
+    | { type: "highest_buy_above"; price: number }
+    | { type: "spread_above"; spread: number }
+    | { type: "moving_avg_shift"; percent: number };
+  export interface PriceAlertRule {
+    id: string;
+    enabled: boolean;
+    wfm_url: string;
+    subType?: SubType;
+    condition: PriceAlertCondition;
   }
   export interface NotificationSetting {
     system_notify: SystemNotify;
     discord_notify: DiscordNotify;
     webhook_notify: WebHookNotify;
+    channels: NotificationChannelSetting[];
+  }
+  export type NotificationChannelSetting =
+    | ({ type: "telegram" } & TelegramNotify)
+    | ({ type: "ntfy" } & NtfyNotify)
+    | ({ type: "matrix" } & MatrixNotify);
+  export interface TelegramNotify {
+    enabled: boolean;
+    bot_token: string;
+    chat_id: string;
+    content: string;
+  }
+  export interface NtfyNotify {
+    enabled: boolean;
+    server: string;
+    topic: string;
+    token: string;
+    title: string;
+    content: string;
+    priority: number;
+    tags: string[];
+  }
+  export interface MatrixNotify {
+    enabled: boolean;
+    homeserver: string;
+    room_id: string;
+    access_token: string;
+    content: string;
   }
   export interface SystemNotify {
     enabled: boolean;
@@ -233,15 +321,56 @@ export namespace TauriTypes {
     content: string;
     webhook: string;
     user_ids: string[];
+    embed?: DiscordEmbedTemplate;
+  }
+  export interface DiscordEmbedTemplate {
+    title: string;
+    description: string;
+    color: string;
+    thumbnail: string;
+    fields: DiscordEmbedFieldTemplate[];
+  }
+  export interface DiscordEmbedFieldTemplate {
+    name: string;
+    value: string;
+    inline: boolean;
+  }
+  export type NotifyChannel = "discord" | "webhook";
+  export type DeliveryStatus = "pending" | "retrying" | "delivered" | "failed";
+  export interface DeliveryAttempt {
+    at: string;
+    status_code?: number;
+    error?: string;
+    retry_after_ms?: number;
+  }
+  export interface SecretRef {
+    placeholder: string;
+    fingerprint: string;
+  }
+  export interface QueuedNotification {
+    id: string;
+    channel: NotifyChannel;
+    url: string;
+    headers: Record<string, string>;
+    body: any;
+    secrets?: SecretRef[];
+    status: DeliveryStatus;
+    tries: number;
+    attempts: DeliveryAttempt[];
+    created_at: string;
+    next_attempt_at: string;
+    delivered_at?: string;
   }
   export interface WebHookNotify {
     enabled: boolean;
     url: string;
+    secret: string;
   }
   export interface HttpServerSettings {
     enable: boolean;
     host: string;
     port: number;
+    token: string;
   }
   export interface GenerateTradeMessageSetting {
     templates: SaveTemplateSetting[];
@@ -319,6 +448,7 @@ export namespace TauriTypes {
     best_seller: FinancialItemReport;
     recent_days: FinancialWithGraph;
     today: FinancialWithGraph;
+    this_week: FinancialWithGraph;
     total: DashboardTotal;
     resent_transactions: TransactionDto[];
     categories: FinancialCategoryReport[];
@@ -496,6 +626,20 @@ export namespace TauriTypes {
     sub_type?: SubType;
     quantity: number;
     price: number;
+    // Lots to sell from when the lot method is "specific"
+    lot_ids?: number[];
+  }
+  export interface StockLot {
+    id: number;
+    wfm_id: string;
+    wfm_url: string;
+    sub_type?: SubType;
+    quantity: number;
+    remaining: number;
+    unit_cost: number;
+    acquired_at: string;
+    created_at: string;
+    updated_at: string;
   }
   export interface StockRiven<T = StockEntryPropertiesBase> extends StockEntryBase<T> {
     attributes: RivenAttribute[];
@@ -525,6 +669,7 @@ export namespace TauriTypes {
     re_rolls?: MinMaxDto;
     required_negative?: boolean;
     similarity?: null | number;
+    min_similarity?: null | number;
   }
   export interface StockRivenFilterAttribute {
     positive: boolean;
@@ -733,6 +878,42 @@ export namespace TauriTypes {
     week_price_shift: number;
     sub_type: SubType;
   }
+  export interface SchedulerStats {
+    tokens: number;
+    capacity: number;
+    requests_per_second: number;
+    total_requests: number;
+    rate_limited: number;
+    backoff_multiplier: number;
+    backoff_remaining_secs: number;
+    queued_items: number;
+    tracked_items: number;
+    max_staleness_secs: number;
+  }
+  export interface BacktestItemReport {
+    wfm_id: string;
+    wfm_url: string;
+    sub_type?: SubType;
+    snapshots: number;
+    buy_fills: number;
+    sell_fills: number;
+    units_held: number;
+    average_cost: number;
+    capital_tied_up: number;
+    max_capital_tied_up: number;
+    realized_profit: number;
+    open_buy_price?: number;
+    open_sell_price?: number;
+  }
+  export interface BacktestReport {
+    snapshots: number;
+    first_snapshot: string;
+    last_snapshot: string;
+    realized_profit: number;
+    capital_tied_up: number;
+    max_capital_tied_up: number;
+    items: BacktestItemReport[];
+  }
 
   //--------------------------------------------------------------------------------
   //  Transaction
@@ -755,7 +936,9 @@ export namespace TauriTypes {
     user_name: string;
     wfm_id: string;
     wfm_url: string;
+    source: TransactionSource;
   }
+  export type TransactionSource = "app" | "gdpr";
   export interface UpdateTransaction {
     id: number;
     price?: number;
@@ -822,6 +1005,8 @@ export namespace TauriTypes {
     receivedItems: TradeItem[];
     tradeTime: Date;
     type: string;
+    gdpr_id?: number;
+    notes?: string;
   }
   export interface TradeItem<T = any> {
     item_type: string;
@@ -835,6 +1020,52 @@ export namespace TauriTypes {
   //--------------------------------------------------------------------------------
   //  GDPR
   //--------------------------------------------------------------------------------
+  export interface GdprParseStats {
+    accounts: number;
+    new_trades: number;
+    new_logins: number;
+    new_purchases: number;
+    new_transactions: number;
+    skipped_records: number;
+    warnings: string[];
+    elapsed_ms: number;
+  }
+  export interface GdprImportSummary {
+    trades: number;
+    imported: number;
+    duplicates: number;
+    skipped_trades: number;
+  }
+  export interface TradePartnerSummary {
+    user_name: string;
+    transactions: number;
+    sales: number;
+    purchases: number;
+    revenue: number;
+    expenses: number;
+    first_trade: string;
+    last_trade: string;
+  }
+  export interface TradedItemSummary {
+    item_name: string;
+    item_unique_name: string;
+    transactions: number;
+    quantity_sold: number;
+    quantity_bought: number;
+    revenue: number;
+    expenses: number;
+  }
+  export interface TradeHistoryReport {
+    total_transactions: number;
+    first_trade?: string;
+    last_trade?: string;
+    top_partners: TradePartnerSummary[];
+    platinum_flow: {
+      labels: string[];
+      values: { revenue?: number[]; expenses?: number[]; profit?: number[] };
+    };
+    most_traded_items: TradedItemSummary[];
+  }
   export interface WFGDPRAccount {
     account_creation_date: Date;
     activated: boolean;
@@ -895,6 +1126,29 @@ export namespace TauriTypes {
     properties?: T;
     item_types?: string[];
   }
+  export type WFInvReconciliationKind = "missing_in_stock" | "over_counted" | "under_counted" | "unveiled_not_in_stock";
+  export interface WFInvItemReconciliation {
+    kind: WFInvReconciliationKind;
+    stock_id?: number;
+    wfm_url: string;
+    name: string;
+    sub_type?: SubType;
+    inventory_quantity: number;
+    stock_quantity: number;
+  }
+  export interface WFInvRivenReconciliation {
+    kind: WFInvReconciliationKind;
+    stock_id?: number;
+    uuid: string;
+    weapon_name: string;
+    mod_name: string;
+    riven?: ItemRiven;
+  }
+  export interface WFInvReconciliationReport {
+    items: WFInvItemReconciliation[];
+    rivens: WFInvRivenReconciliation[];
+    skipped_rivens: number;
+  }
   export type WFInvRivenControllerGetListData = PaginatedDto & {
     results?: ItemRiven<{
       is_in_stock: boolean;
@@ -920,6 +1174,13 @@ export namespace TauriTypes {
   export type EELogControllerGetListData = PaginatedDto & {
     results?: EELog[];
   };
+  export interface ReplayedTrade {
+    result: string;
+    language: string;
+    lines: string[];
+    unresolved_items: string[];
+    trade: PlayerTrade;
+  }
   export interface EELogControllerGetListParams {
     page: number;
     limit: number;
@@ -929,6 +1190,12 @@ export namespace TauriTypes {
     hide_empty?: boolean;
   }
 
+  export type ExportFormat = "json" | "csv" | "xlsx" | "ods";
+  export interface ExportOptions {
+    format: ExportFormat;
+    columns: string[];
+  }
+
   //--------------------------------------------------------------------------------
   //  Controller Params
   //--------------------------------------------------------------------------------
@@ -939,6 +1206,8 @@ export namespace TauriTypes {
     sort_direction?: "asc" | "desc";
     query?: string;
     status?: StockStatus;
+    from_date?: string;
+    to_date?: string;
   }
   export type StockItemControllerGetListData = PaginatedDto & {
     results?: StockItem[];
@@ -950,6 +1219,8 @@ export namespace TauriTypes {
     sort_direction?: "asc" | "desc";
     query?: string;
     status?: StockStatus;
+    from_date?: string;
+    to_date?: string;
   }
   export type StockRivenControllerGetListData = PaginatedDto & {
     results?: StockRiven[];
@@ -961,6 +1232,8 @@ export namespace TauriTypes {
     sort_direction?: "asc" | "desc";
     query?: string;
     status?: StockStatus;
+    from_date?: string;
+    to_date?: string;
   }
   export type WishListControllerGetListData = PaginatedDto & {
     results?: WishListItem[];
@@ -976,6 +1249,7 @@ export namespace TauriTypes {
     tags?: string[];
     from_date?: string;
     to_date?: string;
+    source?: TransactionSource;
   }
   export type TransactionControllerGetListData = PaginatedDto & {
     results?: TransactionDto[];